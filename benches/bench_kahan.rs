@@ -10,7 +10,7 @@ fn bench_add_assign() {
 }
 
 fn bench_kahan(c: &mut Criterion) {
-    c.bench_function("KahanSum::add_assign", |b| b.iter(|| bench_add_assign()));
+    c.bench_function("KahanSum::add_assign", |b| b.iter(bench_add_assign));
 }
 
 criterion_group!(benches, bench_kahan);
@@ -1,3 +1,5 @@
+#![allow(clippy::single_element_loop)]
+
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand::Rng;
 use stats_ci::*;
@@ -83,7 +85,7 @@ fn bench_mean_rayon(c: &mut Criterion) {
                             }
                             stats
                         })
-                        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2);
+                        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2);
 
                     stats.ci_mean(confidence)
                 })
@@ -99,7 +101,7 @@ fn bench_mean_rayon(c: &mut Criterion) {
                         .clone()
                         .par_iter()
                         .map(|&x| mean::Arithmetic::from_iter(&[x]).unwrap())
-                        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2);
+                        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2);
 
                     stats.ci_mean(confidence)
                 })
@@ -109,10 +111,63 @@ fn bench_mean_rayon(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_mean_small_samples(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let confidence = Confidence::new_two_sided(0.95);
+
+    let mut group = c.benchmark_group("mean::Arithmetic::ci (many small samples)");
+
+    for keys in [1_000] {
+        let samples = (0..keys)
+            .map(|_| {
+                let size = rng.gen_range(2..30);
+                (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("per key", keys), &samples, |b, samples| {
+            b.iter(|| {
+                samples
+                    .iter()
+                    .map(|data| mean::Arithmetic::ci(confidence, data))
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_mean_integer(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let confidence = Confidence::new_two_sided(0.95);
+
+    let mut group =
+        c.benchmark_group("mean::Integer::ci vs. mean::Arithmetic::ci (integer samples)");
+
+    for size in [100_000] {
+        let data = (0..size)
+            .map(|_| rng.gen_range(0..1_u64 << 40))
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("Integer", size), &data, |b, data| {
+            b.iter(|| mean::Integer::ci(confidence, data))
+        });
+        group.bench_with_input(BenchmarkId::new("Arithmetic", size), &data, |b, data| {
+            b.iter(|| {
+                let data = data.iter().map(|&x| x as f64).collect::<Vec<_>>();
+                mean::Arithmetic::ci(confidence, &data)
+            })
+        });
+    }
+    group.finish();
+}
+
 fn bench_mean(c: &mut Criterion) {
     bench_mean_arithmetic(c);
     bench_mean_category(c);
     bench_mean_rayon(c);
+    bench_mean_small_samples(c);
+    bench_mean_integer(c);
 }
 
 criterion_group!(benches, bench_mean);
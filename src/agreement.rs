@@ -0,0 +1,1198 @@
+//!
+//! Inter-rater agreement: chance-corrected agreement between several raters assigning categorical labels to the same items
+//! (e.g., annotators of a crowdsourced labeling pipeline).
+//!
+//! * [`FleissKappa`] - Fleiss' kappa for any number of raters per item and nominal categories, with an analytic interval
+//!   and a bootstrap interval over the items.
+//! * [`KrippendorffAlpha`] - Krippendorff's alpha for nominal, ordinal, or interval ratings with missing ratings
+//!   (e.g., when each rater only rates some of the units), with a bootstrap interval over the units.
+//!
+//! Agreement between two methods measuring the same continuous quantity (method-comparison studies):
+//!
+//! * [`ConcordanceCorrelation`] - Lin's concordance correlation coefficient, with an interval based on the Fisher z-transform.
+//!
+//! Internal consistency of the items of a scale (e.g., a questionnaire):
+//!
+//! * [`CronbachAlpha`] - Cronbach's alpha, with the exact interval of Feldt.
+//!
+//! A kappa (or an alpha) of 1 indicates perfect agreement, while a value of 0 indicates no more agreement than expected by chance.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // number of raters (out of 4) assigning each item to the categories "spam", "ham", and "unsure"
+//! let counts = [[4, 0, 0], [3, 1, 0], [0, 4, 0], [1, 2, 1], [0, 3, 1], [4, 0, 0], [0, 0, 4], [2, 2, 0]];
+//! let kappa = agreement::FleissKappa::from_counts(&counts)?;
+//! let ci = kappa.ci(Confidence::new_two_sided(0.95))?;
+//! assert_abs_diff_eq!(kappa.kappa(), 0.506173, epsilon = 1e-6);
+//! assert!(ci.contains(&kappa.kappa()));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * J.L. Fleiss. "Measuring nominal scale agreement among many raters." Psychological Bulletin 76(5): 378-382 (1971).
+//! * K. Krippendorff. Content Analysis: An Introduction to Its Methodology, 4th edition. Sage, 2018.
+//! * L.J. Cronbach. "Coefficient alpha and the internal structure of tests." Psychometrika 16(3): 297-334 (1951).
+//! * L.I. Lin. "A concordance correlation coefficient to evaluate reproducibility." Biometrics 45(1): 255-268 (1989).
+//! * K.L. Gwet. Handbook of Inter-Rater Reliability, 4th edition. Advanced Analytics, 2014.
+//! * [Wikipedia - Fleiss' kappa](https://en.wikipedia.org/wiki/Fleiss%27_kappa)
+//!
+use super::*;
+use distributions::z_value;
+use error::*;
+use num_traits::Float;
+use utils::Bootstrap;
+
+///
+/// Fleiss' kappa of the agreement between raters assigning each item to one of several nominal categories.
+///
+/// Item \\( i \\) is rated by \\( r_i \geq 2 \\) raters, \\( r_{ik} \\) of which assign it to category \\( k \\).
+/// The kappa compares the observed agreement \\( p_a \\) (the proportion of agreeing pairs of raters, averaged over the items)
+/// with the agreement \\( p_e = \sum_k \pi_k^2 \\) expected by chance given the overall proportion \\( \pi_k \\) of each category:
+/// \\[
+/// \kappa = \frac{p_a - p_e}{1 - p_e}
+/// \\]
+///
+/// The raters need not be the same for every item, nor be equally many.
+///
+/// The analytic interval ([`FleissKappa::ci`]) uses the standard error of Gwet (2014), obtained by linearization of the kappa
+/// over the items; unlike the standard error of Fleiss et al. (1979), which only holds when the kappa is zero, it is valid for any kappa.
+/// With few items, the bootstrap interval ([`FleissKappa::ci_bootstrap`]), which resamples the items, is more reliable.
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // example of Fleiss (1971): 10 items rated by 14 raters into 5 categories
+/// let counts = [
+///     [0, 0, 0, 0, 14], [0, 2, 6, 4, 2], [0, 0, 3, 5, 6], [0, 3, 9, 2, 0], [2, 2, 8, 1, 1],
+///     [7, 7, 0, 0, 0], [3, 2, 6, 3, 0], [2, 5, 3, 2, 2], [6, 5, 2, 1, 0], [0, 2, 2, 3, 7],
+/// ];
+/// let kappa = agreement::FleissKappa::from_counts(&counts)?;
+/// assert_abs_diff_eq!(kappa.kappa(), 0.209931, epsilon = 1e-6);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(kappa.ci(confidence)?, Interval::new(0.028887, 0.390975)?, epsilon = 1e-6);
+/// let ci = kappa.ci_bootstrap(confidence, &utils::Bootstrap::new(2_000, 42))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.040366, 0.363953)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FleissKappa {
+    categories: usize,
+    /// proportion of agreeing pairs of raters for each item
+    agreement: Vec<f64>,
+    /// proportion of the raters assigning each item to each category (row-major)
+    shares: Vec<f64>,
+    kappa: f64,
+}
+
+impl FleissKappa {
+    ///
+    /// Compute Fleiss' kappa from the number of raters assigning each item to each category.
+    ///
+    /// # Arguments
+    ///
+    /// * `counts` - for each item, the number of raters assigning the item to each category (in the same order for every item)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 items
+    /// * [`CIError::DifferentSampleSizes`] - if the items do not all have the same number of categories
+    /// * [`CIError::InvalidInputData`] - if there are less than 2 categories, an item has less than 2 ratings,
+    ///   or all the ratings are in the same category (the kappa is undefined)
+    ///
+    pub fn from_counts<I, R>(counts: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[usize]>,
+    {
+        let mut categories = None;
+        let mut agreement = Vec::new();
+        let mut shares = Vec::new();
+        for row in counts {
+            let row = row.as_ref();
+            let k = *categories.get_or_insert(row.len());
+            if row.len() != k {
+                return Err(CIError::DifferentSampleSizes(k, row.len()));
+            }
+            let raters = row
+                .iter()
+                .try_fold(0_usize, |total, &count| total.checked_add(count))
+                .ok_or_else(|| CIError::Overflow("number of raters".to_string()))?;
+            if raters < 2 {
+                return Err(CIError::InvalidInputData);
+            }
+            let r = raters as f64;
+            let pairs = row
+                .iter()
+                .map(|&count| count as f64 * (count as f64 - 1.))
+                .sum::<f64>();
+            agreement.push(pairs / (r * (r - 1.)));
+            shares.extend(row.iter().map(|&count| count as f64 / r));
+        }
+        let categories = categories.unwrap_or(0);
+        if agreement.len() < 2 {
+            return Err(CIError::TooFewSamples(agreement.len()));
+        }
+        if categories < 2 {
+            return Err(CIError::InvalidInputData);
+        }
+        let mut kappa = Self {
+            categories,
+            agreement,
+            shares,
+            kappa: f64::NAN,
+        };
+        kappa.kappa = kappa.kappa_of(0..kappa.item_count());
+        if !kappa.kappa.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(kappa)
+    }
+
+    ///
+    /// Number of rated items
+    ///
+    pub fn item_count(&self) -> usize {
+        self.agreement.len()
+    }
+
+    ///
+    /// Number of categories
+    ///
+    pub fn category_count(&self) -> usize {
+        self.categories
+    }
+
+    ///
+    /// Fleiss' kappa
+    ///
+    pub fn kappa(&self) -> f64 {
+        self.kappa
+    }
+
+    ///
+    /// Standard error of the kappa (Gwet, 2014):
+    /// \\[
+    /// \sqrt{\frac{1}{n (n-1)} \sum_{i=1}^n (\kappa_i^\star - \kappa)^2} \qquad \text{where }
+    /// \kappa_i^\star = \frac{p_{a|i} - p_e}{1 - p_e} - 2 (1 - \kappa) \frac{p_{e|i} - p_e}{1 - p_e}
+    /// \\]
+    /// where \\( p_{a|i} \\) is the agreement on item \\( i \\) and \\( p_{e|i} = \sum_k \pi_k r_{ik} / r_i \\).
+    ///
+    pub fn std_err(&self) -> f64 {
+        let n = self.item_count() as f64;
+        let proportions = self.proportions(0..self.item_count());
+        let p_e = proportions.iter().map(|p| p * p).sum::<f64>();
+        let sum_sq = self
+            .agreement
+            .iter()
+            .zip(self.shares.chunks_exact(self.categories))
+            .map(|(p_a, shares)| {
+                let p_e_item = shares
+                    .iter()
+                    .zip(&proportions)
+                    .map(|(share, p)| share * p)
+                    .sum::<f64>();
+                let linearized =
+                    (p_a - p_e - 2. * (1. - self.kappa) * (p_e_item - p_e)) / (1. - p_e);
+                (linearized - self.kappa).powi(2)
+            })
+            .sum::<f64>();
+        (sum_sq / (n * (n - 1.))).sqrt()
+    }
+
+    ///
+    /// Confidence interval of the kappa, based on the normal distribution and the standard error of [`FleissKappa::std_err`].
+    /// The bounds are clamped to \\( [-1, 1] \\).
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let span = z_value(confidence)? * self.std_err();
+        self.interval(confidence, self.kappa - span, self.kappa + span)
+    }
+
+    ///
+    /// Bootstrap confidence interval of the kappa, resampling the items (percentile method).
+    /// Resamples where the kappa is undefined (all the ratings in the same category) are discarded.
+    ///
+    /// Complexity: \\( O(B \cdot n \cdot k) \\) for \\( B \\) resamples of \\( n \\) items with \\( k \\) categories
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::TooFewSamples`] - if the kappa is undefined on every resample (e.g., there are no resamples)
+    ///
+    pub fn ci_bootstrap(
+        &self,
+        confidence: Confidence,
+        bootstrap: &Bootstrap,
+    ) -> CIResult<Interval<f64>> {
+        let (lo, hi) = bootstrap.percentile_bounds(confidence, self.item_count(), |items| {
+            self.kappa_of(items.iter().copied())
+        })?;
+        self.interval(confidence, lo, hi)
+    }
+
+    fn interval(&self, confidence: Confidence, lo: f64, hi: f64) -> CIResult<Interval<f64>> {
+        let (lo, hi) = (lo.max(-1.), hi.min(1.));
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(-1., hi).map_err(|e| e.into()),
+        }
+    }
+
+    ///
+    /// Overall proportion \\( \pi_k \\) of each category over the given items.
+    ///
+    fn proportions<It: Iterator<Item = usize>>(&self, items: It) -> Vec<f64> {
+        let mut proportions = vec![0.; self.categories];
+        let mut n = 0;
+        for item in items {
+            let shares = &self.shares[item * self.categories..(item + 1) * self.categories];
+            proportions
+                .iter_mut()
+                .zip(shares)
+                .for_each(|(p, share)| *p += share);
+            n += 1;
+        }
+        proportions.iter_mut().for_each(|p| *p /= n as f64);
+        proportions
+    }
+
+    ///
+    /// Kappa over the given items (possibly repeated), or NaN if undefined.
+    ///
+    fn kappa_of<It: Iterator<Item = usize> + Clone>(&self, items: It) -> f64 {
+        let (sum, n) = items.clone().fold((0., 0), |(sum, n), item| {
+            (sum + self.agreement[item], n + 1)
+        });
+        let p_a = sum / n as f64;
+        let p_e = self.proportions(items).iter().map(|p| p * p).sum::<f64>();
+        if p_e < 1. {
+            (p_a - p_e) / (1. - p_e)
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+///
+/// Level of measurement of the ratings for [`KrippendorffAlpha`], which defines the distance \\( \delta_{ck} \\) between two values.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Metric {
+    ///
+    /// Unordered categories (e.g., labels): two values either agree or disagree, \\( \delta_{ck}^2 = [c \neq k] \\).
+    ///
+    Nominal,
+
+    ///
+    /// Ordered categories (e.g., a Likert scale): the distance between two values grows with the number of ratings between them,
+    /// \\( \delta_{ck}^2 = \left( \sum_{g=c}^{k} n_g - \frac{n_c + n_k}{2} \right)^2 \\).
+    ///
+    Ordinal,
+
+    ///
+    /// Numerical values on an interval scale (e.g., scores): \\( \delta_{ck}^2 = (c - k)^2 \\).
+    ///
+    Interval,
+}
+
+///
+/// Krippendorff's alpha of the agreement between raters, for nominal, ordinal, or interval ratings, where some raters may not
+/// have rated some units.
+///
+/// The alpha compares the disagreement \\( D_o \\) observed between the ratings of the same unit with the disagreement \\( D_e \\)
+/// expected between any two ratings:
+/// \\[
+/// \alpha = 1 - \frac{D_o}{D_e} = 1 - (n - 1) \frac{\sum_u \frac{1}{m_u - 1} \sum_{i \neq j} \delta^2(x_{ui}, x_{uj})}{\sum_{c, k} n_c n_k \delta_{ck}^2}
+/// \\]
+/// where \\( m_u \\) is the number of ratings of unit \\( u \\), \\( n_c \\) is the number of ratings with value \\( c \\),
+/// and \\( n \\) is the total number of pairable ratings.
+/// Units with less than two ratings cannot be paired and are ignored.
+///
+/// The interval ([`KrippendorffAlpha::ci_bootstrap`]) is a bootstrap over the units (percentile method).
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // example of Krippendorff (2011): 4 raters, 12 units, with missing ratings
+/// let units = [
+///     [Some(1.), Some(1.), None, Some(1.)],
+///     [Some(2.), Some(2.), Some(3.), Some(2.)],
+///     [Some(3.), Some(3.), Some(3.), Some(3.)],
+///     [Some(3.), Some(3.), Some(3.), Some(3.)],
+///     [Some(2.), Some(2.), Some(2.), Some(2.)],
+///     [Some(1.), Some(2.), Some(3.), Some(4.)],
+///     [Some(4.), Some(4.), Some(4.), Some(4.)],
+///     [Some(1.), Some(1.), Some(2.), Some(1.)],
+///     [Some(2.), Some(2.), Some(2.), Some(2.)],
+///     [None, Some(5.), Some(5.), Some(5.)],
+///     [None, None, Some(1.), Some(1.)],
+///     [None, Some(3.), None, None],
+/// ];
+/// let alpha = agreement::KrippendorffAlpha::from_units(&units, agreement::Metric::Nominal)?;
+/// assert_abs_diff_eq!(alpha.alpha(), 0.743421, epsilon = 1e-6);
+/// let alpha = agreement::KrippendorffAlpha::from_units(&units, agreement::Metric::Interval)?;
+/// assert_abs_diff_eq!(alpha.alpha(), 0.849107, epsilon = 1e-6);
+/// let ci = alpha.ci_bootstrap(Confidence::new_upper(0.9), &utils::Bootstrap::new(1_000, 7))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.584516, 1.)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * K. Krippendorff. "Computing Krippendorff's alpha-reliability." University of Pennsylvania, 2011.
+/// * A.F. Hayes, K. Krippendorff. "Answering the call for a standard reliability measure for coding data."
+///   Communication Methods and Measures 1(1): 77-89 (2007).
+/// * [Wikipedia - Krippendorff's alpha](https://en.wikipedia.org/wiki/Krippendorff%27s_alpha)
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KrippendorffAlpha {
+    metric: Metric,
+    /// distinct values of the ratings, in increasing order
+    values: Vec<f64>,
+    /// ratings of each pairable unit, as indices in `values`
+    units: Vec<Vec<usize>>,
+    alpha: f64,
+}
+
+impl KrippendorffAlpha {
+    ///
+    /// Compute Krippendorff's alpha from the ratings of each unit, where a missing rating is `None`.
+    /// Nominal categories must be encoded as numbers (e.g., their index).
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - for each unit, the ratings of the raters (missing or not)
+    /// * `metric` - the level of measurement of the ratings
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if some rating is not finite, or if all the ratings are equal (the alpha is undefined)
+    /// * [`CIError::TooFewSamples`] - if no unit has at least two ratings
+    ///
+    pub fn from_units<I, R>(units: &I, metric: Metric) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[Option<f64>]>,
+    {
+        let mut ratings = Vec::new();
+        for unit in units {
+            let unit = unit.as_ref().iter().flatten().copied().collect::<Vec<_>>();
+            if unit.iter().any(|x| !x.is_finite()) {
+                return Err(CIError::InvalidInputData);
+            }
+            if unit.len() >= 2 {
+                ratings.push(unit);
+            }
+        }
+        if ratings.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let mut values = ratings.iter().flatten().copied().collect::<Vec<_>>();
+        values.sort_by(|a, b| a.total_cmp(b));
+        values.dedup();
+        let units = ratings
+            .iter()
+            .map(|unit| {
+                unit.iter()
+                    .map(|x| values.partition_point(|v| v < x))
+                    .collect()
+            })
+            .collect();
+        let mut alpha = Self {
+            metric,
+            values,
+            units,
+            alpha: f64::NAN,
+        };
+        alpha.alpha = alpha.alpha_of(0..alpha.unit_count());
+        if !alpha.alpha.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(alpha)
+    }
+
+    ///
+    /// Number of pairable units, i.e., with at least two ratings
+    ///
+    pub fn unit_count(&self) -> usize {
+        self.units.len()
+    }
+
+    ///
+    /// Number of pairable ratings \\( n \\)
+    ///
+    pub fn pairable_count(&self) -> usize {
+        self.units.iter().map(|unit| unit.len()).sum()
+    }
+
+    ///
+    /// Level of measurement of the ratings
+    ///
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    ///
+    /// Krippendorff's alpha
+    ///
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    ///
+    /// Bootstrap confidence interval of the alpha, resampling the units (percentile method).
+    /// Resamples where the alpha is undefined (all the ratings equal) are discarded.
+    /// The upper bound is clamped to 1.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::TooFewSamples`] - if the alpha is undefined on every resample (e.g., there are no resamples)
+    ///
+    pub fn ci_bootstrap(
+        &self,
+        confidence: Confidence,
+        bootstrap: &Bootstrap,
+    ) -> CIResult<Interval<f64>> {
+        let (lo, hi) = bootstrap.percentile_bounds(confidence, self.unit_count(), |units| {
+            self.alpha_of(units.iter().copied())
+        })?;
+        let hi = hi.min(1.);
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Alpha over the given units (possibly repeated), or NaN if undefined.
+    ///
+    fn alpha_of<It: Iterator<Item = usize> + Clone>(&self, units: It) -> f64 {
+        let mut counts = vec![0.; self.values.len()];
+        for unit in units.clone() {
+            self.units[unit].iter().for_each(|&c| counts[c] += 1.);
+        }
+        let n = counts.iter().sum::<f64>();
+        // number of ratings below each value, for the ordinal metric
+        let below = counts
+            .iter()
+            .scan(0., |total, count| {
+                let below = *total;
+                *total += count;
+                Some(below)
+            })
+            .collect::<Vec<_>>();
+        let delta2 = |c: usize, k: usize| match self.metric {
+            Metric::Nominal => f64::from(c != k),
+            Metric::Ordinal => {
+                let (lo, hi) = (c.min(k), c.max(k));
+                let between = below[hi] + counts[hi] - below[lo];
+                (between - (counts[c] + counts[k]) / 2.).powi(2)
+            }
+            Metric::Interval => (self.values[c] - self.values[k]).powi(2),
+        };
+        let observed = units
+            .map(|unit| {
+                let ratings = &self.units[unit];
+                let m = ratings.len() as f64;
+                let pairs = ratings
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &c)| ratings[i + 1..].iter().map(move |&k| (c, k)))
+                    .map(|(c, k)| delta2(c, k))
+                    .sum::<f64>();
+                2. * pairs / (m - 1.)
+            })
+            .sum::<f64>();
+        let expected = match self.metric {
+            Metric::Nominal => n * n - counts.iter().map(|count| count * count).sum::<f64>(),
+            Metric::Ordinal => (0..counts.len())
+                .filter(|&c| counts[c] > 0.)
+                .flat_map(|c| (0..counts.len()).map(move |k| (c, k)))
+                .map(|(c, k)| counts[c] * counts[k] * delta2(c, k))
+                .sum::<f64>(),
+            Metric::Interval => {
+                let mean = counts
+                    .iter()
+                    .zip(&self.values)
+                    .map(|(count, value)| count * value)
+                    .sum::<f64>()
+                    / n;
+                let sum_sq = counts
+                    .iter()
+                    .zip(&self.values)
+                    .map(|(count, value)| count * (value - mean).powi(2))
+                    .sum::<f64>();
+                2. * n * sum_sq
+            }
+        };
+        if expected > 0. {
+            1. - (n - 1.) * observed / expected
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+///
+/// Lin's concordance correlation coefficient (CCC) between paired measurements \\( (x_i, y_i) \\) of the same items
+/// (e.g., by two instruments or two methods), i.e., how far the pairs deviate from the identity line \\( y = x \\):
+/// \\[
+/// \rho_c = \frac{2 s_{xy}}{s_x^2 + s_y^2 + (\bar{x} - \bar{y})^2}
+/// \\]
+/// where the variances and the covariance are computed with the divisor \\( n \\).
+/// Unlike the Pearson correlation \\( r \\), the CCC also penalizes a systematic bias (location or scale shift) between the
+/// two measurements: \\( \rho_c = r \cdot C_b \\), where the bias correction factor \\( C_b \leq 1 \\) measures the accuracy.
+///
+/// The interval is computed on the Fisher z-transform \\( Z = \tanh^{-1}(\rho_c) \\), with the asymptotic variance of Lin (1989):
+/// \\[
+/// \sigma_Z^2 = \frac{1}{n - 2} \left( \frac{(1 - r^2) \rho_c^2}{(1 - \rho_c^2) r^2} +
+/// \frac{2 \rho_c^3 (1 - \rho_c) u^2}{r (1 - \rho_c^2)^2} - \frac{\rho_c^4 u^4}{2 r^2 (1 - \rho_c^2)^2} \right)
+/// \\]
+/// where \\( u = (\bar{y} - \bar{x}) / \sqrt{s_x s_y} \\).
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // blood pressure of 12 patients measured with a reference device and with a new device
+/// let pairs = [
+///     (102., 104.), (98., 97.), (110., 113.), (121., 125.), (95., 96.), (88., 91.),
+///     (130., 133.), (105., 103.), (99., 103.), (117., 119.), (108., 112.), (93., 94.),
+/// ];
+/// let ccc = agreement::ConcordanceCorrelation::from_iter(&pairs)?;
+/// assert_abs_diff_eq!(ccc.ccc(), 0.975138, epsilon = 1e-6);
+/// assert_abs_diff_eq!(ccc.pearson(), 0.989889, epsilon = 1e-6);
+/// let ci = ccc.ci(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.927545, 0.991605)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * L.I. Lin. "A concordance correlation coefficient to evaluate reproducibility." Biometrics 45(1): 255-268 (1989).
+/// * [Wikipedia - Concordance correlation coefficient](https://en.wikipedia.org/wiki/Concordance_correlation_coefficient)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConcordanceCorrelation {
+    sample_count: usize,
+    ccc: f64,
+    pearson: f64,
+    /// location shift relative to the scale \\( u \\)
+    shift: f64,
+}
+
+impl ConcordanceCorrelation {
+    ///
+    /// Compute the CCC from pairs of measurements of the same items.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 3 pairs
+    /// * [`CIError::InvalidInputData`] - if some measurement is not finite, or the measurements of either side are all equal
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<F: Float, I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let pairs = data
+            .into_iter()
+            .map(|&(x, y)| {
+                let (x, y) = (x.try_f64("x")?, y.try_f64("y")?);
+                if x.is_finite() && y.is_finite() {
+                    Ok((x, y))
+                } else {
+                    Err(CIError::InvalidInputData)
+                }
+            })
+            .collect::<CIResult<Vec<_>>>()?;
+        let sample_count = pairs.len();
+        if sample_count < 3 {
+            return Err(CIError::TooFewSamples(sample_count));
+        }
+        let n = sample_count as f64;
+        let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let (var_x, var_y, cov) = pairs.iter().fold((0., 0., 0.), |(sxx, syy, sxy), (x, y)| {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            (sxx + dx * dx / n, syy + dy * dy / n, sxy + dx * dy / n)
+        });
+        if var_x <= 0. || var_y <= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        let scale = (var_x * var_y).sqrt();
+        Ok(Self {
+            sample_count,
+            ccc: 2. * cov / (var_x + var_y + (mean_x - mean_y).powi(2)),
+            pearson: cov / scale,
+            shift: (mean_y - mean_x) / scale.sqrt(),
+        })
+    }
+
+    ///
+    /// Number of pairs
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    ///
+    /// Concordance correlation coefficient \\( \rho_c \\)
+    ///
+    pub fn ccc(&self) -> f64 {
+        self.ccc
+    }
+
+    ///
+    /// Pearson correlation \\( r \\) between the two measurements (precision)
+    ///
+    pub fn pearson(&self) -> f64 {
+        self.pearson
+    }
+
+    ///
+    /// Bias correction factor \\( C_b = \rho_c / r \\) (accuracy), equal to 1 when the two measurements have the same mean
+    /// and the same variance
+    ///
+    pub fn bias_correction(&self) -> f64 {
+        self.ccc / self.pearson
+    }
+
+    ///
+    /// Confidence interval of the CCC, based on the normal distribution of its Fisher z-transform.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidInputData`] - if the CCC is \\( \pm 1 \\) or the Pearson correlation is 0 (the variance is undefined)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let (rho, r, u2) = (self.ccc, self.pearson, self.shift * self.shift);
+        let rho2 = rho * rho;
+        let variance = ((1. - r * r) * rho2 * (1. - rho2) / (r * r)
+            + 2. * rho * rho2 * (1. - rho) * u2 / r
+            - rho2 * rho2 * u2 * u2 / (2. * r * r))
+            / (self.sample_count - 2) as f64;
+        let std_err = variance.sqrt() / (1. - rho2);
+        if !std_err.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        let z = rho.atanh();
+        let span = z_value(confidence)? * std_err;
+        let (lo, hi) = ((z - span).tanh(), (z + span).tanh());
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(-1., hi).map_err(|e| e.into()),
+        }
+    }
+}
+
+///
+/// Cronbach's alpha of the internal consistency of a scale of \\( k \\) items (e.g., the questions of a questionnaire),
+/// each answered by the same \\( n \\) respondents:
+/// \\[
+/// \alpha = \frac{k}{k - 1} \left( 1 - \frac{\sum_{j=1}^k s_j^2}{s_T^2} \right)
+/// \\]
+/// where \\( s_j^2 \\) is the sample variance of the scores of item \\( j \\) and \\( s_T^2 \\) that of the total scores of the respondents.
+///
+/// The interval is the exact interval of Feldt (1965), under the assumption of normally distributed scores:
+/// \\( (1 - \alpha) / (1 - \hat{\alpha}) \\) follows an F-distribution with \\( n - 1 \\) and \\( (n - 1)(k - 1) \\) degrees of freedom.
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // scores of 10 respondents to the 4 items of a Likert scale
+/// let scores = [
+///     [3, 4, 3, 5], [2, 2, 3, 2], [4, 5, 4, 4], [5, 5, 5, 4], [1, 2, 2, 1],
+///     [3, 3, 4, 3], [4, 4, 3, 5], [2, 3, 2, 2], [5, 4, 5, 5], [3, 2, 3, 3],
+/// ].map(|row| row.map(f64::from));
+/// let alpha = agreement::CronbachAlpha::from_scores(&scores)?;
+/// assert_abs_diff_eq!(alpha.alpha(), 0.922632, epsilon = 1e-6);
+/// let ci = alpha.ci(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.796456, 0.978416)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * L.J. Cronbach. "Coefficient alpha and the internal structure of tests." Psychometrika 16(3): 297-334 (1951).
+/// * L.S. Feldt. "The approximate sampling distribution of Kuder-Richardson reliability coefficient twenty."
+///   Psychometrika 30(3): 357-370 (1965).
+/// * [Wikipedia - Cronbach's alpha](https://en.wikipedia.org/wiki/Cronbach%27s_alpha)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CronbachAlpha {
+    respondents: usize,
+    items: usize,
+    alpha: f64,
+}
+
+impl CronbachAlpha {
+    ///
+    /// Compute Cronbach's alpha from the scores of each respondent to the items.
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - for each respondent, the score to each item (in the same order for every respondent)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 respondents
+    /// * [`CIError::DifferentSampleSizes`] - if the respondents do not all have the same number of items
+    /// * [`CIError::InvalidInputData`] - if there are less than 2 items, some score is not finite,
+    ///   or the total scores are all equal (the alpha is undefined)
+    /// * [`CIError::FloatConversionError`] - if some score cannot be converted to `f64`
+    ///
+    pub fn from_scores<F, I, R>(scores: &I) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[F]>,
+    {
+        let mut items = None;
+        let mut rows = Vec::new();
+        for row in scores {
+            let row = row.as_ref();
+            let k = *items.get_or_insert(row.len());
+            if row.len() != k {
+                return Err(CIError::DifferentSampleSizes(k, row.len()));
+            }
+            for score in row {
+                let score = score.try_f64("score")?;
+                if !score.is_finite() {
+                    return Err(CIError::InvalidInputData);
+                }
+                rows.push(score);
+            }
+        }
+        let items = items.unwrap_or(0);
+        let respondents = rows.len().checked_div(items).unwrap_or(0);
+        if respondents < 2 {
+            return Err(CIError::TooFewSamples(respondents));
+        }
+        if items < 2 {
+            return Err(CIError::InvalidInputData);
+        }
+        let variance = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+        };
+        let item_variances = (0..items)
+            .map(|j| {
+                variance(
+                    &rows
+                        .iter()
+                        .skip(j)
+                        .step_by(items)
+                        .copied()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .sum::<f64>();
+        let totals = rows
+            .chunks_exact(items)
+            .map(|row| row.iter().sum())
+            .collect::<Vec<f64>>();
+        let total_variance = variance(&totals);
+        if total_variance <= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        let k = items as f64;
+        Ok(Self {
+            respondents,
+            items,
+            alpha: k / (k - 1.) * (1. - item_variances / total_variance),
+        })
+    }
+
+    ///
+    /// Number of respondents \\( n \\)
+    ///
+    pub fn respondent_count(&self) -> usize {
+        self.respondents
+    }
+
+    ///
+    /// Number of items \\( k \\)
+    ///
+    pub fn item_count(&self) -> usize {
+        self.items
+    }
+
+    ///
+    /// Cronbach's alpha
+    ///
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    ///
+    /// Feldt confidence interval of the alpha:
+    /// \\[
+    /// \left[ 1 - (1 - \hat{\alpha}) F_{1 - \gamma/2}, 1 - (1 - \hat{\alpha}) F_{\gamma/2} \right]
+    /// \\]
+    /// where \\( F_p \\) is the quantile of the F-distribution with \\( n - 1 \\) and \\( (n - 1)(k - 1) \\) degrees of freedom
+    /// for a two-sided confidence level \\( 1 - \gamma \\).
+    /// The upper bound is clamped to 1.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let d1 = (self.respondents - 1) as f64;
+        let d2 = d1 * (self.items - 1) as f64;
+        let quantile = distributions::checked_quantile(confidence)?;
+        let bound = |p: f64| -> CIResult<f64> {
+            Ok(1. - (1. - self.alpha) * distributions::f_inverse_cdf(p, d1, d2)?)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => {
+                Interval::new(bound(quantile)?, bound(1. - quantile)?.min(1.)).map_err(|e| e.into())
+            }
+            Confidence::UpperOneSided(_) => {
+                Interval::new(bound(quantile)?, 1.).map_err(|e| e.into())
+            }
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(bound(1. - quantile)?.min(1.))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_fleiss_kappa() -> CIResult<()> {
+        // reference values computed in python
+        let counts = [
+            [0, 0, 0, 0, 14],
+            [0, 2, 6, 4, 2],
+            [0, 0, 3, 5, 6],
+            [0, 3, 9, 2, 0],
+            [2, 2, 8, 1, 1],
+            [7, 7, 0, 0, 0],
+            [3, 2, 6, 3, 0],
+            [2, 5, 3, 2, 2],
+            [6, 5, 2, 1, 0],
+            [0, 2, 2, 3, 7],
+        ];
+        let kappa = FleissKappa::from_counts(&counts)?;
+        assert_eq!(kappa.item_count(), 10);
+        assert_eq!(kappa.category_count(), 5);
+        assert_abs_diff_eq!(kappa.kappa(), 0.20993070442195522, epsilon = 1e-12);
+        assert_abs_diff_eq!(kappa.std_err(), 0.09237111160600824, epsilon = 1e-12);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            kappa.ci(confidence)?,
+            Interval::new(0.02888665246224928, 0.3909747563816611)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            kappa.ci(Confidence::new_upper(0.95))?,
+            Interval::new(0.05799374647127334, 1.)?,
+            epsilon = 1e-9
+        );
+        let bootstrap = Bootstrap::new(2_000, 42);
+        assert_abs_diff_eq!(
+            kappa.ci_bootstrap(confidence, &bootstrap)?,
+            Interval::new(0.04036557501904042, 0.3639531989046553)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            kappa.ci_bootstrap(Confidence::new_upper(0.95), &bootstrap)?,
+            Interval::new(0.05574030252170922, 1.)?,
+            epsilon = 1e-9
+        );
+
+        // items rated by different numbers of raters
+        let counts = vec![vec![3, 0], vec![1, 2], vec![0, 2], vec![4, 1]];
+        assert!(FleissKappa::from_counts(&counts)?.kappa().is_finite());
+
+        assert!(matches!(
+            FleissKappa::from_counts(&[[2, 1]]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            FleissKappa::from_counts(&[[2, 1], [1, 0]]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            FleissKappa::from_counts(&[[3, 0], [3, 0]]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            FleissKappa::from_counts(&vec![vec![2, 1], vec![1, 1, 1]]),
+            Err(CIError::DifferentSampleSizes(2, 3))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_krippendorff_alpha() -> CIResult<()> {
+        // reference values computed in python
+        let ratings = [
+            [
+                Some(1.),
+                Some(2.),
+                Some(3.),
+                Some(3.),
+                Some(2.),
+                Some(1.),
+                Some(4.),
+                Some(1.),
+                Some(2.),
+                None,
+                None,
+                None,
+            ],
+            [
+                Some(1.),
+                Some(2.),
+                Some(3.),
+                Some(3.),
+                Some(2.),
+                Some(2.),
+                Some(4.),
+                Some(1.),
+                Some(2.),
+                Some(5.),
+                None,
+                Some(3.),
+            ],
+            [
+                None,
+                Some(3.),
+                Some(3.),
+                Some(3.),
+                Some(2.),
+                Some(3.),
+                Some(4.),
+                Some(2.),
+                Some(2.),
+                Some(5.),
+                Some(1.),
+                None,
+            ],
+            [
+                Some(1.),
+                Some(2.),
+                Some(3.),
+                Some(3.),
+                Some(2.),
+                Some(4.),
+                Some(4.),
+                Some(1.),
+                Some(2.),
+                Some(5.),
+                Some(1.),
+                None,
+            ],
+        ];
+        let units = (0..12)
+            .map(|u| ratings.iter().map(|rater| rater[u]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let bootstrap = Bootstrap::new(1_000, 7);
+        let confidence = Confidence::new_two_sided(0.95);
+        for (metric, expected, lo) in [
+            (Metric::Nominal, 0.743421052631579, 0.4133545310015898),
+            (Metric::Ordinal, 0.8153875037548813, 0.391843298820043),
+            (Metric::Interval, 0.8491071428571428, 0.39021074099252206),
+        ] {
+            let alpha = KrippendorffAlpha::from_units(&units, metric)?;
+            assert_eq!(alpha.unit_count(), 11);
+            assert_eq!(alpha.pairable_count(), 40);
+            assert_eq!(alpha.metric(), metric);
+            assert_abs_diff_eq!(alpha.alpha(), expected, epsilon = 1e-12);
+            assert_abs_diff_eq!(
+                alpha.ci_bootstrap(confidence, &bootstrap)?,
+                Interval::new(lo, 1.)?,
+                epsilon = 1e-9
+            );
+        }
+        let alpha = KrippendorffAlpha::from_units(&units, Metric::Interval)?;
+        assert_abs_diff_eq!(
+            alpha.ci_bootstrap(Confidence::new_upper(0.9), &bootstrap)?,
+            Interval::new(0.584516129032258, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            alpha
+                .ci_bootstrap(Confidence::new_lower(0.9), &bootstrap)?
+                .high_f(),
+            0.9750164365548981,
+            epsilon = 1e-9
+        );
+
+        assert!(matches!(
+            KrippendorffAlpha::from_units(&[[Some(1.), None], [None, Some(2.)]], Metric::Nominal),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            KrippendorffAlpha::from_units(
+                &[[Some(1.), Some(1.)], [Some(1.), Some(1.)]],
+                Metric::Ordinal
+            ),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            KrippendorffAlpha::from_units(&[[Some(1.), Some(f64::NAN)]], Metric::Interval),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_concordance_correlation() -> CIResult<()> {
+        // reference values computed in python
+        let pairs = [
+            (102., 104.),
+            (98., 97.),
+            (110., 113.),
+            (121., 125.),
+            (95., 96.),
+            (88., 91.),
+            (130., 133.),
+            (105., 103.),
+            (99., 103.),
+            (117., 119.),
+            (108., 112.),
+            (93., 94.),
+        ];
+        let ccc = ConcordanceCorrelation::from_iter(&pairs)?;
+        assert_eq!(ccc.sample_count(), 12);
+        assert_abs_diff_eq!(ccc.ccc(), 0.9751381215469616, epsilon = 1e-12);
+        assert_abs_diff_eq!(ccc.pearson(), 0.9898893978096579, epsilon = 1e-12);
+        assert_abs_diff_eq!(ccc.bias_correction(), 0.9850980561107769, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            ccc.ci(Confidence::new_two_sided(0.95))?,
+            Interval::new(0.9275454543000669, 0.9916051071560664)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ccc.ci(Confidence::new_upper(0.95))?,
+            Interval::new(0.9388743871670843, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ccc.ci(Confidence::new_lower(0.95))?,
+            Interval::new(-1., 0.9899987980553363)?,
+            epsilon = 1e-9
+        );
+
+        // identical measurements agree perfectly, but the interval is degenerate
+        let identical = ConcordanceCorrelation::from_iter(&[(1., 1.), (2., 2.), (4., 4.)])?;
+        assert_eq!(identical.ccc(), 1.);
+        assert!(matches!(
+            identical.ci(Confidence::new_two_sided(0.95)),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ConcordanceCorrelation::from_iter(&[(1., 2.), (1., 3.), (1., 4.)]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ConcordanceCorrelation::from_iter(&[(1., 2.), (2., 3.)]),
+            Err(CIError::TooFewSamples(2))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cronbach_alpha() -> CIResult<()> {
+        // reference values computed in python
+        let scores = [
+            [3., 4., 3., 5.],
+            [2., 2., 3., 2.],
+            [4., 5., 4., 4.],
+            [5., 5., 5., 4.],
+            [1., 2., 2., 1.],
+            [3., 3., 4., 3.],
+            [4., 4., 3., 5.],
+            [2., 3., 2., 2.],
+            [5., 4., 5., 5.],
+            [3., 2., 3., 3.],
+        ];
+        let alpha = CronbachAlpha::from_scores(&scores)?;
+        assert_eq!(alpha.respondent_count(), 10);
+        assert_eq!(alpha.item_count(), 4);
+        assert_abs_diff_eq!(alpha.alpha(), 0.9226319595083152, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            alpha.ci(Confidence::new_two_sided(0.95))?,
+            Interval::new(0.7964558583458128, 0.9784156589417629)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            alpha.ci(Confidence::new_upper(0.95))?,
+            Interval::new(0.8259117367601695, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            alpha.ci(Confidence::new_lower(0.95))?,
+            Interval::new_lower(0.9731372599560644),
+            epsilon = 1e-9
+        );
+
+        // uncorrelated items
+        let alpha = CronbachAlpha::from_scores(&[[1., 1.], [1., 3.], [3., 1.], [3., 3.]])?;
+        assert_abs_diff_eq!(alpha.alpha(), 0., epsilon = 1e-12);
+
+        assert!(matches!(
+            CronbachAlpha::from_scores(&[vec![1., 2.], vec![2., 1., 3.]]),
+            Err(CIError::DifferentSampleSizes(2, 3))
+        ));
+        assert!(matches!(
+            CronbachAlpha::from_scores(&[[1., 2.]]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            CronbachAlpha::from_scores(&[[1.], [2.]]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
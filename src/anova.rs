@@ -0,0 +1,1119 @@
+//!
+//! Confidence intervals for one-way analysis of variance (ANOVA) designs, i.e., comparisons of the means of
+//! \\( k \geq 2 \\) independent groups (e.g., several variants of an experiment).
+//!
+//! * [`OneWay`] - the F-test decomposition of the variance between and within the groups, and the proportion of the variance
+//!   explained by the groups (eta-squared and omega-squared) with an interval based on the noncentral F-distribution.
+//! * [`OneWay::ci_contrast`] - intervals of linear contrasts of the group means (e.g., the mean of the treatments vs. a
+//!   control), with the pooled variance or the separate variances of the groups (see [`ContrastVariance`]).
+//! * [`OneWay::ci_scheffe`] - intervals of linear contrasts of the group means that hold simultaneously for all the
+//!   contrasts (Scheffé's method), e.g., for contrasts chosen after looking at the data.
+//! * [`OneWay::ci_tukey_hsd`] - simultaneous intervals of the differences between all pairs of group means (Tukey's honestly
+//!   significant difference), based on the studentized range distribution, and their counterparts for groups with unequal
+//!   variances ([`OneWay::ci_games_howell`]).
+//! * [`OneWay::ci_dunnett`] - simultaneous intervals of the differences between the mean of each group and the mean of a
+//!   control group (Dunnett's many-to-one comparisons), which are narrower than the pairwise intervals.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // completion times of a task with three variants of a user interface
+//! let groups = [
+//!     vec![23., 25., 21., 27., 24., 26.],
+//!     vec![28., 30., 27., 31., 29., 26., 32.],
+//!     vec![22., 20., 24., 23., 21.],
+//! ];
+//! let anova = anova::OneWay::from_groups(&groups)?;
+//! assert!(anova.omega_squared() < anova.eta_squared());
+//! let ci = anova.ci_variance_explained(Confidence::new_two_sided(0.95))?;
+//! assert!(ci.contains(&anova.eta_squared()));
+//!
+//! // the first variant vs. the mean of the other two
+//! let ci = anova.ci_contrast(
+//!     Confidence::new_two_sided(0.95),
+//!     &[1., -0.5, -0.5],
+//!     anova::ContrastVariance::Welch,
+//! )?;
+//! assert!(ci.contains(&anova.contrast(&[1., -0.5, -0.5])?));
+//!
+//! // the same contrast, chosen after looking at the data
+//! let scheffe = anova.ci_scheffe(Confidence::new_two_sided(0.95), &[1., -0.5, -0.5])?;
+//! assert!(scheffe.includes(&anova.ci_contrast(
+//!     Confidence::new_two_sided(0.95),
+//!     &[1., -0.5, -0.5],
+//!     anova::ContrastVariance::Pooled,
+//! )?));
+//!
+//! // all pairwise differences, with a 95% confidence that all the intervals hold together
+//! let pairs = anova.ci_tukey_hsd(Confidence::new_two_sided(0.95))?;
+//! assert_eq!(pairs.len(), 3);
+//! assert_eq!(pairs[0].groups(), (0, 1));
+//! assert!(!pairs[0].interval().contains(&0.));
+//!
+//! // the two other variants vs. the first one
+//! let differences = anova.ci_dunnett(Confidence::new_two_sided(0.95), 0)?;
+//! assert_eq!(differences.len(), 2);
+//! assert_eq!(differences[0].groups(), (1, 0));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * C.W. Dunnett. "A multiple comparison procedure for comparing several treatments with a control."
+//!   Journal of the American Statistical Association 50(272): 1096-1121 (1955).
+//! * P.A. Games, J.F. Howell. "Pairwise multiple comparison procedures with unequal n's and/or variances: a Monte Carlo
+//!   study." Journal of Educational Statistics 1(2): 113-125 (1976).
+//! * C.Y. Kramer. "Extension of multiple range tests to group means with unequal numbers of replications."
+//!   Biometrics 12(3): 307-310 (1956).
+//! * H. Scheffé. "A method for judging all contrasts in the analysis of variance." Biometrika 40(1-2): 87-110 (1953).
+//! * J.H. Steiger. "Beyond the F test: effect size confidence intervals and tests of close fit in the analysis of variance
+//!   and contrast analysis." Psychological Methods 9(2): 164-182 (2004).
+//! * [Wikipedia - One-way analysis of variance](https://en.wikipedia.org/wiki/One-way_analysis_of_variance)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Estimator of the variance of a contrast of the group means (see [`OneWay::ci_contrast`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContrastVariance {
+    ///
+    /// Pooled variance of the groups \\( MS_W \\), with \\( N - k \\) degrees of freedom, assuming that the groups have
+    /// equal variances.
+    ///
+    #[default]
+    Pooled,
+
+    ///
+    /// Separate variance \\( s_i^2 \\) of each group, with the Welch–Satterthwaite approximation of the degrees of freedom.
+    /// It does not assume equal variances, but requires at least 2 observations in each group involved in the contrast.
+    ///
+    Welch,
+}
+
+///
+/// Difference between the means of two groups, with its confidence interval (see [`OneWay::ci_tukey_hsd`],
+/// [`OneWay::ci_games_howell`], and [`OneWay::ci_dunnett`]).
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairwiseDifference {
+    groups: (usize, usize),
+    difference: f64,
+    interval: Interval<f64>,
+}
+
+impl PairwiseDifference {
+    ///
+    /// Indices \\( (i, j) \\) of the two groups
+    ///
+    pub fn groups(&self) -> (usize, usize) {
+        self.groups
+    }
+
+    ///
+    /// Difference \\( \bar{x}_i - \bar{x}_j \\) between the means of the two groups
+    ///
+    pub fn difference(&self) -> f64 {
+        self.difference
+    }
+
+    ///
+    /// Confidence interval of the difference between the means of the two groups
+    ///
+    pub fn interval(&self) -> &Interval<f64> {
+        &self.interval
+    }
+}
+
+///
+/// One-way ANOVA of \\( k \\) independent groups, with \\( n_i \\) observations, mean \\( \bar{x}_i \\), and sample variance
+/// \\( s_i^2 \\) in group \\( i \\), and \\( N = \sum_i n_i \\) observations in total.
+///
+/// The total sum of squares is decomposed between the groups and within the groups:
+/// \\[
+/// SS_B = \sum_{i=1}^k n_i (\bar{x}_i - \bar{x})^2 \qquad SS_W = \sum_{i=1}^k (n_i - 1) s_i^2
+/// \\]
+/// with \\( k - 1 \\) and \\( N - k \\) degrees of freedom, and the F statistic is
+/// \\( F = \frac{SS_B / (k - 1)}{SS_W / (N - k)} \\).
+///
+/// The proportion of the variance explained by the groups is estimated by
+/// \\[
+/// \eta^2 = \frac{SS_B}{SS_B + SS_W} \qquad \omega^2 = \frac{SS_B - (k - 1) MS_W}{SS_B + SS_W + MS_W}
+/// \\]
+/// where \\( MS_W = SS_W / (N - k) \\). Eta-squared is biased upward, while omega-squared is nearly unbiased (and may be negative).
+///
+/// Both estimate the same population parameter, whose interval ([`OneWay::ci_variance_explained`]) is obtained by inverting the
+/// noncentral F-distribution: the bounds \\( \lambda \\) on the noncentrality parameter, for which the observed F statistic is the
+/// upper and lower critical value, translate into bounds \\( \lambda / (\lambda + N) \\) on the proportion of variance explained.
+///
+/// A linear contrast \\( \psi = \sum_i c_i \mu_i \\) of the group means, with coefficients summing to zero,
+/// is estimated by \\( \hat{\psi} = \sum_i c_i \bar{x}_i \\) ([`OneWay::contrast`]). Its interval
+/// ([`OneWay::ci_contrast`]) is based on the t-distribution with the standard error
+/// \\[
+/// \sqrt{MS_W \sum_{i=1}^k \frac{c_i^2}{n_i}} \qquad \text{or} \qquad \sqrt{\sum_{i=1}^k \frac{c_i^2 s_i^2}{n_i}}
+/// \\]
+/// for the pooled and the Welch variance respectively (see [`ContrastVariance`]).
+/// The intervals of Scheffé ([`OneWay::ci_scheffe`]) replace the quantile of the t-distribution with
+/// \\( \sqrt{(k - 1) F_{k - 1, N - k}} \\), where \\( F_{k - 1, N - k} \\) is the quantile of the F-distribution at the confidence
+/// level, so that they hold simultaneously for all the contrasts (with the pooled variance).
+///
+/// The differences between all pairs of group means have simultaneous intervals ([`OneWay::ci_tukey_hsd`])
+/// \\[
+/// \bar{x}_i - \bar{x}_j \pm \frac{q_{k, N - k}}{\sqrt{2}} \sqrt{MS_W \left( \frac{1}{n_i} + \frac{1}{n_j} \right)}
+/// \\]
+/// where \\( q_{k, N - k} \\) is the quantile of the studentized range distribution of \\( k \\) means with \\( N - k \\) degrees
+/// of freedom at the confidence level (Tukey–Kramer).
+/// When the groups have unequal variances, the intervals of Games and Howell ([`OneWay::ci_games_howell`])
+/// \\[
+/// \bar{x}_i - \bar{x}_j \pm \frac{q_{k, \nu_{ij}}}{\sqrt{2}} \sqrt{\frac{s_i^2}{n_i} + \frac{s_j^2}{n_j}}
+/// \\]
+/// use the separate variances of the groups, with the Welch–Satterthwaite approximation \\( \nu_{ij} \\) of the degrees of freedom.
+///
+/// The differences between the mean of each group and the mean of a control group \\( c \\) have narrower simultaneous
+/// intervals ([`OneWay::ci_dunnett`])
+/// \\[
+/// \bar{x}_i - \bar{x}_c \pm d \sqrt{MS_W \left( \frac{1}{n_i} + \frac{1}{n_c} \right)}
+/// \\]
+/// where \\( d \\) is the quantile of the maximum (of the absolute values, for two-sided intervals) of the
+/// \\( k - 1 \\) statistics, which follow a multivariate t-distribution with \\( N - k \\) degrees of freedom and correlations
+/// \\( \rho_{ij} = \lambda_i \lambda_j \\), with \\( \lambda_i = \sqrt{n_i / (n_i + n_c)} \\) (Dunnett).
+///
+/// # Notes
+///
+/// The groups are assumed to be drawn from normal distributions with equal variances
+/// (except for the contrasts with [`ContrastVariance::Welch`] and the intervals of [`OneWay::ci_games_howell`]).
+///
+/// The contrast intervals of [`OneWay::ci_contrast`] are not adjusted for multiple comparisons.
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let groups = [
+///     vec![23., 25., 21., 27., 24., 26.],
+///     vec![28., 30., 27., 31., 29., 26., 32.],
+///     vec![22., 20., 24., 23., 21.],
+///     vec![26., 27., 25., 29., 28., 24.],
+/// ];
+/// let anova = anova::OneWay::from_groups(&groups)?;
+/// assert_abs_diff_eq!(anova.f_statistic(), 13.502467, epsilon = 1e-6);
+/// assert_abs_diff_eq!(anova.eta_squared(), 0.669462, epsilon = 1e-6);
+/// assert_abs_diff_eq!(anova.omega_squared(), 0.609803, epsilon = 1e-6);
+/// let ci = anova.ci_variance_explained(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.312682, 0.770436)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneWay {
+    counts: Vec<usize>,
+    means: Vec<f64>,
+    variances: Vec<f64>,
+}
+
+impl OneWay {
+    ///
+    /// Compute the ANOVA from the observations of each group.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - the observations of each group
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if there are less than 2 groups, some value is not finite,
+    ///   or all the observations are equal within every group
+    /// * [`CIError::TooFewSamples`] - if some group is empty, or there are no more observations than groups
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    pub fn from_groups<F, I, R>(groups: &I) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[F]>,
+    {
+        let mut counts = Vec::new();
+        let mut means = Vec::new();
+        let mut variances = Vec::new();
+        for group in groups {
+            let values = group
+                .as_ref()
+                .iter()
+                .map(|value| {
+                    let value = value.try_f64("value")?;
+                    if value.is_finite() {
+                        Ok(value)
+                    } else {
+                        Err(CIError::InvalidInputData)
+                    }
+                })
+                .collect::<CIResult<Vec<_>>>()?;
+            let n = values.len();
+            if n == 0 {
+                return Err(CIError::TooFewSamples(0));
+            }
+            let mean = values.iter().sum::<f64>() / n as f64;
+            let variance = if n > 1 {
+                values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+            } else {
+                0.
+            };
+            counts.push(n);
+            means.push(mean);
+            variances.push(variance);
+        }
+        if counts.len() < 2 {
+            return Err(CIError::InvalidInputData);
+        }
+        let anova = Self {
+            counts,
+            means,
+            variances,
+        };
+        if anova.sample_count() <= anova.group_count() {
+            return Err(CIError::TooFewSamples(anova.sample_count()));
+        }
+        if anova.ss_within() <= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(anova)
+    }
+
+    ///
+    /// Number of groups \\( k \\)
+    ///
+    pub fn group_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    ///
+    /// Total number of observations \\( N \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    ///
+    /// Number of observations of each group
+    ///
+    pub fn group_sizes(&self) -> &[usize] {
+        &self.counts
+    }
+
+    ///
+    /// Mean of each group
+    ///
+    pub fn group_means(&self) -> &[f64] {
+        &self.means
+    }
+
+    ///
+    /// Grand mean \\( \bar{x} \\) of all the observations
+    ///
+    pub fn grand_mean(&self) -> f64 {
+        self.counts
+            .iter()
+            .zip(&self.means)
+            .map(|(&n, mean)| n as f64 * mean)
+            .sum::<f64>()
+            / self.sample_count() as f64
+    }
+
+    ///
+    /// Sum of squares between the groups \\( SS_B \\)
+    ///
+    pub fn ss_between(&self) -> f64 {
+        let grand_mean = self.grand_mean();
+        self.counts
+            .iter()
+            .zip(&self.means)
+            .map(|(&n, mean)| n as f64 * (mean - grand_mean).powi(2))
+            .sum()
+    }
+
+    ///
+    /// Sum of squares within the groups \\( SS_W \\)
+    ///
+    pub fn ss_within(&self) -> f64 {
+        self.counts
+            .iter()
+            .zip(&self.variances)
+            .map(|(&n, variance)| (n - 1) as f64 * variance)
+            .sum()
+    }
+
+    ///
+    /// Degrees of freedom between the groups \\( k - 1 \\)
+    ///
+    pub fn df_between(&self) -> f64 {
+        (self.group_count() - 1) as f64
+    }
+
+    ///
+    /// Degrees of freedom within the groups \\( N - k \\)
+    ///
+    pub fn df_within(&self) -> f64 {
+        (self.sample_count() - self.group_count()) as f64
+    }
+
+    ///
+    /// Mean square within the groups \\( MS_W = SS_W / (N - k) \\), i.e., the pooled estimate of the variance of the groups
+    ///
+    pub fn ms_within(&self) -> f64 {
+        self.ss_within() / self.df_within()
+    }
+
+    ///
+    /// F statistic \\( F = MS_B / MS_W \\)
+    ///
+    pub fn f_statistic(&self) -> f64 {
+        self.ss_between() / self.df_between() / self.ms_within()
+    }
+
+    ///
+    /// Eta-squared \\( \eta^2 = SS_B / (SS_B + SS_W) \\), the proportion of the variance of the sample explained by the groups
+    ///
+    pub fn eta_squared(&self) -> f64 {
+        let ss_between = self.ss_between();
+        ss_between / (ss_between + self.ss_within())
+    }
+
+    ///
+    /// Omega-squared \\( \omega^2 = (SS_B - (k - 1) MS_W) / (SS_B + SS_W + MS_W) \\), a nearly unbiased estimate of the
+    /// proportion of the variance of the population explained by the groups
+    ///
+    pub fn omega_squared(&self) -> f64 {
+        let ms_within = self.ms_within();
+        (self.ss_between() - self.df_between() * ms_within)
+            / (self.ss_between() + self.ss_within() + ms_within)
+    }
+
+    ///
+    /// Confidence interval of the proportion of the variance of the population explained by the groups (the population
+    /// eta-squared, which is also estimated by omega-squared), based on the noncentral F-distribution (Steiger, 2004).
+    ///
+    /// The lower bound is 0 when the F statistic is too small to reject the equality of the means at the confidence level.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_variance_explained(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let quantile = distributions::checked_quantile(confidence)?;
+        let n = self.sample_count() as f64;
+        let proportion = |p: f64| {
+            let lambda = self.invert_noncentrality(p);
+            lambda / (lambda + n)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => {
+                Interval::new(proportion(quantile), proportion(1. - quantile)).map_err(|e| e.into())
+            }
+            Confidence::UpperOneSided(_) => {
+                Interval::new(proportion(quantile), 1.).map_err(|e| e.into())
+            }
+            Confidence::LowerOneSided(_) => {
+                Interval::new(0., proportion(1. - quantile)).map_err(|e| e.into())
+            }
+        }
+    }
+
+    ///
+    /// Estimate \\( \hat{\psi} = \sum_i c_i \bar{x}_i \\) of the linear contrast of the group means with the given coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficients` - the coefficient \\( c_i \\) of each group, summing to zero (e.g., `[-1., 0.5, 0.5]` compares the
+    ///   first group to the mean of the two others)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the number of coefficients differs from the number of groups
+    /// * [`CIError::InvalidInputData`] - if some coefficient is not finite, or the coefficients are all zero or do not sum to zero
+    ///
+    pub fn contrast(&self, coefficients: &[f64]) -> CIResult<f64> {
+        self.check_contrast(coefficients)?;
+        Ok(coefficients
+            .iter()
+            .zip(&self.means)
+            .map(|(c, mean)| c * mean)
+            .sum())
+    }
+
+    ///
+    /// Confidence interval of the linear contrast of the group means with the given coefficients, based on the t-distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `coefficients` - the coefficient \\( c_i \\) of each group, summing to zero
+    /// * `variance` - the estimator of the variance of the contrast
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the number of coefficients differs from the number of groups
+    /// * [`CIError::InvalidInputData`] - if some coefficient is not finite, or the coefficients are all zero or do not sum to zero
+    /// * [`CIError::TooFewSamples`] - with the Welch variance, if a group with a nonzero coefficient has less than 2 observations
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_contrast(
+        &self,
+        confidence: Confidence,
+        coefficients: &[f64],
+        variance: ContrastVariance,
+    ) -> CIResult<Interval<f64>> {
+        let estimate = self.contrast(coefficients)?;
+        let weights = coefficients
+            .iter()
+            .zip(&self.counts)
+            .map(|(c, &n)| c * c / n as f64);
+        let (std_err, dof) = match variance {
+            ContrastVariance::Pooled => (
+                (self.ms_within() * weights.sum::<f64>()).sqrt(),
+                self.df_within(),
+            ),
+            ContrastVariance::Welch => {
+                let mut sum = 0.;
+                let mut sum_squares = 0.;
+                for ((weight, variance), &n) in weights.zip(&self.variances).zip(&self.counts) {
+                    if weight == 0. {
+                        continue;
+                    }
+                    if n < 2 {
+                        return Err(CIError::TooFewSamples(n));
+                    }
+                    let term = weight * variance;
+                    sum += term;
+                    sum_squares += term * term / (n - 1) as f64;
+                }
+                if sum <= 0. {
+                    return Err(CIError::InvalidInputData);
+                }
+                (sum.sqrt(), sum * sum / sum_squares)
+            }
+        };
+        let (lo, hi) = stats::interval_bounds(confidence, estimate, std_err, dof)?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Confidence interval of the linear contrast of the group means with the given coefficients, with Scheffé's method,
+    /// i.e., the intervals of all the possible contrasts hold together with the given confidence. It is the safe choice
+    /// when the contrasts are chosen after looking at the data.
+    ///
+    /// One-sided intervals are the corresponding bounds of the two-sided intervals with the same confidence level,
+    /// which are conservative.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `coefficients` - the coefficient \\( c_i \\) of each group, summing to zero
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the number of coefficients differs from the number of groups
+    /// * [`CIError::InvalidInputData`] - if some coefficient is not finite, or the coefficients are all zero or do not sum to zero
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_scheffe(
+        &self,
+        confidence: Confidence,
+        coefficients: &[f64],
+    ) -> CIResult<Interval<f64>> {
+        let estimate = self.contrast(coefficients)?;
+        distributions::checked_quantile(confidence)?;
+        let f =
+            distributions::f_inverse_cdf(confidence.level(), self.df_between(), self.df_within())?;
+        let std_err = (self.ms_within()
+            * coefficients
+                .iter()
+                .zip(&self.counts)
+                .map(|(c, &n)| c * c / n as f64)
+                .sum::<f64>())
+        .sqrt();
+        let span = (self.df_between() * f).sqrt() * std_err;
+        let (lo, hi) = (estimate - span, estimate + span);
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Simultaneous confidence intervals of the differences between the means of all the pairs of groups, with Tukey's
+    /// honestly significant difference (the Tukey–Kramer intervals when the groups have different sizes), i.e., the intervals
+    /// hold together with the given confidence.
+    ///
+    /// The pairs \\( (i, j) \\) with \\( i < j \\) are in lexicographic order.
+    /// One-sided intervals are the corresponding bounds of the two-sided intervals with the same confidence level,
+    /// which are conservative.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_tukey_hsd(&self, confidence: Confidence) -> CIResult<Vec<PairwiseDifference>> {
+        distributions::checked_quantile(confidence)?;
+        let q = special::studentized_range_inverse_cdf(
+            confidence.level(),
+            self.group_count(),
+            self.df_within(),
+        );
+        let ms_within = self.ms_within();
+        let mut pairs = Vec::new();
+        for i in 0..self.group_count() {
+            for j in i + 1..self.group_count() {
+                let difference = self.means[i] - self.means[j];
+                let std_err =
+                    (ms_within * (1. / self.counts[i] as f64 + 1. / self.counts[j] as f64) / 2.)
+                        .sqrt();
+                let (lo, hi) = (difference - q * std_err, difference + q * std_err);
+                let interval = match confidence {
+                    Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+                    Confidence::UpperOneSided(_) => Interval::new_upper(lo),
+                    Confidence::LowerOneSided(_) => Interval::new_lower(hi),
+                };
+                pairs.push(PairwiseDifference {
+                    groups: (i, j),
+                    difference,
+                    interval,
+                });
+            }
+        }
+        Ok(pairs)
+    }
+
+    ///
+    /// Simultaneous confidence intervals of the differences between the means of all the pairs of groups, with the procedure
+    /// of Games and Howell, which does not assume that the groups have equal variances (unlike [`OneWay::ci_tukey_hsd`]).
+    /// The confidence level is approximate, and slightly liberal for small groups.
+    ///
+    /// The pairs \\( (i, j) \\) with \\( i < j \\) are in lexicographic order.
+    /// One-sided intervals are the corresponding bounds of the two-sided intervals with the same confidence level.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if some group has less than 2 observations
+    /// * [`CIError::InvalidInputData`] - if all the observations are equal within both groups of some pair
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_games_howell(&self, confidence: Confidence) -> CIResult<Vec<PairwiseDifference>> {
+        distributions::checked_quantile(confidence)?;
+        if let Some(&n) = self.counts.iter().find(|&&n| n < 2) {
+            return Err(CIError::TooFewSamples(n));
+        }
+        let mut pairs = Vec::new();
+        for i in 0..self.group_count() {
+            for j in i + 1..self.group_count() {
+                let difference = self.means[i] - self.means[j];
+                let (n_i, n_j) = (self.counts[i] as f64, self.counts[j] as f64);
+                let (v_i, v_j) = (self.variances[i] / n_i, self.variances[j] / n_j);
+                let variance = v_i + v_j;
+                if variance <= 0. {
+                    return Err(CIError::InvalidInputData);
+                }
+                let dof = variance * variance / (v_i * v_i / (n_i - 1.) + v_j * v_j / (n_j - 1.));
+                let q = special::studentized_range_inverse_cdf(
+                    confidence.level(),
+                    self.group_count(),
+                    dof,
+                );
+                let span = q * (variance / 2.).sqrt();
+                let (lo, hi) = (difference - span, difference + span);
+                let interval = match confidence {
+                    Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+                    Confidence::UpperOneSided(_) => Interval::new_upper(lo),
+                    Confidence::LowerOneSided(_) => Interval::new_lower(hi),
+                };
+                pairs.push(PairwiseDifference {
+                    groups: (i, j),
+                    difference,
+                    interval,
+                });
+            }
+        }
+        Ok(pairs)
+    }
+
+    ///
+    /// Simultaneous confidence intervals of the differences between the mean of each group and the mean of the control group,
+    /// with Dunnett's procedure, i.e., the intervals hold together with the given confidence.
+    ///
+    /// The differences are in the order of the groups (skipping the control group), with the indices `(i, control)`.
+    /// Unlike [`OneWay::ci_tukey_hsd`], one-sided intervals have an exact simultaneous confidence level (e.g., to show that
+    /// all the treatments improve on the control).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `control` - the index of the control group
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the index of the control group is out of range
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_dunnett(
+        &self,
+        confidence: Confidence,
+        control: usize,
+    ) -> CIResult<Vec<PairwiseDifference>> {
+        if control >= self.group_count() {
+            return Err(CIError::InvalidInputData);
+        }
+        distributions::checked_quantile(confidence)?;
+        let n_control = self.counts[control] as f64;
+        let treatments = (0..self.group_count())
+            .filter(|&i| i != control)
+            .collect::<Vec<_>>();
+        let weights = treatments
+            .iter()
+            .map(|&i| {
+                let n = self.counts[i] as f64;
+                (n / (n + n_control)).sqrt()
+            })
+            .collect::<Vec<_>>();
+        let d = special::dunnett_inverse_cdf(
+            confidence.level(),
+            &weights,
+            self.df_within(),
+            confidence.is_two_sided(),
+        );
+        let ms_within = self.ms_within();
+        treatments
+            .into_iter()
+            .map(|i| {
+                let difference = self.means[i] - self.means[control];
+                let std_err = (ms_within * (1. / self.counts[i] as f64 + 1. / n_control)).sqrt();
+                let (lo, hi) = (difference - d * std_err, difference + d * std_err);
+                let interval = match confidence {
+                    Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+                    Confidence::UpperOneSided(_) => Interval::new_upper(lo),
+                    Confidence::LowerOneSided(_) => Interval::new_lower(hi),
+                };
+                Ok(PairwiseDifference {
+                    groups: (i, control),
+                    difference,
+                    interval,
+                })
+            })
+            .collect()
+    }
+
+    ///
+    /// Check that the coefficients define a contrast of the groups.
+    ///
+    fn check_contrast(&self, coefficients: &[f64]) -> CIResult<()> {
+        if coefficients.len() != self.group_count() {
+            return Err(CIError::DifferentSampleSizes(
+                self.group_count(),
+                coefficients.len(),
+            ));
+        }
+        if coefficients.iter().any(|c| !c.is_finite()) {
+            return Err(CIError::InvalidInputData);
+        }
+        let scale = coefficients.iter().map(|c| c.abs()).sum::<f64>();
+        let sum = coefficients.iter().sum::<f64>();
+        if scale == 0. || sum.abs() > 1e-9 * scale {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(())
+    }
+
+    ///
+    /// Find the noncentrality \\( \lambda \geq 0 \\) such that \\( P(F \leq F_{obs} \mid \lambda) = p \\), by bisection
+    /// (the probability decreases with \\( \lambda \\)), or 0 if the probability is below `p` for the central F-distribution.
+    ///
+    fn invert_noncentrality(&self, p: f64) -> f64 {
+        let (f, d1, d2) = (self.f_statistic(), self.df_between(), self.df_within());
+        let cdf = |lambda: f64| special::noncentral_f_cdf(f, d1, d2, lambda) - p;
+        if cdf(0.) <= 0. {
+            return 0.;
+        }
+        let (mut lo, mut hi) = (0., 1.);
+        while cdf(hi) > 0. {
+            lo = hi;
+            hi *= 2.;
+        }
+        let tolerance = 1e-12 * hi.max(1.);
+        while hi - lo > tolerance {
+            let mid = (lo + hi) / 2.;
+            if cdf(mid) > 0. {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        (lo + hi) / 2.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_one_way() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        assert_eq!(anova.group_count(), 4);
+        assert_eq!(anova.sample_count(), 24);
+        assert_eq!(anova.df_between(), 3.);
+        assert_eq!(anova.df_within(), 20.);
+        assert_abs_diff_eq!(anova.ms_within(), 3.9416666666666673, epsilon = 1e-12);
+        assert_abs_diff_eq!(anova.f_statistic(), 13.502466525722339, epsilon = 1e-10);
+        assert_abs_diff_eq!(anova.eta_squared(), 0.6694619147449337, epsilon = 1e-12);
+        assert_abs_diff_eq!(anova.omega_squared(), 0.6098030454061115, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            anova.ci_variance_explained(Confidence::new_two_sided(0.95))?,
+            Interval::new(0.31268222006816045, 0.7704357406002242)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_variance_explained(Confidence::new_upper(0.95))?,
+            Interval::new(0.36988663583488696, 1.)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_variance_explained(Confidence::new_lower(0.95))?,
+            Interval::new(0., 0.7510386910702676)?,
+            epsilon = 1e-8
+        );
+
+        // no significant difference: the lower bound is zero and omega-squared is negative
+        let anova = OneWay::from_groups(&[[1., 2., 3.], [2., 3., 4.], [1.5, 2.5, 3.5]])?;
+        assert_abs_diff_eq!(anova.eta_squared(), 0.2, epsilon = 1e-12);
+        assert_abs_diff_eq!(anova.omega_squared(), -1. / 17., epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            anova.ci_variance_explained(Confidence::new_two_sided(0.95))?,
+            Interval::new(0., 0.4999363997581557)?,
+            epsilon = 1e-8
+        );
+
+        assert!(matches!(
+            OneWay::from_groups(&[[1., 2., 3.]]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            OneWay::from_groups(&[vec![1., 2.], vec![]]),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            OneWay::from_groups(&[[1.], [2.]]),
+            Err(CIError::TooFewSamples(2))
+        ));
+        assert!(matches!(
+            OneWay::from_groups(&[[1., 1.], [2., 2.]]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_contrasts() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        let two_sided = Confidence::new_two_sided(0.95);
+
+        // treatments vs. control
+        let treatments = [-1., 1. / 3., 1. / 3., 1. / 3.];
+        assert_abs_diff_eq!(anova.contrast(&treatments)?, 1.5, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            anova.ci_contrast(two_sided, &treatments, ContrastVariance::Pooled)?,
+            Interval::new(-0.45691501124186473, 3.4569150112418647)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_contrast(two_sided, &treatments, ContrastVariance::Welch)?,
+            Interval::new(-0.7918376211354989, 3.7918376211354987)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_contrast(
+                Confidence::new_upper(0.95),
+                &treatments,
+                ContrastVariance::Pooled
+            )?,
+            Interval::new_upper(-0.11801829469172247),
+            epsilon = 1e-8
+        );
+
+        // pairwise difference
+        let pair = [0., 1., -1., 0.];
+        assert_abs_diff_eq!(anova.contrast(&pair)?, 7., epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            anova.ci_contrast(two_sided, &pair, ContrastVariance::Pooled)?,
+            Interval::new(4.575047466105335, 9.424952533894665)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_contrast(Confidence::new_lower(0.95), &pair, ContrastVariance::Welch)?,
+            Interval::new_lower(8.958357660043307),
+            epsilon = 1e-8
+        );
+
+        assert!(matches!(
+            anova.contrast(&[1., -1.]),
+            Err(CIError::DifferentSampleSizes(4, 2))
+        ));
+        assert!(matches!(
+            anova.contrast(&[1., 1., 0., 0.]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            anova.contrast(&[0., 0., 0., 0.]),
+            Err(CIError::InvalidInputData)
+        ));
+
+        let anova = OneWay::from_groups(&[vec![1.], vec![2., 3.], vec![4., 6.]])?;
+        assert!(matches!(
+            anova.ci_contrast(two_sided, &[1., -1., 0.], ContrastVariance::Welch),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(anova
+            .ci_contrast(two_sided, &[0., 1., -1.], ContrastVariance::Welch)
+            .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scheffe() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        let treatments = [-1., 1. / 3., 1. / 3., 1. / 3.];
+        let pair = [0., 1., -1., 0.];
+        assert_abs_diff_eq!(
+            anova.ci_scheffe(Confidence::new_two_sided(0.95), &treatments)?,
+            Interval::new(-1.3601843381656353, 4.360184338165635)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_scheffe(Confidence::new_two_sided(0.95), &pair)?,
+            Interval::new(3.455742217522721, 10.54425778247728)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_scheffe(Confidence::new_upper(0.9), &treatments)?,
+            Interval::new_upper(-1.0068151971672497),
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            anova.ci_scheffe(Confidence::new_lower(0.9), &pair)?,
+            Interval::new_lower(10.10637295409098),
+            epsilon = 1e-8
+        );
+
+        // wider than the unadjusted interval
+        let scheffe = anova.ci_scheffe(Confidence::new_two_sided(0.95), &pair)?;
+        let unadjusted = anova.ci_contrast(
+            Confidence::new_two_sided(0.95),
+            &pair,
+            ContrastVariance::Pooled,
+        )?;
+        assert!(scheffe.includes(&unadjusted));
+
+        assert!(matches!(
+            anova.ci_scheffe(Confidence::new_two_sided(0.95), &[1., 0., 0., 0.]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tukey_hsd() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        let pairs = anova.ci_tukey_hsd(Confidence::new_two_sided(0.95))?;
+        let expected = [
+            (
+                (0, 1),
+                -4.666666666666668,
+                -7.758243044937023,
+                -1.5750902883963125,
+            ),
+            (
+                (0, 2),
+                2.333333333333332,
+                -1.031539567926894,
+                5.698206234593558,
+            ),
+            (
+                (0, 3),
+                -2.166666666666668,
+                -5.374947095584571,
+                1.0416137622512354,
+            ),
+            ((1, 2), 7., 3.7462097741653646, 10.253790225834635),
+            ((1, 3), 2.5, -0.5915763782703554, 5.591576378270355),
+            ((2, 3), -4.5, -7.864872901260226, -1.135127098739774),
+        ];
+        assert_eq!(pairs.len(), expected.len());
+        for (pair, (groups, difference, lo, hi)) in pairs.iter().zip(expected) {
+            assert_eq!(pair.groups(), groups);
+            assert_abs_diff_eq!(pair.difference(), difference, epsilon = 1e-12);
+            assert_abs_diff_eq!(*pair.interval(), Interval::new(lo, hi)?, epsilon = 1e-8);
+        }
+
+        let pairs = anova.ci_tukey_hsd(Confidence::new_upper(0.95))?;
+        assert_abs_diff_eq!(
+            *pairs[3].interval(),
+            Interval::new_upper(3.7462097741653646),
+            epsilon = 1e-8
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_games_howell() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        let pairs = anova.ci_games_howell(Confidence::new_two_sided(0.95))?;
+        let expected = [
+            ((0, 1), -8.300556281048175, -1.032777052285161),
+            ((0, 2), -1.203710609976838, 5.870377276643502),
+            ((0, 3), -5.749143211262917, 1.4158098779295814),
+            ((1, 2), 3.6934815024043695, 10.306518497595631),
+            ((1, 3), -0.8651030269478803, 5.86510302694788),
+            ((2, 3), -7.75005116684071, -1.2499488331592898),
+        ];
+        assert_eq!(pairs.len(), expected.len());
+        for (pair, (groups, lo, hi)) in pairs.iter().zip(expected) {
+            assert_eq!(pair.groups(), groups);
+            assert_abs_diff_eq!(*pair.interval(), Interval::new(lo, hi)?, epsilon = 1e-8);
+        }
+
+        let pairs = anova.ci_games_howell(Confidence::new_lower(0.95))?;
+        assert_abs_diff_eq!(
+            *pairs[5].interval(),
+            Interval::new_lower(-1.2499488331592898),
+            epsilon = 1e-8
+        );
+
+        let anova = OneWay::from_groups(&[vec![1.], vec![2., 3.], vec![4., 6.]])?;
+        assert!(matches!(
+            anova.ci_games_howell(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(1))
+        ));
+        let anova = OneWay::from_groups(&[[1., 1.], [2., 2.], [4., 6.]])?;
+        assert!(matches!(
+            anova.ci_games_howell(Confidence::new_two_sided(0.95)),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dunnett() -> CIResult<()> {
+        // reference values computed in python
+        let groups = [
+            vec![23., 25., 21., 27., 24., 26.],
+            vec![28., 30., 27., 31., 29., 26., 32.],
+            vec![22., 20., 24., 23., 21.],
+            vec![26., 27., 25., 29., 28., 24.],
+        ];
+        let anova = OneWay::from_groups(&groups)?;
+        let differences = anova.ci_dunnett(Confidence::new_two_sided(0.95), 0)?;
+        let expected = [
+            (
+                (1, 0),
+                4.666666666666668,
+                1.8600120977642551,
+                7.473321235569081,
+            ),
+            (
+                (2, 0),
+                -2.333333333333332,
+                -5.388097229343428,
+                0.7214305626767636,
+            ),
+            (
+                (3, 0),
+                2.166666666666668,
+                -0.745936427157071,
+                5.079269760490407,
+            ),
+        ];
+        assert_eq!(differences.len(), expected.len());
+        for (difference, (groups, value, lo, hi)) in differences.iter().zip(expected) {
+            assert_eq!(difference.groups(), groups);
+            assert_abs_diff_eq!(difference.difference(), value, epsilon = 1e-12);
+            assert_abs_diff_eq!(
+                *difference.interval(),
+                Interval::new(lo, hi)?,
+                epsilon = 1e-8
+            );
+        }
+
+        let differences = anova.ci_dunnett(Confidence::new_upper(0.95), 0)?;
+        for (difference, lo) in
+            differences
+                .iter()
+                .zip([2.2443271080766984, -4.969808626533176, -0.34711389286968686])
+        {
+            assert_abs_diff_eq!(
+                *difference.interval(),
+                Interval::new_upper(lo),
+                epsilon = 1e-8
+            );
+        }
+
+        // the intervals are narrower than Tukey's
+        let pairs = anova.ci_tukey_hsd(Confidence::new_two_sided(0.95))?;
+        let dunnett = anova.ci_dunnett(Confidence::new_two_sided(0.95), 3)?;
+        assert_eq!(dunnett[1].groups(), (1, 3));
+        assert!(dunnett[1].interval().width() < pairs[4].interval().width());
+
+        assert!(matches!(
+            anova.ci_dunnett(Confidence::new_two_sided(0.95), 4),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
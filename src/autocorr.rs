@@ -0,0 +1,825 @@
+//!
+//! Confidence intervals for the mean of autocorrelated series
+//!
+//! The intervals of the [`mean`] module assume that the samples are independent. Time-ordered measurements
+//! (e.g., telemetry, or the successive iterations of a benchmark) are usually positively autocorrelated,
+//! in which case those intervals are much too narrow: successive samples carry less information than independent ones.
+//!
+//! * [`ci_mean_hac`] - the interval on the mean with a heteroskedasticity and autocorrelation consistent (HAC) standard error
+//!   (Newey–West), where the bandwidth is either given or selected automatically (see [`Bandwidth`]).
+//! * [`long_run_variance`] - the HAC estimate of the long-run variance \\( \sigma^2_\infty = \lim_{n \to \infty} n \operatorname{Var}(\bar{x}) \\).
+//! * [`integrated_autocorrelation_time`] and [`effective_sample_size`] - the number of independent samples that would give the same
+//!   precision on the mean as the series, which is a direct measure of the dependence between the samples.
+//! * [`ci_mean_ess`] - the interval on the mean where the number of samples is deflated to the effective sample size
+//!   (see also [`mean::Arithmetic::ci_mean_effective`]).
+//! * [`mser_truncation`] - the length of the warm-up period (initialization bias) of a series with the MSER rule, and
+//!   [`ci_steady_state_mean`] - the interval on the mean of the series once its warm-up is discarded.
+//!
+//! All functions require a copy of the data and are only available with the `std` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! // request rate sampled every second: successive samples are strongly correlated
+//! let data = [
+//!     101.2, 103.5, 104.1, 102.8, 99.7, 97.9, 98.4, 100.6, 103.0, 105.2,
+//!     106.1, 104.7, 102.2, 100.1, 98.8, 97.5, 98.9, 101.4, 103.8, 104.9,
+//! ];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let naive = mean::Arithmetic::ci(confidence, &data)?;
+//! let hac = autocorr::ci_mean_hac(confidence, &data, autocorr::Bandwidth::NeweyWest)?;
+//! assert!(naive.is_included_in(hac.interval()));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * W.K. Newey, K.D. West. "A simple, positive semi-definite, heteroskedasticity and autocorrelation consistent covariance matrix." Econometrica 55(3): 703-708 (1987).
+//! * D.W.K. Andrews. "Heteroskedasticity and autocorrelation consistent covariance matrix estimation." Econometrica 59(3): 817-858 (1991).
+//! * K.P. White Jr. "An effective truncation heuristic for bias reduction in simulation output." Simulation 69(6): 323-334 (1997).
+//! * [Wikipedia - Newey–West estimator](https://en.wikipedia.org/wiki/Newey%E2%80%93West_estimator)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Bandwidth of the Bartlett kernel of the Newey–West estimator, i.e., how many lags of the autocovariance are included.
+///
+/// With a bandwidth \\( b \\), the autocovariance at lag \\( j \\) is weighted by \\( 1 - j/b \\) for \\( j < b \\).
+/// Too few lags underestimate the variance when the autocorrelation is long-lived, while too many lags make the estimate noisy.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bandwidth {
+    ///
+    /// Include the given number of lags \\( L \\), i.e., the bandwidth is \\( L + 1 \\) (zero lags ignore the autocorrelation).
+    ///
+    Lags(usize),
+
+    ///
+    /// Rule of thumb of Newey and West (1994): \\( L = \lfloor 4 (n / 100)^{2/9} \rfloor \\) lags for \\( n \\) samples.
+    ///
+    NeweyWest,
+
+    ///
+    /// Automatic bandwidth of Andrews (1991), using a first-order autoregressive model of the series as plug-in:
+    /// \\( b = 1.1447 (\alpha n)^{1/3} \\), where \\( \alpha = 4 \hat{\rho}^2 / ((1 - \hat{\rho})^2 (1 + \hat{\rho})^2) \\)
+    /// and \\( \hat{\rho} \\) is the lag-one autocorrelation of the series.
+    ///
+    Andrews,
+}
+
+///
+/// Compute the Newey–West estimate of the long-run variance of a series, i.e., the variance of the mean multiplied by the number of samples:
+/// \\[
+/// \hat{\sigma}^2_\infty = \hat{\gamma}_0 + 2 \sum_{j=1}^{b-1} \left(1 - \frac{j}{b}\right) \hat{\gamma}_j
+/// \\]
+/// where \\( \hat{\gamma}_j = \frac{1}{n} \sum_{t=j+1}^{n} (x_t - \bar{x})(x_{t-j} - \bar{x}) \\) is the sample autocovariance at lag \\( j \\)
+/// and \\( b \\) is the bandwidth. The Bartlett weights guarantee that the estimate is never negative.
+///
+/// Complexity: \\( O(n b) \\), where \\( n \\) is the number of samples and \\( b \\) the bandwidth.
+///
+/// # Arguments
+///
+/// * `data` - the series, in time order
+/// * `bandwidth` - the bandwidth of the kernel
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // without lags, this is the (biased) variance of the samples
+/// let data = [1., 3., 2., 4., 3., 5.];
+/// let variance = autocorr::long_run_variance(&data, autocorr::Bandwidth::Lags(0))?;
+/// assert_abs_diff_eq!(variance, 10. / 6., epsilon = 1e-12);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn long_run_variance<F, I>(data: &I, bandwidth: Bandwidth) -> CIResult<f64>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let (_, deviations) = centered_series(data)?;
+    Ok(newey_west(&deviations, bandwidth))
+}
+
+///
+/// Compute the confidence interval on the mean of an autocorrelated series, with the Newey–West (HAC) standard error
+/// \\( \sqrt{\hat{\sigma}^2_\infty / n} \\) (see [`long_run_variance`]).
+///
+/// The interval uses the Student t distribution with \\( n - 1 \\) degrees of freedom, as the intervals of [`mean::Arithmetic`].
+///
+/// Complexity: \\( O(n b) \\), where \\( n \\) is the number of samples and \\( b \\) the bandwidth.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the series, in time order
+/// * `bandwidth` - the bandwidth of the kernel
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// The HAC standard error is consistent, but biased downwards for small series with strong autocorrelation.
+/// When the series is long enough, the method of batch means (e.g., with [`mean::Arithmetic`] on the means of consecutive batches) is a simpler alternative.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [
+///     101.2, 103.5, 104.1, 102.8, 99.7, 97.9, 98.4, 100.6, 103.0, 105.2,
+///     106.1, 104.7, 102.2, 100.1, 98.8, 97.5, 98.9, 101.4, 103.8, 104.9,
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let hac = autocorr::ci_mean_hac(confidence, &data, autocorr::Bandwidth::Lags(4))?;
+/// assert_abs_diff_eq!(hac.estimate(), 101.74, epsilon = 1e-10);
+/// assert_abs_diff_eq!(hac.std_err(), 0.740080, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_mean_hac<F, I>(
+    confidence: Confidence,
+    data: &I,
+    bandwidth: Bandwidth,
+) -> CIResult<CiEstimate<f64>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let (mean, deviations) = centered_series(data)?;
+    let n = deviations.len() as f64;
+    let std_err = (newey_west(&deviations, bandwidth) / n).sqrt();
+    let (lo, hi) = stats::interval_bounds(confidence, mean, std_err, n - 1.)?;
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+        Confidence::UpperOneSided(_) => Interval::new_upper(lo),
+        Confidence::LowerOneSided(_) => Interval::new_lower(hi),
+    };
+    Ok(CiEstimate::new(
+        mean,
+        interval,
+        confidence,
+        deviations.len(),
+        std_err,
+    ))
+}
+
+///
+/// Compute the integrated autocorrelation time of a series, i.e., the factor by which the variance of the mean is inflated by the
+/// autocorrelation:
+/// \\[
+/// \tau = 1 + 2 \sum_{k=1}^\infty \rho_k
+/// \\]
+/// where \\( \rho_k \\) is the autocorrelation at lag \\( k \\).
+/// The sum is truncated with the initial monotone sequence estimator of Geyer: the sums \\( \Gamma_m = \hat{\rho}_{2m} + \hat{\rho}_{2m+1} \\)
+/// of consecutive pairs of sample autocorrelations are accumulated while they are positive, and made monotonically decreasing.
+///
+/// The value is 1 for independent samples, larger for positively correlated samples, and can be smaller than 1 for
+/// negatively correlated (antithetic) samples. As in Stan, it is floored at \\( 1 / \log_{10} n \\) (for \\( n \geq 10 \\)) to keep the
+/// estimate positive for strongly alternating series. It is 1 for a constant series.
+///
+/// Complexity: \\( O(n K) \\), where \\( n \\) is the number of samples and \\( K \\) the number of lags included.
+///
+/// # Arguments
+///
+/// * `data` - the series, in time order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+///
+/// # References
+///
+/// * C.J. Geyer. "Practical Markov chain Monte Carlo." Statistical Science 7(4): 473-483 (1992).
+/// * Stan Reference Manual, Section "Effective sample size". [Online](https://mc-stan.org/docs/reference-manual/analysis.html#effective-sample-size.section)
+///
+pub fn integrated_autocorrelation_time<F, I>(data: &I) -> CIResult<f64>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let (_, deviations) = centered_series(data)?;
+    Ok(geyer_time(&deviations))
+}
+
+///
+/// Compute the effective sample size of a series, i.e., the number of samples divided by the integrated autocorrelation time
+/// (see [`integrated_autocorrelation_time`]).
+///
+/// Complexity: \\( O(n K) \\), where \\( n \\) is the number of samples and \\( K \\) the number of lags included.
+///
+/// # Arguments
+///
+/// * `data` - the series, in time order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [
+///     101.2, 103.5, 104.1, 102.8, 99.7, 97.9, 98.4, 100.6, 103.0, 105.2,
+///     106.1, 104.7, 102.2, 100.1, 98.8, 97.5, 98.9, 101.4, 103.8, 104.9,
+/// ];
+/// // the 20 samples are worth about 8 independent ones
+/// let ess = autocorr::effective_sample_size(&data)?;
+/// assert_abs_diff_eq!(ess, 8.265, epsilon = 1e-3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn effective_sample_size<F, I>(data: &I) -> CIResult<f64>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let (_, deviations) = centered_series(data)?;
+    Ok(deviations.len() as f64 / geyer_time(&deviations))
+}
+
+///
+/// Compute the confidence interval on the mean of an autocorrelated series, where the number of samples is deflated to the
+/// effective sample size \\( n_{\text{eff}} \\) (see [`effective_sample_size`]): the standard error is \\( s / \sqrt{n_{\text{eff}}} \\)
+/// and the interval uses the Student t distribution with \\( n_{\text{eff}} - 1 \\) degrees of freedom.
+///
+/// The effective sample size is capped to the number of samples, so that the interval is never narrower than the interval
+/// assuming independent samples, and floored at 2.
+///
+/// Complexity: \\( O(n K) \\), where \\( n \\) is the number of samples and \\( K \\) the number of lags included.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the series, in time order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [
+///     101.2, 103.5, 104.1, 102.8, 99.7, 97.9, 98.4, 100.6, 103.0, 105.2,
+///     106.1, 104.7, 102.2, 100.1, 98.8, 97.5, 98.9, 101.4, 103.8, 104.9,
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let estimate = autocorr::ci_mean_ess(confidence, &data)?;
+/// let naive = mean::Arithmetic::ci(confidence, &data)?;
+/// assert!(naive.is_included_in(estimate.interval()));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_mean_ess<F, I>(confidence: Confidence, data: &I) -> CIResult<CiEstimate<f64>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    ess_interval(confidence, &series(data)?)
+}
+
+///
+/// Detect the end of the warm-up period of a series with the MSER rule (marginal standard error rule), and return the number of
+/// initial samples to discard.
+///
+/// The samples are grouped into \\( k \\) consecutive batches of `batch_size` samples (a trailing partial batch is ignored),
+/// with batch means \\( Z_1, \ldots, Z_k \\). The truncation point is the number of batches \\( d^* \\) that minimizes the
+/// marginal standard error of the remaining batch means:
+/// \\[
+/// \operatorname{MSER}(d) = \frac{1}{(k - d)^2} \sum_{j = d + 1}^{k} \left( Z_j - \bar{Z}_{d} \right)^2
+/// \\]
+/// over \\( 0 \le d \le k/2 \\), where \\( \bar{Z}_d \\) is the mean of the batches after the first \\( d \\).
+/// Discarding the initial transient reduces the variability of the remaining samples faster than it reduces their number,
+/// until the steady state is reached.
+/// The usual choice is MSER-5, i.e., a batch size of 5 (see [`ci_steady_state_mean`]), while a batch size of 1 is the original MSER rule.
+///
+/// When the minimum is reached at \\( d = k/2 \\), the series is still in its transient halfway through, and the end of the warm-up
+/// cannot be detected: the series must be extended.
+///
+/// Complexity: \\( O(n) \\), where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `data` - the series, in time order
+/// * `batch_size` - the number of samples per batch (5 for MSER-5)
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 2 batches, or if the end of the warm-up cannot be detected because the series is too short
+/// * [`CIError::InvalidInputData`] - if the batch size is zero, or if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // benchmark iterations (in µs): the first iterations are slowed down by JIT compilation and cold caches
+/// let data = [
+///     182.4, 151.0, 133.7, 121.9, 115.2, 110.8, 107.3, 105.1, 103.6, 102.9,
+///     100.4, 99.1, 101.3, 100.8, 98.7, 99.9, 100.2, 101.1, 99.4, 100.6,
+///     98.9, 100.3, 101.0, 99.6, 100.1, 99.2, 100.9, 100.5, 99.8, 98.6,
+///     100.7, 99.5, 101.2, 100.0, 99.3, 100.4, 99.7, 101.4, 98.8, 100.2,
+/// ];
+/// assert_eq!(autocorr::mser_truncation(&data, 5)?, 10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * K.P. White Jr. "An effective truncation heuristic for bias reduction in simulation output." Simulation 69(6): 323-334 (1997).
+/// * W.W. Franklin, K.P. White Jr. "Stationarity tests and MSER-5: exploring the intuition behind mean-squared-error-reduction in warm-up period determination." Proceedings of the 2008 Winter Simulation Conference: 541-546 (2008).
+///
+pub fn mser_truncation<F, I>(data: &I, batch_size: usize) -> CIResult<usize>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    mser(&series(data)?, batch_size)
+}
+
+///
+/// Steady-state estimate of the mean of a series, after discarding its warm-up period (see [`ci_steady_state_mean`]).
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SteadyState {
+    warmup: usize,
+    estimate: CiEstimate<f64>,
+}
+
+impl SteadyState {
+    ///
+    /// Number of initial samples discarded as warm-up.
+    ///
+    pub fn warmup_count(&self) -> usize {
+        self.warmup
+    }
+
+    ///
+    /// Estimate of the mean on the samples after the warm-up, with its confidence interval.
+    ///
+    pub fn estimate(&self) -> &CiEstimate<f64> {
+        &self.estimate
+    }
+
+    ///
+    /// Confidence interval on the steady-state mean.
+    ///
+    pub fn interval(&self) -> &Interval<f64> {
+        self.estimate.interval()
+    }
+}
+
+///
+/// Compute the confidence interval on the steady-state mean of a series, e.g., the successive iterations of a benchmark or the
+/// output of a simulation, whose initial samples are biased by a warm-up period (JIT compilation, cold caches, empty queues, ...).
+///
+/// The warm-up is detected with the MSER-5 rule (see [`mser_truncation`]) and discarded, and the interval is computed on the
+/// remaining samples with the effective sample size to account for their autocorrelation (see [`ci_mean_ess`]).
+///
+/// Complexity: \\( O(n K) \\), where \\( n \\) is the number of samples and \\( K \\) the number of lags included.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the series, in time order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if the series has less than 10 samples, or if the end of the warm-up cannot be detected because the series is too short
+/// * [`CIError::InvalidInputData`] - if the series contains values that are not finite
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [
+///     182.4, 151.0, 133.7, 121.9, 115.2, 110.8, 107.3, 105.1, 103.6, 102.9,
+///     100.4, 99.1, 101.3, 100.8, 98.7, 99.9, 100.2, 101.1, 99.4, 100.6,
+///     98.9, 100.3, 101.0, 99.6, 100.1, 99.2, 100.9, 100.5, 99.8, 98.6,
+///     100.7, 99.5, 101.2, 100.0, 99.3, 100.4, 99.7, 101.4, 98.8, 100.2,
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let steady = autocorr::ci_steady_state_mean(confidence, &data)?;
+/// assert_eq!(steady.warmup_count(), 10);
+/// assert_eq!(steady.estimate().sample_count(), 30);
+/// assert!(steady.interval().contains(&100.));
+///
+/// // the warm-up samples bias the naive interval
+/// let naive = mean::Arithmetic::ci(confidence, &data)?;
+/// assert!(!naive.contains(&100.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_steady_state_mean<F, I>(confidence: Confidence, data: &I) -> CIResult<SteadyState>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let series = series(data)?;
+    let warmup = mser(&series, 5)?;
+    Ok(SteadyState {
+        warmup,
+        estimate: ess_interval(confidence, &series[warmup..])?,
+    })
+}
+
+///
+/// Interval on the mean of a series deflated to its effective sample size (see [`ci_mean_ess`]).
+///
+fn ess_interval(confidence: Confidence, series: &[f64]) -> CIResult<CiEstimate<f64>> {
+    let (mean, deviations) = center(series);
+    let n = deviations.len() as f64;
+    let effective_count = (n / geyer_time(&deviations)).clamp(2., n);
+    let std_dev = (deviations.iter().map(|x| x * x).sum::<f64>() / (n - 1.)).sqrt();
+    let std_err = std_dev / effective_count.sqrt();
+    let (lo, hi) = stats::interval_bounds(confidence, mean, std_err, effective_count - 1.)?;
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+        Confidence::UpperOneSided(_) => Interval::new_upper(lo),
+        Confidence::LowerOneSided(_) => Interval::new_lower(hi),
+    };
+    Ok(CiEstimate::new(
+        mean,
+        interval,
+        confidence,
+        deviations.len(),
+        std_err,
+    ))
+}
+
+///
+/// Collect the series, checking that it has at least two samples and that all values are finite.
+///
+pub(crate) fn series<F, I>(data: &I) -> CIResult<Vec<f64>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let series = data
+        .into_iter()
+        .map(|x| {
+            let x = x.try_f64("data")?;
+            if x.is_finite() {
+                Ok(x)
+            } else {
+                Err(CIError::InvalidInputData)
+            }
+        })
+        .collect::<CIResult<Vec<_>>>()?;
+    if series.len() < 2 {
+        return Err(CIError::TooFewSamples(series.len()));
+    }
+    Ok(series)
+}
+
+///
+/// Collect the mean of the series and the deviations from it (see [`series`]).
+///
+fn centered_series<F, I>(data: &I) -> CIResult<(f64, Vec<f64>)>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    Ok(center(&series(data)?))
+}
+
+///
+/// Mean of a series and the deviations from it.
+///
+pub(crate) fn center(series: &[f64]) -> (f64, Vec<f64>) {
+    let mean = series.iter().sum::<f64>() / series.len() as f64;
+    (mean, series.iter().map(|x| x - mean).collect())
+}
+
+///
+/// Sample autocovariance at the given lag of a centered series (with the divisor \\( n \\)).
+///
+fn autocovariance(deviations: &[f64], lag: usize) -> f64 {
+    deviations
+        .iter()
+        .zip(&deviations[lag..])
+        .map(|(x, y)| x * y)
+        .sum::<f64>()
+        / deviations.len() as f64
+}
+
+///
+/// Newey–West estimate of the long-run variance of a centered series.
+///
+fn newey_west(deviations: &[f64], bandwidth: Bandwidth) -> f64 {
+    let n = deviations.len();
+    let gamma_0 = autocovariance(deviations, 0);
+    let bandwidth = match bandwidth {
+        Bandwidth::Lags(lags) => lags as f64 + 1.,
+        Bandwidth::NeweyWest => (4. * (n as f64 / 100.).powf(2. / 9.)).floor() + 1.,
+        Bandwidth::Andrews => {
+            let rho = if gamma_0 > 0. {
+                (autocovariance(deviations, 1) / gamma_0).clamp(-0.99, 0.99)
+            } else {
+                0.
+            };
+            let alpha = 4. * rho * rho / ((1. - rho).powi(2) * (1. + rho).powi(2));
+            1.1447 * (alpha * n as f64).cbrt()
+        }
+    };
+    let variance = (1..n)
+        .take_while(|&lag| (lag as f64) < bandwidth)
+        .map(|lag| 2. * (1. - lag as f64 / bandwidth) * autocovariance(deviations, lag))
+        .sum::<f64>()
+        + gamma_0;
+    variance.max(0.)
+}
+
+///
+/// Truncation point (in samples) of the MSER rule with the given batch size (see [`mser_truncation`]).
+///
+fn mser(series: &[f64], batch_size: usize) -> CIResult<usize> {
+    if batch_size == 0 {
+        return Err(CIError::InvalidInputData);
+    }
+    let batches = series
+        .chunks_exact(batch_size)
+        .map(|batch| batch.iter().sum::<f64>() / batch_size as f64)
+        .collect::<Vec<_>>();
+    let k = batches.len();
+    if k < 2 {
+        return Err(CIError::TooFewSamples(series.len()));
+    }
+    // update the mean and sum of squared deviations of the batch means from the end (Welford),
+    // to evaluate every truncation point in linear time
+    let mut mean = 0.;
+    let mut sum_sq_dev = 0.;
+    let mut best = (0, f64::INFINITY);
+    for d in (0..k).rev() {
+        let count = (k - d) as f64;
+        let delta = batches[d] - mean;
+        mean += delta / count;
+        sum_sq_dev += delta * (batches[d] - mean);
+        if d <= k / 2 {
+            let mser = sum_sq_dev / (count * count);
+            if mser <= best.1 {
+                best = (d, mser);
+            }
+        }
+    }
+    if best.0 == k / 2 {
+        return Err(CIError::TooFewSamples(series.len()));
+    }
+    Ok(best.0 * batch_size)
+}
+
+///
+/// Integrated autocorrelation time of a centered series with Geyer's initial monotone sequence estimator.
+///
+pub(crate) fn geyer_time(deviations: &[f64]) -> f64 {
+    let n = deviations.len();
+    let gamma_0 = autocovariance(deviations, 0);
+    if gamma_0 <= 0. {
+        return 1.;
+    }
+    let mut sum = 0.;
+    let mut previous = f64::INFINITY;
+    for m in 0..n / 2 {
+        let pair =
+            (autocovariance(deviations, 2 * m) + autocovariance(deviations, 2 * m + 1)) / gamma_0;
+        if pair <= 0. {
+            break;
+        }
+        previous = pair.min(previous);
+        sum += previous;
+    }
+    (2. * sum - 1.).max(1. / (n as f64).log10().max(1.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const DATA: [f64; 20] = [
+        101.2, 103.5, 104.1, 102.8, 99.7, 97.9, 98.4, 100.6, 103.0, 105.2, 106.1, 104.7, 102.2,
+        100.1, 98.8, 97.5, 98.9, 101.4, 103.8, 104.9,
+    ];
+
+    #[test]
+    fn test_long_run_variance() -> CIResult<()> {
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            long_run_variance(&DATA, Bandwidth::Lags(0))?,
+            6.7354,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            long_run_variance(&DATA, Bandwidth::Lags(4))?,
+            10.95436,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            long_run_variance(&DATA, Bandwidth::NeweyWest)?,
+            13.64765333333333,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            long_run_variance(&DATA, Bandwidth::Andrews)?,
+            7.085112287045348,
+            epsilon = 1e-9
+        );
+        // more lags than samples: all the autocovariances are included
+        assert_abs_diff_eq!(
+            long_run_variance(&DATA, Bandwidth::Lags(100))?,
+            0.3095841584158429,
+            epsilon = 1e-9
+        );
+
+        let constant = [2.; 10];
+        assert_eq!(long_run_variance(&constant, Bandwidth::Andrews)?, 0.);
+        assert!(matches!(
+            long_run_variance(&[1.], Bandwidth::NeweyWest),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            long_run_variance(&[1., f64::NAN], Bandwidth::NeweyWest),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_mean_hac() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let hac = ci_mean_hac(confidence, &DATA, Bandwidth::Lags(4))?;
+        assert_abs_diff_eq!(hac.estimate(), 101.74, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            *hac.interval(),
+            Interval::new(100.1909953324846, 103.2890046675154)?,
+            epsilon = 1e-9
+        );
+        assert_eq!(hac.sample_count(), 20);
+
+        // without lags, this is the usual interval up to the divisor of the variance
+        let hac = ci_mean_hac(confidence, &DATA, Bandwidth::Lags(0))?;
+        let stats = mean::Arithmetic::from_iter(&DATA)?;
+        let n = DATA.len() as f64;
+        assert_abs_diff_eq!(
+            hac.std_err(),
+            stats.sample_std_dev() * ((n - 1.) / n).sqrt() / n.sqrt(),
+            epsilon = 1e-12
+        );
+
+        let hac = ci_mean_hac(Confidence::new_upper(0.975), &DATA, Bandwidth::Lags(4))?;
+        assert_abs_diff_eq!(hac.interval().low_f(), 100.1909953324846, epsilon = 1e-9);
+        assert!(hac.interval().is_upper());
+
+        let hac = ci_mean_hac(confidence, &DATA.map(|x| x as f32), Bandwidth::Lags(4))?;
+        assert_abs_diff_eq!(hac.interval().low_f(), 100.1909953324846, epsilon = 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_effective_sample_size() -> CIResult<()> {
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            integrated_autocorrelation_time(&DATA)?,
+            2.419877067434748,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            effective_sample_size(&DATA)?,
+            20. / 2.419877067434748,
+            epsilon = 1e-9
+        );
+
+        // alternating series: negatively correlated
+        let alternating = [
+            10., 8., 11., 7., 10., 9., 12., 8., 11., 9., 10., 7., 11., 8., 12., 9., 10., 8., 11.,
+            9.,
+        ];
+        assert_abs_diff_eq!(
+            integrated_autocorrelation_time(&alternating)?,
+            1. / 20_f64.log10(),
+            epsilon = 1e-12
+        );
+        let trend = [1., 3., 2., 4., 3., 5., 4., 6.];
+        assert_abs_diff_eq!(
+            integrated_autocorrelation_time(&trend)?,
+            1.722222222222222,
+            epsilon = 1e-9
+        );
+        assert_eq!(integrated_autocorrelation_time(&[2.; 5])?, 1.);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let estimate = ci_mean_ess(confidence, &DATA)?;
+        let stats = mean::Arithmetic::from_iter(&DATA)?;
+        assert_abs_diff_eq!(
+            *estimate.interval(),
+            stats.ci_mean_effective(confidence, 20. / 2.419877067434748)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            estimate.std_err(),
+            stats.sample_std_dev() / (20. / 2.419877067434748).sqrt(),
+            epsilon = 1e-12
+        );
+
+        // the effective sample size is capped to the number of samples
+        let estimate = ci_mean_ess(confidence, &alternating)?;
+        assert_abs_diff_eq!(
+            *estimate.interval(),
+            mean::Arithmetic::ci(confidence, &alternating)?,
+            epsilon = 1e-9
+        );
+
+        assert!(matches!(
+            effective_sample_size(&[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            stats.ci_mean_effective(confidence, 1.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mser() -> CIResult<()> {
+        let warmup = [
+            182.4, 151.0, 133.7, 121.9, 115.2, 110.8, 107.3, 105.1, 103.6, 102.9, 100.4, 99.1,
+            101.3, 100.8, 98.7, 99.9, 100.2, 101.1, 99.4, 100.6, 98.9, 100.3, 101.0, 99.6, 100.1,
+            99.2, 100.9, 100.5, 99.8, 98.6, 100.7, 99.5, 101.2, 100.0, 99.3, 100.4, 99.7, 101.4,
+            98.8, 100.2,
+        ];
+        // reference values computed in python
+        assert_eq!(mser_truncation(&warmup, 5)?, 10);
+        assert_eq!(mser_truncation(&warmup, 1)?, 10);
+        assert_eq!(mser_truncation(&warmup[10..].to_vec(), 5)?, 0);
+        assert_eq!(mser_truncation(&[2.; 20], 5)?, 0);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let steady = ci_steady_state_mean(confidence, &warmup)?;
+        assert_eq!(steady.warmup_count(), 10);
+        assert_eq!(steady.estimate().sample_count(), 30);
+        assert_abs_diff_eq!(
+            steady.estimate().estimate(),
+            100.05333333333333,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            *steady.interval(),
+            Interval::new(99.74780786296023, 100.3588588037064)?,
+            epsilon = 1e-9
+        );
+        assert_eq!(
+            *steady.estimate(),
+            ci_mean_ess(confidence, &warmup[10..].to_vec())?
+        );
+
+        // still decreasing halfway through the series
+        assert!(matches!(
+            mser_truncation(&warmup[..10].to_vec(), 5),
+            Err(CIError::TooFewSamples(10))
+        ));
+        assert!(matches!(
+            mser_truncation(&warmup[..9].to_vec(), 5),
+            Err(CIError::TooFewSamples(9))
+        ));
+        assert!(matches!(
+            mser_truncation(&warmup, 0),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,1748 @@
+//!
+//! Confidence intervals for the evaluation metrics of binary classifiers (e.g., machine learning models, anomaly detectors).
+//!
+//! * [`Auc`] - area under the ROC curve (AUC) of a scoring classifier, with the standard error of DeLong et al. or of
+//!   Hanley and McNeil (see [`AucStdErr`])
+//! * [`ConfusionMatrix`] - precision, recall, F1 score, and Matthews correlation coefficient of a binary classifier
+//! * [`Diagnostics`] - sensitivity, specificity, likelihood ratios, and predictive values at a given prevalence of a diagnostic test
+//! * [`ConfusionMatrix::ci_true_prevalence`] - prevalence in a population tested with an imperfect test (Rogan-Gladen estimator)
+//! * [`ci_brier_score`] and [`ci_expected_calibration_error`] - accuracy and calibration of probabilistic predictions
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // scores of a model, and whether the instance is actually positive
+//! let predictions = [(0.9, true), (0.8, true), (0.7, false), (0.6, true), (0.55, true), (0.4, false), (0.3, true), (0.1, false)];
+//! let auc = classification::Auc::from_iter(&predictions)?;
+//! assert_abs_diff_eq!(auc.auc(), 11. / 15., epsilon = 1e-10);
+//! let ci = auc.ci(Confidence::new_two_sided(0.95), classification::AucStdErr::DeLong)?;
+//! assert!(ci.contains(&auc.auc()));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * [Wikipedia - Receiver operating characteristic](https://en.wikipedia.org/wiki/Receiver_operating_characteristic)
+//!
+use super::*;
+use distributions::z_value;
+use error::*;
+use num_traits::Float;
+use utils::Bootstrap;
+
+///
+/// Estimator of the standard error of the AUC (see [`Auc::std_err`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AucStdErr {
+    ///
+    /// Nonparametric estimator of DeLong et al. (1988), from the variance of the placement values of the positive and negative
+    /// instances (i.e., the proportion of instances of the other class that each instance outranks).
+    ///
+    #[default]
+    DeLong,
+
+    ///
+    /// Estimator of Hanley and McNeil (1982), which only depends on the AUC and the number of positive and negative instances,
+    /// assuming exponentially distributed scores. It is cheaper but less accurate.
+    ///
+    HanleyMcNeil,
+}
+
+///
+/// Area under the receiver operating characteristic (ROC) curve of a scoring classifier, i.e., the probability that a
+/// randomly chosen positive instance has a higher score than a randomly chosen negative instance (ties count for one half).
+///
+/// The AUC is the Mann–Whitney statistic \\( \hat{A} = \frac{1}{m n} \sum_{i=1}^m \sum_{j=1}^n \psi(x_i, y_j) \\) of the scores
+/// \\( x_i \\) of the \\( m \\) positive instances and \\( y_j \\) of the \\( n \\) negative instances,
+/// where \\( \psi(x, y) \\) is 1 if \\( x > y \\), 1/2 if \\( x = y \\), and 0 otherwise.
+/// It is computed with midranks in \\( O((m + n) \log (m + n)) \\).
+///
+/// The interval is based on the normal distribution with the chosen standard error (see [`AucStdErr`]), and clamped to \\( [0, 1] \\).
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let predictions = [
+///     (0.9, true), (0.8, true), (0.7, false), (0.6, true), (0.55, true), (0.54, true), (0.53, false), (0.52, false),
+///     (0.51, true), (0.505, true), (0.4, false), (0.39, true), (0.38, false), (0.37, true), (0.36, false), (0.35, false),
+///     (0.34, true), (0.33, false), (0.30, true), (0.1, false), (0.7, true), (0.4, true),
+/// ];
+/// let auc = classification::Auc::from_iter(&predictions)?;
+/// assert_eq!((auc.positive_count(), auc.negative_count()), (13, 9));
+/// assert_abs_diff_eq!(auc.auc(), 0.683761, epsilon = 1e-6);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = auc.ci(confidence, classification::AucStdErr::DeLong)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.449697, 0.917825)?, epsilon = 1e-6);
+/// let ci = auc.ci(confidence, classification::AucStdErr::HanleyMcNeil)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.458743, 0.908778)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * E.R. DeLong, D.M. DeLong, D.L. Clarke-Pearson. "Comparing the areas under two or more correlated receiver operating
+///   characteristic curves: a nonparametric approach." Biometrics 44(3): 837-845 (1988).
+/// * J.A. Hanley, B.J. McNeil. "The meaning and use of the area under a receiver operating characteristic (ROC) curve."
+///   Radiology 143(1): 29-36 (1982).
+/// * X. Sun, W. Xu. "Fast implementation of DeLong's algorithm for comparing the areas under correlated receiver operating
+///   characteristic curves." IEEE Signal Processing Letters 21(11): 1389-1393 (2014).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Auc {
+    positive_count: usize,
+    negative_count: usize,
+    auc: f64,
+    delong_variance: f64,
+}
+
+impl Auc {
+    ///
+    /// Compute the AUC from pairs of a score and whether the instance is positive.
+    /// A higher score must indicate a more likely positive instance.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if some score is NaN
+    /// * [`CIError::TooFewSamples`] - if there are less than two positive or two negative instances
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<F: Float, I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+    {
+        let mut positives = Vec::new();
+        let mut negatives = Vec::new();
+        for &(score, label) in data {
+            if score.is_nan() {
+                return Err(CIError::InvalidInputData);
+            }
+            let score = score.try_f64("score")?;
+            if label {
+                positives.push(score);
+            } else {
+                negatives.push(score);
+            }
+        }
+        let (m, n) = (positives.len(), negatives.len());
+        if m.min(n) < 2 {
+            return Err(CIError::TooFewSamples(m.min(n)));
+        }
+        // placement values from the midranks within each class and among all the instances
+        let ranks = midranks(&[&positives[..], &negatives[..]].concat());
+        let positive_ranks = midranks(&positives);
+        let negative_ranks = midranks(&negatives);
+        let (mf, nf) = (m as f64, n as f64);
+        let positive_placements = ranks[..m]
+            .iter()
+            .zip(&positive_ranks)
+            .map(|(rank, own)| (rank - own) / nf)
+            .collect::<Vec<_>>();
+        let negative_placements = ranks[m..]
+            .iter()
+            .zip(&negative_ranks)
+            .map(|(rank, own)| 1. - (rank - own) / mf)
+            .collect::<Vec<_>>();
+        let auc = positive_placements.iter().sum::<f64>() / mf;
+        let delong_variance = sample_variance(&positive_placements, auc) / mf
+            + sample_variance(&negative_placements, auc) / nf;
+        Ok(Self {
+            positive_count: m,
+            negative_count: n,
+            auc,
+            delong_variance,
+        })
+    }
+
+    ///
+    /// Number of positive instances \\( m \\)
+    ///
+    pub fn positive_count(&self) -> usize {
+        self.positive_count
+    }
+
+    ///
+    /// Number of negative instances \\( n \\)
+    ///
+    pub fn negative_count(&self) -> usize {
+        self.negative_count
+    }
+
+    ///
+    /// Area under the ROC curve
+    ///
+    pub fn auc(&self) -> f64 {
+        self.auc
+    }
+
+    ///
+    /// Standard error of the AUC with the given estimator.
+    ///
+    /// The estimator of Hanley and McNeil is
+    /// \\[
+    /// \sqrt{\frac{A (1 - A) + (m - 1) (Q_1 - A^2) + (n - 1) (Q_2 - A^2)}{m n}} \qquad \text{where }
+    /// Q_1 = \frac{A}{2 - A}, \quad Q_2 = \frac{2 A^2}{1 + A}
+    /// \\]
+    ///
+    pub fn std_err(&self, method: AucStdErr) -> f64 {
+        match method {
+            AucStdErr::DeLong => self.delong_variance.sqrt(),
+            AucStdErr::HanleyMcNeil => {
+                let a = self.auc;
+                let (m, n) = (self.positive_count as f64, self.negative_count as f64);
+                let q1 = a / (2. - a);
+                let q2 = 2. * a * a / (1. + a);
+                ((a * (1. - a) + (m - 1.) * (q1 - a * a) + (n - 1.) * (q2 - a * a)) / (m * n))
+                    .sqrt()
+            }
+        }
+    }
+
+    ///
+    /// Confidence interval of the AUC, based on the normal distribution with the given estimator of the standard error.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence, method: AucStdErr) -> CIResult<Interval<f64>> {
+        let span = z_value(confidence)? * self.std_err(method);
+        unit_interval(confidence, self.auc - span, self.auc + span)
+    }
+}
+
+///
+/// Confusion matrix of a binary classifier, from which the usual metrics (precision, recall, F1 score, MCC) are estimated with
+/// confidence intervals.
+///
+/// The instances are assumed to be drawn independently from the population on which the classifier is evaluated.
+/// Precision and recall are proportions (of the predicted positives and of the actual positives, respectively), and their
+/// intervals are Wilson score intervals (see [`proportion::ci_wilson`]). The interval of the F1 score uses either the delta
+/// method ([`ConfusionMatrix::ci_f1`]) or a bootstrap of the instances ([`ConfusionMatrix::ci_f1_bootstrap`]).
+///
+/// # Notes
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let matrix = classification::ConfusionMatrix::new(42, 8, 11, 139);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(matrix.precision(), 0.84, epsilon = 1e-10);
+/// assert_abs_diff_eq!(matrix.ci_precision(confidence)?, Interval::new(0.714858, 0.916626)?, epsilon = 1e-6);
+/// assert_abs_diff_eq!(matrix.ci_recall(confidence)?, Interval::new(0.665423, 0.879954)?, epsilon = 1e-6);
+/// assert_abs_diff_eq!(matrix.f1_score(), 0.815534, epsilon = 1e-6);
+/// assert_abs_diff_eq!(matrix.ci_f1(confidence)?, Interval::new(0.734013, 0.897055)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfusionMatrix {
+    true_positives: usize,
+    false_positives: usize,
+    false_negatives: usize,
+    true_negatives: usize,
+}
+
+impl ConfusionMatrix {
+    ///
+    /// Create a confusion matrix from its four cells.
+    ///
+    pub fn new(
+        true_positives: usize,
+        false_positives: usize,
+        false_negatives: usize,
+        true_negatives: usize,
+    ) -> Self {
+        Self {
+            true_positives,
+            false_positives,
+            false_negatives,
+            true_negatives,
+        }
+    }
+
+    ///
+    /// Create a confusion matrix from pairs of a predicted label and an actual label.
+    ///
+    pub fn from_predictions<I>(data: &I) -> Self
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (bool, bool)>,
+    {
+        let mut matrix = Self::default();
+        for &(predicted, actual) in data {
+            matrix.add(predicted, actual);
+        }
+        matrix
+    }
+
+    ///
+    /// Add an instance with its predicted label and its actual label.
+    ///
+    pub fn add(&mut self, predicted: bool, actual: bool) {
+        match (predicted, actual) {
+            (true, true) => self.true_positives += 1,
+            (true, false) => self.false_positives += 1,
+            (false, true) => self.false_negatives += 1,
+            (false, false) => self.true_negatives += 1,
+        }
+    }
+
+    ///
+    /// Number of true positives (TP)
+    ///
+    pub fn true_positives(&self) -> usize {
+        self.true_positives
+    }
+
+    ///
+    /// Number of false positives (FP)
+    ///
+    pub fn false_positives(&self) -> usize {
+        self.false_positives
+    }
+
+    ///
+    /// Number of false negatives (FN)
+    ///
+    pub fn false_negatives(&self) -> usize {
+        self.false_negatives
+    }
+
+    ///
+    /// Number of true negatives (TN)
+    ///
+    pub fn true_negatives(&self) -> usize {
+        self.true_negatives
+    }
+
+    ///
+    /// Total number of instances
+    ///
+    pub fn total(&self) -> usize {
+        self.true_positives + self.false_positives + self.false_negatives + self.true_negatives
+    }
+
+    ///
+    /// Precision (positive predictive value) \\( TP / (TP + FP) \\), or NaN if there are no predicted positives
+    ///
+    pub fn precision(&self) -> f64 {
+        self.true_positives as f64 / (self.true_positives + self.false_positives) as f64
+    }
+
+    ///
+    /// Recall (sensitivity, true positive rate) \\( TP / (TP + FN) \\), or NaN if there are no actual positives
+    ///
+    pub fn recall(&self) -> f64 {
+        self.true_positives as f64 / (self.true_positives + self.false_negatives) as f64
+    }
+
+    ///
+    /// F1 score, the harmonic mean of the precision and the recall \\( 2 TP / (2 TP + FP + FN) \\), or NaN if there are only
+    /// true negatives
+    ///
+    pub fn f1_score(&self) -> f64 {
+        f1_score(
+            self.true_positives,
+            self.false_positives,
+            self.false_negatives,
+        )
+    }
+
+    ///
+    /// Prevalence of the actual positives in the sample \\( (TP + FN) / N \\), or NaN if there are no instances
+    ///
+    pub fn prevalence(&self) -> f64 {
+        (self.true_positives + self.false_negatives) as f64 / self.total() as f64
+    }
+
+    ///
+    /// Specificity (true negative rate) \\( TN / (TN + FP) \\), or NaN if there are no actual negatives
+    ///
+    pub fn specificity(&self) -> f64 {
+        self.true_negatives as f64 / (self.true_negatives + self.false_positives) as f64
+    }
+
+    ///
+    /// Positive likelihood ratio \\( \text{recall} / (1 - \text{specificity}) \\), i.e., the ratio of the true positive rate
+    /// to the false positive rate
+    ///
+    pub fn positive_likelihood_ratio(&self) -> f64 {
+        self.recall() / (1. - self.specificity())
+    }
+
+    ///
+    /// Negative likelihood ratio \\( (1 - \text{recall}) / \text{specificity} \\), i.e., the ratio of the false negative rate
+    /// to the true negative rate
+    ///
+    pub fn negative_likelihood_ratio(&self) -> f64 {
+        (1. - self.recall()) / self.specificity()
+    }
+
+    ///
+    /// Sensitivity, specificity, and likelihood ratios with their confidence intervals (see [`Diagnostics`]).
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSuccesses` - if there are less than two true positives or less than two true negatives
+    /// * `TooFewFailures` - if there are less than two false negatives or less than two false positives
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    pub fn diagnostics(&self, confidence: Confidence) -> CIResult<Diagnostics> {
+        Diagnostics::new(confidence, *self)
+    }
+
+    ///
+    /// Rogan-Gladen estimate of the true prevalence in a population, from the apparent prevalence \\( AP = k / n \\) of
+    /// the `positives` \\( k \\) among the `population` \\( n \\) tested, correcting for the sensitivity \\( Se \\) and the
+    /// specificity \\( Sp \\) of the test, estimated by this confusion matrix (i.e., a validation study of the test):
+    /// \\[
+    /// \hat{P} = \frac{AP + Sp - 1}{Se + Sp - 1}
+    /// \\]
+    /// truncated to \\( [0, 1] \\).
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if nobody is tested, or the confusion matrix has no actual positives or no actual negatives
+    /// * `InvalidSuccesses` - if there are more positives than tested
+    /// * `InvalidInputData` - if the test is no better than chance (\\( Se + Sp \le 1 \\))
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// // the test detects 85 of 100 infected and 10 of 200 healthy subjects
+    /// let validation = classification::ConfusionMatrix::new(85, 10, 15, 190);
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // 120 of 500 surveyed subjects test positive
+    /// assert_abs_diff_eq!(validation.true_prevalence(500, 120)?, 0.2375, epsilon = 1e-10);
+    /// let ci = validation.ci_true_prevalence(confidence, 500, 120)?;
+    /// assert_abs_diff_eq!(ci, Interval::new(0.175087, 0.297879)?, epsilon = 1e-6);
+    /// let bootstrap = utils::Bootstrap::new(1_000, 7);
+    /// let ci = validation.ci_true_prevalence_bootstrap(confidence, 500, 120, &bootstrap)?;
+    /// assert_abs_diff_eq!(ci, Interval::new(0.181818, 0.293827)?, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * W.J. Rogan, B. Gladen. "Estimating prevalence from the results of a screening test."
+    ///   American Journal of Epidemiology 107(1): 71-76 (1978).
+    ///
+    pub fn true_prevalence(&self, population: usize, positives: usize) -> CIResult<f64> {
+        self.check_survey(population, positives)?;
+        let prevalence = rogan_gladen(
+            positives as f64 / population as f64,
+            self.recall(),
+            self.specificity(),
+        );
+        if prevalence.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(prevalence)
+    }
+
+    ///
+    /// Confidence interval of the true prevalence (see [`Self::true_prevalence`]) with the method of Lang and Reiczigel,
+    /// which accounts for the uncertainty of the apparent prevalence as well as that of the sensitivity and the specificity.
+    ///
+    /// The three proportions are first adjusted as in the Agresti-Coull interval, e.g., \\( AP^* = (k + z^2/2) / (n + z^2) \\),
+    /// giving the estimate \\( P^* \\). The interval is the Wald interval shifted by
+    /// \\[
+    /// \Delta = 2 z^2 \left( P^* \frac{Se^* (1 - Se^*)}{n_{Se}^*} - (1 - P^*) \frac{Sp^* (1 - Sp^*)}{n_{Sp}^*} \right)
+    /// \\]
+    /// with the delta-method variance
+    /// \\[
+    /// \frac{1}{(Se^* + Sp^* - 1)^2} \left( \frac{AP^* (1 - AP^*)}{n^*} + P^{*2} \frac{Se^* (1 - Se^*)}{n_{Se}^*} + (1 - P^*)^2 \frac{Sp^* (1 - Sp^*)}{n_{Sp}^*} \right)
+    /// \\]
+    /// where \\( n_{Se} \\) and \\( n_{Sp} \\) are the numbers of actual positives and negatives in the confusion matrix.
+    /// The bounds are clamped to \\( [0, 1] \\).
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if nobody is tested, or the confusion matrix has no actual positives or no actual negatives
+    /// * `InvalidSuccesses` - if there are more positives than tested
+    /// * `InvalidInputData` - if the adjusted test is no better than chance (\\( Se^* + Sp^* \le 1 \\))
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    /// # References
+    ///
+    /// * Z. Lang, J. Reiczigel. "Confidence limits for prevalence of disease adjusted for estimated sensitivity and specificity."
+    ///   Preventive Veterinary Medicine 113(1): 13-22 (2014).
+    ///
+    pub fn ci_true_prevalence(
+        &self,
+        confidence: Confidence,
+        population: usize,
+        positives: usize,
+    ) -> CIResult<Interval<f64>> {
+        self.check_survey(population, positives)?;
+        let z = z_value(confidence)?;
+        let z_sq = z * z;
+        let adjusted = |successes: usize, count: usize| {
+            let n = count as f64 + z_sq;
+            let p = (successes as f64 + z_sq / 2.) / n;
+            (p, p * (1. - p) / n)
+        };
+        let (apparent, apparent_variance) = adjusted(positives, population);
+        let (sensitivity, sensitivity_variance) = adjusted(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
+        );
+        let (specificity, specificity_variance) = adjusted(
+            self.true_negatives,
+            self.true_negatives + self.false_positives,
+        );
+        let youden = sensitivity + specificity - 1.;
+        if youden <= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        let prevalence = (apparent + specificity - 1.) / youden;
+        let shift = 2.
+            * z_sq
+            * (prevalence * sensitivity_variance - (1. - prevalence) * specificity_variance);
+        let std_err = (apparent_variance
+            + prevalence * prevalence * sensitivity_variance
+            + (1. - prevalence) * (1. - prevalence) * specificity_variance)
+            .sqrt()
+            / youden;
+        let center = prevalence + shift;
+        unit_interval(confidence, center - z * std_err, center + z * std_err)
+    }
+
+    ///
+    /// Bootstrap confidence interval of the true prevalence (see [`Self::true_prevalence`]), resampling separately the
+    /// tested population, and the actual positives and negatives of the confusion matrix (percentile method).
+    /// Resamples where the test is no better than chance are discarded.
+    ///
+    /// Complexity: \\( O(B \cdot n) \\) for \\( B \\) resamples of \\( n \\) subjects in total
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if nobody is tested, if the confusion matrix has no actual positives or no actual negatives,
+    ///   or if the test is no better than chance on every resample
+    /// * `InvalidSuccesses` - if there are more positives than tested
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_true_prevalence_bootstrap(
+        &self,
+        confidence: Confidence,
+        population: usize,
+        positives: usize,
+        bootstrap: &Bootstrap,
+    ) -> CIResult<Interval<f64>> {
+        self.check_survey(population, positives)?;
+        let actual_positives = self.true_positives + self.false_negatives;
+        let actual_negatives = self.true_negatives + self.false_positives;
+        // subjects numbered by sample: the positives of the survey, the true positives and the true negatives come first
+        let survey_positives = 0..positives;
+        let true_positives = population..population + self.true_positives;
+        let true_negatives =
+            population + actual_positives..population + actual_positives + self.true_negatives;
+        let (lo, hi) = bootstrap.stratified_percentile_bounds(
+            confidence,
+            &[population, actual_positives, actual_negatives],
+            |subjects| {
+                let count = |range: &core::ops::Range<usize>| {
+                    subjects.iter().filter(|&i| range.contains(i)).count() as f64
+                };
+                rogan_gladen(
+                    count(&survey_positives) / population as f64,
+                    count(&true_positives) / actual_positives as f64,
+                    count(&true_negatives) / actual_negatives as f64,
+                )
+            },
+        )?;
+        unit_interval(confidence, lo, hi)
+    }
+
+    fn check_survey(&self, population: usize, positives: usize) -> CIResult<()> {
+        if positives > population {
+            return Err(CIError::InvalidSuccesses(positives, population));
+        }
+        if population == 0
+            || self.true_positives + self.false_negatives == 0
+            || self.true_negatives + self.false_positives == 0
+        {
+            return Err(CIError::TooFewSamples(0));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Wilson score interval of the precision (see [`proportion::ci_wilson`]).
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSuccesses` - if there are less than two true positives
+    /// * `TooFewFailures` - if there are less than two false positives
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_precision(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        proportion::ci_wilson(
+            confidence,
+            self.true_positives + self.false_positives,
+            self.true_positives,
+        )
+    }
+
+    ///
+    /// Wilson score interval of the recall (see [`proportion::ci_wilson`]).
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSuccesses` - if there are less than two true positives
+    /// * `TooFewFailures` - if there are less than two false negatives
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_recall(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        proportion::ci_wilson(
+            confidence,
+            self.true_positives + self.false_negatives,
+            self.true_positives,
+        )
+    }
+
+    ///
+    /// Confidence interval of the F1 score with the delta method, treating the cells as multinomial counts:
+    /// the standard error is
+    /// \\[
+    /// \frac{2}{(2 TP + FP + FN)^2} \sqrt{TP (FP + FN) (TP + FP + FN)}
+    /// \\]
+    /// The bounds are clamped to \\( [0, 1] \\).
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidInputData` - if the F1 score is undefined (only true negatives)
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_f1(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let f1 = self.f1_score();
+        if f1.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        let tp = self.true_positives as f64;
+        let errors = (self.false_positives + self.false_negatives) as f64;
+        let denominator = 2. * tp + errors;
+        let std_err = 2. * (tp * errors * (tp + errors)).sqrt() / (denominator * denominator);
+        let span = z_value(confidence)? * std_err;
+        unit_interval(confidence, f1 - span, f1 + span)
+    }
+
+    ///
+    /// Bootstrap confidence interval of the F1 score, resampling the instances (percentile method).
+    /// Resamples where the F1 score is undefined are discarded.
+    ///
+    /// Complexity: \\( O(B \cdot n) \\) for \\( B \\) resamples of \\( n \\) instances
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    /// * `TooFewSamples` - if the F1 score is undefined on every resample (e.g., there are no instances)
+    ///
+    pub fn ci_f1_bootstrap(
+        &self,
+        confidence: Confidence,
+        bootstrap: &Bootstrap,
+    ) -> CIResult<Interval<f64>> {
+        let (lo, hi) = bootstrap.percentile_bounds(confidence, self.total(), |instances| {
+            let cells = self.resampled_cells(instances);
+            f1_score(cells[0], cells[1], cells[2])
+        })?;
+        unit_interval(confidence, lo, hi)
+    }
+
+    ///
+    /// Matthews correlation coefficient (MCC), i.e., the correlation between the predicted and the actual labels:
+    /// \\[
+    /// \frac{TP \cdot TN - FP \cdot FN}{\sqrt{(TP + FP) (TP + FN) (TN + FP) (TN + FN)}}
+    /// \\]
+    /// or NaN if a row or a column of the matrix is empty.
+    ///
+    /// Unlike the F1 score, the MCC accounts for the true negatives and is only high when the classifier performs well on both classes.
+    ///
+    pub fn mcc(&self) -> f64 {
+        mcc(self.cells())
+    }
+
+    ///
+    /// Confidence interval of the Matthews correlation coefficient with the delta method, treating the cells as multinomial counts:
+    /// the variance is \\( \frac{1}{n} \sum_i p_i \left( \frac{\partial \text{MCC}}{\partial p_i} \right)^2 \\), where \\( p_i \\) are the
+    /// proportions of the four cells.
+    /// The bounds are clamped to \\( [-1, 1] \\).
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidInputData` - if the MCC is undefined (a row or a column of the matrix is empty)
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let matrix = classification::ConfusionMatrix::new(42, 8, 11, 139);
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_abs_diff_eq!(matrix.mcc(), 0.752213, epsilon = 1e-6);
+    /// assert_abs_diff_eq!(matrix.ci_mcc(confidence)?, Interval::new(0.647235, 0.857190)?, epsilon = 1e-6);
+    /// let ci = matrix.ci_mcc_bootstrap(confidence, &utils::Bootstrap::new(1_000, 5))?;
+    /// assert_abs_diff_eq!(ci, Interval::new(0.636437, 0.850300)?, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mcc(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let mcc = self.mcc();
+        if mcc.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        let n = self.total() as f64;
+        let [tp, fp, fn_, tn] = self.cells().map(|cell| cell / n);
+        let s = ((tp + fp) * (tp + fn_) * (tn + fp) * (tn + fn_)).sqrt();
+        // gradient of the MCC with respect to the proportions of the cells
+        let half = mcc / 2.;
+        let gradient = [
+            tn / s - half * (1. / (tp + fp) + 1. / (tp + fn_)),
+            -fn_ / s - half * (1. / (tp + fp) + 1. / (tn + fp)),
+            -fp / s - half * (1. / (tp + fn_) + 1. / (tn + fn_)),
+            tp / s - half * (1. / (tn + fp) + 1. / (tn + fn_)),
+        ];
+        // the MCC is homogeneous of degree 0, so that the gradient is orthogonal to the proportions
+        let variance = gradient
+            .iter()
+            .zip([tp, fp, fn_, tn])
+            .map(|(g, p)| g * g * p)
+            .sum::<f64>()
+            / n;
+        let span = z_value(confidence)? * variance.sqrt();
+        correlation_interval(confidence, mcc - span, mcc + span)
+    }
+
+    ///
+    /// Bootstrap confidence interval of the Matthews correlation coefficient, resampling the instances (percentile method).
+    /// Resamples where the MCC is undefined are discarded.
+    ///
+    /// Complexity: \\( O(B \cdot n) \\) for \\( B \\) resamples of \\( n \\) instances
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    /// * `TooFewSamples` - if the MCC is undefined on every resample (e.g., there are no instances)
+    ///
+    pub fn ci_mcc_bootstrap(
+        &self,
+        confidence: Confidence,
+        bootstrap: &Bootstrap,
+    ) -> CIResult<Interval<f64>> {
+        let (lo, hi) = bootstrap.percentile_bounds(confidence, self.total(), |instances| {
+            mcc(self.resampled_cells(instances).map(|cell| cell as f64))
+        })?;
+        correlation_interval(confidence, lo, hi)
+    }
+
+    ///
+    /// Standard error of the logarithm of the positive likelihood ratio, which is also that of the logit of the
+    /// positive predictive value.
+    ///
+    fn positive_log_std_err(&self) -> f64 {
+        let [tp, fp, fn_, tn] = self.cells();
+        (1. / tp - 1. / (tp + fn_) + 1. / fp - 1. / (fp + tn)).sqrt()
+    }
+
+    ///
+    /// Standard error of the logarithm of the negative likelihood ratio, which is also that of the logit of the
+    /// negative predictive value.
+    ///
+    fn negative_log_std_err(&self) -> f64 {
+        let [tp, fp, fn_, tn] = self.cells();
+        (1. / fn_ - 1. / (tp + fn_) + 1. / tn - 1. / (fp + tn)).sqrt()
+    }
+
+    fn cells(&self) -> [f64; 4] {
+        [
+            self.true_positives as f64,
+            self.false_positives as f64,
+            self.false_negatives as f64,
+            self.true_negatives as f64,
+        ]
+    }
+
+    ///
+    /// Cells (TP, FP, FN, TN) of the resampled instances, where the instances are numbered cell by cell.
+    ///
+    fn resampled_cells(&self, instances: &[usize]) -> [usize; 4] {
+        let tp = self.true_positives;
+        let fp = tp + self.false_positives;
+        let fn_ = fp + self.false_negatives;
+        let mut cells = [0; 4];
+        for &i in instances {
+            let cell = if i < tp {
+                0
+            } else if i < fp {
+                1
+            } else if i < fn_ {
+                2
+            } else {
+                3
+            };
+            cells[cell] += 1;
+        }
+        cells
+    }
+}
+
+///
+/// Diagnostic accuracy of a binary test (e.g., a medical test or an anomaly detector) with confidence intervals,
+/// computed from a confusion matrix (see [`diagnostics`] and [`ConfusionMatrix::diagnostics`]):
+///
+/// * the sensitivity \\( TP / (TP + FN) \\) and the specificity \\( TN / (TN + FP) \\), with Wilson score intervals
+///   (see [`proportion::ci_wilson`]);
+/// * the positive likelihood ratio \\( LR^+ = \text{sensitivity} / (1 - \text{specificity}) \\) and the negative likelihood ratio
+///   \\( LR^- = (1 - \text{sensitivity}) / \text{specificity} \\), with Wald intervals on the log scale:
+///   \\[
+///   \ln LR^+ \pm z \sqrt{\frac{1}{TP} - \frac{1}{TP + FN} + \frac{1}{FP} - \frac{1}{FP + TN}}
+///   \qquad
+///   \ln LR^- \pm z \sqrt{\frac{1}{FN} - \frac{1}{TP + FN} + \frac{1}{TN} - \frac{1}{FP + TN}}
+///   \\]
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let diagnostics = classification::diagnostics(Confidence::new_two_sided(0.95), 42, 8, 11, 139)?;
+/// assert_abs_diff_eq!(diagnostics.sensitivity(), 0.792453, epsilon = 1e-6);
+/// assert_abs_diff_eq!(diagnostics.ci_specificity(), Interval::new(0.896293, 0.972169)?, epsilon = 1e-6);
+/// assert_abs_diff_eq!(diagnostics.positive_likelihood_ratio(), 14.561321, epsilon = 1e-6);
+/// assert_abs_diff_eq!(diagnostics.ci_positive_likelihood_ratio(), Interval::new(7.319887, 28.966577)?, epsilon = 1e-6);
+/// // positive predictive value where only 5% of the tested population is positive
+/// assert_abs_diff_eq!(diagnostics.positive_predictive_value(0.05)?, 0.433872, epsilon = 1e-6);
+/// assert_abs_diff_eq!(diagnostics.ci_positive_predictive_value(0.05)?, Interval::new(0.278112, 0.603891)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * D.L. Simel, G.P. Samsa, D.B. Matchar. "Likelihood ratios with confidence: sample size estimation for diagnostic test studies."
+///   Journal of Clinical Epidemiology 44(8): 763-770 (1991).
+/// * [Wikipedia - Likelihood ratios in diagnostic testing](https://en.wikipedia.org/wiki/Likelihood_ratios_in_diagnostic_testing)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostics {
+    confidence: Confidence,
+    matrix: ConfusionMatrix,
+    sensitivity: Interval<f64>,
+    specificity: Interval<f64>,
+    positive_likelihood_ratio: Interval<f64>,
+    negative_likelihood_ratio: Interval<f64>,
+}
+
+impl Diagnostics {
+    fn new(confidence: Confidence, matrix: ConfusionMatrix) -> CIResult<Self> {
+        let ConfusionMatrix {
+            true_positives,
+            false_positives,
+            false_negatives,
+            true_negatives,
+        } = matrix;
+        let sensitivity =
+            proportion::ci_wilson(confidence, true_positives + false_negatives, true_positives)?;
+        let specificity =
+            proportion::ci_wilson(confidence, true_negatives + false_positives, true_negatives)?;
+        let positive_likelihood_ratio = log_wald_interval(
+            confidence,
+            matrix.positive_likelihood_ratio(),
+            matrix.positive_log_std_err(),
+        )?;
+        let negative_likelihood_ratio = log_wald_interval(
+            confidence,
+            matrix.negative_likelihood_ratio(),
+            matrix.negative_log_std_err(),
+        )?;
+        Ok(Self {
+            confidence,
+            matrix,
+            sensitivity,
+            specificity,
+            positive_likelihood_ratio,
+            negative_likelihood_ratio,
+        })
+    }
+
+    ///
+    /// Confidence level of the intervals
+    ///
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    ///
+    /// Confusion matrix from which the metrics are computed
+    ///
+    pub fn confusion_matrix(&self) -> &ConfusionMatrix {
+        &self.matrix
+    }
+
+    ///
+    /// Sensitivity (recall, true positive rate) \\( TP / (TP + FN) \\)
+    ///
+    pub fn sensitivity(&self) -> f64 {
+        self.matrix.recall()
+    }
+
+    ///
+    /// Wilson score interval of the sensitivity
+    ///
+    pub fn ci_sensitivity(&self) -> Interval<f64> {
+        self.sensitivity
+    }
+
+    ///
+    /// Specificity (true negative rate) \\( TN / (TN + FP) \\)
+    ///
+    pub fn specificity(&self) -> f64 {
+        self.matrix.specificity()
+    }
+
+    ///
+    /// Wilson score interval of the specificity
+    ///
+    pub fn ci_specificity(&self) -> Interval<f64> {
+        self.specificity
+    }
+
+    ///
+    /// Positive likelihood ratio \\( \text{sensitivity} / (1 - \text{specificity}) \\)
+    ///
+    pub fn positive_likelihood_ratio(&self) -> f64 {
+        self.matrix.positive_likelihood_ratio()
+    }
+
+    ///
+    /// Log-scale Wald interval of the positive likelihood ratio
+    ///
+    pub fn ci_positive_likelihood_ratio(&self) -> Interval<f64> {
+        self.positive_likelihood_ratio
+    }
+
+    ///
+    /// Negative likelihood ratio \\( (1 - \text{sensitivity}) / \text{specificity} \\)
+    ///
+    pub fn negative_likelihood_ratio(&self) -> f64 {
+        self.matrix.negative_likelihood_ratio()
+    }
+
+    ///
+    /// Log-scale Wald interval of the negative likelihood ratio
+    ///
+    pub fn ci_negative_likelihood_ratio(&self) -> Interval<f64> {
+        self.negative_likelihood_ratio
+    }
+
+    ///
+    /// Positive predictive value \\( P(\text{positive} \mid \text{detected}) \\) at the given prevalence of the positives:
+    /// \\[
+    /// PPV = \frac{\text{sensitivity} \cdot \pi}{\text{sensitivity} \cdot \pi + (1 - \text{specificity}) (1 - \pi)}
+    /// \\]
+    /// With the prevalence of the sample (see [`ConfusionMatrix::prevalence`]), this is the precision.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProbability` - if the prevalence is not in (0, 1)
+    ///
+    pub fn positive_predictive_value(&self, prevalence: f64) -> CIResult<f64> {
+        let odds = self.positive_likelihood_ratio() * prevalence_odds(prevalence)?;
+        Ok(odds / (1. + odds))
+    }
+
+    ///
+    /// Confidence interval of the positive predictive value at the given prevalence (see [`Self::positive_predictive_value`]),
+    /// with the Wald interval of its logit:
+    /// \\[
+    /// \operatorname{logit} PPV \pm z \sqrt{\frac{1}{TP} - \frac{1}{TP + FN} + \frac{1}{FP} - \frac{1}{FP + TN}}
+    /// \\]
+    /// The prevalence is taken as known, e.g., the prevalence in the population where the test is deployed,
+    /// which usually differs from that of the sample.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProbability` - if the prevalence is not in (0, 1)
+    ///
+    /// # References
+    ///
+    /// * N.D. Mercaldo, K.F. Lau, X.H. Zhou. "Confidence intervals for predictive values with an emphasis to case-control studies."
+    ///   Statistics in Medicine 26(10): 2170-2183 (2007).
+    ///
+    pub fn ci_positive_predictive_value(&self, prevalence: f64) -> CIResult<Interval<f64>> {
+        let odds = self.positive_likelihood_ratio() * prevalence_odds(prevalence)?;
+        logit_interval(self.confidence, odds, self.matrix.positive_log_std_err())
+    }
+
+    ///
+    /// Negative predictive value \\( P(\text{negative} \mid \text{not detected}) \\) at the given prevalence of the positives:
+    /// \\[
+    /// NPV = \frac{\text{specificity} (1 - \pi)}{\text{specificity} (1 - \pi) + (1 - \text{sensitivity}) \pi}
+    /// \\]
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProbability` - if the prevalence is not in (0, 1)
+    ///
+    pub fn negative_predictive_value(&self, prevalence: f64) -> CIResult<f64> {
+        let odds = 1. / (self.negative_likelihood_ratio() * prevalence_odds(prevalence)?);
+        Ok(odds / (1. + odds))
+    }
+
+    ///
+    /// Confidence interval of the negative predictive value at the given prevalence (see [`Self::negative_predictive_value`]),
+    /// with the Wald interval of its logit:
+    /// \\[
+    /// \operatorname{logit} NPV \pm z \sqrt{\frac{1}{FN} - \frac{1}{TP + FN} + \frac{1}{TN} - \frac{1}{FP + TN}}
+    /// \\]
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProbability` - if the prevalence is not in (0, 1)
+    ///
+    pub fn ci_negative_predictive_value(&self, prevalence: f64) -> CIResult<Interval<f64>> {
+        let odds = 1. / (self.negative_likelihood_ratio() * prevalence_odds(prevalence)?);
+        logit_interval(self.confidence, odds, self.matrix.negative_log_std_err())
+    }
+}
+
+///
+/// Computes the sensitivity, the specificity, and the likelihood ratios of a binary test with their confidence intervals
+/// (see [`Diagnostics`]).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `true_positives` - number of actual positives detected by the test (TP)
+/// * `false_positives` - number of actual negatives detected by the test (FP)
+/// * `false_negatives` - number of actual positives missed by the test (FN)
+/// * `true_negatives` - number of actual negatives not detected by the test (TN)
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSuccesses`] - if there are less than two true positives or less than two true negatives
+/// * [`CIError::TooFewFailures`] - if there are less than two false negatives or less than two false positives
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+pub fn diagnostics(
+    confidence: Confidence,
+    true_positives: usize,
+    false_positives: usize,
+    false_negatives: usize,
+    true_negatives: usize,
+) -> CIResult<Diagnostics> {
+    ConfusionMatrix::new(
+        true_positives,
+        false_positives,
+        false_negatives,
+        true_negatives,
+    )
+    .diagnostics(confidence)
+}
+
+///
+/// Computes the confidence interval of the Brier score of probabilistic predictions, i.e., the mean squared difference
+/// \\( \frac{1}{n} \sum_{i=1}^n (p_i - y_i)^2 \\) between the predicted probability \\( p_i \\) of a positive outcome and the
+/// actual outcome \\( y_i \in \\{0, 1\\} \\).
+///
+/// The interval is the interval on the mean of the squared differences (see [`mean::Arithmetic::ci_mean`]), clamped to \\( [0, 1] \\).
+/// A lower Brier score indicates better predictions (0.25 for always predicting 1/2).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - pairs of a predicted probability and the actual outcome
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if some predicted probability is not in [0, 1]
+/// * [`CIError::TooFewSamples`] - if there are less than two predictions
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let forecasts = [(0.9, true), (0.8, true), (0.3, false), (0.6, false), (0.7, true), (0.2, false), (0.4, true), (0.1, false)];
+/// let ci = classification::ci_brier_score(Confidence::new_two_sided(0.95), &forecasts)?;
+/// assert!(ci.contains(&0.125));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * G.W. Brier. "Verification of forecasts expressed in terms of probability." Monthly Weather Review 78(1): 1-3 (1950).
+/// * [Wikipedia - Brier score](https://en.wikipedia.org/wiki/Brier_score)
+///
+pub fn ci_brier_score<F: Float, I>(confidence: Confidence, data: &I) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+{
+    let mut stats = mean::Arithmetic::new();
+    for (p, outcome) in probabilities(data)? {
+        stats.append((p - f64::from(u8::from(outcome))).powi(2))?;
+    }
+    if stats.sample_count() < 2 {
+        return Err(CIError::TooFewSamples(stats.sample_count()));
+    }
+    let (lo, hi) = match stats.ci_mean(confidence)? {
+        Interval::TwoSided(lo, hi) => (lo, hi),
+        Interval::UpperOneSided(lo) => (lo, 1.),
+        Interval::LowerOneSided(hi) => (0., hi),
+    };
+    unit_interval(confidence, lo, hi)
+}
+
+///
+/// Computes the expected calibration error (ECE) of probabilistic predictions, with `bins` bins of equal width over \\( [0, 1] \\):
+/// \\[
+/// \text{ECE} = \sum_{b} \frac{n_b}{n} \left| \bar{p}_b - \bar{y}_b \right|
+/// \\]
+/// where \\( n_b \\) is the number of predictions in bin \\( b \\), \\( \bar{p}_b \\) their mean predicted probability, and
+/// \\( \bar{y}_b \\) the proportion of positive outcomes among them.
+/// A perfectly calibrated model has an ECE of 0.
+///
+/// # Arguments
+///
+/// * `data` - pairs of a predicted probability and the actual outcome
+/// * `bins` - the number of bins (e.g., 10)
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if some predicted probability is not in [0, 1]
+/// * [`CIError::InvalidInputData`] - if the number of bins is zero
+/// * [`CIError::TooFewSamples`] - if there are no predictions
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # References
+///
+/// * M.P. Naeini, G. Cooper, M. Hauskrecht. "Obtaining well calibrated probabilities using Bayesian binning."
+///   AAAI Conference on Artificial Intelligence 29(1): 2901-2907 (2015).
+///
+pub fn expected_calibration_error<F: Float, I>(data: &I, bins: usize) -> CIResult<f64>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+{
+    let calibration = Calibration::new(data, bins)?;
+    Ok(calibration.error(0..calibration.predictions.len()))
+}
+
+///
+/// Computes the bootstrap confidence interval of the expected calibration error (see [`expected_calibration_error`]),
+/// resampling the predictions and binning each resample with the same bins (percentile method).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - pairs of a predicted probability and the actual outcome
+/// * `bins` - the number of bins (e.g., 10)
+/// * `bootstrap` - the number of resamples and the seed
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if some predicted probability is not in [0, 1]
+/// * [`CIError::InvalidInputData`] - if the number of bins is zero
+/// * [`CIError::TooFewSamples`] - if there are no predictions
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// The binned ECE is biased upward, since the absolute value turns the sampling noise of each bin into a positive error.
+/// The bias is large when bins contain few predictions, in which case the interval may lie entirely above the estimate;
+/// using fewer bins reduces it.
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let forecasts = [
+///     (0.95, true), (0.9, true), (0.85, false), (0.8, true), (0.75, true), (0.7, false), (0.65, true), (0.6, true),
+///     (0.55, false), (0.5, true), (0.45, false), (0.4, true), (0.35, false), (0.3, false), (0.25, true), (0.2, false),
+///     (0.15, false), (0.1, false), (0.05, false), (0.02, false), (0.88, true), (0.72, true), (0.33, false), (0.12, true),
+///     (0.67, false),
+/// ];
+/// assert_abs_diff_eq!(classification::expected_calibration_error(&forecasts, 5)?, 0.0624, epsilon = 1e-10);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let bootstrap = utils::Bootstrap::new(1_000, 11);
+/// let ci = classification::ci_expected_calibration_error(confidence, &forecasts, 5, &bootstrap)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.074, 0.2848)?, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_expected_calibration_error<F: Float, I>(
+    confidence: Confidence,
+    data: &I,
+    bins: usize,
+    bootstrap: &Bootstrap,
+) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+{
+    let calibration = Calibration::new(data, bins)?;
+    let (lo, hi) =
+        bootstrap.percentile_bounds(confidence, calibration.predictions.len(), |predictions| {
+            calibration.error(predictions.iter().copied())
+        })?;
+    unit_interval(confidence, lo, hi)
+}
+
+///
+/// Binned predictions for the computation of the expected calibration error.
+///
+struct Calibration {
+    bins: usize,
+    /// bin, predicted probability, and outcome of each prediction
+    predictions: Vec<(usize, f64, f64)>,
+}
+
+impl Calibration {
+    fn new<F: Float, I>(data: &I, bins: usize) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+    {
+        if bins == 0 {
+            return Err(CIError::InvalidInputData);
+        }
+        let predictions = probabilities(data)?
+            .into_iter()
+            .map(|(p, outcome)| {
+                let bin = ((p * bins as f64) as usize).min(bins - 1);
+                (bin, p, f64::from(u8::from(outcome)))
+            })
+            .collect::<Vec<_>>();
+        if predictions.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        Ok(Self { bins, predictions })
+    }
+
+    ///
+    /// Expected calibration error over the given predictions (possibly repeated).
+    ///
+    fn error<It: Iterator<Item = usize>>(&self, predictions: It) -> f64 {
+        let mut gaps = vec![0.; self.bins];
+        let mut n = 0;
+        for i in predictions {
+            let (bin, p, outcome) = self.predictions[i];
+            gaps[bin] += p - outcome;
+            n += 1;
+        }
+        gaps.iter().map(|gap| gap.abs()).sum::<f64>() / n as f64
+    }
+}
+
+///
+/// Predicted probabilities (checked to be in [0, 1]) and outcomes.
+///
+fn probabilities<F: Float, I>(data: &I) -> CIResult<Vec<(f64, bool)>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+{
+    data.into_iter()
+        .map(|&(p, outcome)| {
+            let p = p.try_f64("probability")?;
+            if (0. ..=1.).contains(&p) {
+                Ok((p, outcome))
+            } else {
+                Err(CIError::InvalidProbability(p))
+            }
+        })
+        .collect()
+}
+
+///
+/// Matthews correlation coefficient of the cells (TP, FP, FN, TN), or NaN if some margin is empty.
+///
+fn mcc(cells: [f64; 4]) -> f64 {
+    let [tp, fp, fn_, tn] = cells;
+    let product = (tp + fp) * (tp + fn_) * (tn + fp) * (tn + fn_);
+    if product > 0. {
+        (tp * tn - fp * fn_) / product.sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+fn f1_score(true_positives: usize, false_positives: usize, false_negatives: usize) -> f64 {
+    let tp = true_positives as f64;
+    2. * tp / (2. * tp + (false_positives + false_negatives) as f64)
+}
+
+///
+/// Wald interval of a positive ratio computed on the log scale, given the standard error of its logarithm.
+///
+fn log_wald_interval(
+    confidence: Confidence,
+    ratio: f64,
+    log_std_err: f64,
+) -> CIResult<Interval<f64>> {
+    let log_ratio = ratio.ln();
+    let span = z_value(confidence)? * log_std_err;
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(log_ratio - span, log_ratio + span)?,
+        Confidence::UpperOneSided(_) => Interval::new_upper(log_ratio - span),
+        Confidence::LowerOneSided(_) => Interval::new_lower(log_ratio + span),
+    };
+    Ok(interval.map(f64::exp))
+}
+
+///
+/// Rogan-Gladen estimate of the true prevalence, truncated to [0, 1], or NaN if the test is no better than chance.
+///
+fn rogan_gladen(apparent: f64, sensitivity: f64, specificity: f64) -> f64 {
+    let youden = sensitivity + specificity - 1.;
+    if youden > 0. {
+        ((apparent + specificity - 1.) / youden).clamp(0., 1.)
+    } else {
+        f64::NAN
+    }
+}
+
+///
+/// Odds \\( \pi / (1 - \pi) \\) of a prevalence \\( \pi \\) in (0, 1).
+///
+fn prevalence_odds(prevalence: f64) -> CIResult<f64> {
+    if prevalence > 0. && prevalence < 1. {
+        Ok(prevalence / (1. - prevalence))
+    } else {
+        Err(CIError::InvalidProbability(prevalence))
+    }
+}
+
+///
+/// Wald interval of a probability computed on the logit scale, given its odds and the standard error of its logit.
+///
+fn logit_interval(
+    confidence: Confidence,
+    odds: f64,
+    logit_std_err: f64,
+) -> CIResult<Interval<f64>> {
+    let interval = log_wald_interval(confidence, odds, logit_std_err)?;
+    let probability = |odds: f64| odds / (1. + odds);
+    unit_interval(
+        confidence,
+        interval.low().map_or(0., probability),
+        interval.high().map_or(1., probability),
+    )
+}
+
+///
+/// Interval of a quantity within \\( [0, 1] \\), with the bounds clamped to that range.
+///
+fn unit_interval(confidence: Confidence, lo: f64, hi: f64) -> CIResult<Interval<f64>> {
+    clamped_interval(confidence, lo, hi, 0.)
+}
+
+///
+/// Interval of a quantity within \\( [-1, 1] \\) (e.g., a correlation), with the bounds clamped to that range.
+///
+fn correlation_interval(confidence: Confidence, lo: f64, hi: f64) -> CIResult<Interval<f64>> {
+    clamped_interval(confidence, lo, hi, -1.)
+}
+
+fn clamped_interval(confidence: Confidence, lo: f64, hi: f64, min: f64) -> CIResult<Interval<f64>> {
+    let (lo, hi) = (lo.max(min), hi.min(1.));
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+        Confidence::LowerOneSided(_) => Interval::new(min, hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Ranks (starting at 1) of the values, where tied values get the mean of their ranks.
+///
+fn midranks(values: &[f64]) -> Vec<f64> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+    let mut ranks = vec![0.; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let value = values[order[start]];
+        let end = start + order[start..].partition_point(|&i| values[i] == value);
+        let rank = (start + end + 1) as f64 / 2.;
+        order[start..end].iter().for_each(|&i| ranks[i] = rank);
+        start = end;
+    }
+    ranks
+}
+
+fn sample_variance(values: &[f64], mean: f64) -> f64 {
+    values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_auc() -> CIResult<()> {
+        // reference values computed in python
+        let predictions = [
+            (0.9, true),
+            (0.8, true),
+            (0.7, false),
+            (0.6, true),
+            (0.55, true),
+            (0.54, true),
+            (0.53, false),
+            (0.52, false),
+            (0.51, true),
+            (0.505, true),
+            (0.4, false),
+            (0.39, true),
+            (0.38, false),
+            (0.37, true),
+            (0.36, false),
+            (0.35, false),
+            (0.34, true),
+            (0.33, false),
+            (0.30, true),
+            (0.1, false),
+            (0.7, true),
+            (0.4, true),
+        ];
+        let auc = Auc::from_iter(&predictions)?;
+        assert_eq!(auc.positive_count(), 13);
+        assert_eq!(auc.negative_count(), 9);
+        assert_abs_diff_eq!(auc.auc(), 0.6837606837606837, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            auc.std_err(AucStdErr::DeLong),
+            0.11942259074630142,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            auc.std_err(AucStdErr::HanleyMcNeil),
+            0.1148069283388872,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            auc.ci(Confidence::new_upper(0.95), AucStdErr::DeLong)?,
+            Interval::new(0.4873280022316885, 1.)?,
+            epsilon = 1e-9
+        );
+
+        // perfect separation
+        let auc = Auc::from_iter(&[(3., true), (4., true), (1., false), (2., false)])?;
+        assert_eq!(auc.auc(), 1.);
+        assert_eq!(auc.std_err(AucStdErr::DeLong), 0.);
+
+        assert_eq!(midranks(&[3., 1., 3., 2., 3.]), vec![4., 1., 4., 2., 4.]);
+        assert!(matches!(
+            Auc::from_iter(&[(0.5, true), (0.4, false), (0.3, false)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            Auc::from_iter(&[(f64::NAN, true), (0.4, false)]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_confusion_matrix() -> CIResult<()> {
+        // reference values computed in python
+        let matrix = ConfusionMatrix::new(42, 8, 11, 139);
+        assert_eq!(matrix.total(), 200);
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            matrix.ci_precision(confidence)?,
+            Interval::new(0.7148578393696501, 0.916625793219666)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            matrix.ci_precision(Confidence::new_upper(0.9))?,
+            Interval::new(0.7629207322156353, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(matrix.recall(), 0.7924528301886793, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            matrix.ci_recall(confidence)?,
+            Interval::new(0.6654225529835207, 0.8799540197358604)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(matrix.f1_score(), 0.8155339805825242, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            matrix.ci_f1(confidence)?,
+            Interval::new(0.734012886346989, 0.8970550748180595)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            matrix.ci_f1_bootstrap(confidence, &Bootstrap::new(1_000, 3))?,
+            Interval::new(0.7294117647058823, 0.8909090909090909)?,
+            epsilon = 1e-9
+        );
+
+        let predictions = [
+            (true, true),
+            (true, false),
+            (false, true),
+            (false, false),
+            (true, true),
+        ];
+        assert_eq!(
+            ConfusionMatrix::from_predictions(&predictions),
+            ConfusionMatrix::new(2, 1, 1, 1)
+        );
+        assert!(matches!(
+            ConfusionMatrix::new(0, 0, 0, 10).ci_f1(confidence),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calibration() -> CIResult<()> {
+        // reference values computed in python
+        let forecasts = [
+            (0.95, true),
+            (0.9, true),
+            (0.85, false),
+            (0.8, true),
+            (0.75, true),
+            (0.7, false),
+            (0.65, true),
+            (0.6, true),
+            (0.55, false),
+            (0.5, true),
+            (0.45, false),
+            (0.4, true),
+            (0.35, false),
+            (0.3, false),
+            (0.25, true),
+            (0.2, false),
+            (0.15, false),
+            (0.1, false),
+            (0.05, false),
+            (0.02, false),
+            (0.88, true),
+            (0.72, true),
+            (0.33, false),
+            (0.12, true),
+            (0.67, false),
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            ci_brier_score(confidence, &forecasts)?,
+            Interval::new(0.10417158056363966, 0.29586041943636027)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ci_brier_score(Confidence::new_upper(0.95), &forecasts)?,
+            Interval::new(0.12056514775286059, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            expected_calibration_error(&forecasts, 5)?,
+            0.0624,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            ci_expected_calibration_error(confidence, &forecasts, 5, &Bootstrap::new(1_000, 11))?,
+            Interval::new(0.074, 0.2848)?,
+            epsilon = 1e-9
+        );
+
+        // perfectly calibrated in a single bin
+        assert_eq!(
+            expected_calibration_error(&[(0.5, true), (0.5, false)], 10)?,
+            0.
+        );
+        assert!(matches!(
+            ci_brier_score(confidence, &[(1.5, true), (0.5, false)]),
+            Err(CIError::InvalidProbability(_))
+        ));
+        assert!(matches!(
+            expected_calibration_error(&[(0.5, true)], 0),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_brier_score(confidence, &[(0.5, true)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagnostics() -> CIResult<()> {
+        // reference values computed in python
+        let two_sided = diagnostics(Confidence::new_two_sided(0.95), 42, 8, 11, 139)?;
+        assert_abs_diff_eq!(two_sided.sensitivity(), 0.7924528301886793, epsilon = 1e-12);
+        assert_abs_diff_eq!(two_sided.specificity(), 0.9455782312925171, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            two_sided.ci_sensitivity(),
+            Interval::new(0.6654225529835208, 0.8799540197358605)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_specificity(),
+            Interval::new(0.8962927611287353, 0.9721687415919477)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            two_sided.positive_likelihood_ratio(),
+            14.561320754716995,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_positive_likelihood_ratio(),
+            Interval::new(7.319886741935849, 28.966576888002084)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            two_sided.negative_likelihood_ratio(),
+            0.2194923306637708,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_negative_likelihood_ratio(),
+            Interval::new(0.12951877798866918, 0.3719683274376539)?,
+            epsilon = 1e-9
+        );
+
+        let matrix = ConfusionMatrix::new(42, 8, 11, 139);
+        let upper = matrix.diagnostics(Confidence::new_upper(0.95))?;
+        assert_abs_diff_eq!(
+            upper.ci_sensitivity(),
+            Interval::new(0.6877506836333505, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            upper.ci_positive_likelihood_ratio(),
+            Interval::new_upper(8.175735666351457),
+            epsilon = 1e-8
+        );
+        let lower = matrix.diagnostics(Confidence::new_lower(0.95))?;
+        assert_abs_diff_eq!(
+            lower.ci_negative_likelihood_ratio(),
+            Interval::new_lower(0.3417235388701899),
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            upper.ci_negative_predictive_value(0.05)?,
+            Interval::new(0.9823323118964324, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            lower.ci_positive_predictive_value(0.05)?,
+            Interval::new(0., 0.5771605248125301)?,
+            epsilon = 1e-9
+        );
+
+        // predictive values at the deployment prevalence, and at the prevalence of the sample
+        assert_abs_diff_eq!(
+            two_sided.positive_predictive_value(0.05)?,
+            0.4338721011946594,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_positive_predictive_value(0.05)?,
+            Interval::new(0.27811239515225467, 0.6038908499899128)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            two_sided.negative_predictive_value(0.05)?,
+            0.9885797019563528,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_negative_predictive_value(0.05)?,
+            Interval::new(0.980798630208846, 0.9932293760500813)?,
+            epsilon = 1e-9
+        );
+        let prevalence = two_sided.confusion_matrix().prevalence();
+        assert_abs_diff_eq!(
+            two_sided.positive_predictive_value(prevalence)?,
+            two_sided.confusion_matrix().precision(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            two_sided.ci_positive_predictive_value(prevalence)?,
+            Interval::new(0.7252100166823267, 0.9126159178490962)?,
+            epsilon = 1e-9
+        );
+        assert!(matches!(
+            two_sided.ci_positive_predictive_value(1.),
+            Err(CIError::InvalidProbability(_))
+        ));
+
+        // true prevalence from a survey, with a validation study of the test
+        let validation = ConfusionMatrix::new(85, 10, 15, 190);
+        assert_abs_diff_eq!(
+            validation.true_prevalence(500, 120)?,
+            0.2375,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            validation.ci_true_prevalence(Confidence::new_two_sided(0.95), 500, 120)?,
+            Interval::new(0.1750867662238865, 0.2978787276054918)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            validation.ci_true_prevalence(Confidence::new_upper(0.95), 500, 120)?,
+            Interval::new(0.18595117396822003, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            validation.ci_true_prevalence_bootstrap(
+                Confidence::new_lower(0.95),
+                500,
+                120,
+                &Bootstrap::new(1_000, 7)
+            )?,
+            Interval::new(0., 0.2862745098039216)?,
+            epsilon = 1e-9
+        );
+        // fewer positives than the false positive rate
+        assert_eq!(validation.true_prevalence(500, 20)?, 0.);
+        assert!(matches!(
+            ConfusionMatrix::new(10, 60, 10, 40).true_prevalence(500, 120),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            validation.true_prevalence(100, 120),
+            Err(CIError::InvalidSuccesses(120, 100))
+        ));
+
+        // perfect specificity
+        assert!(matches!(
+            diagnostics(Confidence::new_two_sided(0.95), 42, 0, 11, 147),
+            Err(CIError::TooFewFailures(..))
+        ));
+        Ok(())
+    }
+}
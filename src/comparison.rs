@@ -20,6 +20,14 @@
 //! The structure [`Unpaired`] deals with the case of unpaired observations and can be used in simple form through the function [`Unpaired::ci`]
 //! or incrementally with the function [`Unpaired::ci_mean`].
 //!
+//! # Equivalence
+//!
+//! Showing that two samples do not differ by more than a given margin (e.g., that a refactoring does not make
+//! a program slower by more than 1%) is a different question than detecting a difference: failing to detect a
+//! difference does not demonstrate equivalence.
+//! The methods [`Paired::tost`] and [`Unpaired::tost`] implement the two one-sided tests (TOST) procedure and
+//! return an [`Equivalence`].
+//!
 //! # Examples
 //!
 //! ## Paired observations
@@ -436,8 +444,80 @@ impl<T: Float> Paired<T> {
         stats.extend(data_a, data_b)?;
         stats.ci_mean(confidence)
     }
+
+    ///
+    /// Test the equivalence of the means of the two samples within a given margin, using the two one-sided tests (TOST)
+    /// procedure (see [`Equivalence`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \\alpha = 0.05 \\))
+    /// * `margin` - the equivalence margin \\( \\delta \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidMargin`] - if the margin is not strictly positive and finite
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 pairs of observations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1.02, 1.99, 3.01, 4.03, 4.98];
+    /// let data_b = [1., 2., 3., 4., 5.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_a, &data_b)?;
+    /// let equivalence = stats.tost(Confidence::new_two_sided(0.95), 0.05)?;
+    /// assert!(equivalence.is_equivalent());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn tost(&self, confidence: Confidence, margin: T) -> CIResult<Equivalence<T>> {
+        Equivalence::new(
+            self.ci_mean(Equivalence::<T>::tost_confidence(confidence))?,
+            margin,
+        )
+    }
+
+    ///
+    /// Paired t-test of a zero difference between the means of the two samples, dual to [`Paired::ci_mean`]
+    /// (see [`hypothesis`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the direction and the significance level of the test
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 pairs of observations
+    /// * [`CIError::InvalidInputData`] - if all the differences are zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let data_bottom_water = [
+    ///     0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+    /// ];
+    /// let data_surface_water = [
+    ///     0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+    /// ];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_bottom_water, &data_surface_water)?;
+    /// let test = stats.t_test(Confidence::new_two_sided(0.95))?;
+    /// assert_abs_diff_eq!(test.statistic(), 4.8638, epsilon = 1e-4);
+    /// assert!(test.is_significant());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn t_test(&self, confidence: Confidence) -> CIResult<hypothesis::TestResult> {
+        self.stats.t_test(confidence, T::zero())
+    }
 }
 
+estimate::impl_statistic_for_mean!(Paired<F>, sem_over_n_minus_one);
+
 impl<T: Float> Default for Paired<T> {
     fn default() -> Self {
         Self {
@@ -790,38 +870,9 @@ impl<T: Float> Unpaired<T> {
     /// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
     ///
     pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<T>> {
-        let stats_a = self.stats_a;
-        let stats_b = self.stats_b;
-
-        let n_a = T::from(stats_a.sample_count()).convert("stats_a.sample_count")?;
-        let n_b = T::from(stats_b.sample_count()).convert("stats_b.sample_count")?;
-        let mean_a = stats_a.sample_mean();
-        let mean_b = stats_b.sample_mean();
-        let std_dev_a = stats_a.sample_std_dev();
-        let std_dev_b = stats_b.sample_std_dev();
-
-        let mean_difference = mean_a - mean_b;
-        let sa2_na = // $s_a^2 / n_a$
-            std_dev_a * std_dev_a / n_a;
-        let sb2_nb = // $s_b^2 / n_b$
-            std_dev_b * std_dev_b / n_b;
-        let sum_s2_n = // $s_a^2 / n_a + s_b^2 / n_b$
-            sa2_na + sb2_nb;
-        let std_err_mean = // $\sqrt{s_a^2 / n_a + s_b^2 / n_b}$
-            sum_s2_n.sqrt();
-        let effective_dof = // $ \frac{ (s_a^a / n_a + s_b^2 / n_b)^2 }{ \frac{1}{n_a+1} \left(\frac{s_a^2}{n_a}\right)^2 + \frac{1}{n_b+1} \left(\frac{s_b^2}{n_b}\right)^2 } - 2$
-            sum_s2_n * sum_s2_n
-                / (sa2_na * sa2_na / (n_a + T::one())
-                    + sb2_nb * sb2_nb / (n_b + T::one())) - T::one() - T::one();
-
-        let (lo, hi) = stats::interval_bounds(
-            confidence,
-            mean_difference.try_f64("mean_difference")?,
-            std_err_mean.try_f64("std_err_mean")?,
-            effective_dof.try_f64("effective_dof")?,
-        );
-        let lo = T::from(lo).convert("lo")?;
-        let hi = T::from(hi).convert("hi")?;
+        let (mean_difference, std_err_mean, effective_dof) = self.welch()?;
+        let (lo, hi) =
+            stats::float_interval_bounds(confidence, mean_difference, std_err_mean, effective_dof)?;
         match confidence {
             Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
             Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
@@ -883,6 +934,116 @@ impl<T: Float> Unpaired<T> {
         stats.extend(data_a, data_b)?;
         stats.ci_mean(confidence)
     }
+
+    ///
+    /// Test the equivalence of the means of the two samples within a given margin, using the two one-sided tests (TOST)
+    /// procedure (see [`Equivalence`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \\alpha = 0.05 \\))
+    /// * `margin` - the equivalence margin \\( \\delta \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidMargin`] - if the margin is not strictly positive and finite
+    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+    ///
+    /// # Examples
+    ///
+    /// Show that a new version of a program is not slower than the old one by more than 1%:
+    /// ```
+    /// # use stats_ci::*;
+    /// let times_new = [10.03, 9.98, 10.01, 9.97, 10.02, 10.0, 9.99, 10.01];
+    /// let times_old = [10.02, 10.01, 9.98, 10.03, 10.0, 9.99, 10.02, 10.01];
+    /// let stats = comparison::Unpaired::from_iter(&times_new, &times_old)?;
+    /// let margin = 0.01 * stats.stats_b().sample_mean();
+    /// // one-sided: only an increase of the running time matters
+    /// let equivalence = stats.tost(Confidence::new_lower(0.95), margin)?;
+    /// assert!(equivalence.is_equivalent());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn tost(&self, confidence: Confidence, margin: T) -> CIResult<Equivalence<T>> {
+        Equivalence::new(
+            self.ci_mean(Equivalence::<T>::tost_confidence(confidence))?,
+            margin,
+        )
+    }
+
+    ///
+    /// Welch's t-test of a zero difference between the means of the two samples, dual to [`Unpaired::ci_mean`]
+    /// (see [`hypothesis`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the direction and the significance level of the test
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+    /// * [`CIError::InvalidInputData`] - if both samples are constant and have the same mean
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_high_protein = [
+    ///     134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+    /// ];
+    /// let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+    /// let stats = comparison::Unpaired::from_iter(&data_high_protein, &data_low_protein)?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let test = stats.t_test(confidence)?;
+    /// // the interval includes zero, hence the difference is not significant
+    /// assert!(stats.ci_mean(confidence)?.contains(&0.));
+    /// assert!(!test.is_significant());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn t_test(&self, confidence: Confidence) -> CIResult<hypothesis::TestResult> {
+        let (mean_difference, std_err_mean, effective_dof) = self.welch()?;
+        hypothesis::TestResult::t(
+            confidence,
+            (mean_difference / std_err_mean).try_f64("statistic")?,
+            effective_dof.try_f64("effective_dof")?,
+        )
+    }
+
+    ///
+    /// Return the difference of the means, its standard error, and the effective degrees of freedom of Welch's t-test.
+    ///
+    fn welch(&self) -> CIResult<(T, T, T)> {
+        let stats_a = self.stats_a;
+        let stats_b = self.stats_b;
+        let min_count = stats_a.sample_count().min(stats_b.sample_count());
+        if min_count < 2 {
+            return Err(CIError::TooFewSamples(min_count));
+        }
+
+        let n_a = T::from(stats_a.sample_count()).convert("stats_a.sample_count")?;
+        let n_b = T::from(stats_b.sample_count()).convert("stats_b.sample_count")?;
+        let mean_a = stats_a.sample_mean();
+        let mean_b = stats_b.sample_mean();
+        let std_dev_a = stats_a.sample_std_dev();
+        let std_dev_b = stats_b.sample_std_dev();
+
+        let mean_difference = mean_a - mean_b;
+        let sa2_na = // $s_a^2 / n_a$
+            std_dev_a * std_dev_a / n_a;
+        let sb2_nb = // $s_b^2 / n_b$
+            std_dev_b * std_dev_b / n_b;
+        let sum_s2_n = // $s_a^2 / n_a + s_b^2 / n_b$
+            sa2_na + sb2_nb;
+        let std_err_mean = // $\sqrt{s_a^2 / n_a + s_b^2 / n_b}$
+            sum_s2_n.sqrt();
+        let effective_dof = // $ \frac{ (s_a^a / n_a + s_b^2 / n_b)^2 }{ \frac{1}{n_a+1} \left(\frac{s_a^2}{n_a}\right)^2 + \frac{1}{n_b+1} \left(\frac{s_b^2}{n_b}\right)^2 } - 2$
+            sum_s2_n * sum_s2_n
+                / (sa2_na * sa2_na / (n_a + T::one())
+                    + sb2_nb * sb2_nb / (n_b + T::one())) - T::one() - T::one();
+
+        Ok((mean_difference, std_err_mean, effective_dof))
+    }
 }
 
 impl<F: Float> core::ops::Add for Unpaired<F> {
@@ -905,6 +1066,134 @@ impl<F: Float> core::ops::AddAssign for Unpaired<F> {
     }
 }
 
+impl<F: Float> Statistic<F> for Unpaired<F> {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<F>> {
+        let interval = self.ci_mean(confidence)?;
+        let (stats_a, stats_b) = (&self.stats_a, &self.stats_b);
+        let n_a = F::from(stats_a.sample_count()).convert("stats_a.sample_count")?;
+        let n_b = F::from(stats_b.sample_count()).convert("stats_b.sample_count")?;
+        let var_a = stats_a.sample_std_dev().powi(2);
+        let var_b = stats_b.sample_std_dev().powi(2);
+        Ok(CiEstimate::new(
+            stats_a.sample_mean() - stats_b.sample_mean(),
+            interval,
+            confidence,
+            stats_a.sample_count() + stats_b.sample_count(),
+            (var_a / n_a + var_b / n_b).sqrt(),
+        ))
+    }
+}
+
+///
+/// Outcome of an equivalence test between two means, using the two one-sided tests (TOST) procedure.
+///
+/// Given an equivalence margin \\( \\delta > 0 \\), the two samples are deemed equivalent at the significance
+/// level \\( \\alpha \\) if both null hypotheses \\( \\mu_a - \\mu_b \\leq -\\delta \\) and \\( \\mu_a - \\mu_b \\geq \\delta \\)
+/// are rejected by one-sided tests at level \\( \\alpha \\).
+/// Equivalently, the two-sided confidence interval of the difference at the level \\( 1 - 2\\alpha \\) (e.g., 90% for
+/// \\( \\alpha = 0.05 \\)) must be included in \\( (-\\delta, \\delta) \\).
+///
+/// With a one-sided confidence, only one of the two tests is performed, which corresponds to a non-inferiority test:
+/// * with an upper one-sided confidence, the interval is \\( [l, +\\infty) \\) and equivalence means \\( l > -\\delta \\)
+///   (i.e., the first mean is not smaller than the second by more than the margin);
+/// * with a lower one-sided confidence, the interval is \\( (-\\infty, h] \\) and equivalence means \\( h < \\delta \\)
+///   (i.e., the first mean is not greater than the second by more than the margin).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // the TOST at alpha = 0.05 relies on the 90% confidence interval
+/// let tost_confidence = comparison::Equivalence::<f64>::tost_confidence(confidence);
+/// assert!(tost_confidence.is_two_sided());
+/// assert!((tost_confidence.level() - 0.9).abs() < 1e-12);
+///
+/// let interval = Interval::new(-0.2, 0.5)?;
+/// let equivalence = comparison::Equivalence::new(interval, 1.)?;
+/// assert!(equivalence.is_equivalent());
+/// let equivalence = comparison::Equivalence::new(interval, 0.4)?;
+/// assert!(!equivalence.is_equivalent());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * Donald J. Schuirmann. "A comparison of the two one-sided tests procedure and the power approach for assessing the equivalence of average bioavailability." Journal of Pharmacokinetics and Biopharmaceutics 15: 657-680 (1987).
+/// * [Wikipedia article on equivalence test](https://en.wikipedia.org/wiki/Equivalence_test)
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equivalence<T: Float> {
+    interval: Interval<T>,
+    margin: T,
+}
+
+impl<T: Float> Equivalence<T> {
+    ///
+    /// Create the outcome of an equivalence test from the confidence interval of the difference,
+    /// computed at the confidence given by [`Equivalence::tost_confidence`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - the confidence interval of the difference
+    /// * `margin` - the equivalence margin \\( \\delta \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidMargin`] - if the margin is not strictly positive and finite
+    ///
+    pub fn new(interval: Interval<T>, margin: T) -> CIResult<Self> {
+        if !(margin.is_finite() && margin > T::zero()) {
+            return Err(CIError::InvalidMargin(margin.to_f64().unwrap_or(f64::NAN)));
+        }
+        Ok(Self { interval, margin })
+    }
+
+    ///
+    /// Return the confidence of the interval used by the TOST procedure at the significance level given by `confidence`:
+    /// \\( 1 - 2\\alpha \\) for a two-sided confidence \\( 1 - \\alpha \\), and unchanged for a one-sided confidence.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn tost_confidence(confidence: Confidence) -> Confidence {
+        match confidence {
+            Confidence::TwoSided(level) => Confidence::TwoSided(2. * level - 1.),
+            _ => confidence,
+        }
+    }
+
+    ///
+    /// Return the confidence interval of the difference.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn interval(&self) -> &Interval<T> {
+        &self.interval
+    }
+
+    ///
+    /// Return the equivalence margin.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn margin(&self) -> T {
+        self.margin
+    }
+
+    ///
+    /// Return `true` if equivalence is demonstrated, i.e., if the interval lies within the margins.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn is_equivalent(&self) -> bool {
+        (self.interval.is_lower() || self.interval.low_f() > -self.margin)
+            && (self.interval.is_upper() || self.interval.high_f() < self.margin)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1009,4 +1298,97 @@ mod tests {
             e => panic!("unexpected error: {}", e),
         }
     }
+
+    #[test]
+    fn test_tost() -> CIResult<()> {
+        let data_a = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_b = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        let mut stats = Paired::default();
+        stats.extend(&data_a, &data_b)?;
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let equivalence = stats.tost(confidence, 0.12)?;
+        assert_abs_diff_eq!(
+            *equivalence.interval(),
+            Interval::new(0.050098200258023105, 0.11070179974197689)?,
+            epsilon = 1e-9
+        );
+        assert!(equivalence.is_equivalent());
+        assert!(!stats.tost(confidence, 0.11)?.is_equivalent());
+
+        // non-inferiority
+        let equivalence = stats.tost(Confidence::new_upper(0.95), 0.01)?;
+        assert_abs_diff_eq!(
+            equivalence.interval().low_f(),
+            0.050098200258023105,
+            epsilon = 1e-9
+        );
+        assert!(equivalence.is_equivalent());
+        assert!(!stats
+            .tost(Confidence::new_lower(0.95), 0.1)?
+            .is_equivalent());
+
+        let stats = Unpaired::from_iter(&data_a, &data_b)?;
+        let equivalence = stats.tost(confidence, 0.2)?;
+        assert_eq!(
+            *equivalence.interval(),
+            stats.ci_mean(Confidence::new_two_sided(0.9))?
+        );
+        assert!(equivalence.is_equivalent());
+        assert!(!stats.tost(confidence, 0.15)?.is_equivalent());
+
+        assert!(matches!(
+            stats.tost(confidence, 0.),
+            Err(CIError::InvalidMargin(_))
+        ));
+        assert!(matches!(
+            stats.tost(confidence, f64::INFINITY),
+            Err(CIError::InvalidMargin(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_t_test() -> CIResult<()> {
+        let data_a = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_b = [70., 118., 101., 85., 107., 132., 94.];
+        let stats = Unpaired::from_iter(&data_a, &data_b)?;
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let test = stats.t_test(confidence)?;
+        assert_abs_diff_eq!(test.statistic(), 1.9107001042454415, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            test.degrees_of_freedom().unwrap(),
+            15.055780018384468,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(test.p_value(), 0.07527612729446786, epsilon = 1e-9);
+
+        let test = stats.t_test(Confidence::new_upper(0.95))?;
+        assert_abs_diff_eq!(test.p_value(), 0.07527612729446786 / 2., epsilon = 1e-9);
+        assert!(test.is_significant());
+        assert!(!stats.ci_mean(Confidence::new_upper(0.95))?.contains(&0.));
+
+        let mut stats = Paired::default();
+        stats.extend(&data_a[..7].to_vec(), &data_b.to_vec())?;
+        let test = stats.t_test(confidence)?;
+        assert_eq!(
+            test.is_significant(),
+            !stats.ci_mean(confidence)?.contains(&0.)
+        );
+
+        assert!(matches!(
+            Unpaired::from_iter(&data_a, &[1.])?.t_test(confidence),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
 }
@@ -13,6 +13,9 @@
 /// * [`Confidence::new_two_sided`] - _idem_
 /// * [`Confidence::new_upper`] - create a new one-sided upper confidence interval with the given confidence level
 /// * [`Confidence::new_lower`] - create a new one-sided lower confidence interval with the given confidence level
+/// * [`Confidence::try_new`], [`Confidence::try_new_two_sided`], [`Confidence::try_new_upper`], [`Confidence::try_new_lower`], [`Confidence::try_from_percent`] - _idem_ but return an error instead of panicking on an invalid confidence level
+/// * [`Confidence::from_percent`] - create a new two-sided confidence interval with the given confidence level as a percentage
+/// * [`str::parse`] - parse a confidence from a string such as `"95%"`, `"0.95"`, or `"90% upper one-sided"` (see [`FromStr`](core::str::FromStr))
 ///
 /// ### Accessors
 ///
@@ -63,6 +66,18 @@
 /// let confidence = Confidence::new_lower(0.99);
 /// ```
 ///
+/// To create a confidence from a percentage or parse it from a string (e.g., from a configuration file or the command line):
+/// ```
+/// # use stats_ci::*;
+/// #
+/// assert_eq!(Confidence::from_percent(95.), Confidence::new(0.95));
+/// assert_eq!("95%".parse::<Confidence>()?, Confidence::new(0.95));
+/// assert_eq!("0.95".parse::<Confidence>()?, Confidence::new(0.95));
+/// assert_eq!("90% upper".parse::<Confidence>()?, Confidence::new_upper(0.9));
+/// assert_eq!("99% lower one-sided".parse::<Confidence>()?, Confidence::new_lower(0.99));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
 /// ## Accessors
 ///
 /// The confidence object provides several accessors:
@@ -79,6 +94,19 @@
 /// assert!(!confidence.is_lower());
 /// ```
 ///
+/// ## Display
+///
+/// A confidence is displayed as its percentage followed by its kind, and parsing the result yields
+/// the same confidence (up to the rounding error of dividing the percentage by 100).
+/// The precision of the format applies to the percentage.
+/// ```
+/// # use stats_ci::*;
+/// #
+/// assert_eq!(Confidence::new(0.95).to_string(), "95% two-sided");
+/// assert_eq!(format!("{:.1}", Confidence::new_upper(0.9)), "90.0% upper one-sided");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
 /// ## Conversions
 ///
 /// ```
@@ -98,6 +126,12 @@
 /// assert!(confidence < Confidence::new(0.99));
 /// ```
 ///
+/// ## Serialization
+///
+/// With the feature `serde`, a confidence is serialized with the kind of interval as a key,
+/// followed by the level, e.g., `{"TwoSided":0.95}` in JSON.
+/// This representation is kept stable across versions, in the same way as for [`Interval`](crate::Interval).
+///
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Confidence {
@@ -111,6 +145,25 @@ pub enum Confidence {
     LowerOneSided(f64),
 }
 
+///
+/// Correction of the confidence level for a family of intervals that must hold simultaneously
+/// (see [`Confidence::adjusted`]).
+///
+/// # References
+///
+/// * [Wikipedia - Bonferroni correction](https://en.wikipedia.org/wiki/Bonferroni_correction)
+/// * [Wikipedia - Šidák correction](https://en.wikipedia.org/wiki/%C5%A0id%C3%A1k_correction)
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Correction {
+    /// Bonferroni correction: valid regardless of the dependence between the intervals
+    #[default]
+    Bonferroni,
+    /// Šidák correction: slightly less conservative, valid for independent (or positively dependent) intervals
+    Sidak,
+}
+
 impl Confidence {
     ///
     /// Create a new two-sided confidence interval with the given confidence level.
@@ -140,10 +193,9 @@ impl Confidence {
     /// * if `confidence` is not in the range (0, 1)
     ///
     pub fn new_two_sided(confidence: f64) -> Self {
-        if confidence > 0. && confidence < 1. {
-            Confidence::TwoSided(confidence)
-        } else {
-            panic!("Confidence level must be in the range (0, 1).")
+        match Self::try_new_two_sided(confidence) {
+            Ok(confidence) => confidence,
+            Err(_) => panic!("Confidence level must be in the range (0, 1)."),
         }
     }
 
@@ -159,10 +211,9 @@ impl Confidence {
     /// * if `confidence` is not in the range (0, 1)
     ///
     pub fn new_upper(confidence: f64) -> Self {
-        if confidence > 0. && confidence < 1. {
-            Confidence::UpperOneSided(confidence)
-        } else {
-            panic!("Confidence level must be in the range (0, 1).")
+        match Self::try_new_upper(confidence) {
+            Ok(confidence) => confidence,
+            Err(_) => panic!("Confidence level must be in the range (0, 1)."),
         }
     }
 
@@ -178,16 +229,130 @@ impl Confidence {
     /// * if `confidence` is not in the range (0, 1)
     ///
     pub fn new_lower(confidence: f64) -> Self {
-        if confidence > 0. && confidence < 1. {
-            Confidence::LowerOneSided(confidence)
-        } else {
-            panic!("Confidence level must be in the range (0, 1).")
+        match Self::try_new_lower(confidence) {
+            Ok(confidence) => confidence,
+            Err(_) => panic!("Confidence level must be in the range (0, 1)."),
+        }
+    }
+
+    ///
+    /// Create a new two-sided confidence interval with the given confidence level, or return an error
+    /// if the confidence level is invalid.
+    /// This is the same as [`Confidence::try_new_two_sided`].
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if `confidence` is not in the range (0, 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// assert_eq!(Confidence::try_new(0.95)?, Confidence::new(0.95));
+    /// assert!(Confidence::try_new(95.).is_err());
+    /// assert!(Confidence::try_new_upper(f64::NAN).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_new(confidence: f64) -> CIResult<Self> {
+        Self::try_new_two_sided(confidence)
+    }
+
+    ///
+    /// Create a new two-sided confidence interval with the given confidence level, or return an error
+    /// if the confidence level is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if `confidence` is not in the range (0, 1)
+    ///
+    pub fn try_new_two_sided(confidence: f64) -> CIResult<Self> {
+        check_level(confidence).map(Confidence::TwoSided)
+    }
+
+    ///
+    /// Create a new one-sided upper confidence interval with the given confidence level, or return an error
+    /// if the confidence level is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if `confidence` is not in the range (0, 1)
+    ///
+    pub fn try_new_upper(confidence: f64) -> CIResult<Self> {
+        check_level(confidence).map(Confidence::UpperOneSided)
+    }
+
+    ///
+    /// Create a new one-sided lower confidence interval with the given confidence level, or return an error
+    /// if the confidence level is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if `confidence` is not in the range (0, 1)
+    ///
+    pub fn try_new_lower(confidence: f64) -> CIResult<Self> {
+        check_level(confidence).map(Confidence::LowerOneSided)
+    }
+
+    ///
+    /// Create a new two-sided confidence interval with the given confidence level as a percentage.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - the confidence level as a percentage, e.g. 95 for 95% confidence
+    ///
+    /// # Panics
+    ///
+    /// * if `percent` is not in the range (0, 100)
+    ///
+    pub fn from_percent(percent: f64) -> Self {
+        match Self::try_from_percent(percent) {
+            Ok(confidence) => confidence,
+            Err(_) => panic!("Confidence level must be in the range (0, 100)%."),
         }
     }
 
+    ///
+    /// Create a new two-sided confidence interval with the given confidence level as a percentage,
+    /// or return an error if the confidence level is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - the confidence level as a percentage, e.g. 95 for 95% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if `percent` is not in the range (0, 100)
+    ///
+    pub fn try_from_percent(percent: f64) -> CIResult<Self> {
+        Self::try_new_two_sided(percent / 100.)
+    }
+
     ///
     /// Return the confidence level of the interval as a number in the range (0, 1).
     ///
+    /// # Notes
+    ///
+    /// A confidence built directly from its variants (e.g., `Confidence::TwoSided(1.5)`) is not checked,
+    /// so its level may be outside of that range. Functions of the crate return
+    /// [`CIError::InvalidConfidenceLevel`] when given such a confidence.
+    ///
     pub fn level(&self) -> f64 {
         match self {
             Confidence::TwoSided(confidence)
@@ -256,6 +421,89 @@ impl Confidence {
         }
     }
 
+    ///
+    /// Return the confidence that each of `family_size` intervals must have so that all of them
+    /// simultaneously hold with this (joint) confidence, i.e., the family-wise error rate is controlled.
+    ///
+    /// With a joint confidence level \\( 1 - \\alpha \\) and \\( m \\) intervals, the individual level is
+    /// \\( 1 - \\alpha/m \\) with the Bonferroni correction, and \\( (1 - \\alpha)^{1/m} \\) with the Šidák correction.
+    ///
+    /// # Arguments
+    ///
+    /// * `family_size` - the number of intervals in the family (a size of zero is treated as one)
+    /// * `correction` - the correction to apply
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // comparing 20 metrics
+    /// let individual = confidence.adjusted(20, Correction::Bonferroni);
+    /// assert_abs_diff_eq!(individual.level(), 0.9975, epsilon = 1e-12);
+    /// let individual = confidence.adjusted(20, Correction::Sidak);
+    /// assert_abs_diff_eq!(individual.level(), 0.95_f64.powf(1. / 20.), epsilon = 1e-12);
+    /// assert!(individual.is_two_sided());
+    /// ```
+    ///
+    pub fn adjusted(&self, family_size: usize, correction: Correction) -> Self {
+        let m = family_size.max(1) as f64;
+        let level = self.level();
+        let individual = match correction {
+            Correction::Bonferroni => 1. - (1. - level) / m,
+            Correction::Sidak => level.powf(m.recip()),
+        };
+        self.with_level(individual)
+    }
+
+    ///
+    /// Return the confidence of the intervals constructed for `selected` parameters, selected among `family_size`
+    /// parameters, so that the false coverage-statement rate (FCR) is at most \\( \\alpha = 1 - \\) the level of this confidence
+    /// (Benjamini and Yekutieli, 2005).
+    ///
+    /// The FCR is the expected proportion of intervals that miss their parameter among the selected ones.
+    /// With \\( R \\) parameters selected among \\( m \\), each interval is computed at the level \\( 1 - R \\alpha / m \\).
+    /// This is much less conservative than controlling the family-wise error (see [`Confidence::adjusted`]) when only a few
+    /// parameters are selected among many.
+    ///
+    /// # Arguments
+    ///
+    /// * `family_size` - the total number of parameters \\( m \\) from which the selection is made
+    /// * `selected` - the number of selected parameters \\( R \\) (at most `family_size`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // 5 metrics selected among 200
+    /// let individual = confidence.fcr_adjusted(200, 5);
+    /// assert_abs_diff_eq!(individual.level(), 1. - 5. * 0.05 / 200., epsilon = 1e-12);
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * Yoav Benjamini and Daniel Yekutieli. "False discovery rate–adjusted multiple confidence intervals for selected parameters." Journal of the American Statistical Association 100(469): 71-81 (2005).
+    ///
+    pub fn fcr_adjusted(&self, family_size: usize, selected: usize) -> Self {
+        let m = family_size.max(1) as f64;
+        let r = selected.clamp(1, family_size.max(1)) as f64;
+        self.with_level(1. - r * (1. - self.level()) / m)
+    }
+
+    ///
+    /// Return a confidence of the same kind (two-sided, upper, or lower) but with the given confidence level.
+    ///
+    pub(crate) fn with_level(&self, level: f64) -> Self {
+        match self {
+            Confidence::TwoSided(_) => Confidence::TwoSided(level),
+            Confidence::UpperOneSided(_) => Confidence::UpperOneSided(level),
+            Confidence::LowerOneSided(_) => Confidence::LowerOneSided(level),
+        }
+    }
+
     ///
     /// Return the quantile of the confidence interval.
     ///
@@ -298,16 +546,23 @@ impl PartialOrd for Confidence {
     }
 }
 
-use crate::error::CIError;
+use crate::error::{CIError, CIResult};
 impl TryFrom<f64> for Confidence {
     type Error = CIError;
 
     fn try_from(confidence: f64) -> Result<Self, Self::Error> {
-        if confidence > 0. && confidence < 1. {
-            Ok(Confidence::new_two_sided(confidence))
-        } else {
-            Err(CIError::InvalidConfidenceLevel(confidence))
-        }
+        Confidence::try_new_two_sided(confidence)
+    }
+}
+
+///
+/// Check that a confidence level is in the range (0, 1).
+///
+fn check_level(confidence: f64) -> CIResult<f64> {
+    if confidence > 0. && confidence < 1. {
+        Ok(confidence)
+    } else {
+        Err(CIError::InvalidConfidenceLevel(confidence))
     }
 }
 
@@ -319,11 +574,60 @@ impl TryFrom<f32> for Confidence {
     }
 }
 
+impl core::str::FromStr for Confidence {
+    type Err = CIError;
+
+    ///
+    /// Parse a confidence from a string made of a level, either as a percentage (e.g., `"95%"`)
+    /// or as a number in the range (0, 1) (e.g., `"0.95"`), optionally followed by the kind of interval:
+    /// `two-sided` (default), `upper` or `upper one-sided`, `lower` or `lower one-sided`.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the level is not in the range (0, 1)
+    /// * [`CIError::ParseConfidenceError`] - if the string is not of the form described above
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = || CIError::ParseConfidenceError(s.to_string());
+        let (level, kind) = match s.trim().split_once(char::is_whitespace) {
+            Some((level, kind)) => (level, kind.trim()),
+            None => (s.trim(), ""),
+        };
+        let level = match level.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok().map(|percent| percent / 100.),
+            None => level.parse::<f64>().ok(),
+        }
+        .ok_or_else(parse_error)?;
+        let kind = kind.to_ascii_lowercase();
+        match kind.as_str() {
+            "" | "two-sided" => Confidence::try_new_two_sided(level),
+            "upper" | "upper one-sided" => Confidence::try_new_upper(level),
+            "lower" | "lower one-sided" => Confidence::try_new_lower(level),
+            _ => Err(parse_error()),
+        }
+    }
+}
+
+impl core::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}% {}", precision, self.percent(), self.kind()),
+            None => {
+                // round off the representation error of the multiplication (e.g., 0.07 * 100)
+                let percent = (self.percent() * 1e9).round() / 1e9;
+                write!(f, "{}% {}", percent, self.kind())
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::*;
 
     #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
     fn test_ordering() {
         let two_sided = Confidence::new_two_sided(0.95);
         let upper = Confidence::new_upper(0.95);
@@ -359,6 +663,111 @@ mod tests {
         assert_eq!(lower.quantile(), 0.95);
     }
 
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Confidence::from_percent(90.),
+            Confidence::new_two_sided(0.9)
+        );
+        assert_abs_diff_eq!(
+            Confidence::from_percent(99.9).level(),
+            0.999,
+            epsilon = 1e-12
+        );
+        assert_eq!(Confidence::from_percent(99.9).kind(), "two-sided");
+        assert_eq!("95%".parse::<Confidence>().unwrap(), Confidence::new(0.95));
+        assert_eq!(
+            " 0.95 ".parse::<Confidence>().unwrap(),
+            Confidence::new(0.95)
+        );
+        let parsed = "99.9% Upper One-Sided".parse::<Confidence>().unwrap();
+        assert_abs_diff_eq!(parsed.level(), 0.999, epsilon = 1e-12);
+        assert_eq!(parsed.kind(), "upper one-sided");
+        assert_eq!(
+            "0.8 lower".parse::<Confidence>().unwrap(),
+            Confidence::new_lower(0.8)
+        );
+        assert!(matches!(
+            "95".parse::<Confidence>(),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        assert!(matches!(
+            "100%".parse::<Confidence>(),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        for invalid in ["", "%", "ninety", "95% both", "0.9.5"] {
+            assert!(matches!(
+                invalid.parse::<Confidence>(),
+                Err(CIError::ParseConfidenceError(_))
+            ));
+        }
+
+        for confidence in [
+            Confidence::new(0.95),
+            Confidence::new_upper(0.07),
+            Confidence::new_lower(0.999),
+        ] {
+            let parsed = confidence.to_string().parse::<Confidence>().unwrap();
+            assert_abs_diff_eq!(parsed.level(), confidence.level(), epsilon = 1e-12);
+            assert_eq!(parsed.kind(), confidence.kind());
+        }
+        assert_eq!(
+            Confidence::new_upper(0.07).to_string(),
+            "7% upper one-sided"
+        );
+        assert_eq!(
+            format!("{:.2}", Confidence::new_lower(0.9)),
+            "90.00% lower one-sided"
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Confidence::try_new(0.9).unwrap(), Confidence::new(0.9));
+        assert_eq!(
+            Confidence::try_new_upper(0.9).unwrap(),
+            Confidence::new_upper(0.9)
+        );
+        assert_eq!(
+            Confidence::try_new_lower(0.9).unwrap(),
+            Confidence::new_lower(0.9)
+        );
+        assert_eq!(
+            Confidence::try_from_percent(90.).unwrap(),
+            Confidence::new(0.9)
+        );
+        for invalid in [0., 1., -0.5, 95., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                Confidence::try_new_two_sided(invalid),
+                Err(CIError::InvalidConfidenceLevel(_))
+            ));
+            assert!(Confidence::try_new_upper(invalid).is_err());
+            assert!(Confidence::try_new_lower(invalid).is_err());
+        }
+        assert!(Confidence::try_from_percent(100.).is_err());
+
+        // unchecked confidence levels are reported as errors rather than panics
+        let data = [1., 2., 3., 4., 5.];
+        for invalid in [
+            Confidence::TwoSided(1.5),
+            Confidence::UpperOneSided(0.),
+            Confidence::LowerOneSided(f64::NAN),
+        ] {
+            assert!(matches!(
+                crate::mean::Arithmetic::ci(invalid, &data),
+                Err(CIError::InvalidConfidenceLevel(_))
+            ));
+            assert!(matches!(
+                crate::proportion::ci(invalid, 100, 50),
+                Err(CIError::InvalidConfidenceLevel(_))
+            ));
+            assert!(matches!(
+                crate::rate::ci(invalid, 10, 2.),
+                Err(CIError::InvalidConfidenceLevel(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
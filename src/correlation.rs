@@ -0,0 +1,413 @@
+//!
+//! Confidence intervals for the correlation between two variables, possibly controlling for other variables (covariates).
+//!
+//! * [`Correlation::pearson`] - the Pearson correlation \\( r \\) between two variables
+//! * [`Correlation::partial`] - the partial correlation between two variables, controlling both for the covariates
+//!   (e.g., the correlation between two metrics once the effect of a confounder such as the load has been removed)
+//! * [`Correlation::semi_partial`] - the semi-partial (part) correlation between two variables, controlling only the first
+//!   variable for the covariates (e.g., the contribution of a predictor to a regression over that of the other predictors)
+//!
+//! The intervals are computed on the Fisher z-transform \\( Z = \tanh^{-1}(r) \\), which is approximately normally distributed
+//! with standard error \\( 1 / \sqrt{n - 3 - k} \\) for \\( n \\) observations and \\( k \\) covariates.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // latency (x), CPU usage (y), and request rate (covariate) of 8 servers
+//! let rows = [
+//!     [12., 30., 5.], [15., 34., 6.], [11., 29., 5.], [18., 41., 8.],
+//!     [14., 33., 6.], [20., 45., 9.], [9., 25., 4.], [16., 36., 7.],
+//! ];
+//! let pearson = correlation::Correlation::pearson(&rows.map(|row| (row[0], row[1])))?;
+//! let partial = correlation::Correlation::partial(&rows)?;
+//! assert!(partial.estimate() < pearson.estimate());
+//! let ci = partial.ci(Confidence::new_two_sided(0.95))?;
+//! assert!(ci.contains(&partial.estimate()));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * R.A. Fisher. "The distribution of the partial correlation coefficient." Metron 3: 329-332 (1924).
+//! * J. Cohen, P. Cohen, S.G. West, L.S. Aiken. Applied Multiple Regression/Correlation Analysis for the Behavioral Sciences,
+//!   3rd edition. Lawrence Erlbaum, 2003.
+//! * [Wikipedia - Partial correlation](https://en.wikipedia.org/wiki/Partial_correlation)
+//!
+use super::*;
+use distributions::z_value;
+use error::*;
+use num_traits::Float;
+
+///
+/// Correlation between two variables, possibly controlling for \\( k \\) covariates, computed from \\( n \\) observations.
+///
+/// The partial correlation is the correlation between the residuals of the two variables after their linear regression on the
+/// covariates, and the semi-partial correlation the correlation between the residuals of the first variable and the second variable.
+/// Both equal the Pearson correlation without covariates.
+///
+/// # Notes
+///
+/// The interval of the semi-partial correlation uses the same standard error as the partial correlation, which is only an
+/// approximation of the sampling distribution of the semi-partial correlation.
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // x, y, and two covariates
+/// let rows = [
+///     [12., 30., 5., 1.2], [15., 34., 6., 1.0], [11., 29., 5., 1.5], [18., 41., 8., 0.9], [14., 33., 6., 1.1],
+///     [20., 45., 9., 0.7], [9., 25., 4., 1.8], [16., 36., 7., 1.0], [13., 35., 6., 1.3], [17., 38., 8., 0.8],
+///     [10., 28., 4., 1.6], [19., 40., 9., 0.9], [14., 31., 5., 1.2], [12., 33., 6., 1.4], [21., 44., 10., 0.6],
+/// ];
+/// let partial = correlation::Correlation::partial(&rows)?;
+/// assert_eq!(partial.covariate_count(), 2);
+/// assert_abs_diff_eq!(partial.estimate(), 0.275208, epsilon = 1e-6);
+/// let ci = partial.ci(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(-0.325069, 0.717409)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Correlation {
+    sample_count: usize,
+    covariate_count: usize,
+    estimate: f64,
+}
+
+impl Correlation {
+    ///
+    /// Compute the Pearson correlation from pairs of observations of the two variables.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 4 pairs
+    /// * [`CIError::InvalidInputData`] - if some value is not finite, or either variable is constant
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    pub fn pearson<F: Float, I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let (x, y): (Vec<_>, Vec<_>) = data
+            .into_iter()
+            .map(|&(x, y)| Ok((finite(x)?, finite(y)?)))
+            .collect::<CIResult<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        Self::new(x, y, Vec::new(), false)
+    }
+
+    ///
+    /// Compute the partial correlation between the first two variables of the observations, controlling for the other variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - for each observation, the values of the two variables followed by the values of the covariates
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than \\( k + 4 \\) observations
+    /// * [`CIError::DifferentSampleSizes`] - if the observations do not all have the same number of variables
+    /// * [`CIError::InvalidInputData`] - if there are less than 2 variables, some value is not finite,
+    ///   some covariate is constant or a linear combination of the others, or either variable is explained by the covariates
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    pub fn partial<F, I, R>(rows: &I) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[F]>,
+    {
+        let (x, y, covariates) = columns(rows)?;
+        Self::new(x, y, covariates, false)
+    }
+
+    ///
+    /// Compute the semi-partial correlation between the first two variables of the observations, controlling only the first
+    /// variable for the other variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - for each observation, the values of the two variables followed by the values of the covariates
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than \\( k + 4 \\) observations
+    /// * [`CIError::DifferentSampleSizes`] - if the observations do not all have the same number of variables
+    /// * [`CIError::InvalidInputData`] - if there are less than 2 variables, some value is not finite,
+    ///   some covariate is constant or a linear combination of the others, or either variable is explained by the covariates
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    pub fn semi_partial<F, I, R>(rows: &I) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a I: IntoIterator<Item = &'a R>,
+        R: AsRef<[F]>,
+    {
+        let (x, y, covariates) = columns(rows)?;
+        Self::new(x, y, covariates, true)
+    }
+
+    fn new(x: Vec<f64>, y: Vec<f64>, covariates: Vec<Vec<f64>>, semi: bool) -> CIResult<Self> {
+        let sample_count = x.len();
+        let covariate_count = covariates.len();
+        if sample_count < covariate_count + 4 {
+            return Err(CIError::TooFewSamples(sample_count));
+        }
+        let basis = orthogonal_basis(covariates)?;
+        let x = residuals(x, &basis);
+        let y = if semi {
+            residuals(y, &[])
+        } else {
+            residuals(y, &basis)
+        };
+        let (xx, yy) = (dot(&x, &x), dot(&y, &y));
+        if xx <= 0. || yy <= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        Ok(Self {
+            sample_count,
+            covariate_count,
+            estimate: (dot(&x, &y) / (xx * yy).sqrt()).clamp(-1., 1.),
+        })
+    }
+
+    ///
+    /// Number of observations \\( n \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    ///
+    /// Number of covariates \\( k \\) controlled for
+    ///
+    pub fn covariate_count(&self) -> usize {
+        self.covariate_count
+    }
+
+    ///
+    /// Correlation
+    ///
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    ///
+    /// Confidence interval of the correlation, based on the normal distribution of its Fisher z-transform:
+    /// \\[
+    /// \tanh \left( \tanh^{-1}(r) \pm \frac{z}{\sqrt{n - 3 - k}} \right)
+    /// \\]
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let z = self.estimate.atanh();
+        let span =
+            z_value(confidence)? / ((self.sample_count - self.covariate_count - 3) as f64).sqrt();
+        let (lo, hi) = ((z - span).tanh(), (z + span).tanh());
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(-1., hi).map_err(|e| e.into()),
+        }
+    }
+}
+
+fn finite<F: Float>(value: F) -> CIResult<f64> {
+    let value = value.try_f64("value")?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(CIError::InvalidInputData)
+    }
+}
+
+///
+/// Columns of the two variables and of the covariates.
+///
+#[allow(clippy::type_complexity)]
+fn columns<F, I, R>(rows: &I) -> CIResult<(Vec<f64>, Vec<f64>, Vec<Vec<f64>>)>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a R>,
+    R: AsRef<[F]>,
+{
+    let mut columns: Vec<Vec<f64>> = Vec::new();
+    for row in rows {
+        let row = row.as_ref();
+        if columns.is_empty() {
+            if row.len() < 2 {
+                return Err(CIError::InvalidInputData);
+            }
+            columns = vec![Vec::new(); row.len()];
+        }
+        if row.len() != columns.len() {
+            return Err(CIError::DifferentSampleSizes(columns.len(), row.len()));
+        }
+        for (column, &value) in columns.iter_mut().zip(row) {
+            column.push(finite(value)?);
+        }
+    }
+    if columns.is_empty() {
+        return Err(CIError::TooFewSamples(0));
+    }
+    let covariates = columns.split_off(2);
+    let y = columns.pop().unwrap_or_default();
+    let x = columns.pop().unwrap_or_default();
+    Ok((x, y, covariates))
+}
+
+///
+/// Orthogonal basis of the centered covariates (modified Gram-Schmidt).
+///
+/// # Errors
+///
+/// * [`CIError::InvalidInputData`] - if some covariate is constant or a linear combination of the others
+///
+fn orthogonal_basis(covariates: Vec<Vec<f64>>) -> CIResult<Vec<Vec<f64>>> {
+    let mut basis: Vec<Vec<f64>> = Vec::with_capacity(covariates.len());
+    for covariate in covariates {
+        let centered = residuals(covariate, &[]);
+        let norm = dot(&centered, &centered);
+        let mut vector = residuals(centered, &basis);
+        let residual_norm = dot(&vector, &vector);
+        if residual_norm <= norm * 1e-12 {
+            return Err(CIError::InvalidInputData);
+        }
+        let scale = residual_norm.sqrt();
+        vector.iter_mut().for_each(|v| *v /= scale);
+        basis.push(vector);
+    }
+    Ok(basis)
+}
+
+///
+/// Residuals of the centered values after projection on the orthonormal basis.
+///
+fn residuals(mut values: Vec<f64>, basis: &[Vec<f64>]) -> Vec<f64> {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter_mut().for_each(|v| *v -= mean);
+    for vector in basis {
+        let projection = dot(&values, vector);
+        values
+            .iter_mut()
+            .zip(vector)
+            .for_each(|(v, b)| *v -= projection * b);
+    }
+    values
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_correlation() -> CIResult<()> {
+        // reference values computed in python
+        let rows = [
+            [12., 30., 5., 1.2],
+            [15., 34., 6., 1.0],
+            [11., 29., 5., 1.5],
+            [18., 41., 8., 0.9],
+            [14., 33., 6., 1.1],
+            [20., 45., 9., 0.7],
+            [9., 25., 4., 1.8],
+            [16., 36., 7., 1.0],
+            [13., 35., 6., 1.3],
+            [17., 38., 8., 0.8],
+            [10., 28., 4., 1.6],
+            [19., 40., 9., 0.9],
+            [14., 31., 5., 1.2],
+            [12., 33., 6., 1.4],
+            [21., 44., 10., 0.6],
+        ];
+        let two_sided = Confidence::new_two_sided(0.95);
+
+        let pearson = Correlation::pearson(&rows.map(|row| (row[0], row[1])))?;
+        assert_eq!(pearson.covariate_count(), 0);
+        assert_abs_diff_eq!(pearson.estimate(), 0.9653949172865851, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            pearson.ci(two_sided)?,
+            Interval::new(0.8964674532864623, 0.988706738921161)?,
+            epsilon = 1e-9
+        );
+        // without covariates, all the correlations are the Pearson correlation
+        let pairs = rows.map(|row| [row[0], row[1]]);
+        assert_abs_diff_eq!(
+            Correlation::partial(&pairs)?.estimate(),
+            pearson.estimate(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            Correlation::semi_partial(&pairs)?.estimate(),
+            pearson.estimate(),
+            epsilon = 1e-12
+        );
+
+        let partial = Correlation::partial(&rows)?;
+        assert_eq!(partial.sample_count(), 15);
+        assert_eq!(partial.covariate_count(), 2);
+        assert_abs_diff_eq!(partial.estimate(), 0.2752080603167688, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            partial.ci(two_sided)?,
+            Interval::new(-0.3250693222170688, 0.7174086708128226)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            partial.ci(Confidence::new_upper(0.95))?,
+            Interval::new(-0.23328278524850724, 1.)?,
+            epsilon = 1e-9
+        );
+
+        let semi_partial = Correlation::semi_partial(&rows)?;
+        assert_abs_diff_eq!(
+            semi_partial.estimate(),
+            0.06323120680251551,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            semi_partial.ci(two_sided)?,
+            Interval::new(-0.5053605880504575, 0.5935379633980434)?,
+            epsilon = 1e-9
+        );
+
+        let single = Correlation::partial(&rows.map(|row| [row[0], row[1], row[2]]))?;
+        assert_abs_diff_eq!(single.estimate(), 0.4648611945321589, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            single.ci(two_sided)?,
+            Interval::new(-0.08723427816863352, 0.7984951940732578)?,
+            epsilon = 1e-9
+        );
+
+        // collinear covariates
+        let collinear = rows.map(|row| [row[0], row[1], row[2], 2. * row[2] + 1.]);
+        assert!(matches!(
+            Correlation::partial(&collinear),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            Correlation::partial(&rows[..5].to_vec()),
+            Err(CIError::TooFewSamples(5))
+        ));
+        assert!(matches!(
+            Correlation::partial(&[vec![1., 2., 3.], vec![1., 2.]]),
+            Err(CIError::DifferentSampleSizes(3, 2))
+        ));
+        Ok(())
+    }
+}
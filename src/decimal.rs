@@ -0,0 +1,477 @@
+//!
+//! Confidence intervals over samples of exact decimal or fixed-point numbers (e.g., monetary amounts).
+//!
+//! Converting such samples to `f64` introduces the very rounding errors that the confidence interval
+//! is meant to quantify. Instead, the sums and sums of squares are accumulated exactly in the type of the
+//! samples, the mean and standard deviation are computed in that type, and only the critical value of the
+//! t-distribution is computed as a float before being converted to the type of the samples.
+//! The resulting interval is expressed in the type of the samples.
+//!
+//! Any type implementing the arithmetic traits of [`num_traits`] can be used (see [`ExactNumber`]),
+//! e.g., `rust_decimal::Decimal`, or the fixed-point types of the crate `fixed` (with its `num-traits` feature).
+//!
+//! Decimal and fixed-point numbers are totally ordered, so confidence intervals on quantiles (see [`quantile`])
+//! apply to them directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! use rust_decimal::Decimal;
+//! use std::str::FromStr;
+//! let prices = [
+//!     "19.99", "24.50", "18.75", "22.10", "21.35", "19.80", "23.45", "20.00", "25.15", "18.90",
+//! ]
+//! .map(|x| Decimal::from_str(x).unwrap());
+//! let confidence = Confidence::new_two_sided(0.95);
+//!
+//! // interval on the mean price
+//! let stats = decimal::Mean::from_iter(&prices)?;
+//! assert_eq!(stats.sample_mean()?, Decimal::from_str("21.399")?);
+//! let ci = stats.ci_mean(confidence)?;
+//! assert_eq!(ci.low().unwrap().round_dp(4), Decimal::from_str("19.7444")?);
+//! assert_eq!(ci.high().unwrap().round_dp(4), Decimal::from_str("23.0536")?);
+//!
+//! // interval on the median price
+//! let ci = quantile::ci(confidence, &prices, 0.5)?;
+//! assert_eq!(ci, Interval::new(Decimal::from_str("18.90")?, Decimal::from_str("24.50")?)?);
+//! # Ok::<(),Box<dyn std::error::Error>>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, One, ToPrimitive, Zero,
+};
+
+///
+/// Trait for the exact numeric types (e.g., decimal or fixed-point) over which [`Mean`] computes confidence intervals.
+///
+/// It is implemented for all types providing checked arithmetic and conversions from/to primitive types,
+/// such as `rust_decimal::Decimal`.
+///
+pub trait ExactNumber:
+    Copy
+    + PartialOrd
+    + Zero
+    + One
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + CheckedDiv
+    + FromPrimitive
+    + ToPrimitive
+{
+}
+
+impl<D> ExactNumber for D where
+    D: Copy
+        + PartialOrd
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + FromPrimitive
+        + ToPrimitive
+{
+}
+
+///
+/// Represents the state of the computation of a confidence interval on the mean of a sample of exact numbers.
+///
+/// The sum and the sum of squares of the samples are accumulated exactly, so the state does not suffer
+/// from any summation error. Arithmetic overflows are reported as errors rather than silently rounded.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use rust_decimal::Decimal;
+/// let mut stats = decimal::Mean::new();
+/// for cents in [1099, 1250, 999, 1175, 1325, 1010] {
+///     stats.append(Decimal::new(cents, 2))?;
+/// }
+/// assert_eq!(stats.sample_count(), 6);
+/// assert_eq!(stats.sample_mean()?, Decimal::new(1143, 2));
+/// let ci = stats.ci_mean(Confidence::new_upper(0.95))?;
+/// assert_eq!(ci.low().unwrap().round_dp(4), Decimal::new(103514, 4));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mean<D> {
+    sum: D,
+    sum_sq: D,
+    count: usize,
+}
+
+impl<D: ExactNumber> Default for Mean<D> {
+    fn default() -> Self {
+        Self {
+            sum: D::zero(),
+            sum_sq: D::zero(),
+            count: 0,
+        }
+    }
+}
+
+impl<D: ExactNumber> Mean<D> {
+    ///
+    /// Create a new empty state
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state and populate it with the given samples
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows the type of the samples
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a D>,
+    {
+        let mut stats = Self::new();
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Append a new sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows the type of the samples
+    ///   (in which case the state is left unchanged)
+    ///
+    pub fn append(&mut self, x: D) -> CIResult<()> {
+        let sum = checked(self.sum.checked_add(&x), "sum")?;
+        let sum_sq = checked(
+            x.checked_mul(&x)
+                .and_then(|x2| self.sum_sq.checked_add(&x2)),
+            "sum of squares",
+        )?;
+        self.sum = sum;
+        self.sum_sq = sum_sq;
+        self.count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Extend the sample with additional samples
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows the type of the samples
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a D>,
+    {
+        for &x in data {
+            self.append(x)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Exact sum of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sum(&self) -> D {
+        self.sum
+    }
+
+    ///
+    /// Mean of the sample (rounded to the precision of the type)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the sample is empty
+    /// * [`CIError::Overflow`] - If the number of samples cannot be represented in the type of the samples
+    ///
+    pub fn sample_mean(&self) -> CIResult<D> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        checked(self.sum.checked_div(&self.count_as()?), "mean")
+    }
+
+    ///
+    /// Variance of the sample
+    /// \\( \frac{n \sum_{i=1}^n x_i^2 - \left(\sum_{i=1}^n x_i\right)^2}{n(n-1)} \\),
+    /// where the numerator is computed exactly
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two samples
+    /// * [`CIError::Overflow`] - If an intermediate value overflows the type of the samples
+    ///
+    pub fn sample_variance(&self) -> CIResult<D> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let n = self.count_as()?;
+        let numerator = checked(
+            n.checked_mul(&self.sum_sq)
+                .zip(self.sum.checked_mul(&self.sum))
+                .and_then(|(a, b)| a.checked_sub(&b)),
+            "variance",
+        )?;
+        let denominator = checked(n.checked_mul(&(n - D::one())), "variance")?;
+        checked(numerator.checked_div(&denominator), "variance")
+    }
+
+    ///
+    /// Standard deviation of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two samples
+    /// * [`CIError::Overflow`] - If an intermediate value overflows the type of the samples
+    ///
+    pub fn sample_std_dev(&self) -> CIResult<D> {
+        sqrt(self.sample_variance()?)
+    }
+
+    ///
+    /// Standard error of the sample mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two samples
+    /// * [`CIError::Overflow`] - If an intermediate value overflows the type of the samples
+    ///
+    pub fn sample_sem(&self) -> CIResult<D> {
+        let sqrt_n = sqrt(self.count_as()?)?;
+        checked(self.sample_std_dev()?.checked_div(&sqrt_n), "sem")
+    }
+
+    ///
+    /// Confidence interval of the sample mean
+    ///
+    /// Only the critical value of the t-distribution is computed as `f64`; the bounds are then
+    /// computed in the type of the samples.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two samples
+    /// * [`CIError::Overflow`] - If an intermediate value overflows the type of the samples
+    /// * [`CIError::FloatConversionError`] - If the critical value cannot be represented in the type of the samples
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<D>> {
+        let mean = self.sample_mean()?;
+        let std_err_mean = self.sample_sem()?;
+        // critical value as the upper bound of the interval with mean 0 and unit standard error
+        let (_, critical_value) =
+            stats::interval_bounds(confidence, 0., 1., (self.count - 1) as f64)?;
+        let critical_value = D::from_f64(critical_value).ok_or_else(|| {
+            CIError::FloatConversionError(format!(
+                "Error converting critical value ({}) to {}",
+                critical_value,
+                core::any::type_name::<D>()
+            ))
+        })?;
+        let span = checked(critical_value.checked_mul(&std_err_mean), "span")?;
+        let lo = checked(mean.checked_sub(&span), "lo")?;
+        let hi = checked(mean.checked_add(&span), "hi")?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample of exact numbers
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two samples
+    /// * [`CIError::Overflow`] - If an intermediate value overflows the type of the samples
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<D>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a D>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+
+    ///
+    /// Number of samples in the type of the samples
+    ///
+    fn count_as(&self) -> CIResult<D> {
+        checked(D::from_usize(self.count), "count")
+    }
+}
+
+impl<D: ExactNumber> Statistic<D> for Mean<D> {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<D>> {
+        Ok(CiEstimate::new(
+            self.sample_mean()?,
+            self.ci_mean(confidence)?,
+            confidence,
+            self.sample_count(),
+            self.sample_sem()?,
+        ))
+    }
+}
+
+///
+/// Convert the result of a checked operation into a [`CIResult`]
+///
+fn checked<D>(value: Option<D>, var_name: &str) -> CIResult<D> {
+    value.ok_or_else(|| CIError::Overflow(var_name.to_string()))
+}
+
+///
+/// Square root of a non-negative exact number, from an initial guess in `f64` refined with Newton's method
+/// in the type of the number (so that the result has the full precision of the type)
+///
+fn sqrt<D: ExactNumber>(x: D) -> CIResult<D> {
+    const MAX_ITERATIONS: usize = 10;
+    if x <= D::zero() {
+        return Ok(D::zero());
+    }
+    let guess = x.to_f64().map(f64::sqrt).and_then(D::from_f64);
+    let mut y = checked(guess.filter(|y| *y > D::zero()), "sqrt")?;
+    let two = D::one() + D::one();
+    for _ in 0..MAX_ITERATIONS {
+        let next = checked(
+            x.checked_div(&y)
+                .and_then(|q| y.checked_add(&q))
+                .and_then(|s| s.checked_div(&two)),
+            "sqrt",
+        )?;
+        if next == y {
+            break;
+        }
+        y = next;
+    }
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_decimal_mean() -> CIResult<()> {
+        let cents = [
+            1999, 2450, 1875, 2210, 2135, 1980, 2345, 2000, 2515, 1890, 2120, 1960,
+        ];
+        let prices = cents.map(|c| Decimal::new(c, 2));
+        let floats = cents.map(|c| c as f64 / 100.);
+
+        let stats = Mean::from_iter(&prices)?;
+        let reference = mean::Arithmetic::from_iter(&floats)?;
+        assert_eq!(stats.sample_count(), 12);
+        assert_eq!(stats.sample_sum(), Decimal::new(25479, 2));
+        assert_eq!(stats.sample_mean()?, Decimal::new(212325, 4));
+        assert_abs_diff_eq!(
+            stats.sample_std_dev()?.to_f64().unwrap(),
+            reference.sample_std_dev(),
+            epsilon = 1e-12
+        );
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let ci = stats.ci_mean(confidence)?;
+            let reference_ci = reference.ci_mean(confidence)?;
+            assert_eq!(ci.is_two_sided(), reference_ci.is_two_sided());
+            for (bound, reference) in [
+                (ci.low(), reference_ci.low()),
+                (ci.high(), reference_ci.high()),
+            ] {
+                if let (Some(bound), Some(reference)) = (bound, reference) {
+                    assert_abs_diff_eq!(bound.to_f64().unwrap(), reference, epsilon = 1e-12);
+                }
+            }
+        }
+        let estimate = stats.estimate(Confidence::new_two_sided(0.95))?;
+        assert_eq!(estimate.estimate(), stats.sample_mean()?);
+
+        // exact accumulation: the sum of 0.1 ten thousand times is exactly 1000
+        let mut stats = Mean::new();
+        stats.extend(&vec![Decimal::new(1, 1); 10_000])?;
+        assert_eq!(stats.sample_sum(), Decimal::new(1000, 0));
+        assert_eq!(stats.sample_variance()?, Decimal::ZERO);
+        assert_eq!(
+            stats.ci_mean(Confidence::new_two_sided(0.95))?,
+            Interval::new(Decimal::new(1, 1), Decimal::new(1, 1))?
+        );
+
+        // errors
+        assert!(matches!(
+            Mean::<Decimal>::new().sample_mean(),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            Mean::ci(Confidence::new(0.95), &[Decimal::ONE]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        let mut stats = Mean::new();
+        stats.append(Decimal::ONE)?;
+        assert!(matches!(
+            stats.append(Decimal::MAX),
+            Err(CIError::Overflow(_))
+        ));
+        assert_eq!(stats.sample_count(), 1);
+        assert_eq!(stats.sample_sum(), Decimal::ONE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt() -> CIResult<()> {
+        assert_eq!(sqrt(Decimal::new(144, 0))?, Decimal::new(12, 0));
+        assert_eq!(sqrt(Decimal::ZERO)?, Decimal::ZERO);
+        let two = sqrt(Decimal::TWO)?;
+        assert_eq!(
+            two.round_dp(25),
+            Decimal::from_str_exact("1.4142135623730950488016887").unwrap()
+        );
+        assert_eq!(sqrt(42_i64)?, 6);
+        Ok(())
+    }
+}
@@ -0,0 +1,686 @@
+//!
+//! Critical values and quantile functions of the distributions used to compute confidence intervals.
+//!
+//! The critical value functions take a [`Confidence`] and split the tails accordingly: for a two-sided
+//! confidence with level \\( 1-\alpha \\), they return the \\( 1-\alpha/2 \\) quantile of the distribution,
+//! and for a one-sided confidence, they return the \\( 1-\alpha \\) quantile.
+//! The quantile functions (inverse CDFs) give access to any other quantile, e.g., the lower critical value of
+//! an asymmetric distribution such as chi-square.
+//!
+//! # Critical values
+//!
+//! * [`z_value`] - critical value of the standard normal distribution
+//! * [`t_value`] - critical value of the Student t-distribution
+//! * [`chi2_value`] - (upper) critical value of the chi-square distribution
+//! * [`f_value`] - (upper) critical value of the F-distribution
+//! * [`z_value_as`], [`t_value_as`] - critical values of the normal and t-distributions computed in a given float type (e.g., `f32`)
+//!
+//! # Quantile functions
+//!
+//! * [`normal_inverse_cdf`] - quantile of the standard normal distribution
+//! * [`t_inverse_cdf`] - quantile of the Student t-distribution
+//! * [`chi2_inverse_cdf`] - quantile of the chi-square distribution
+//! * [`f_inverse_cdf`] - quantile of the F-distribution
+//! * [`beta_inverse_cdf`] - quantile of the beta distribution (e.g., the posterior of a proportion)
+//!
+//! # Performance
+//!
+//! The critical values of the t-distribution are cached per thread, keyed by quantile and degrees of freedom,
+//! so that computing many confidence intervals on small samples (e.g., per-key aggregations) does not
+//! repeatedly invert the CDF of the t-distribution.
+//!
+//! # Implementation
+//!
+//! With the feature `statrs` (enabled by default), the quantile functions are those of the crate
+//! [`statrs`](https://crates.io/crates/statrs). Without it, the crate uses built-in implementations:
+//! the normal quantile uses the rational approximation of Acklam refined by one step of Halley's method,
+//! and the quantiles of the Student t, chi-square, and F distributions are obtained by inverting the
+//! regularized incomplete beta and gamma functions with Halley's or Newton's method.
+//! The built-in quantiles have a relative error below \\( 10^{-12} \\) for probabilities in
+//! \\( [10^{-10}, 1 - 10^{-10}] \\).
+//!
+//! The built-in implementations are generic over the float type, which [`z_value_as`] and [`t_value_as`]
+//! use to compute critical values without any `f64` arithmetic (e.g., for `f32`-only pipelines).
+//! In `f32`, the relative error is below \\( 10^{-6} \\) for the normal distribution, and below \\( 10^{-5} \\)
+//! for the t-distribution, for confidence levels up to \\( 1 - 10^{-4} \\).
+//!
+//! # Examples
+//!
+//! A custom interval on the variance of a normal population, from the sample variance of 11 samples:
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let (variance, dof) = (4., 10.);
+//! let alpha = 1. - confidence.level();
+//! let upper = distributions::chi2_value(confidence, dof)?;
+//! let lower = distributions::chi2_inverse_cdf(alpha / 2., dof)?;
+//! let ci = Interval::new(dof * variance / upper, dof * variance / lower)?;
+//! assert_abs_diff_eq!(ci, Interval::new(1.9528, 12.3192)?, epsilon = 1e-4);
+//!
+//! // critical values of the normal and t-distributions
+//! assert_abs_diff_eq!(distributions::z_value(confidence)?, 1.96, epsilon = 1e-2);
+//! assert_abs_diff_eq!(distributions::t_value(confidence, dof)?, 2.2281, epsilon = 1e-4);
+//! assert_abs_diff_eq!(distributions::t_value(Confidence::new_upper(0.95), 4.)?, 2.1318, epsilon = 1e-4);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+///
+/// Return the critical value of the standard normal distribution for a given confidence,
+/// e.g., 1.96 for a two-sided 95% confidence.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. two-sided 95% confidence
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+///
+pub fn z_value(confidence: Confidence) -> CIResult<f64> {
+    normal_inverse_cdf(checked_quantile(confidence)?)
+}
+
+///
+/// Return the critical value of the Student t-distribution for a given confidence and degrees of freedom,
+/// e.g., 2.228 for a two-sided 95% confidence with 10 degrees of freedom.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. two-sided 95% confidence
+/// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+/// # Notes
+///
+/// The values are cached per thread (up to a bounded number of entries), so that repeated calls
+/// with the same confidence and degrees of freedom are nearly free.
+///
+pub fn t_value(confidence: Confidence, degrees_of_freedom: f64) -> CIResult<f64> {
+    let quantile = checked_quantile(confidence)?;
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    let key = (quantile.to_bits(), degrees_of_freedom.to_bits());
+    if let Some(t) = T_VALUES.with(|cache| cache.borrow().get(&key).copied()) {
+        return Ok(t);
+    }
+    let t = t_inverse_cdf(quantile, degrees_of_freedom)?;
+    T_VALUES.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= T_VALUES_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(key, t);
+    });
+    Ok(t)
+}
+
+///
+/// Maximum number of entries in the per-thread cache of t-values (cleared when full).
+///
+const T_VALUES_CAPACITY: usize = 4096;
+
+thread_local! {
+    ///
+    /// Per-thread cache of t-values, keyed by the bits of the quantile and of the degrees of freedom.
+    ///
+    static T_VALUES: RefCell<HashMap<(u64, u64), f64>> = RefCell::new(HashMap::new());
+}
+
+///
+/// Return the critical value of the standard normal distribution for a given confidence,
+/// computed entirely in the float type `F` (e.g., `f32`) rather than in `f64`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. two-sided 95% confidence
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+/// * [`CIError::FloatConversionError`] - if the quantile of the confidence cannot be represented in `F`
+///
+/// # Notes
+///
+/// The value is computed by the built-in quantile function (regardless of the feature `statrs`),
+/// with the precision of `F` (see [Implementation](self#implementation)).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let z = distributions::z_value_as::<f32>(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(z, 1.959964, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn z_value_as<F: Float>(confidence: Confidence) -> CIResult<F> {
+    Ok(-special::normal_inverse_cdf(checked_tail::<F>(confidence)?))
+}
+
+///
+/// Return the critical value of the Student t-distribution for a given confidence and degrees of freedom,
+/// computed entirely in the float type `F` (e.g., `f32`) rather than in `f64`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. two-sided 95% confidence
+/// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+/// * [`CIError::FloatConversionError`] - if the quantile of the confidence cannot be represented in `F`
+///
+/// # Notes
+///
+/// The value is computed by the built-in quantile function (regardless of the feature `statrs`),
+/// with the precision of `F` (see [Implementation](self#implementation)). Unlike [`t_value`], the values are not cached.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let t = distributions::t_value_as::<f32>(Confidence::new_two_sided(0.95), 10.)?;
+/// assert_abs_diff_eq!(t, 2.228139, epsilon = 1e-5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn t_value_as<F: Float>(confidence: Confidence, degrees_of_freedom: F) -> CIResult<F> {
+    let tail = checked_tail::<F>(confidence)?;
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    Ok(-special::t_inverse_cdf(tail, degrees_of_freedom))
+}
+
+///
+/// Return the upper critical value of the chi-square distribution for a given confidence and degrees of freedom,
+/// e.g., 20.483 for a two-sided 95% confidence with 10 degrees of freedom.
+///
+/// The lower critical value of a two-sided confidence with level \\( 1-\alpha \\) is
+/// given by [`chi2_inverse_cdf`] with probability \\( \alpha/2 \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. two-sided 95% confidence
+/// * `degrees_of_freedom` - the degrees of freedom of the chi-square distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+pub fn chi2_value(confidence: Confidence, degrees_of_freedom: f64) -> CIResult<f64> {
+    chi2_inverse_cdf(checked_quantile(confidence)?, degrees_of_freedom)
+}
+
+///
+/// Return the upper critical value of the F-distribution for a given confidence and degrees of freedom,
+/// e.g., 3.708 for a one-sided 95% confidence with 3 and 10 degrees of freedom.
+///
+/// The lower critical value of a two-sided confidence with level \\( 1-\alpha \\) is
+/// given by [`f_inverse_cdf`] with probability \\( \alpha/2 \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and kind, e.g. one-sided 95% confidence
+/// * `numerator_degrees_of_freedom` - the degrees of freedom of the numerator
+/// * `denominator_degrees_of_freedom` - the degrees of freedom of the denominator
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if some degrees of freedom are negative, zero, or NaN
+///
+pub fn f_value(
+    confidence: Confidence,
+    numerator_degrees_of_freedom: f64,
+    denominator_degrees_of_freedom: f64,
+) -> CIResult<f64> {
+    f_inverse_cdf(
+        checked_quantile(confidence)?,
+        numerator_degrees_of_freedom,
+        denominator_degrees_of_freedom,
+    )
+}
+
+///
+/// Return the quantile of the standard normal distribution for a given probability.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range [0, 1]
+///
+pub fn normal_inverse_cdf(p: f64) -> CIResult<f64> {
+    check_probability(p)?;
+    backend::normal_inverse_cdf(p)
+}
+
+///
+/// Return the quantile of the Student t-distribution for a given probability and degrees of freedom.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range [0, 1]
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+pub fn t_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+    check_probability(p)?;
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    backend::t_inverse_cdf(p, degrees_of_freedom)
+}
+
+///
+/// Return the quantile of the chi-square distribution for a given probability and degrees of freedom.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `degrees_of_freedom` - the degrees of freedom of the chi-square distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range [0, 1]
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+pub fn chi2_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+    check_probability(p)?;
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    backend::chi2_inverse_cdf(p, degrees_of_freedom)
+}
+
+///
+/// Return the quantile of the F-distribution for a given probability and degrees of freedom.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.95
+/// * `numerator_degrees_of_freedom` - the degrees of freedom of the numerator
+/// * `denominator_degrees_of_freedom` - the degrees of freedom of the denominator
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range [0, 1]
+/// * [`CIError::InvalidDegreesOfFreedom`] - if some degrees of freedom are negative, zero, or NaN
+///
+pub fn f_inverse_cdf(
+    p: f64,
+    numerator_degrees_of_freedom: f64,
+    denominator_degrees_of_freedom: f64,
+) -> CIResult<f64> {
+    check_probability(p)?;
+    check_degrees_of_freedom(numerator_degrees_of_freedom)?;
+    check_degrees_of_freedom(denominator_degrees_of_freedom)?;
+    backend::f_inverse_cdf(
+        p,
+        numerator_degrees_of_freedom,
+        denominator_degrees_of_freedom,
+    )
+}
+
+///
+/// Return the quantile of the beta distribution for a given probability and shape parameters.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `shape_a` - the first shape parameter \( \alpha \) of the beta distribution
+/// * `shape_b` - the second shape parameter \( \beta \) of the beta distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range [0, 1]
+/// * [`CIError::NonPositiveValue`] - if some shape parameter is negative, zero, infinite, or NaN
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// assert_abs_diff_eq!(distributions::beta_inverse_cdf(0.3, 2., 5.)?, 0.1818035, epsilon = 1e-7);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn beta_inverse_cdf(p: f64, shape_a: f64, shape_b: f64) -> CIResult<f64> {
+    check_probability(p)?;
+    check_shape(shape_a)?;
+    check_shape(shape_b)?;
+    backend::beta_inverse_cdf(p, shape_a, shape_b)
+}
+
+///
+/// Quantile functions implemented with the crate `statrs`.
+///
+#[cfg(feature = "statrs")]
+mod backend {
+    use crate::error::*;
+    use lazy_static::lazy_static;
+    use statrs::distribution::ContinuousCDF;
+    use statrs::distribution::{Beta, ChiSquared, FisherSnedecor, Normal, StudentsT};
+
+    pub(super) fn normal_inverse_cdf(p: f64) -> CIResult<f64> {
+        lazy_static! {
+            static ref NORMAL: Normal = Normal::new(0., 1.).unwrap();
+        }
+        Ok(NORMAL.inverse_cdf(p))
+    }
+
+    pub(super) fn t_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+        let student_t = StudentsT::new(0., 1., degrees_of_freedom)
+            .map_err(|_| CIError::InvalidDegreesOfFreedom(degrees_of_freedom))?;
+        Ok(student_t.inverse_cdf(p))
+    }
+
+    pub(super) fn chi2_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+        let chi2 = ChiSquared::new(degrees_of_freedom)
+            .map_err(|_| CIError::InvalidDegreesOfFreedom(degrees_of_freedom))?;
+        Ok(chi2.inverse_cdf(p))
+    }
+
+    pub(super) fn f_inverse_cdf(p: f64, d1: f64, d2: f64) -> CIResult<f64> {
+        let f = FisherSnedecor::new(d1, d2).map_err(|_| CIError::InvalidDegreesOfFreedom(d1))?;
+        Ok(f.inverse_cdf(p))
+    }
+
+    pub(super) fn beta_inverse_cdf(p: f64, a: f64, b: f64) -> CIResult<f64> {
+        let beta = Beta::new(a, b).map_err(|_| CIError::NonPositiveValue(a.min(b)))?;
+        Ok(beta.inverse_cdf(p))
+    }
+}
+
+///
+/// Quantile functions implemented with the built-in special functions.
+///
+#[cfg(not(feature = "statrs"))]
+mod backend {
+    use crate::error::*;
+    use crate::special;
+
+    pub(super) fn normal_inverse_cdf(p: f64) -> CIResult<f64> {
+        Ok(special::normal_inverse_cdf(p))
+    }
+
+    pub(super) fn t_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+        Ok(special::t_inverse_cdf(p, degrees_of_freedom))
+    }
+
+    pub(super) fn chi2_inverse_cdf(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+        Ok(special::chi2_inverse_cdf(p, degrees_of_freedom))
+    }
+
+    pub(super) fn f_inverse_cdf(p: f64, d1: f64, d2: f64) -> CIResult<f64> {
+        Ok(special::f_inverse_cdf(p, d1, d2))
+    }
+
+    pub(super) fn beta_inverse_cdf(p: f64, a: f64, b: f64) -> CIResult<f64> {
+        Ok(special::beta_reg_inverse(p, a, b))
+    }
+}
+
+///
+/// Return the quantile of the confidence after checking that the confidence level is in the range (0, 1).
+///
+pub(crate) fn checked_quantile(confidence: Confidence) -> CIResult<f64> {
+    let level = confidence.level();
+    if level > 0. && level < 1. {
+        Ok(confidence.quantile())
+    } else {
+        Err(CIError::InvalidConfidenceLevel(level))
+    }
+}
+
+///
+/// Return the probability of the upper tail beyond the critical value of the confidence (i.e., one minus its quantile)
+/// in the float type `F`, after checking that the confidence level is in the range (0, 1).
+///
+/// Taking the tail rather than the quantile preserves the precision of small tails in `F` (e.g., `f32`).
+///
+fn checked_tail<F: Float>(confidence: Confidence) -> CIResult<F> {
+    F::from(1. - checked_quantile(confidence)?).convert("tail")
+}
+
+///
+/// Check that a probability is in the range [0, 1].
+///
+fn check_probability(p: f64) -> CIResult<()> {
+    if (0. ..=1.).contains(&p) {
+        Ok(())
+    } else {
+        Err(CIError::InvalidProbability(p))
+    }
+}
+
+///
+/// Check that the degrees of freedom of a distribution are strictly positive.
+///
+pub(crate) fn check_degrees_of_freedom<F: Float>(degrees_of_freedom: F) -> CIResult<()> {
+    if degrees_of_freedom > F::zero() {
+        Ok(())
+    } else {
+        Err(CIError::InvalidDegreesOfFreedom(
+            degrees_of_freedom.to_f64().unwrap_or(f64::NAN),
+        ))
+    }
+}
+
+///
+/// Check that a shape parameter of a distribution is strictly positive and finite.
+///
+fn check_shape(shape: f64) -> CIResult<()> {
+    if shape > 0. && shape.is_finite() {
+        Ok(())
+    } else {
+        Err(CIError::NonPositiveValue(shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_critical_values() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(z_value(confidence)?, 1.959963984540054, epsilon = 1e-9);
+        assert_abs_diff_eq!(t_value(confidence, 10.)?, 2.228138851986274, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            chi2_value(confidence, 10.)?,
+            20.483177350807388,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            chi2_inverse_cdf(0.025, 10.)?,
+            3.24697278023684,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            f_value(confidence, 3., 10.)?,
+            4.825621493405402,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            f_inverse_cdf(0.025, 3., 10.)?,
+            0.06935321586551413,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            beta_inverse_cdf(0.3, 2., 5.)?,
+            0.1818034713189492,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            beta_inverse_cdf(0.975, 0.5, 0.5)?,
+            0.998458666866564,
+            epsilon = 1e-9
+        );
+        for confidence in [Confidence::new_upper(0.95), Confidence::new_lower(0.95)] {
+            assert_abs_diff_eq!(t_value(confidence, 4.)?, 2.1318467863266495, epsilon = 1e-9);
+            assert_abs_diff_eq!(
+                f_value(confidence, 3., 10.)?,
+                3.7082648190468372,
+                epsilon = 1e-8
+            );
+        }
+        assert_abs_diff_eq!(
+            chi2_value(Confidence::new_upper(0.9), 3.)?,
+            6.251388631170327,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            t_inverse_cdf(0.025, 10.)?,
+            -t_value(confidence, 10.)?,
+            epsilon = 1e-12
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_t_and_z_value() {
+        for confidence_level in [0.5, 0.8, 0.9, 0.95, 0.99, 0.999] {
+            for new_confidence in [
+                Confidence::new_two_sided,
+                Confidence::new_upper,
+                Confidence::new_lower,
+            ] {
+                let confidence = new_confidence(confidence_level);
+                let t_value = t_value(confidence, 1000.).unwrap();
+                let z_value = z_value(confidence).unwrap();
+                assert_abs_diff_eq!(t_value, z_value, epsilon = 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_t_value_cache() -> CIResult<()> {
+        for confidence in [Confidence::new(0.95), Confidence::new_upper(0.9)] {
+            for degrees_of_freedom in 1..=(2 * T_VALUES_CAPACITY) {
+                let degrees_of_freedom = degrees_of_freedom as f64;
+                let expected = t_inverse_cdf(confidence.quantile(), degrees_of_freedom)?;
+                assert_eq!(t_value(confidence, degrees_of_freedom)?, expected);
+                assert_eq!(t_value(confidence, degrees_of_freedom)?, expected);
+            }
+        }
+        assert!(T_VALUES.with(|cache| cache.borrow().len()) <= T_VALUES_CAPACITY);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(matches!(
+            z_value(Confidence::TwoSided(1.)),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        assert!(matches!(
+            t_value(Confidence::UpperOneSided(-0.5), 10.),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        for p in [-0.1, 1.1, f64::NAN] {
+            assert!(matches!(
+                normal_inverse_cdf(p),
+                Err(CIError::InvalidProbability(_))
+            ));
+            assert!(matches!(
+                f_inverse_cdf(p, 1., 1.),
+                Err(CIError::InvalidProbability(_))
+            ));
+        }
+        for shape in [0., -1., f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                beta_inverse_cdf(0.5, shape, 1.),
+                Err(CIError::NonPositiveValue(_))
+            ));
+            assert!(matches!(
+                beta_inverse_cdf(0.5, 1., shape),
+                Err(CIError::NonPositiveValue(_))
+            ));
+        }
+        for degrees_of_freedom in [0., -1., f64::NAN] {
+            let confidence = Confidence::new(0.95);
+            assert!(matches!(
+                t_value(confidence, degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+            assert!(matches!(
+                chi2_value(confidence, degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+            assert!(matches!(
+                f_value(confidence, 1., degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_float_critical_values() -> CIResult<()> {
+        for level in [0.5, 0.9, 0.95, 0.99, 0.999] {
+            for confidence in [
+                Confidence::new_two_sided(level),
+                Confidence::new_upper(level),
+                Confidence::new_lower(level),
+            ] {
+                let z = z_value(confidence)?;
+                assert_relative_eq!(
+                    z_value_as::<f64>(confidence)?,
+                    z,
+                    max_relative = 1e-12,
+                    epsilon = 1e-15
+                );
+                assert_relative_eq!(
+                    z_value_as::<f32>(confidence)? as f64,
+                    z,
+                    max_relative = 1e-6,
+                    epsilon = 1e-7
+                );
+                for degrees_of_freedom in [1., 2., 4.5, 10., 30., 50., 1000., 5000.] {
+                    // statrs is only accurate to about 1e-8 for large degrees of freedom
+                    let t = t_value(confidence, degrees_of_freedom)?;
+                    assert_relative_eq!(
+                        t_value_as::<f64>(confidence, degrees_of_freedom)?,
+                        t,
+                        max_relative = 1e-7,
+                        epsilon = 1e-15
+                    );
+                    assert_relative_eq!(
+                        t_value_as::<f32>(confidence, degrees_of_freedom as f32)? as f64,
+                        t,
+                        max_relative = 1e-5,
+                        epsilon = 1e-7
+                    );
+                }
+            }
+        }
+        assert!(matches!(
+            t_value_as::<f32>(Confidence::new(0.95), f32::NAN),
+            Err(CIError::InvalidDegreesOfFreedom(_))
+        ));
+        assert!(matches!(
+            z_value_as::<f32>(Confidence::TwoSided(1.)),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        Ok(())
+    }
+}
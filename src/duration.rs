@@ -0,0 +1,373 @@
+//!
+//! Confidence intervals over samples of durations (e.g., latencies or response times).
+//!
+//! The statistics on the mean are maintained over the durations expressed in nanoseconds, and the
+//! results are converted back to [`Duration`], so that callers do not need to convert to and from
+//! seconds themselves. Any state on the mean can be used (see [`mean::StatisticsOps`]), including
+//! the geometric or harmonic means, or the stream accumulator [`incremental::MeanAccumulator`].
+//!
+//! Durations are totally ordered, so confidence intervals on quantiles (see [`quantile`]) apply
+//! to them directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! use std::time::Duration;
+//! let latencies = [
+//!     12, 15, 11, 18, 14, 13, 25, 12, 16, 14, 13, 17, 12, 95, 14, 15, 13, 16, 12, 14,
+//! ]
+//! .map(Duration::from_millis);
+//! let confidence = Confidence::new_two_sided(0.95);
+//!
+//! // interval on the mean latency
+//! let ci = duration::Mean::<mean::Arithmetic<f64>>::ci(confidence, &latencies)?;
+//! assert!(ci.contains(&Duration::from_millis(20)));
+//!
+//! // interval on the geometric mean latency
+//! let ci = duration::Mean::<mean::Geometric<f64>>::ci(confidence, &latencies)?;
+//! assert!(ci.contains(&Duration::from_millis(15)));
+//!
+//! // interval on the median latency
+//! let ci = quantile::ci(confidence, &latencies, 0.5)?;
+//! assert_eq!(ci, Interval::new(Duration::from_millis(13), Duration::from_millis(16))?);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use core::time::Duration;
+use error::*;
+use mean::StatisticsOps;
+
+///
+/// Represents the state of the computation of a confidence interval on the mean of a sample of durations.
+///
+/// The type parameter `S` is the state of the statistics over the durations in nanoseconds, which
+/// determines the kind of mean (e.g., [`mean::Arithmetic`] (default), [`mean::Geometric`], [`mean::Harmonic`]).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use std::time::Duration;
+/// let mut stats = duration::Mean::<incremental::MeanAccumulator<f64>>::new();
+/// for ms in [10, 12, 11, 13, 14, 12, 11, 10, 13, 12] {
+///     stats.append(Duration::from_millis(ms))?;
+/// }
+/// assert_eq!(stats.sample_mean()?, Duration::from_micros(11_800));
+/// let ci = stats.ci_mean(Confidence::new_upper(0.95))?;
+/// assert_eq!(ci, Interval::new_upper(Duration::from_nanos(11_036_814)));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mean<S = mean::Arithmetic<f64>> {
+    stats: S,
+}
+
+impl<S: StatisticsOps<f64>> Mean<S> {
+    ///
+    /// Create a new empty state
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn new() -> Self {
+        Self {
+            stats: S::default(),
+        }
+    }
+
+    ///
+    /// Create a new state and populate it with the given durations
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If some duration is zero (for harmonic/geometric means)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a Duration>,
+    {
+        let mut stats = Self::new();
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Append a new duration to the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the duration is zero (for harmonic/geometric means)
+    ///
+    pub fn append(&mut self, x: Duration) -> CIResult<()> {
+        self.stats.append(to_nanos(x))
+    }
+
+    ///
+    /// Extend the sample with additional durations
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If some duration is zero (for harmonic/geometric means)
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a Duration>,
+    {
+        for &x in data {
+            self.append(x)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.stats.sample_count()
+    }
+
+    ///
+    /// Mean of the sample (rounded to the nanosecond)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the sample is empty
+    ///
+    pub fn sample_mean(&self) -> CIResult<Duration> {
+        if self.sample_count() == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        from_nanos(self.stats.sample_mean())
+    }
+
+    ///
+    /// Standard error of the sample mean (rounded to the nanosecond)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are fewer than two samples
+    ///
+    pub fn sample_sem(&self) -> CIResult<Duration> {
+        if self.sample_count() < 2 {
+            return Err(CIError::TooFewSamples(self.sample_count()));
+        }
+        from_nanos(self.stats.sample_sem())
+    }
+
+    ///
+    /// Confidence interval of the sample mean (bounds rounded to the nanosecond)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are too few samples to compute the confidence interval
+    ///
+    /// # Notes
+    ///
+    /// Since durations cannot be negative, a lower bound below zero is clamped to zero.
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<Duration>> {
+        if self.sample_count() < 2 {
+            return Err(CIError::TooFewSamples(self.sample_count()));
+        }
+        match self.stats.ci_mean(confidence)? {
+            Interval::TwoSided(lo, hi) => {
+                Interval::new(from_nanos(lo)?, from_nanos(hi)?).map_err(|e| e.into())
+            }
+            Interval::UpperOneSided(lo) => Ok(Interval::new_upper(from_nanos(lo)?)),
+            Interval::LowerOneSided(hi) => Ok(Interval::new_lower(from_nanos(hi)?)),
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample of durations
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If some duration is zero (for harmonic/geometric means)
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<Duration>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a Duration>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+
+    ///
+    /// Access the underlying statistics over the durations in nanoseconds
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn stats(&self) -> &S {
+        &self.stats
+    }
+}
+
+impl<S: StatisticsOps<f64> + Statistic<f64>> Statistic<Duration> for Mean<S> {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<Duration>> {
+        Ok(CiEstimate::new(
+            self.sample_mean()?,
+            self.ci_mean(confidence)?,
+            confidence,
+            self.sample_count(),
+            from_nanos(self.stats.estimate(confidence)?.std_err())?,
+        ))
+    }
+}
+
+impl<S: core::ops::Add<Output = S>> core::ops::Add for Mean<S> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            stats: self.stats + rhs.stats,
+        }
+    }
+}
+
+impl<S: core::ops::AddAssign> core::ops::AddAssign for Mean<S> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.stats += rhs.stats;
+    }
+}
+
+///
+/// Convert a duration to nanoseconds
+///
+fn to_nanos(x: Duration) -> f64 {
+    x.as_nanos() as f64
+}
+
+///
+/// Convert nanoseconds to a duration, rounding to the nearest nanosecond
+/// (negative values saturate to zero and too large values to the maximum representable duration)
+///
+/// # Errors
+///
+/// * [`CIError::InvalidInputData`] - If the value is NaN
+///
+fn from_nanos(x: f64) -> CIResult<Duration> {
+    if x.is_nan() {
+        return Err(CIError::InvalidInputData);
+    }
+    Ok(Duration::from_nanos(x.round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_duration_mean() -> CIResult<()> {
+        let millis = [
+            12., 15., 11., 18., 14., 13., 25., 12., 16., 14., 13., 17., 12., 95., 14., 15., 13.,
+            16., 12., 14.,
+        ];
+        let latencies = millis.map(|ms| Duration::from_secs_f64(ms / 1_000.));
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let stats = Mean::<mean::Arithmetic<f64>>::from_iter(&latencies)?;
+        let reference = mean::Arithmetic::from_iter(&millis)?;
+        assert_eq!(stats.sample_count(), 20);
+        assert_abs_diff_eq!(
+            stats.sample_mean()?.as_secs_f64() * 1e3,
+            reference.sample_mean(),
+            epsilon = 1e-6
+        );
+        let ci = stats.ci_mean(confidence)?;
+        let reference_ci = reference.ci_mean(confidence)?;
+        assert_abs_diff_eq!(
+            ci.low_as_ref().unwrap().as_secs_f64() * 1e3,
+            reference_ci.low_f(),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            ci.high_as_ref().unwrap().as_secs_f64() * 1e3,
+            reference_ci.high_f(),
+            epsilon = 1e-6
+        );
+
+        // the lower bound is clamped at zero
+        let skewed = [1, 1, 1, 1000].map(Duration::from_millis);
+        let ci = Mean::<mean::Arithmetic<f64>>::ci(confidence, &skewed)?;
+        assert_eq!(ci.low(), Some(Duration::ZERO));
+
+        // other means and accumulators
+        let geometric = Mean::<mean::Geometric<f64>>::ci(confidence, &latencies)?;
+        let reference = mean::Geometric::ci(confidence, &millis)?;
+        assert_abs_diff_eq!(
+            geometric.low_as_ref().unwrap().as_secs_f64() * 1e3,
+            reference.low_f(),
+            epsilon = 1e-6
+        );
+        let mut acc = Mean::<incremental::MeanAccumulator<f64>>::new();
+        acc.extend(&latencies[..10].to_vec())?;
+        acc += Mean::from_iter(&latencies[10..].to_vec())?;
+        assert_eq!(
+            acc.ci_mean(confidence)?,
+            ci_arithmetic(confidence, &latencies)?
+        );
+        assert_eq!(acc.estimate(confidence)?.estimate(), acc.sample_mean()?);
+
+        // an empty state has no mean (rather than a zero duration)
+        let empty = Mean::<mean::Arithmetic<f64>>::new();
+        assert!(matches!(
+            empty.sample_mean(),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(empty.sample_sem(), Err(CIError::TooFewSamples(0))));
+        assert!(empty.estimate(confidence).is_err());
+        let single = Mean::<mean::Arithmetic<f64>>::from_iter(&[Duration::from_secs(1)])?;
+        assert_eq!(single.sample_mean()?, Duration::from_secs(1));
+        assert!(matches!(
+            single.sample_sem(),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(from_nanos(f64::NAN).is_err());
+        assert_eq!(from_nanos(-3.)?, Duration::ZERO);
+
+        assert!(matches!(
+            Mean::<mean::Harmonic<f64>>::ci(confidence, &[Duration::ZERO, Duration::from_secs(1)]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            Mean::<mean::Arithmetic<f64>>::ci(confidence, &[Duration::from_secs(1)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+
+        // quantiles apply directly
+        let ci = quantile::ci(confidence, &latencies, 0.5)?;
+        assert_eq!(
+            ci,
+            Interval::new(Duration::from_millis(13), Duration::from_millis(16))?
+        );
+        Ok(())
+    }
+
+    fn ci_arithmetic(confidence: Confidence, data: &[Duration]) -> CIResult<Interval<Duration>> {
+        Mean::<mean::Arithmetic<f64>>::ci(confidence, &data.to_vec())
+    }
+}
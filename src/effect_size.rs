@@ -0,0 +1,808 @@
+//!
+//! Confidence intervals for standardized effect sizes between two samples.
+//!
+//! Raw differences (see [`comparison`]) are expressed in the unit of the measurements, which makes them
+//! hard to compare across experiments. Standardized effect sizes express the difference between the means
+//! of two independent samples in units of their pooled standard deviation:
+//!
+//! * [`cohens_d`] - Cohen's d, \\( d = (\bar{x}_a - \bar{x}_b) / s_p \\)
+//! * [`hedges_g`] - Hedges' g, Cohen's d corrected for its small-sample bias
+//!
+//! Multiplicative effects are better expressed by the ratio of the means:
+//!
+//! * [`log_response_ratio`] - the log response ratio, \\( \ln RR = \ln(\bar{x}_a / \bar{x}_b) \\)
+//!
+//! These assume normal distributions, and are meaningless for heavy-tailed or ordinal data (e.g., latencies).
+//! Ordinal effect sizes only depend on the order between the observations of the two samples:
+//!
+//! * [`cliffs_delta`] - Cliff's delta, \\( \delta = P(A > B) - P(A < B) \\)
+//! * [`vargha_delaney_a12`] - the Vargha–Delaney A12 statistic (probability of superiority),
+//!   \\( A_{12} = P(A > B) + \frac{1}{2} P(A = B) = (1 + \delta) / 2 \\)
+//!
+//! The intervals are exact under the assumption that the two samples are drawn from normal distributions
+//! with equal variances: the statistic \\( t = d / \sqrt{1/n_a + 1/n_b} \\) follows a noncentral
+//! t-distribution with \\( n_a + n_b - 2 \\) degrees of freedom, whose noncentrality parameter is
+//! bracketed by inverting its cumulative distribution function.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // Gain in weight of 19 female rats between 28 and 84 days after birth.
+//! // 12 were fed on a high protein diet and 7 on a low protein diet.
+//! let data_high_protein = [
+//!     134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+//! ];
+//! let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+//! let stats = comparison::Unpaired::from_iter(&data_high_protein, &data_low_protein)?;
+//! let confidence = Confidence::new_two_sided(0.95);
+//!
+//! let d = effect_size::cohens_d(confidence, &stats)?;
+//! assert_abs_diff_eq!(d.estimate(), 0.8996, epsilon = 1e-4);
+//! assert_abs_diff_eq!(d.interval(), &Interval::new(-0.0916, 1.8669)?, epsilon = 1e-4);
+//!
+//! let g = effect_size::hedges_g(confidence, &stats)?;
+//! assert!(g.estimate() < d.estimate());
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * Norman Cliff. "Dominance statistics: ordinal analyses to answer ordinal questions." Psychological Bulletin 114(3): 494-509 (1993).
+//! * Edgar Brunner, Ullrich Munzel. "The nonparametric Behrens-Fisher problem: asymptotic theory and a small-sample approximation." Biometrical Journal 42(1): 17-25 (2000).
+//! * András Vargha, Harold D. Delaney. "A critique and improvement of the CL common language effect size statistics of McGraw and Wong." Journal of Educational and Behavioral Statistics 25(2): 101-132 (2000).
+//! * Larry V. Hedges, Ingram Olkin. Statistical Methods for Meta-Analysis. Academic Press, 1985.
+//! * Ken Kelley. "Confidence intervals for standardized effect sizes: theory, application, and implementation." Journal of Statistical Software 20(8) (2007).
+//! * [Wikipedia article on effect size](https://en.wikipedia.org/wiki/Effect_size#Cohen's_d)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Cohen's d between two independent samples with its confidence interval (see the [module documentation](self)).
+///
+/// The standard error of the result is the large-sample approximation
+/// \\( \sqrt{\frac{n_a + n_b}{n_a n_b} + \frac{d^2}{2 (n_a + n_b)}} \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `stats` - the statistics of the two samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+/// * [`CIError::InvalidInputData`] - if the pooled standard deviation is zero or not finite
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+pub fn cohens_d<T: Float>(
+    confidence: Confidence,
+    stats: &comparison::Unpaired<T>,
+) -> CIResult<CiEstimate<f64>> {
+    let (d, n_a, n_b) = standardized_difference(stats)?;
+    let scale = (1. / n_a + 1. / n_b).sqrt();
+    let degrees_of_freedom = n_a + n_b - 2.;
+    let (lo, hi) = noncentrality_bounds(confidence, d / scale, degrees_of_freedom)?;
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo * scale, hi * scale)?,
+        Confidence::UpperOneSided(_) => Interval::new_upper(lo * scale),
+        Confidence::LowerOneSided(_) => Interval::new_lower(hi * scale),
+    };
+    let std_err = ((n_a + n_b) / (n_a * n_b) + d * d / (2. * (n_a + n_b))).sqrt();
+    Ok(CiEstimate::new(
+        d,
+        interval,
+        confidence,
+        (n_a + n_b) as usize,
+        std_err,
+    ))
+}
+
+///
+/// Hedges' g between two independent samples with its confidence interval (see the [module documentation](self)).
+///
+/// Hedges' g is Cohen's d multiplied by the correction factor
+/// \\[
+/// J(\nu) = \frac{\Gamma(\nu / 2)}{\sqrt{\nu / 2} ~ \Gamma((\nu - 1) / 2)}
+/// \\]
+/// where \\( \nu = n_a + n_b - 2 \\), which removes the bias of Cohen's d on small samples.
+/// The bounds and the standard error of the interval of Cohen's d are multiplied by the same factor.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `stats` - the statistics of the two samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+/// * [`CIError::InvalidInputData`] - if the pooled standard deviation is zero or not finite
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data_a = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4, 5.2];
+/// let data_b = [4.2, 4.8, 4.4, 5.0, 4.6, 4.1];
+/// let stats = comparison::Unpaired::from_iter(&data_a, &data_b)?;
+/// let g = effect_size::hedges_g(Confidence::new_upper(0.95), &stats)?;
+/// // the effect is large, and certainly positive
+/// assert!(g.estimate() > 0.8);
+/// assert!(g.interval().low_f() > 0.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn hedges_g<T: Float>(
+    confidence: Confidence,
+    stats: &comparison::Unpaired<T>,
+) -> CIResult<CiEstimate<f64>> {
+    let d = cohens_d(confidence, stats)?;
+    let nu = d.sample_count() as f64 - 2.;
+    let correction =
+        (special::ln_gamma(nu / 2.) - special::ln_gamma((nu - 1.) / 2.)).exp() / (nu / 2.).sqrt();
+    Ok(CiEstimate::new(
+        d.estimate() * correction,
+        d.interval().map(|x| x * correction),
+        confidence,
+        d.sample_count(),
+        d.std_err() * correction,
+    ))
+}
+
+///
+/// Return Cohen's d and the sizes of the two samples.
+///
+fn standardized_difference<T: Float>(stats: &comparison::Unpaired<T>) -> CIResult<(f64, f64, f64)> {
+    let (stats_a, stats_b) = (stats.stats_a(), stats.stats_b());
+    let min_count = stats_a.sample_count().min(stats_b.sample_count());
+    if min_count < 2 {
+        return Err(CIError::TooFewSamples(min_count));
+    }
+    let n_a = stats_a.sample_count() as f64;
+    let n_b = stats_b.sample_count() as f64;
+    let var_a = stats_a.sample_variance().try_f64("sample_variance")?;
+    let var_b = stats_b.sample_variance().try_f64("sample_variance")?;
+    let pooled_std_dev = (((n_a - 1.) * var_a + (n_b - 1.) * var_b) / (n_a + n_b - 2.)).sqrt();
+    if !(pooled_std_dev > 0. && pooled_std_dev.is_finite()) {
+        return Err(CIError::InvalidInputData);
+    }
+    let mean_difference =
+        (stats_a.sample_mean() - stats_b.sample_mean()).try_f64("mean_difference")?;
+    Ok((mean_difference / pooled_std_dev, n_a, n_b))
+}
+
+///
+/// Return the bounds of the confidence interval on the noncentrality parameter \\( \delta \\) of a noncentral
+/// t-distribution given an observed statistic, i.e., the values of \\( \delta \\) for which the observed
+/// statistic is the upper and lower critical value of the distribution.
+///
+fn noncentrality_bounds(
+    confidence: Confidence,
+    statistic: f64,
+    degrees_of_freedom: f64,
+) -> CIResult<(f64, f64)> {
+    let quantile = distributions::checked_quantile(confidence)?;
+    let lo = invert_noncentrality(statistic, degrees_of_freedom, quantile);
+    let hi = invert_noncentrality(statistic, degrees_of_freedom, 1. - quantile);
+    Ok((lo, hi))
+}
+
+///
+/// Find \\( \delta \\) such that \\( P(T \leq t \mid \delta) = p \\), by bisection
+/// (the probability decreases with \\( \delta \\)).
+///
+fn invert_noncentrality(statistic: f64, degrees_of_freedom: f64, p: f64) -> f64 {
+    let cdf = |delta: f64| special::noncentral_t_cdf(statistic, degrees_of_freedom, delta) - p;
+    let mut step = 1.;
+    let (mut lo, mut hi) = (statistic - step, statistic + step);
+    while cdf(lo) < 0. {
+        step *= 2.;
+        lo = statistic - step;
+    }
+    while cdf(hi) > 0. {
+        step *= 2.;
+        hi = statistic + step;
+    }
+    let tolerance = 1e-12 * statistic.abs().max(1.);
+    while hi - lo > tolerance {
+        let mid = (lo + hi) / 2.;
+        if cdf(mid) > 0. {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.
+}
+
+///
+/// Log response ratio between two independent samples with its confidence interval, i.e., the logarithm of the ratio of their means:
+/// \\[
+/// \ln RR = \ln(\bar{x}_a / \bar{x}_b)
+/// \\]
+/// Unlike a difference, the ratio does not depend on the unit of the measurements, and its logarithm is approximately normal
+/// with the (delta method) variance
+/// \\[
+/// v = \frac{s_a^2}{n_a \bar{x}_a^2} + \frac{s_b^2}{n_b \bar{x}_b^2}
+/// \\]
+/// which gives the interval \\( \ln RR \pm z ~ \sqrt{v} \\). The interval on the ratio itself is obtained by exponentiating the bounds.
+///
+/// The log response ratio is the usual effect size to combine multiplicative effects (e.g., relative throughput improvements)
+/// across experiments with the [`meta`] module, using the estimate and its standard error.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `stats` - the statistics of the two samples, the first sample being the numerator
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+/// * [`CIError::NonPositiveValue`] - if the mean of one of the two samples is not strictly positive
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+///
+/// # Notes
+///
+/// The measurements must be on a ratio scale (i.e., with a meaningful zero, such as a throughput or a duration), and the
+/// approximation assumes that the means are far from zero relative to their standard errors.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // throughput (in requests/s) of a new and an old version, in two experiments
+/// let new_version = [1210., 1185., 1302., 1254., 1198., 1276.];
+/// let old_version = [1102., 1150., 1087., 1131., 1095.];
+/// let stats = comparison::Unpaired::from_iter(&new_version, &old_version)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let lnrr = effect_size::log_response_ratio(confidence, &stats)?;
+/// assert_abs_diff_eq!(lnrr.estimate().exp(), 1.1119, epsilon = 1e-4);
+/// // the throughput of the new version is between 7% and 15% higher
+/// let ratio = lnrr.interval().map(f64::exp);
+/// assert!(ratio.low_f() > 1.07 && ratio.high_f() < 1.16);
+///
+/// let other_experiment = comparison::Unpaired::from_iter(&[980., 1012., 1035., 1003.], &[941., 925., 958., 933.])?;
+/// let other = effect_size::log_response_ratio(confidence, &other_experiment)?;
+/// let pooled = meta::FixedEffect::from_iter(&[
+///     (lnrr.estimate(), lnrr.std_err()),
+///     (other.estimate(), other.std_err()),
+/// ])?;
+/// let pooled_ratio = pooled.ci(confidence)?.map(f64::exp);
+/// assert!(pooled_ratio.low_f() > 1.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * Larry V. Hedges, Jessica Gurevitch, Peter S. Curtis. "The meta-analysis of response ratios in experimental ecology." Ecology 80(4): 1150-1156 (1999).
+///
+pub fn log_response_ratio<T: Float>(
+    confidence: Confidence,
+    stats: &comparison::Unpaired<T>,
+) -> CIResult<CiEstimate<f64>> {
+    let (stats_a, stats_b) = (stats.stats_a(), stats.stats_b());
+    let min_count = stats_a.sample_count().min(stats_b.sample_count());
+    if min_count < 2 {
+        return Err(CIError::TooFewSamples(min_count));
+    }
+    let mut log_ratio = 0.;
+    let mut variance = 0.;
+    for (stats, sign) in [(stats_a, 1.), (stats_b, -1.)] {
+        let mean = stats.sample_mean().try_f64("sample_mean")?;
+        if mean.is_nan() || mean <= 0. {
+            return Err(CIError::NonPositiveValue(mean));
+        }
+        let n = stats.sample_count() as f64;
+        log_ratio += sign * mean.ln();
+        variance += stats.sample_variance().try_f64("sample_variance")? / (n * mean * mean);
+    }
+    let std_err = variance.sqrt();
+    let span = distributions::z_value(confidence)? * std_err;
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(log_ratio - span, log_ratio + span)?,
+        Confidence::UpperOneSided(_) => Interval::new_upper(log_ratio - span),
+        Confidence::LowerOneSided(_) => Interval::new_lower(log_ratio + span),
+    };
+    Ok(CiEstimate::new(
+        log_ratio,
+        interval,
+        confidence,
+        stats_a.sample_count() + stats_b.sample_count(),
+        std_err,
+    ))
+}
+
+///
+/// Cliff's delta between two independent samples with its confidence interval, i.e., the probability that an observation
+/// of the first sample is larger than one of the second sample, minus the probability that it is smaller:
+/// \\[
+/// \delta = \frac{1}{mn} \sum_{i=1}^m \sum_{j=1}^n \mathrm{sign}(a_i - b_j)
+/// \\]
+///
+/// The variance of \\( \delta \\) is estimated with the consistent estimator of Cliff (1993):
+/// \\[
+/// s^2 = \frac{(n-1) s^2_{i\cdot} + (m-1) s^2_{\cdot j} + s^2_{ij}}{mn}
+/// \\]
+/// where \\( s^2_{i\cdot} \\) and \\( s^2_{\cdot j} \\) are the variances of the row and column means of the dominance
+/// matrix \\( d_{ij} = \mathrm{sign}(a_i - b_j) \\), and \\( s^2_{ij} = \sum_{i,j} (d_{ij} - \delta)^2 / ((m-1)(n-1)) \\).
+/// The interval is the asymmetric interval of Cliff, which remains within \\( [-1, 1] \\):
+/// \\[
+/// \frac{\delta - \delta^3 \pm z s \sqrt{(1 - \delta^2)^2 + z^2 s^2}}{1 - \delta^2 + z^2 s^2}
+/// \\]
+///
+/// Complexity: \\( O((m + n) \log(m + n)) \\) where \\( m \\) and \\( n \\) are the sizes of the samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+/// * [`CIError::InvalidInputData`] - if the data contains values that are not comparable (e.g., NaN)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // latencies (in ms) of two configurations, with heavy tails
+/// let config_a = [12.1, 14.3, 11.8, 152.0, 13.9, 12.7, 16.1, 13.3];
+/// let config_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1, 98.5];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let delta = effect_size::cliffs_delta(confidence, &config_a, &config_b)?;
+/// assert_abs_diff_eq!(delta.estimate(), 0.6429, epsilon = 1e-4);
+/// assert_abs_diff_eq!(delta.interval(), &Interval::new(-0.0456, 0.9173)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn cliffs_delta<T, Ia, Ib>(
+    confidence: Confidence,
+    data_a: &Ia,
+    data_b: &Ib,
+) -> CIResult<CiEstimate<f64>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+{
+    let dominance = Dominance::new(data_a, data_b)?;
+    let (delta, variance) = (dominance.delta, dominance.delta_variance());
+    let std_err = variance.sqrt();
+    let z = distributions::z_value(confidence)?;
+    let (z_sq, one_minus_sq) = (z * z, 1. - delta * delta);
+    let center = delta - delta * delta * delta;
+    let span = z * std_err * (one_minus_sq * one_minus_sq + z_sq * variance).sqrt();
+    let denominator = one_minus_sq + z_sq * variance;
+    let (lo, hi) = if denominator > 0. {
+        ((center - span) / denominator, (center + span) / denominator)
+    } else {
+        // complete dominance without variance
+        (delta, delta)
+    };
+    let (lo, hi) = (lo.max(-1.), hi.min(1.));
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+        Confidence::UpperOneSided(_) => Interval::new(lo, 1.)?,
+        Confidence::LowerOneSided(_) => Interval::new(-1., hi)?,
+    };
+    Ok(CiEstimate::new(
+        delta,
+        interval,
+        confidence,
+        dominance.sample_count(),
+        std_err,
+    ))
+}
+
+///
+/// Vargha–Delaney A12 statistic between two independent samples with its confidence interval, i.e., the probability that
+/// an observation of the first sample is larger than one of the second sample, counting ties for one half:
+/// \\[
+/// A_{12} = P(A > B) + \frac{1}{2} P(A = B)
+/// \\]
+/// A value of 0.5 means no effect; Vargha and Delaney consider values above 0.56, 0.64, and 0.71
+/// (or below 0.44, 0.36, and 0.29) as small, medium, and large effects.
+/// The statistic is the Mann–Whitney statistic \\( U \\) of the first sample divided by \\( mn \\), which makes it the
+/// natural effect size to report along with the interval on the shift given by [`location::ci_shift`].
+///
+/// The interval is that of Brunner and Munzel, which does not assume that the two distributions have the same shape:
+/// \\( \hat{A}_{12} \pm t_{\nu} ~ \hat{\sigma} \\), where \\( \hat{\sigma}^2 = \frac{S_a^2}{m n^2} + \frac{S_b^2}{n m^2} \\) is estimated
+/// from the placements of each observation among the other sample, and \\( \nu \\) are the degrees of freedom given by the
+/// Welch–Satterthwaite approximation. The bounds are clamped to \\( [0, 1] \\).
+///
+/// Complexity: \\( O((m + n) \log(m + n)) \\) where \\( m \\) and \\( n \\) are the sizes of the samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+/// * [`CIError::InvalidInputData`] - if the data contains values that are not comparable (e.g., NaN)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // execution times (in s) of a new and an old algorithm on random instances
+/// let new_algorithm = [3.1, 2.8, 4.0, 3.5, 2.9, 3.3, 3.0, 3.8, 2.7, 3.2];
+/// let old_algorithm = [3.9, 4.4, 3.3, 5.1, 4.2, 3.7, 4.8, 4.0, 3.6, 4.5];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let a12 = effect_size::vargha_delaney_a12(confidence, &new_algorithm, &old_algorithm)?;
+/// // the new algorithm is faster on about 9 pairs of instances out of 10
+/// assert_abs_diff_eq!(a12.estimate(), 0.09, epsilon = 1e-10);
+/// assert!(a12.interval().high_f() < 0.5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn vargha_delaney_a12<T, Ia, Ib>(
+    confidence: Confidence,
+    data_a: &Ia,
+    data_b: &Ib,
+) -> CIResult<CiEstimate<f64>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+{
+    let dominance = Dominance::new(data_a, data_b)?;
+    let (m, n) = (dominance.count_a as f64, dominance.count_b as f64);
+    let a12 = (1. + dominance.delta) / 2.;
+    // the placements (a_i > b, counting ties for one half) are (1 + d_i.) / 2, hence the factor 1/4
+    let variance_a = dominance.row_variance / (4. * m);
+    let variance_b = dominance.column_variance / (4. * n);
+    let variance = variance_a + variance_b;
+    let std_err = variance.sqrt();
+    let span = if variance > 0. {
+        let degrees_of_freedom = variance * variance
+            / (variance_a * variance_a / (m - 1.) + variance_b * variance_b / (n - 1.));
+        distributions::t_value(confidence, degrees_of_freedom)? * std_err
+    } else {
+        distributions::checked_quantile(confidence)?;
+        0.
+    };
+    let (lo, hi) = ((a12 - span).max(0.), (a12 + span).min(1.));
+    let interval = match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi)?,
+        Confidence::UpperOneSided(_) => Interval::new(lo, 1.)?,
+        Confidence::LowerOneSided(_) => Interval::new(0., hi)?,
+    };
+    Ok(CiEstimate::new(
+        a12,
+        interval,
+        confidence,
+        dominance.sample_count(),
+        std_err,
+    ))
+}
+
+///
+/// Summary of the dominance matrix \\( d_{ij} = \mathrm{sign}(a_i - b_j) \\) between two samples: its mean (Cliff's delta)
+/// and the variances of its row means, of its column means, and of its cells.
+///
+#[cfg(any(test, feature = "std"))]
+struct Dominance {
+    delta: f64,
+    row_variance: f64,
+    column_variance: f64,
+    cell_variance: f64,
+    count_a: usize,
+    count_b: usize,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl Dominance {
+    ///
+    /// Compute the summary by sorting each sample and counting, for each observation, the observations of the other sample
+    /// that are smaller and larger (rather than building the \\( m \times n \\) dominance matrix).
+    ///
+    fn new<T, Ia, Ib>(data_a: &Ia, data_b: &Ib) -> CIResult<Self>
+    where
+        T: PartialOrd + Copy,
+        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+    {
+        let sorted_a = sorted_sample(data_a)?;
+        let sorted_b = sorted_sample(data_b)?;
+        let (m, n) = (sorted_a.len(), sorted_b.len());
+        if m.min(n) < 2 {
+            return Err(CIError::TooFewSamples(m.min(n)));
+        }
+        // for each observation, (number of smaller, number of larger) observations in the other sample
+        let counts = |x: &T, other: &[T]| {
+            let smaller = other.partition_point(|y| y < x);
+            let not_larger = other.partition_point(|y| y <= x);
+            (smaller, other.len() - not_larger)
+        };
+        let rows = sorted_a
+            .iter()
+            .map(|x| counts(x, &sorted_b))
+            .collect::<Vec<_>>();
+        let columns = sorted_b
+            .iter()
+            .map(|x| counts(x, &sorted_a))
+            .collect::<Vec<_>>();
+
+        let (m_f, n_f) = (m as f64, n as f64);
+        let dominating = rows.iter().map(|&(smaller, _)| smaller).sum::<usize>() as f64;
+        let dominated = rows.iter().map(|&(_, larger)| larger).sum::<usize>() as f64;
+        let delta = (dominating - dominated) / (m_f * n_f);
+
+        // variances of the row means d_i. and of the column means d.j
+        let row_variance = rows
+            .iter()
+            .map(|&(smaller, larger)| ((smaller as f64 - larger as f64) / n_f - delta).powi(2))
+            .sum::<f64>()
+            / (m_f - 1.);
+        let column_variance = columns
+            .iter()
+            .map(|&(smaller, larger)| ((larger as f64 - smaller as f64) / m_f - delta).powi(2))
+            .sum::<f64>()
+            / (n_f - 1.);
+        // sum of (d_ij - delta)^2, where d_ij^2 is 1 except for ties
+        let cell_variance =
+            ((dominating + dominated) - m_f * n_f * delta * delta) / ((m_f - 1.) * (n_f - 1.));
+        Ok(Self {
+            delta,
+            row_variance,
+            column_variance,
+            cell_variance,
+            count_a: m,
+            count_b: n,
+        })
+    }
+
+    ///
+    /// Consistent estimate of the variance of Cliff's delta (Cliff, 1993).
+    ///
+    fn delta_variance(&self) -> f64 {
+        let (m, n) = (self.count_a as f64, self.count_b as f64);
+        let variance =
+            ((n - 1.) * self.row_variance + (m - 1.) * self.column_variance + self.cell_variance)
+                / (m * n);
+        variance.max(0.)
+    }
+
+    fn sample_count(&self) -> usize {
+        self.count_a + self.count_b
+    }
+}
+
+///
+/// Copy the sample into a sorted vector, checking that all the values are comparable.
+///
+#[cfg(any(test, feature = "std"))]
+fn sorted_sample<T, I>(data: &I) -> CIResult<Vec<T>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let mut data: Vec<T> = data.into_iter().copied().collect();
+    if data.iter().any(|x| x.partial_cmp(x).is_none()) {
+        return Err(CIError::InvalidInputData);
+    }
+    data.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_cohens_d() -> CIResult<()> {
+        let data_a = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_b = [70., 118., 101., 85., 107., 132., 94.];
+        let stats = comparison::Unpaired::from_iter(&data_a, &data_b)?;
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let d = cohens_d(confidence, &stats)?;
+        assert_abs_diff_eq!(d.estimate(), 0.8995574392432594, epsilon = 1e-12);
+        assert_abs_diff_eq!(d.std_err(), 0.4974789517297238, epsilon = 1e-12);
+        assert_eq!(d.sample_count(), 19);
+        assert_abs_diff_eq!(d.interval().low_f(), -0.09161262194564215, epsilon = 1e-9);
+        assert_abs_diff_eq!(d.interval().high_f(), 1.866901131793665, epsilon = 1e-9);
+
+        let g = hedges_g(confidence, &stats)?;
+        assert_abs_diff_eq!(g.estimate(), 0.8591776601814869, epsilon = 1e-12);
+
+        // one-sided interval is bounded by the critical value at the same level
+        let upper = cohens_d(Confidence::new_upper(0.975), &stats)?;
+        assert_abs_diff_eq!(
+            upper.interval().low_f(),
+            -0.09161262194564215,
+            epsilon = 1e-9
+        );
+        assert!(upper.interval().is_upper());
+
+        // swapping the samples negates the effect
+        let swapped = comparison::Unpaired::from_iter(&data_b, &data_a)?;
+        let d = cohens_d(confidence, &swapped)?;
+        assert_abs_diff_eq!(d.interval().low_f(), -1.866901131793665, epsilon = 1e-9);
+        assert_abs_diff_eq!(d.interval().high_f(), 0.09161262194564215, epsilon = 1e-9);
+
+        assert!(matches!(
+            cohens_d(
+                confidence,
+                &comparison::Unpaired::from_iter(&data_a, &[1.])?
+            ),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            cohens_d(
+                confidence,
+                &comparison::Unpaired::from_iter(&[1., 1.], &[2., 2.])?
+            ),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cliffs_delta() -> CIResult<()> {
+        let data_a = [12.1, 14.3, 11.8, 152.0, 13.9, 12.7, 16.1, 13.3, 12.4];
+        let data_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1, 98.5, 12.4];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python (from the full dominance matrix)
+        let delta = cliffs_delta(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(delta.estimate(), 0.6111111111111112, epsilon = 1e-12);
+        assert_abs_diff_eq!(delta.std_err(), 0.24803185574604042, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            delta.interval().low_f(),
+            -0.0030439396351434355,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            delta.interval().high_f(),
+            0.890519373034435,
+            epsilon = 1e-12
+        );
+        assert_eq!(delta.sample_count(), 17);
+
+        // ordinal: any monotone transformation gives the same result
+        let ranks_a = data_a.map(|x| (x * 10.) as i64);
+        let ranks_b = data_b.map(|x| (x * 10.) as i64);
+        assert_eq!(cliffs_delta(confidence, &ranks_a, &ranks_b)?, delta);
+
+        // swapping the samples negates the effect
+        let swapped = cliffs_delta(confidence, &data_b, &data_a)?;
+        assert_abs_diff_eq!(swapped.estimate(), -0.6111111111111112, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            swapped.interval().low_f(),
+            -0.890519373034435,
+            epsilon = 1e-12
+        );
+
+        // complete dominance
+        let delta = cliffs_delta(confidence, &[5, 6, 7], &[1, 2, 3])?;
+        assert_eq!(delta.estimate(), 1.);
+        assert_eq!(delta.interval().high_f(), 1.);
+
+        let delta = cliffs_delta(Confidence::new_upper(0.95), &data_a, &data_b)?;
+        assert_eq!(delta.interval().high_f(), 1.);
+
+        assert!(matches!(
+            cliffs_delta(confidence, &[1.], &data_b),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            cliffs_delta(confidence, &[1., f64::NAN], &data_b),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_vargha_delaney_a12() -> CIResult<()> {
+        let data_a = [12.1, 14.3, 11.8, 152.0, 13.9, 12.7, 16.1, 13.3, 12.4];
+        let data_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1, 98.5, 12.4];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python (Brunner-Munzel from the mid-ranks)
+        let a12 = vargha_delaney_a12(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(a12.estimate(), 0.8055555555555556, epsilon = 1e-12);
+        assert_abs_diff_eq!(a12.std_err(), 0.11973593999413318, epsilon = 1e-12);
+        assert_abs_diff_eq!(a12.interval().low_f(), 0.5390685219919354, epsilon = 1e-9);
+        assert_abs_diff_eq!(a12.interval().high_f(), 1., epsilon = 1e-9);
+        let delta = cliffs_delta(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(
+            a12.estimate(),
+            (1. + delta.estimate()) / 2.,
+            epsilon = 1e-12
+        );
+
+        // swapping the samples gives the complement
+        let swapped = vargha_delaney_a12(confidence, &data_b, &data_a)?;
+        assert_abs_diff_eq!(swapped.estimate(), 1. - 0.8055555555555556, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            swapped.interval().high_f(),
+            1. - 0.5390685219919354,
+            epsilon = 1e-9
+        );
+
+        // complete dominance
+        let a12 = vargha_delaney_a12(confidence, &[5, 6, 7], &[1, 2, 3])?;
+        assert_eq!(a12.estimate(), 1.);
+        assert_eq!(*a12.interval(), Interval::new(1., 1.)?);
+
+        let a12 = vargha_delaney_a12(Confidence::new_lower(0.95), &data_a, &data_b)?;
+        assert_eq!(a12.interval().low_f(), 0.);
+
+        assert!(matches!(
+            vargha_delaney_a12(confidence, &data_a, &[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_response_ratio() -> CIResult<()> {
+        let new_version = [1210., 1185., 1302., 1254., 1198., 1276.];
+        let old_version = [1102., 1150., 1087., 1131., 1095.];
+        let stats = comparison::Unpaired::from_iter(&new_version, &old_version)?;
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let lnrr = log_response_ratio(confidence, &stats)?;
+        assert_abs_diff_eq!(lnrr.estimate(), 0.1060341431673005, epsilon = 1e-12);
+        assert_abs_diff_eq!(lnrr.std_err(), 0.01878136756259405, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            *lnrr.interval(),
+            Interval::new(0.06922333916420738, 0.1428449471703937)?,
+            epsilon = 1e-9
+        );
+        assert_eq!(lnrr.sample_count(), 11);
+
+        // swapping the samples changes the sign
+        let swapped = comparison::Unpaired::from_iter(&old_version, &new_version)?;
+        let swapped = log_response_ratio(Confidence::new_lower(0.975), &swapped)?;
+        assert_abs_diff_eq!(swapped.estimate(), -lnrr.estimate(), epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            swapped.interval().high_f(),
+            -0.06922333916420738,
+            epsilon = 1e-9
+        );
+
+        let stats = comparison::Unpaired::from_iter(&[-1., 0.5], &old_version)?;
+        assert!(matches!(
+            log_response_ratio(confidence, &stats),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        let stats = comparison::Unpaired::from_iter(&new_version, &[1.])?;
+        assert!(matches!(
+            log_response_ratio(confidence, &stats),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+}
@@ -29,24 +29,62 @@ pub enum CIError {
     #[error("Too few failures: {0} (population: {1}; n*q={2}))")]
     TooFewFailures(usize, usize, f64),
 
-    #[error("Invalid confidence level (must be )): {0}")]
+    #[error("Invalid confidence level (must be in (0, 1)): {0}")]
     InvalidConfidenceLevel(f64),
 
+    #[error(
+        "Invalid confidence (expected e.g. \"95%\", \"0.95\", or \"90% upper one-sided\"): {0}"
+    )]
+    ParseConfidenceError(String),
+
+    #[error("Invalid degrees of freedom (must be strictly positive): {0}")]
+    InvalidDegreesOfFreedom(f64),
+
+    #[error("Invalid probability (must be in [0, 1]): {0}")]
+    InvalidProbability(f64),
+
     #[error("Invalid quantile (must be in (0, 1)): {0}")]
     InvalidQuantile(f64),
 
     #[error("Invalid number of successes: {0} (population: {1})")]
     InvalidSuccesses(usize, usize),
 
+    #[error("Invalid exposure (must be strictly positive and finite): {0}")]
+    InvalidExposure(f64),
+
+    #[error("Too few events to compute: {0}")]
+    TooFewEvents(u64),
+
+    #[error("Invalid trim fraction (must be in [0, 0.5)): {0}")]
+    InvalidTrimFraction(f64),
+
     #[error("Geometric/harmonic mean require strictly positive values: found {0}")]
     NonPositiveValue(f64),
 
+    #[error("Invalid target half-width (must be strictly positive and finite): {0}")]
+    InvalidHalfWidth(f64),
+
+    #[error("Invalid effect size (must be strictly positive and finite): {0}")]
+    InvalidEffectSize(f64),
+
+    #[error("Invalid information fraction (must be in (0, 1] and increasing): {0}")]
+    InvalidInformationFraction(f64),
+
+    #[error("Invalid equivalence margin (must be strictly positive and finite): {0}")]
+    InvalidMargin(f64),
+
+    #[error("Invalid reference value of the test: {0}")]
+    InvalidReference(f64),
+
     #[error("Invalid input data found")]
     InvalidInputData,
 
     #[error("Float type conversion error: {0}")]
     FloatConversionError(String),
 
+    #[error("Arithmetic overflow computing the {0}")]
+    Overflow(String),
+
     #[error("Index error: {0} should be in [0, {1})")]
     IndexError(f64, usize),
 
@@ -0,0 +1,353 @@
+//!
+//! Implements the [`CiEstimate`] type, which bundles a point estimate with its confidence interval,
+//! and the [`Statistic`] trait implemented by the estimators of the crate.
+//!
+use super::*;
+use error::*;
+
+///
+/// Result of an estimation: the point estimate together with its confidence interval,
+/// the confidence level used, the sample size, and the standard error of the estimate.
+///
+/// This makes it possible to report "estimate (CI)" from a single call to [`Statistic::estimate`],
+/// without recomputing the point estimate separately.
+///
+/// The default formatting prints the estimate followed by the interval, passing the format options
+/// to the estimate and the precision to the interval.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let stats = mean::Arithmetic::from_iter(&data)?;
+/// let estimate = stats.estimate(Confidence::new_two_sided(0.95))?;
+/// assert_eq!(estimate.estimate(), 5.5);
+/// assert_eq!(estimate.sample_count(), 10);
+/// assert_abs_diff_eq!(estimate.std_err(), 0.9574, epsilon = 1e-4);
+/// assert_abs_diff_eq!(estimate.interval(), &Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+/// assert_eq!(format!("{:.1}", estimate), "5.5 [3.3, 7.7]");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CiEstimate<T: PartialOrd> {
+    estimate: T,
+    interval: Interval<T>,
+    confidence: Confidence,
+    sample_count: usize,
+    std_err: T,
+}
+
+impl<T: PartialOrd> CiEstimate<T> {
+    ///
+    /// Creates a new estimate from its components.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `estimate` - the point estimate
+    /// * `interval` - the confidence interval on the estimated parameter
+    /// * `confidence` - the confidence level of the interval
+    /// * `sample_count` - the number of samples from which the estimate is computed
+    /// * `std_err` - the standard error of the point estimate
+    ///
+    pub fn new(
+        estimate: T,
+        interval: Interval<T>,
+        confidence: Confidence,
+        sample_count: usize,
+        std_err: T,
+    ) -> Self {
+        Self {
+            estimate,
+            interval,
+            confidence,
+            sample_count,
+            std_err,
+        }
+    }
+
+    ///
+    /// Returns the confidence interval on the estimated parameter.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn interval(&self) -> &Interval<T> {
+        &self.interval
+    }
+
+    ///
+    /// Returns the confidence level of the interval.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    ///
+    /// Returns the number of samples from which the estimate is computed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    ///
+    /// Consumes the estimate and returns its confidence interval.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn into_interval(self) -> Interval<T> {
+        self.interval
+    }
+}
+
+impl<T: PartialOrd + Copy> CiEstimate<T> {
+    ///
+    /// Returns the point estimate.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn estimate(&self) -> T {
+        self.estimate
+    }
+
+    ///
+    /// Returns the standard error of the point estimate.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn std_err(&self) -> T {
+        self.std_err
+    }
+}
+
+impl<T: PartialOrd> From<CiEstimate<T>> for Interval<T> {
+    fn from(estimate: CiEstimate<T>) -> Self {
+        estimate.into_interval()
+    }
+}
+
+use core::fmt::Display;
+impl<T: PartialOrd + Display> Display for CiEstimate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.estimate.fmt(f)?;
+        match f.precision() {
+            Some(precision) => write!(f, " {:.*}", precision, self.interval),
+            None => write!(f, " {}", self.interval),
+        }
+    }
+}
+
+///
+/// Trait implemented by estimators that provide a point estimate together with a confidence interval.
+///
+/// ## Implementations
+///
+/// * [`mean::Arithmetic`], [`mean::Geometric`], [`mean::Harmonic`], [`mean::Quadratic`], [`mean::Power`],
+///   [`mean::Weighted`], [`mean::Trimmed`] - estimate of the mean
+/// * [`comparison::Paired`], [`comparison::Unpaired`] - estimate of the difference between means
+/// * [`proportion::Stats`] - estimate of the proportion
+/// * [`incremental::MeanAccumulator`] - estimate of the mean of a stream
+/// * `survey::Stratified`, `survey::Clustered`, `survey::HorvitzThompson` - estimate of the population mean (requires the `std` feature)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// fn report<S: Statistic<f64>>(stats: &S) -> CIResult<String> {
+///     let estimate = stats.estimate(Confidence::new_two_sided(0.95))?;
+///     Ok(format!("{:.2} (n = {})", estimate.estimate(), estimate.sample_count()))
+/// }
+/// let mean = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+/// assert_eq!(report(&mean)?, "3.00 (n = 5)");
+/// let proportion = proportion::Stats::new(100, 30);
+/// assert_eq!(report(&proportion)?, "0.30 (n = 100)");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub trait Statistic<T: PartialOrd> {
+    ///
+    /// Computes the point estimate along with its confidence interval, sample size, and standard error.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the interval
+    ///
+    /// # Errors
+    ///
+    /// The same errors as the method computing the confidence interval of the estimator (e.g., [`mean::Arithmetic::ci_mean`]).
+    ///
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<T>>;
+}
+
+///
+/// Implements [`Statistic`] for a mean estimator providing `sample_mean`, `sample_sem`, `sample_count`, and `ci_mean`.
+///
+/// With `sem_over_n_minus_one`, the estimator's `sample_sem` divides by \\( \sqrt{n-1} \\) and the standard error
+/// is rescaled to divide by \\( \sqrt{n} \\), consistently with the confidence interval (see [`std_err_of_mean`]).
+///
+macro_rules! impl_statistic_for_mean {
+    ( $x:ty, sem_over_n_minus_one ) => {
+        impl<F: num_traits::Float> $crate::Statistic<F> for $x {
+            fn estimate(
+                &self,
+                confidence: $crate::Confidence,
+            ) -> $crate::CIResult<$crate::CiEstimate<F>> {
+                Ok($crate::CiEstimate::new(
+                    self.sample_mean(),
+                    self.ci_mean(confidence)?,
+                    confidence,
+                    self.sample_count(),
+                    $crate::estimate::std_err_of_mean(self.sample_sem(), self.sample_count()),
+                ))
+            }
+        }
+    };
+    ( $x:ty ) => {
+        impl<F: num_traits::Float> $crate::Statistic<F> for $x {
+            fn estimate(
+                &self,
+                confidence: $crate::Confidence,
+            ) -> $crate::CIResult<$crate::CiEstimate<F>> {
+                Ok($crate::CiEstimate::new(
+                    self.sample_mean(),
+                    self.ci_mean(confidence)?,
+                    confidence,
+                    self.sample_count(),
+                    self.sample_sem(),
+                ))
+            }
+        }
+    };
+}
+pub(crate) use impl_statistic_for_mean;
+
+///
+/// Standard error of the mean \\( s / \sqrt{n} \\) used by the confidence intervals, from the value
+/// \\( s / \sqrt{n-1} \\) returned by `sample_sem` of the arithmetic, geometric, harmonic, quadratic, and power means.
+///
+pub(crate) fn std_err_of_mean<F: num_traits::Float>(sem: F, count: usize) -> F {
+    let n = F::from(count).unwrap_or(F::nan());
+    sem * ((n - F::one()) / n).sqrt()
+}
+
+impl Statistic<f64> for proportion::Stats {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<f64>> {
+        let interval = self.ci(confidence)?;
+        let n = self.population() as f64;
+        let p = self.successes() as f64 / n;
+        Ok(CiEstimate::new(
+            p,
+            interval,
+            confidence,
+            self.population(),
+            (p * (1. - p) / n).sqrt(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_estimate() -> CIResult<()> {
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+            0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let stats = mean::Geometric::from_iter(&data)?;
+        let estimate = stats.estimate(confidence)?;
+        assert_eq!(estimate.estimate(), stats.sample_mean());
+        let n = data.len() as f64;
+        assert_abs_diff_eq!(
+            estimate.std_err(),
+            stats.sample_sem() * ((n - 1.) / n).sqrt(),
+            epsilon = 1e-12
+        );
+        assert_eq!(estimate.sample_count(), data.len());
+        assert_eq!(estimate.confidence(), confidence);
+        assert_eq!(estimate.interval(), &stats.ci_mean(confidence)?);
+        assert_eq!(Interval::from(estimate), stats.ci_mean(confidence)?);
+
+        // the standard error is consistent with the half-width of the interval
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        let estimate = stats.estimate(confidence)?;
+        let t = distributions::t_value(confidence, (data.len() - 1) as f64)?;
+        assert_abs_diff_eq!(
+            estimate.interval().high_f() - estimate.estimate(),
+            t * estimate.std_err(),
+            epsilon = 1e-10
+        );
+
+        let confidence = Confidence::new_upper(0.9);
+        let stats = comparison::Unpaired::from_iter(&data[..15].to_vec(), &data[15..].to_vec())?;
+        let estimate = stats.estimate(confidence)?;
+        assert_eq!(estimate.interval(), &stats.ci_mean(confidence)?);
+        assert_abs_diff_eq!(
+            estimate.estimate(),
+            stats.stats_a().sample_mean() - stats.stats_b().sample_mean(),
+            epsilon = 1e-10
+        );
+        // reference values computed in python
+        assert_abs_diff_eq!(estimate.std_err(), 3.54495, epsilon = 1e-5);
+        assert_eq!(estimate.sample_count(), data.len());
+
+        let stats = proportion::Stats::new(200, 50);
+        let estimate = stats.estimate(confidence)?;
+        assert_eq!(estimate.estimate(), 0.25);
+        assert_abs_diff_eq!(estimate.std_err(), 0.0306186, epsilon = 1e-7);
+        assert_eq!(estimate.into_interval(), stats.ci(confidence)?);
+
+        let mut acc = incremental::MeanAccumulator::new();
+        acc.extend(&data)?;
+        assert_eq!(acc.estimate(confidence)?.interval(), &acc.ci(confidence)?);
+
+        assert!(matches!(
+            proportion::Stats::new(10, 0).estimate(confidence),
+            Err(CIError::TooFewSuccesses(..))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() -> CIResult<()> {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Record {
+            estimate: CiEstimate<f64>,
+            upper: Interval<f64>,
+            lower: Interval<f64>,
+            stats: mean::Arithmetic<f64>,
+            proportion: proportion::Stats,
+            quantile: quantile::Stats,
+        }
+        let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+        let record = Record {
+            estimate: stats.estimate(Confidence::new_two_sided(0.95))?,
+            upper: Interval::new_upper(1.5),
+            lower: Interval::new_lower(2.5),
+            stats,
+            proportion: proportion::Stats::new(100, 30),
+            quantile: quantile::Stats::new(100),
+        };
+        let serialized = toml::to_string(&record).unwrap();
+        assert!(serialized.contains("UpperOneSided = 1.5"));
+        assert!(serialized.contains("LowerOneSided = 2.5"));
+        assert!(serialized.contains("TwoSided = 0.95"));
+        let deserialized: Record = toml::from_str(&serialized).unwrap();
+        assert_eq!(record, deserialized);
+        Ok(())
+    }
+}
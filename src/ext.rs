@@ -0,0 +1,152 @@
+//!
+//! Implements the [`StatisticsExt`] extension trait, which computes confidence intervals directly on iterators.
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Extension trait to compute confidence intervals directly at the end of an iterator pipeline.
+///
+/// The trait is implemented for all iterators, and each method consumes the iterator.
+/// Slices and other collections can be used through their iterators (e.g., `data.iter().copied()`).
+///
+/// ## Operations
+///
+/// * [`StatisticsExt::mean_ci`] - confidence interval on the arithmetic mean (see [`mean::Arithmetic`])
+/// * [`StatisticsExt::proportion_ci`] - confidence interval on the proportion of items satisfying a condition (see [`proportion::ci`])
+/// * [`StatisticsExt::quantile_ci`] - confidence interval on a quantile (see [`quantile::ci`])
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = data.iter().copied().mean_ci(confidence)?;
+/// assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+///
+/// let ci = data.iter().map(|x| x * 10.).quantile_ci(confidence, 0.5)?;
+/// assert_eq!(ci, Interval::new(20., 90.)?);
+///
+/// let ci = (1..=100).proportion_ci(confidence, |&x| x % 3 == 0)?;
+/// assert!(ci.contains(&0.33));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub trait StatisticsExt: Iterator + Sized {
+    ///
+    /// Computes the confidence interval on the arithmetic mean of the items.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of items.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are too few items to compute the confidence interval
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted from/to a float
+    ///
+    fn mean_ci<F>(self, confidence: Confidence) -> CIResult<Interval<F>>
+    where
+        F: Float,
+        Self: Iterator<Item = F>,
+    {
+        let mut stats = mean::Arithmetic::new();
+        for x in self {
+            stats.append(x)?;
+        }
+        if stats.sample_count() < 2 {
+            return Err(CIError::TooFewSamples(stats.sample_count()));
+        }
+        stats.ci_mean(confidence)
+    }
+
+    ///
+    /// Computes the confidence interval on the proportion of items that satisfy a given condition (Wilson score interval).
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of items.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSuccesses`] - if too few items satisfy the condition to compute the confidence interval
+    /// * [`CIError::TooFewFailures`] - if too few items do not satisfy the condition to compute the confidence interval
+    ///
+    fn proportion_ci<P>(self, confidence: Confidence, mut condition: P) -> CIResult<Interval<f64>>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let (population, successes) = self.fold((0, 0), |(population, successes), x| {
+            (population + 1, successes + usize::from(condition(&x)))
+        });
+        proportion::ci(confidence, population, successes)
+    }
+
+    ///
+    /// Computes the confidence interval on a given quantile of the items.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of items.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are too few items to compute the confidence interval
+    /// * [`CIError::InvalidQuantile`] - if the quantile is not in (0, 1)
+    ///
+    /// # Panics
+    ///
+    /// * if the items are not comparable (with their partial ordering).
+    ///
+    /// # Notes
+    ///
+    /// This method is only available with the `std` feature enabled, since it collects the items into a [`Vec`].
+    ///
+    #[cfg(any(test, feature = "std"))]
+    fn quantile_ci(self, confidence: Confidence, quantile: f64) -> CIResult<Interval<Self::Item>>
+    where
+        Self::Item: PartialOrd + Clone,
+    {
+        let mut buffer: Vec<Self::Item> = self.collect();
+        quantile::ci_mut(confidence, &mut buffer, quantile)
+    }
+}
+
+impl<I: Iterator> StatisticsExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_statistics_ext() -> CIResult<()> {
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+            0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            data.iter().copied().mean_ci(confidence)?,
+            mean::Arithmetic::ci(confidence, &data)?,
+            epsilon = 1e-10
+        );
+        assert_eq!(
+            data.iter().copied().quantile_ci(confidence, 0.5)?,
+            quantile::ci(confidence, &data, 0.5)?
+        );
+        assert_eq!(
+            data.iter().proportion_ci(confidence, |&&x| x > 10.)?,
+            proportion::ci_if(confidence, &data, |&x| x > 10.)?
+        );
+
+        // pipelines over other item types
+        let ci = data.iter().map(|&x| x as f32).mean_ci(confidence)?;
+        assert_abs_diff_eq!(ci.low_f(), 6.18467, epsilon = 1e-4);
+        let ci = (1..=15).quantile_ci(confidence, 0.5)?;
+        assert_eq!(ci, Interval::new(4, 12)?);
+
+        assert!(matches!(
+            core::iter::empty::<f64>().mean_ci(confidence),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+//!
+//! Highest-density intervals (HDI), i.e., the narrowest intervals containing a given probability mass.
+//!
+//! For a skewed distribution (e.g., the posterior of a small proportion, or the bootstrap distribution of a variance),
+//! the equal-tailed interval leaves the same probability in each tail and is wider than needed. The highest-density
+//! interval is the shortest interval with the requested mass; for a unimodal density, every value inside the interval
+//! is more probable than any value outside of it.
+//!
+//! * [`ci_inverse_cdf`] - the HDI of a continuous unimodal distribution given by its quantile function
+//!   (e.g., [`distributions::beta_inverse_cdf`]).
+//! * [`ci_distribution`] - the HDI of a continuous unimodal distribution of the crate [`statrs`](https://crates.io/crates/statrs)
+//!   (only available with the `statrs` feature enabled).
+//! * [`ci`] and [`ci_sorted_unchecked`] - the HDI of a set of draws, e.g., from MCMC chains or bootstrap replicates.
+//!
+//! The probability mass is given by the level of the [`Confidence`]. Since the HDI is inherently two-sided,
+//! a one-sided confidence gives the same one-sided interval as the quantiles.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // posterior of a conversion rate after 3 conversions out of 20, with a Beta(2, 8) prior
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let hdi = hdi::ci_inverse_cdf(confidence, |p| distributions::beta_inverse_cdf(p, 5., 25.))?;
+//! assert_abs_diff_eq!(hdi, Interval::new(0.047546, 0.298913)?, epsilon = 1e-6);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * R.J. Hyndman. "Computing and graphing highest density regions." The American Statistician 50(2): 120-126 (1996).
+//! * M.-H. Chen, Q.-M. Shao. "Monte Carlo estimation of Bayesian credible and HPD intervals." Journal of Computational and Graphical Statistics 8(1): 69-92 (1999).
+//! * [Wikipedia - Credible interval](https://en.wikipedia.org/wiki/Credible_interval)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Compute the highest-density interval of a continuous unimodal distribution given by its quantile function (inverse CDF).
+///
+/// The interval \\( [Q(t), Q(t + 1 - \alpha)] \\) is the narrowest over the probability \\( t \in [0, \alpha] \\) of the lower tail,
+/// which is found by golden-section search. For a monotone density (e.g., exponential), the interval extends to the boundary
+/// of the support.
+/// For one-sided confidences, the interval is delimited by the quantile \\( Q(\alpha) \\) or \\( Q(1 - \alpha) \\) and the
+/// corresponding end of the support (\\( Q(1) \\) or \\( Q(0) \\)), or is unbounded if that end is infinite.
+///
+/// Complexity: \\( O(1) \\), with a fixed number (about 60) of evaluations of the quantile function.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence (or credibility) level, i.e., the probability mass of the interval
+/// * `inverse_cdf` - the quantile function of the distribution, for probabilities in [0, 1]
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * any error returned by the quantile function
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // the chi-square distribution is skewed: the HDI is shifted towards 0
+/// let hdi = hdi::ci_inverse_cdf(confidence, |p| distributions::chi2_inverse_cdf(p, 4.))?;
+/// assert_abs_diff_eq!(hdi, Interval::new(0.084727, 9.530336)?, epsilon = 1e-6);
+///
+/// // the t distribution is symmetric: the HDI is the equal-tailed interval
+/// let hdi = hdi::ci_inverse_cdf(confidence, |p| distributions::t_inverse_cdf(p, 5.))?;
+/// let t = distributions::t_value(confidence, 5.)?;
+/// assert_abs_diff_eq!(hdi, Interval::new(-t, t)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_inverse_cdf<Q>(confidence: Confidence, inverse_cdf: Q) -> CIResult<Interval<f64>>
+where
+    Q: Fn(f64) -> CIResult<f64>,
+{
+    distributions::checked_quantile(confidence)?;
+    let level = confidence.level();
+    match confidence {
+        Confidence::TwoSided(_) => {}
+        Confidence::UpperOneSided(_) => {
+            let (lo, hi) = (inverse_cdf(1. - level)?, inverse_cdf(1.)?);
+            return if hi.is_finite() {
+                Ok(Interval::new(lo, hi)?)
+            } else {
+                Ok(Interval::new_upper(lo))
+            };
+        }
+        Confidence::LowerOneSided(_) => {
+            let (lo, hi) = (inverse_cdf(0.)?, inverse_cdf(level)?);
+            return if lo.is_finite() {
+                Ok(Interval::new(lo, hi)?)
+            } else {
+                Ok(Interval::new_lower(hi))
+            };
+        }
+    }
+
+    // the width is unimodal in the probability of the lower tail (for a unimodal density):
+    // golden-section search of the lower tail in [0, 1 - level]
+    let bounds = |tail: f64| -> CIResult<(f64, f64)> {
+        Ok((inverse_cdf(tail)?, inverse_cdf((tail + level).min(1.))?))
+    };
+    let width = |tail: f64| -> CIResult<f64> {
+        let (lo, hi) = bounds(tail)?;
+        Ok(hi - lo)
+    };
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+    let (mut lo, mut hi) = (0., 1. - level);
+    let mut x1 = hi - ratio * (hi - lo);
+    let mut x2 = lo + ratio * (hi - lo);
+    let (mut w1, mut w2) = (width(x1)?, width(x2)?);
+    while hi - lo > 1e-12 {
+        if w1 < w2 {
+            hi = x2;
+            (x2, w2) = (x1, w1);
+            x1 = hi - ratio * (hi - lo);
+            w1 = width(x1)?;
+        } else {
+            lo = x1;
+            (x1, w1) = (x2, w2);
+            x2 = lo + ratio * (hi - lo);
+            w2 = width(x2)?;
+        }
+    }
+    let (low, high) = bounds((lo + hi) / 2.)?;
+    Ok(Interval::new(low, high)?)
+}
+
+///
+/// Compute the highest-density interval of a continuous unimodal distribution of the crate `statrs` (see [`ci_inverse_cdf`]).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence (or credibility) level, i.e., the probability mass of the interval
+/// * `distribution` - the distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `statrs` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let gamma = statrs::distribution::Gamma::new(2., 1.).unwrap();
+/// let hdi = hdi::ci_distribution(Confidence::new_two_sided(0.95), &gamma)?;
+/// assert_abs_diff_eq!(hdi, Interval::new(0.042363, 4.765168)?, epsilon = 1e-5);
+///
+/// // the density of the exponential distribution is decreasing: the HDI starts at 0
+/// let exp = statrs::distribution::Exp::new(1.).unwrap();
+/// let hdi = hdi::ci_distribution(Confidence::new_two_sided(0.95), &exp)?;
+/// assert_abs_diff_eq!(hdi, Interval::new(0., 2.995732)?, epsilon = 1e-5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(feature = "statrs")]
+pub fn ci_distribution<D>(confidence: Confidence, distribution: &D) -> CIResult<Interval<f64>>
+where
+    D: statrs::distribution::ContinuousCDF<f64, f64>,
+{
+    ci_inverse_cdf(confidence, |p| Ok(distribution.inverse_cdf(p)))
+}
+
+///
+/// Compute the highest-density interval of a set of draws (e.g., posterior draws of MCMC chains, or bootstrap replicates).
+///
+/// The draws are copied and sorted (see [`ci_sorted_unchecked`]).
+///
+/// Complexity: \\( O(n \log n) \\), where \\( n \\) is the number of draws.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence (or credibility) level, i.e., the fraction of the draws in the interval
+/// * `draws` - the draws
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there is no draw
+/// * [`CIError::InvalidInputData`] - if some draw is NaN
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let draws = [
+///     1.21, 0.55, 2.86, 0.72, 1.1, 0.41, 1.94, 0.69, 1.18, 2.04,
+///     0.62, 1.15, 0.81, 2.47, 0.58, 1.21, 1.01, 0.71, 2.34, 1.92,
+/// ];
+/// let hdi = hdi::ci(Confidence::new_two_sided(0.8), &draws)?;
+/// assert_eq!(hdi, Interval::new(0.41, 2.04)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci<F, I>(confidence: Confidence, draws: &I) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let mut sorted = draws.into_iter().copied().collect::<Vec<_>>();
+    if sorted.iter().any(|x| x.is_nan()) {
+        return Err(CIError::InvalidInputData);
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN values were rejected"));
+    ci_sorted_unchecked(confidence, &sorted)
+}
+
+///
+/// Compute the highest-density interval of a set of draws, assuming that the draws are __already sorted__ (see [`ci`]).
+///
+/// With \\( n \\) draws and a level \\( 1 - \alpha \\), the interval is the narrowest \\( [x_{(i)}, x_{(i + k)}] \\) with
+/// \\( k = \lfloor (1 - \alpha) n \rfloor \\). For one-sided confidences, the interval is unbounded on one side and contains
+/// the same number of draws.
+///
+/// Complexity: \\( O(n) \\), where \\( n \\) is the number of draws.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence (or credibility) level, i.e., the fraction of the draws in the interval
+/// * `sorted` - the draws, sorted in increasing order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there is no draw
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let sorted = [1., 2., 2.5, 2.7, 2.8, 3., 3.2, 5., 8., 13.];
+/// let hdi = hdi::ci_sorted_unchecked(Confidence::new_two_sided(0.5), &sorted)?;
+/// assert_eq!(hdi, Interval::new(2., 3.2)?);
+///
+/// let upper = hdi::ci_sorted_unchecked(Confidence::new_upper(0.5), &sorted)?;
+/// assert_eq!(upper, Interval::new_upper(2.8));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_sorted_unchecked<F: Float>(
+    confidence: Confidence,
+    sorted: &[F],
+) -> CIResult<Interval<F>> {
+    distributions::checked_quantile(confidence)?;
+    let n = sorted.len();
+    if n == 0 {
+        return Err(CIError::TooFewSamples(0));
+    }
+    let span = ((confidence.level() * n as f64).floor() as usize).min(n - 1);
+    match confidence {
+        Confidence::TwoSided(_) => {
+            let lo = (0..n - span)
+                .min_by(|&i, &j| {
+                    let width_i = sorted[i + span] - sorted[i];
+                    let width_j = sorted[j + span] - sorted[j];
+                    width_i
+                        .partial_cmp(&width_j)
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap_or(0);
+            Ok(Interval::new(sorted[lo], sorted[lo + span])?)
+        }
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(sorted[n - 1 - span])),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(sorted[span])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_hdi_inverse_cdf() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        let hdi = ci_inverse_cdf(confidence, |p| distributions::chi2_inverse_cdf(p, 4.))?;
+        assert_abs_diff_eq!(
+            hdi,
+            Interval::new(0.08472666685991313, 9.530336494778153)?,
+            epsilon = 1e-6
+        );
+        // narrower than the equal-tailed interval [0.4844, 11.1433]
+        assert!(hdi.width().unwrap() < 11.1432867818778 - 0.4844185570879298);
+
+        let hdi = ci_inverse_cdf(confidence, |p| distributions::beta_inverse_cdf(p, 5., 25.))?;
+        assert_abs_diff_eq!(
+            hdi,
+            Interval::new(0.04754569386985473, 0.2989130888423892)?,
+            epsilon = 1e-7
+        );
+
+        // one-sided intervals extend to the end of the support
+        let hdi = ci_inverse_cdf(Confidence::new_upper(0.95), |p| {
+            distributions::chi2_inverse_cdf(p, 4.)
+        })?;
+        assert!(hdi.is_upper());
+        assert_abs_diff_eq!(hdi.low_f(), 0.7107230213973241, epsilon = 1e-9);
+        let hdi = ci_inverse_cdf(Confidence::new_lower(0.95), |p| {
+            distributions::chi2_inverse_cdf(p, 4.)
+        })?;
+        assert_eq!(hdi.low_f(), 0.);
+        let hdi = ci_inverse_cdf(Confidence::new_upper(0.95), |p| {
+            distributions::beta_inverse_cdf(p, 5., 25.)
+        })?;
+        assert_eq!(hdi.high_f(), 1.);
+
+        assert!(matches!(
+            ci_inverse_cdf(Confidence::TwoSided(1.), Ok),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        assert!(matches!(
+            ci_inverse_cdf(confidence, |p| distributions::chi2_inverse_cdf(p, -1.)),
+            Err(CIError::InvalidDegreesOfFreedom(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "statrs")]
+    #[test]
+    fn test_hdi_distribution() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let beta = statrs::distribution::Beta::new(5., 25.).unwrap();
+        assert_abs_diff_eq!(
+            ci_distribution(confidence, &beta)?,
+            ci_inverse_cdf(confidence, |p| distributions::beta_inverse_cdf(p, 5., 25.))?,
+            epsilon = 1e-9
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hdi_draws() -> CIResult<()> {
+        let draws = [
+            1.21, 0.55, 2.86, 0.72, 1.1, 0.41, 1.94, 0.69, 1.18, 2.04, 0.62, 1.15, 0.81, 2.47,
+            0.58, 1.21, 1.01, 0.71, 2.34, 1.92,
+        ];
+        // reference values computed in python
+        assert_eq!(
+            ci(Confidence::new_two_sided(0.8), &draws)?,
+            Interval::new(0.41, 2.04)?
+        );
+        assert_eq!(
+            ci(Confidence::new_two_sided(0.5), &draws)?,
+            Interval::new(0.62, 1.21)?
+        );
+        assert_eq!(
+            ci(Confidence::new_upper(0.8), &draws)?,
+            Interval::new_upper(0.62)
+        );
+        assert_eq!(
+            ci(Confidence::new_lower(0.8), &draws)?,
+            Interval::new_lower(2.04)
+        );
+        assert_eq!(
+            ci(Confidence::new_two_sided(0.99), &[3.])?,
+            Interval::new(3., 3.)?
+        );
+
+        assert!(matches!(
+            ci(Confidence::new_two_sided(0.8), &Vec::<f64>::new()),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            ci(Confidence::new_two_sided(0.8), &[1., f64::NAN]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
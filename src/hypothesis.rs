@@ -0,0 +1,234 @@
+//!
+//! Hypothesis tests dual to the confidence intervals of the crate.
+//!
+//! Each test is computed from the same state (i.e., the same sufficient statistics) as the corresponding
+//! confidence interval, so that reporting both a confidence interval and a p-value requires a single pass
+//! over the data:
+//!
+//! * [`mean::Arithmetic::t_test`] - one-sample t-test of the mean against a reference value
+//! * [`comparison::Paired::t_test`] - paired t-test of a zero difference between two means
+//! * [`comparison::Unpaired::t_test`] - Welch's t-test of a zero difference between two means
+//! * [`proportion::Stats::z_test`] - score test of a proportion against a reference value
+//! * [`rate::Exposure::poisson_test`] - exact Poisson test of a rate against a reference value
+//!
+//! The direction of the test is given by the [`Confidence`], consistently with the interval:
+//! * with a two-sided confidence, the alternative hypothesis is that the parameter differs from the reference value;
+//! * with an upper one-sided confidence (interval \\( [l, +\infty) \\)), it is that the parameter is greater;
+//! * with a lower one-sided confidence (interval \\( (-\infty, h] \\)), it is that the parameter is smaller.
+//!
+//! The tests are dual to the intervals: the null hypothesis is rejected at the level \\( \alpha = 1 - \\) `confidence`
+//! if and only if the confidence interval excludes the reference value.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! let data = [
+//!     10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3,
+//!     20.4, 1.2, 28.4, 10.7, 0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1,
+//!     12.6, 21.7, 21.9, 2.0, 8.4, 9.3
+//! ];
+//! let stats = mean::Arithmetic::from_iter(&data)?;
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let interval = stats.ci_mean(confidence)?;
+//! let test = stats.t_test(confidence, 5.)?;
+//! assert_abs_diff_eq!(test.statistic(), 2.7216, epsilon = 1e-4);
+//! assert_abs_diff_eq!(test.p_value(), 0.0109, epsilon = 1e-4);
+//! assert!(test.is_significant());
+//! assert!(!interval.contains(&5.));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+
+///
+/// Outcome of a hypothesis test: the test statistic and the p-value, along with the confidence
+/// giving the direction and the significance level of the test.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    confidence: Confidence,
+    statistic: f64,
+    p_value: f64,
+    degrees_of_freedom: Option<f64>,
+}
+
+impl TestResult {
+    ///
+    /// Create the outcome of a test from the probabilities of observing a statistic at least as large
+    /// (`upper_tail`) or at most as large (`lower_tail`) as the observed statistic under the null hypothesis.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub(crate) fn from_tails(
+        confidence: Confidence,
+        statistic: f64,
+        upper_tail: f64,
+        lower_tail: f64,
+        degrees_of_freedom: Option<f64>,
+    ) -> CIResult<Self> {
+        distributions::checked_quantile(confidence)?;
+        let p_value = match confidence {
+            Confidence::TwoSided(_) => (2. * upper_tail.min(lower_tail)).min(1.),
+            Confidence::UpperOneSided(_) => upper_tail,
+            Confidence::LowerOneSided(_) => lower_tail,
+        };
+        Ok(Self {
+            confidence,
+            statistic,
+            p_value,
+            degrees_of_freedom,
+        })
+    }
+
+    ///
+    /// Create the outcome of a t-test, with the same switch to the normal distribution for large
+    /// degrees of freedom as the confidence intervals on means.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidDegreesOfFreedom`] - if the degrees of freedom are not strictly positive
+    /// * [`CIError::InvalidInputData`] - if the statistic is undefined (e.g., zero variance and zero difference)
+    ///
+    pub(crate) fn t(
+        confidence: Confidence,
+        statistic: f64,
+        degrees_of_freedom: f64,
+    ) -> CIResult<Self> {
+        if statistic.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        let upper_tail = stats::t_upper_tail(statistic, degrees_of_freedom)?;
+        Self::from_tails(
+            confidence,
+            statistic,
+            upper_tail,
+            1. - upper_tail,
+            Some(degrees_of_freedom),
+        )
+    }
+
+    ///
+    /// Create the outcome of a z-test, i.e., with a statistic following the standard normal distribution.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidInputData`] - if the statistic is undefined
+    ///
+    pub(crate) fn z(confidence: Confidence, statistic: f64) -> CIResult<Self> {
+        if statistic.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        Self::from_tails(
+            confidence,
+            statistic,
+            special::normal_cdf(-statistic),
+            special::normal_cdf(statistic),
+            None,
+        )
+    }
+
+    ///
+    /// Return the confidence giving the direction and the significance level of the test.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    ///
+    /// Return the value of the test statistic (e.g., \\( t \\) for a t-test).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    ///
+    /// Return the p-value of the test.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    ///
+    /// Return the degrees of freedom of the distribution of the statistic, if any (e.g., for a t-test).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn degrees_of_freedom(&self) -> Option<f64> {
+        self.degrees_of_freedom
+    }
+
+    ///
+    /// Return `true` if the null hypothesis is rejected, i.e., if the p-value is smaller than \\( \alpha = 1 - \\) `confidence`.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn is_significant(&self) -> bool {
+        self.p_value < 1. - self.confidence.level()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_from_tails() -> CIResult<()> {
+        let two_sided = TestResult::from_tails(Confidence::new(0.95), 1., 0.01, 0.99, None)?;
+        assert_abs_diff_eq!(two_sided.p_value(), 0.02);
+        assert!(two_sided.is_significant());
+        let upper = TestResult::from_tails(Confidence::new_upper(0.95), 1., 0.01, 0.99, None)?;
+        assert_abs_diff_eq!(upper.p_value(), 0.01);
+        let lower = TestResult::from_tails(Confidence::new_lower(0.95), 1., 0.01, 0.99, None)?;
+        assert_abs_diff_eq!(lower.p_value(), 0.99);
+        assert!(!lower.is_significant());
+
+        // discrete distributions: both tails include the observed value
+        let test = TestResult::from_tails(Confidence::new(0.95), 1., 0.7, 0.6, None)?;
+        assert_eq!(test.p_value(), 1.);
+
+        assert!(matches!(
+            TestResult::from_tails(Confidence::TwoSided(1.), 1., 0.01, 0.99, None),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_t_and_z() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        let test = TestResult::t(confidence, 2.5, 9.)?;
+        assert_abs_diff_eq!(test.p_value(), 0.03386182768298574, epsilon = 1e-12);
+        assert_eq!(test.degrees_of_freedom(), Some(9.));
+        let test = TestResult::t(Confidence::new_lower(0.95), 2.5, 9.)?;
+        assert_abs_diff_eq!(test.p_value(), 0.9830690861585071, epsilon = 1e-12);
+        let test = TestResult::z(confidence, -1.96)?;
+        assert_abs_diff_eq!(test.p_value(), 0.04999579029644087, epsilon = 1e-12);
+        assert_eq!(test.degrees_of_freedom(), None);
+
+        assert!(matches!(
+            TestResult::t(confidence, f64::NAN, 9.),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            TestResult::t(confidence, 1., 0.),
+            Err(CIError::InvalidDegreesOfFreedom(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,1368 @@
+//!
+//! Accumulators to maintain confidence intervals over a stream of data
+//!
+//! An accumulator ingests samples one at a time, in constant memory and constant time per sample,
+//! and can be queried for a confidence interval at any point during the ingestion.
+//! This avoids buffering the data and recomputing the interval from scratch (e.g., calling
+//! [`mean::Arithmetic::ci`] repeatedly over a growing vector).
+//!
+//! The accumulators rely on Welford's algorithm, which updates the mean and the sum of squared
+//! deviations from the mean at each new sample. Unlike the accumulation of raw sums and sums of squares,
+//! it does not suffer from catastrophic cancellation when the variance is small relative to the mean.
+//!
+//! Accumulators can be merged (with `+`, `+=`, or `merge`), so that partial states computed on separate
+//! threads or machines combine into a single interval without exchanging the samples themselves.
+//! With the feature `serde`, the accumulators can also be serialized to be sent over the network or stored.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let mut acc = incremental::MeanAccumulator::new();
+//! for x in [1., 2., 3., 4., 5.] {
+//!     acc.push(x)?;
+//! }
+//! // query the interval and keep ingesting data
+//! println!("after {} samples: {}", acc.n(), acc.ci(confidence)?);
+//! for x in [6., 7., 8., 9., 10.] {
+//!     acc.push(x)?;
+//! }
+//! assert_eq!(acc.n(), 10);
+//! use approx::*;
+//! assert_abs_diff_eq!(acc.mean(), 5.5, epsilon = 1e-10);
+//! assert_abs_diff_eq!(acc.ci(confidence)?, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * B.P. Welford. "Note on a method for calculating corrected sums of squares and products." Technometrics 4(3): 419-420 (1962).
+//! * [Wikipedia - Algorithms for calculating variance](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm)
+//!
+use crate::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Accumulator for the confidence interval over the arithmetic mean of a stream of samples.
+///
+/// Samples are accumulated with Welford's algorithm; the mean and the sum of squared deviations are additionally
+/// kept in compensated (Kahan) registers to limit the accumulation of rounding errors over long streams.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let mut acc = incremental::MeanAccumulator::new();
+/// acc.extend(&[1e9 + 4., 1e9 + 7., 1e9 + 13., 1e9 + 16.])?;
+/// assert_eq!(acc.mean(), 1e9 + 10.);
+/// assert_abs_diff_eq!(acc.variance(), 30., epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeanAccumulator<F: Float> {
+    count: usize,
+    mean: utils::KahanSum<F>,
+    sum_sq_dev: utils::KahanSum<F>,
+}
+
+impl<F: Float> Default for MeanAccumulator<F> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: utils::KahanSum::default(),
+            sum_sq_dev: utils::KahanSum::default(),
+        }
+    }
+}
+
+impl<F: Float> MeanAccumulator<F> {
+    ///
+    /// Create a new empty accumulator
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the accumulator
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite (NaN or infinite); the accumulator is left unchanged
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<()> {
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        let count = F::from(self.count + 1).convert("count")?;
+        self.count += 1;
+        let delta = x - self.mean.value();
+        self.mean += delta / count;
+        self.sum_sq_dev += delta * (x - self.mean.value());
+        Ok(())
+    }
+
+    ///
+    /// Append several samples to the accumulator
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite; the samples before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for &x_i in data {
+            self.push(x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Merge the state of another accumulator into this one.
+    /// The result is the same as if all samples of `other` had been appended to this accumulator
+    /// (up to rounding errors), which allows to compute partial states on separate threads or
+    /// machines and combine them without exchanging the samples themselves.
+    ///
+    /// The states are combined with the pairwise update of Chan et al.:
+    /// \\[
+    /// \bar{x} = \bar{x}_a + \delta \frac{n_b}{n}, \quad
+    /// M_2 = M_{2,a} + M_{2,b} + \delta^2 \frac{n_a n_b}{n}
+    /// \\]
+    /// where \\( \delta = \bar{x}_b - \bar{x}_a \\), \\( n = n_a + n_b \\), and \\( M_2 \\) is the sum of squared deviations from the mean.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut acc_a = incremental::MeanAccumulator::new();
+    /// acc_a.extend(&[1., 2., 3., 4., 5.])?;
+    /// let mut acc_b = incremental::MeanAccumulator::new();
+    /// acc_b.extend(&[6., 7., 8., 9., 10.])?;
+    /// acc_a.merge(&acc_b);
+    /// assert_eq!(acc_a.n(), 10);
+    /// assert_abs_diff_eq!(acc_a.mean(), 5.5, epsilon = 1e-10);
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_abs_diff_eq!(acc_a.ci(confidence)?, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * T.F. Chan, G.H. Golub, R.J. LeVeque. "Updating formulae and a pairwise algorithm for computing sample variances." Technical Report STAN-CS-79-773, Stanford University (1979).
+    ///
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let n_a = F::from(self.count).unwrap();
+        let n_b = F::from(other.count).unwrap();
+        let n = F::from(count).unwrap();
+        let delta = other.mean.value() - self.mean.value();
+        self.mean += delta * (n_b / n);
+        self.sum_sq_dev += other.sum_sq_dev;
+        self.sum_sq_dev += delta * delta * (n_a * n_b / n);
+        self.count = count;
+    }
+
+    ///
+    /// Remove a sample previously appended to the accumulator (reverse Welford update).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[cfg(any(test, feature = "std"))]
+    fn remove(&mut self, x: F) {
+        if self.count <= 1 {
+            *self = Self::default();
+            return;
+        }
+        let delta = x - self.mean.value();
+        self.count -= 1;
+        self.mean += -delta / F::from(self.count).unwrap();
+        self.sum_sq_dev += -delta * (x - self.mean.value());
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Mean of the samples (zero if no sample has been appended)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        self.mean.value()
+    }
+
+    ///
+    /// Unbiased variance of the samples
+    /// \\( \frac{1}{n-1} \sum_{i=1}^n (x_i - \bar{x})^2 \\)
+    ///
+    /// The result is NaN if less than two samples have been appended.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn variance(&self) -> F {
+        if self.count < 2 {
+            return F::nan();
+        }
+        self.sum_sq_dev.value() / F::from(self.count - 1).unwrap()
+    }
+
+    ///
+    /// Standard deviation of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn std_dev(&self) -> F {
+        self.variance().sqrt()
+    }
+
+    ///
+    /// Standard error of the mean
+    /// \\( \frac{s}{\sqrt{n}} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sem(&self) -> F {
+        self.std_dev() / F::from(self.count).unwrap().sqrt()
+    }
+
+    ///
+    /// Confidence interval of the mean of the samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let degrees_of_freedom = F::from(self.count - 1).convert("count")?;
+        let (lo, hi) =
+            stats::float_interval_bounds(confidence, self.mean(), self.sem(), degrees_of_freedom)?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+///
+/// Accumulator for the confidence interval over the geometric mean of a stream of samples.
+///
+/// It is implemented as a wrapper around [`MeanAccumulator`] fed with the logarithms of the samples,
+/// in the same way as [`mean::Geometric`] wraps [`mean::Arithmetic`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 4., 8., 16.];
+/// let mut acc = incremental::GeometricAccumulator::new();
+/// acc.extend(&data)?;
+/// assert_abs_diff_eq!(acc.mean(), 4., epsilon = 1e-10);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(acc.ci(confidence)?, mean::Geometric::ci(confidence, &data)?, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometricAccumulator<F: Float> {
+    log_space: MeanAccumulator<F>,
+}
+
+impl<F: Float> Default for GeometricAccumulator<F> {
+    fn default() -> Self {
+        Self {
+            log_space: MeanAccumulator::default(),
+        }
+    }
+}
+
+impl<F: Float> GeometricAccumulator<F> {
+    ///
+    /// Create a new empty accumulator
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the accumulator
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the sample is not strictly positive
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<()> {
+        self.log_space.push(mean::to_log_space(x)?)
+    }
+
+    ///
+    /// Append several samples to the accumulator
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If some sample is not strictly positive
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for &x_i in data {
+            self.push(x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Merge the state of another accumulator into this one (see [`MeanAccumulator::merge`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn merge(&mut self, other: &Self) {
+        self.log_space.merge(&other.log_space);
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.log_space.n()
+    }
+
+    ///
+    /// Geometric mean of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        self.log_space.mean().exp()
+    }
+
+    ///
+    /// Confidence interval of the geometric mean of the samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let log_ci = self.log_space.ci(confidence)?;
+        mean::from_log_space(confidence, log_ci)
+    }
+}
+
+///
+/// Accumulator for the confidence interval over the harmonic mean of a stream of samples.
+///
+/// It is implemented as a wrapper around [`MeanAccumulator`] fed with the reciprocals of the samples,
+/// in the same way as [`mean::Harmonic`] wraps [`mean::Arithmetic`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [2., 3., 4., 4.];
+/// let mut acc = incremental::HarmonicAccumulator::new();
+/// acc.extend(&data)?;
+/// assert_abs_diff_eq!(acc.mean(), 3., epsilon = 1e-10);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(acc.ci(confidence)?, mean::Harmonic::ci(confidence, &data)?, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarmonicAccumulator<F: Float> {
+    recip_space: MeanAccumulator<F>,
+}
+
+impl<F: Float> Default for HarmonicAccumulator<F> {
+    fn default() -> Self {
+        Self {
+            recip_space: MeanAccumulator::default(),
+        }
+    }
+}
+
+impl<F: Float> HarmonicAccumulator<F> {
+    ///
+    /// Create a new empty accumulator
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the accumulator
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the sample is not strictly positive
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<()> {
+        // the reciprocal of an infinite sample would be finite
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.recip_space.push(mean::to_recip_space(x)?)
+    }
+
+    ///
+    /// Append several samples to the accumulator
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If some sample is not strictly positive
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for &x_i in data {
+            self.push(x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Merge the state of another accumulator into this one (see [`MeanAccumulator::merge`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn merge(&mut self, other: &Self) {
+        self.recip_space.merge(&other.recip_space);
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.recip_space.n()
+    }
+
+    ///
+    /// Harmonic mean of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        F::one() / self.recip_space.mean()
+    }
+
+    ///
+    /// Confidence interval of the harmonic mean of the samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let recip_ci = self.recip_space.ci(confidence.flipped())?;
+        mean::from_recip_space(confidence, recip_ci)
+    }
+}
+
+///
+/// Accumulator for the confidence interval over the arithmetic mean of the most recent samples of a stream.
+///
+/// The accumulator keeps the last `capacity` samples in a ring buffer. When a new sample is appended to a full
+/// window, the oldest sample is evicted and its contribution is removed from the running statistics, so that
+/// an up-to-date interval over the window is available at any time in constant time.
+/// Samples can also be evicted explicitly with [`Self::pop_oldest`] (e.g., to expire samples older than some age).
+///
+/// To avoid the drift of rounding errors caused by repeated removals, the running statistics are recomputed
+/// from the buffered samples once every `capacity` evictions, which keeps the amortized cost constant.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut window = incremental::WindowedAccumulator::new(5);
+/// for x in [100., 100., 100., 1., 2., 3., 4., 5.] {
+///     window.push(x)?;
+/// }
+/// // only the last 5 samples are considered
+/// assert_eq!(window.n(), 5);
+/// assert_abs_diff_eq!(window.mean(), 3., epsilon = 1e-10);
+/// assert_abs_diff_eq!(window.ci(confidence)?, mean::Arithmetic::ci(confidence, &[1., 2., 3., 4., 5.])?, epsilon = 1e-10);
+///
+/// assert_eq!(window.pop_oldest(), Some(1.));
+/// assert_eq!(window.n(), 4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Panics
+///
+/// * if the capacity is zero
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowedAccumulator<F: Float> {
+    capacity: usize,
+    window: std::collections::VecDeque<F>,
+    state: MeanAccumulator<F>,
+    evictions: usize,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<F: Float> WindowedAccumulator<F> {
+    ///
+    /// Create a new empty accumulator over a window of the given capacity
+    ///
+    /// # Panics
+    ///
+    /// * if the capacity is zero
+    ///
+    pub fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("Window capacity must be strictly positive.")
+        }
+        Self {
+            capacity,
+            window: std::collections::VecDeque::with_capacity(capacity),
+            state: MeanAccumulator::default(),
+            evictions: 0,
+        }
+    }
+
+    ///
+    /// Append a new sample to the window, evicting the oldest sample if the window is full.
+    ///
+    /// Complexity: \\( O(1) \\) amortized
+    ///
+    /// # Output
+    ///
+    /// * `Ok(Some(x))` - The sample `x` that was evicted from the window
+    /// * `Ok(None)` - If the window was not full
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite (NaN or infinite); the window is left unchanged
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<Option<F>> {
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        let evicted = if self.window.len() == self.capacity {
+            self.pop_oldest()
+        } else {
+            None
+        };
+        self.window.push_back(x);
+        self.state.push(x)?;
+        Ok(evicted)
+    }
+
+    ///
+    /// Append several samples to the window
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite; the samples before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for &x_i in data {
+            self.push(x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Evict the oldest sample from the window
+    ///
+    /// Complexity: \\( O(1) \\) amortized
+    ///
+    /// # Output
+    ///
+    /// * `Some(x)` - The sample `x` that was evicted
+    /// * `None` - If the window is empty
+    ///
+    pub fn pop_oldest(&mut self) -> Option<F> {
+        let oldest = self.window.pop_front()?;
+        self.evictions += 1;
+        if self.evictions >= self.capacity {
+            self.evictions = 0;
+            let mut state = MeanAccumulator::default();
+            for &x in &self.window {
+                // samples were checked when pushed
+                let _ = state.push(x);
+            }
+            self.state = state;
+        } else {
+            self.state.remove(oldest);
+        }
+        Some(oldest)
+    }
+
+    ///
+    /// Remove all samples from the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.state = MeanAccumulator::default();
+        self.evictions = 0;
+    }
+
+    ///
+    /// Maximum number of samples in the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    ///
+    /// Number of samples currently in the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.window.len()
+    }
+
+    ///
+    /// Iterate over the samples in the window, from the oldest to the most recent
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = &F> {
+        self.window.iter()
+    }
+
+    ///
+    /// Mean of the samples in the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        self.state.mean()
+    }
+
+    ///
+    /// Unbiased variance of the samples in the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn variance(&self) -> F {
+        self.state.variance()
+    }
+
+    ///
+    /// Confidence interval of the mean of the samples currently in the window
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the window holds less than two samples
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.state.ci(confidence)
+    }
+}
+
+///
+/// Accumulator for an exponentially weighted moving average (EWMA) with an approximate confidence interval.
+///
+/// Each new sample receives a weight of 1 while the weights of all previous samples decay by a factor \\( 1-\alpha \\),
+/// where \\( \alpha \in (0, 1] \\) is the smoothing factor. The sample of age \\( k \\) hence has weight \\( (1-\alpha)^k \\).
+/// The confidence interval is based on the weighted mean, the (reliability-weighted) unbiased variance, and the
+/// effective sample size implied by the weights (Kish):
+/// \\[
+/// n_{\text{eff}} = \frac{\left(\sum_i w_i\right)^2}{\sum_i w_i^2}
+/// \\]
+/// which converges to \\( \frac{2-\alpha}{\alpha} \\) as the number of samples grows.
+/// The interval is then computed as for an arithmetic mean of \\( n_{\text{eff}} \\) samples.
+///
+/// # Notes
+///
+/// The interval is approximate: it is valid when the mean of the process is stable over a horizon of a
+/// few \\( n_{\text{eff}} \\) samples, and is otherwise best interpreted as a measure of the precision of the
+/// recency-weighted estimate.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut ewma = incremental::EwmaAccumulator::new(0.1);
+/// for i in 0..1000 {
+///     ewma.push(10. + (i % 5) as f64)?;
+/// }
+/// assert_abs_diff_eq!(ewma.effective_sample_size(), 19., epsilon = 1e-6);
+/// let ci = ewma.ci(confidence)?;
+/// assert!(ci.contains(&12.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * [Wikipedia - Exponential smoothing](https://en.wikipedia.org/wiki/Exponential_smoothing)
+/// * [Wikipedia - Effective sample size](https://en.wikipedia.org/wiki/Effective_sample_size)
+/// * D.H.D. West. "Updating mean and variance estimates: an improved method." Communications of the ACM 22(9): 532-535 (1979).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EwmaAccumulator<F: Float> {
+    alpha: F,
+    count: usize,
+    mean: F,
+    sum_sq_dev: F,
+    sum_weights: F,
+    sum_sq_weights: F,
+}
+
+impl<F: Float> EwmaAccumulator<F> {
+    ///
+    /// Create a new empty accumulator with the given smoothing factor
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - the smoothing factor (must be in (0, 1]); larger values give more weight to recent samples
+    ///
+    /// # Panics
+    ///
+    /// * if the smoothing factor is not in (0, 1]
+    ///
+    pub fn new(alpha: F) -> Self {
+        if !(alpha > F::zero() && alpha <= F::one()) {
+            panic!("Smoothing factor must be in (0, 1].")
+        }
+        Self {
+            alpha,
+            count: 0,
+            mean: F::zero(),
+            sum_sq_dev: F::zero(),
+            sum_weights: F::zero(),
+            sum_sq_weights: F::zero(),
+        }
+    }
+
+    ///
+    /// Create a new empty accumulator where the weight of a sample halves every `half_life` samples
+    ///
+    /// The smoothing factor is then \\( \alpha = 1 - 2^{-1/h} \\) where \\( h \\) is the half-life.
+    ///
+    /// # Panics
+    ///
+    /// * if the half-life is not strictly positive
+    ///
+    pub fn with_half_life(half_life: F) -> Self {
+        if half_life.is_nan() || half_life <= F::zero() {
+            panic!("Half-life must be strictly positive.")
+        }
+        let two = F::one() + F::one();
+        Self::new(F::one() - two.powf(-half_life.recip()))
+    }
+
+    ///
+    /// Append a new sample to the accumulator
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite (NaN or infinite); the accumulator is left unchanged
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<()> {
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        let decay = F::one() - self.alpha;
+        self.count += 1;
+        self.sum_weights = decay * self.sum_weights + F::one();
+        self.sum_sq_weights = decay * decay * self.sum_sq_weights + F::one();
+        let delta = x - self.mean;
+        self.mean = self.mean + delta / self.sum_weights;
+        self.sum_sq_dev = decay * self.sum_sq_dev + delta * (x - self.mean);
+        Ok(())
+    }
+
+    ///
+    /// Append several samples to the accumulator, from the oldest to the most recent
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite; the samples before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for &x_i in data {
+            self.push(x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Smoothing factor of the accumulator
+    ///
+    pub fn alpha(&self) -> F {
+        self.alpha
+    }
+
+    ///
+    /// Number of samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Exponentially weighted mean of the samples (zero if no sample has been appended)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        self.mean
+    }
+
+    ///
+    /// Exponentially weighted unbiased variance of the samples (using reliability weights)
+    /// \\( \frac{\sum_i w_i (x_i - \bar{x})^2}{\sum_i w_i - \sum_i w_i^2 / \sum_i w_i} \\)
+    ///
+    /// The result is NaN if less than two samples have been appended.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn variance(&self) -> F {
+        if self.count < 2 || self.alpha == F::one() {
+            return F::nan();
+        }
+        self.sum_sq_dev / (self.sum_weights - self.sum_sq_weights / self.sum_weights)
+    }
+
+    ///
+    /// Effective sample size implied by the weights
+    /// \\( n_{\text{eff}} = \left(\sum_i w_i\right)^2 / \sum_i w_i^2 \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn effective_sample_size(&self) -> F {
+        if self.count == 0 {
+            return F::zero();
+        }
+        self.sum_weights * self.sum_weights / self.sum_sq_weights
+    }
+
+    ///
+    /// Approximate confidence interval of the exponentially weighted mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended, or if the smoothing factor is 1
+    ///   (in which case the effective sample size is 1)
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        if self.count < 2 || self.alpha == F::one() {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let n_eff = self.effective_sample_size();
+        let std_err_mean = self.variance().sqrt() / n_eff.sqrt();
+        let (lo, hi) =
+            stats::float_interval_bounds(confidence, self.mean, std_err_mean, n_eff - F::one())?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+impl<F: Float> Statistic<F> for MeanAccumulator<F> {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<F>> {
+        Ok(CiEstimate::new(
+            self.mean(),
+            self.ci(confidence)?,
+            confidence,
+            self.n(),
+            self.sem(),
+        ))
+    }
+}
+
+impl<F: Float> mean::StatisticsOps<F> for MeanAccumulator<F> {
+    #[inline]
+    fn append(&mut self, x: F) -> CIResult<()> {
+        self.push(x)
+    }
+    #[inline]
+    fn sample_mean(&self) -> F {
+        self.mean()
+    }
+    #[inline]
+    fn sample_sem(&self) -> F {
+        self.sem()
+    }
+    #[inline]
+    fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci(confidence)
+    }
+    #[inline]
+    fn sample_count(&self) -> usize {
+        self.n()
+    }
+    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut acc = Self::new();
+        acc.extend(data)?;
+        acc.ci(confidence)
+    }
+}
+
+impl<F: Float> core::ops::Add for MeanAccumulator<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.merge(&rhs);
+        self
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for MeanAccumulator<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
+
+impl<F: Float> core::ops::Add for GeometricAccumulator<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.merge(&rhs);
+        self
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for GeometricAccumulator<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
+
+impl<F: Float> core::ops::Add for HarmonicAccumulator<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.merge(&rhs);
+        self
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for HarmonicAccumulator<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const DATA: [f64; 30] = [
+        10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7, 0.4,
+        10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+    ];
+
+    #[test]
+    fn test_mean_accumulator() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut acc = MeanAccumulator::new();
+        assert!(matches!(acc.ci(confidence), Err(CIError::TooFewSamples(0))));
+        acc.push(DATA[0])?;
+        assert!(matches!(acc.ci(confidence), Err(CIError::TooFewSamples(1))));
+        acc.extend(&DATA[1..].to_vec())?;
+
+        let stats = mean::Arithmetic::from_iter(&DATA)?;
+        assert_eq!(acc.n(), stats.sample_count());
+        assert_abs_diff_eq!(acc.mean(), stats.sample_mean(), epsilon = 1e-10);
+        assert_abs_diff_eq!(acc.variance(), stats.sample_variance(), epsilon = 1e-10);
+        // reference values from the README
+        assert_abs_diff_eq!(
+            acc.ci(confidence)?,
+            Interval::new(6.18467, 13.34866)?,
+            epsilon = 1e-5
+        );
+        for confidence in [
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+            Confidence::new_two_sided(0.99),
+        ] {
+            assert_abs_diff_eq!(
+                acc.ci(confidence)?,
+                stats.ci_mean(confidence)?,
+                epsilon = 1e-10
+            );
+        }
+
+        // invalid data is rejected and leaves the accumulator unchanged
+        let before = acc;
+        assert!(matches!(acc.push(f64::NAN), Err(CIError::InvalidInputData)));
+        assert!(acc.push(f64::INFINITY).is_err());
+        assert_eq!(acc, before);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mean_accumulator_stability() -> CIResult<()> {
+        // large offset with small variance: naive sums of squares lose all precision
+        let mut acc = MeanAccumulator::new();
+        for i in 0..10_000 {
+            acc.push(1e9 + (i % 2) as f64)?;
+        }
+        assert_abs_diff_eq!(acc.mean(), 1e9 + 0.5, epsilon = 1e-6);
+        assert_abs_diff_eq!(acc.variance(), 0.25 * 10_000. / 9_999., epsilon = 1e-6);
+
+        let mut acc = MeanAccumulator::<f32>::new();
+        acc.extend(&[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.])?;
+        let ci = acc.ci(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geometric_harmonic_accumulators() -> CIResult<()> {
+        let mut geometric = GeometricAccumulator::new();
+        let mut harmonic = HarmonicAccumulator::new();
+        geometric.extend(&DATA)?;
+        harmonic.extend(&DATA)?;
+        let stats_geo = mean::Geometric::from_iter(&DATA)?;
+        let stats_harm = mean::Harmonic::from_iter(&DATA)?;
+        assert_eq!(geometric.n(), 30);
+        assert_eq!(harmonic.n(), 30);
+        assert_abs_diff_eq!(geometric.mean(), stats_geo.sample_mean(), epsilon = 1e-10);
+        assert_abs_diff_eq!(harmonic.mean(), stats_harm.sample_mean(), epsilon = 1e-10);
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+        ] {
+            assert_abs_diff_eq!(
+                geometric.ci(confidence)?,
+                stats_geo.ci_mean(confidence)?,
+                epsilon = 1e-10
+            );
+            assert_abs_diff_eq!(
+                harmonic.ci(confidence)?,
+                stats_harm.ci_mean(confidence)?,
+                epsilon = 1e-10
+            );
+        }
+
+        assert!(matches!(
+            geometric.push(0.),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            harmonic.push(-1.),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            geometric.push(f64::INFINITY),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            harmonic.push(f64::INFINITY),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(harmonic.push(f64::NAN).is_err());
+        assert_eq!(geometric.n(), 30);
+        assert_eq!(harmonic.n(), 30);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut whole = MeanAccumulator::new();
+        whole.extend(&DATA)?;
+
+        // merging partial states in any split gives the same state
+        for split in 0..=DATA.len() {
+            let mut left = MeanAccumulator::new();
+            left.extend(&DATA[..split].to_vec())?;
+            let mut right = MeanAccumulator::new();
+            right.extend(&DATA[split..].to_vec())?;
+            let merged = left + right;
+            assert_eq!(merged.n(), whole.n());
+            assert_abs_diff_eq!(merged.mean(), whole.mean(), epsilon = 1e-10);
+            assert_abs_diff_eq!(merged.variance(), whole.variance(), epsilon = 1e-10);
+            assert_abs_diff_eq!(
+                merged.ci(confidence)?,
+                whole.ci(confidence)?,
+                epsilon = 1e-10
+            );
+        }
+
+        // merging many small shards with a large offset
+        let data = (0..10_000)
+            .map(|i| 1e9 + (i % 10) as f64)
+            .collect::<Vec<_>>();
+        let mut whole = MeanAccumulator::new();
+        whole.extend(&data)?;
+        let mut merged = MeanAccumulator::new();
+        for chunk in data.chunks(7) {
+            let mut shard = MeanAccumulator::new();
+            shard.extend(&chunk.to_vec())?;
+            merged += shard;
+        }
+        assert_eq!(merged.n(), whole.n());
+        assert_abs_diff_eq!(whole.mean(), 1e9 + 4.5, epsilon = 1e-7);
+        assert_abs_diff_eq!(merged.mean(), 1e9 + 4.5, epsilon = 1e-7);
+        // variance of 0..10 repeated: 8.25 (population), corrected for n-1
+        assert_abs_diff_eq!(whole.variance(), 8.25 * 10_000. / 9_999., epsilon = 1e-6);
+        assert_abs_diff_eq!(merged.variance(), whole.variance(), epsilon = 1e-6);
+
+        let mut geometric = GeometricAccumulator::new();
+        geometric.extend(&DATA[..10].to_vec())?;
+        let mut rest = GeometricAccumulator::new();
+        rest.extend(&DATA[10..].to_vec())?;
+        geometric += rest;
+        let mut harmonic = HarmonicAccumulator::new();
+        harmonic.extend(&DATA[..10].to_vec())?;
+        let mut rest = HarmonicAccumulator::new();
+        rest.extend(&DATA[10..].to_vec())?;
+        harmonic.merge(&rest);
+        assert_abs_diff_eq!(
+            geometric.ci(confidence)?,
+            mean::Geometric::ci(confidence, &DATA)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            harmonic.ci(confidence)?,
+            mean::Harmonic::ci(confidence, &DATA)?,
+            epsilon = 1e-10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_windowed() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut window = WindowedAccumulator::new(10);
+        assert_eq!(window.capacity(), 10);
+        assert!(window.ci(confidence).is_err());
+        assert_eq!(window.pop_oldest(), None);
+
+        for (i, &x) in DATA.iter().enumerate() {
+            let evicted = window.push(x)?;
+            if i < 10 {
+                assert_eq!(evicted, None);
+            } else {
+                assert_eq!(evicted, Some(DATA[i - 10]));
+            }
+            let lo = (i + 1).saturating_sub(10);
+            let expected = DATA[lo..=i].to_vec();
+            assert_eq!(window.n(), expected.len());
+            assert!(window.iter().eq(expected.iter()));
+            let stats = mean::Arithmetic::from_iter(&expected)?;
+            assert_abs_diff_eq!(window.mean(), stats.sample_mean(), epsilon = 1e-10);
+            if expected.len() > 1 {
+                assert_abs_diff_eq!(window.variance(), stats.sample_variance(), epsilon = 1e-10);
+                assert_abs_diff_eq!(
+                    window.ci(confidence)?,
+                    stats.ci_mean(confidence)?,
+                    epsilon = 1e-10
+                );
+            }
+        }
+
+        // explicit eviction down to an empty window
+        for &x in &DATA[20..] {
+            assert_eq!(window.pop_oldest(), Some(x));
+        }
+        assert_eq!(window.n(), 0);
+        assert_eq!(window.pop_oldest(), None);
+
+        assert!(window.push(f64::NAN).is_err());
+        assert_eq!(window.n(), 0);
+        window.extend(&[1., 2., 3.])?;
+        window.clear();
+        assert_eq!(window.n(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_windowed_drift() -> CIResult<()> {
+        // long streams with large offsets do not accumulate rounding errors
+        let mut window = WindowedAccumulator::new(100);
+        for i in 0..100_000 {
+            window.push(1e9 + ((i * 7919) % 100) as f64)?;
+        }
+        let stats =
+            mean::Arithmetic::from_iter(&window.iter().map(|x| x - 1e9).collect::<Vec<_>>())?;
+        assert_abs_diff_eq!(window.mean() - 1e9, stats.sample_mean(), epsilon = 1e-6);
+        assert_abs_diff_eq!(window.variance(), stats.sample_variance(), epsilon = 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windowed_zero_capacity() {
+        WindowedAccumulator::<f64>::new(0);
+    }
+
+    #[test]
+    fn test_ewma() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let alpha = 0.2;
+        let mut ewma = EwmaAccumulator::new(alpha);
+        assert!(ewma.ci(confidence).is_err());
+        ewma.extend(&DATA)?;
+        assert_eq!(ewma.n(), DATA.len());
+
+        // direct computation with explicit weights
+        let n = DATA.len();
+        let weights = (0..n)
+            .map(|i| (1. - alpha).powi((n - 1 - i) as i32))
+            .collect::<Vec<_>>();
+        let sum_w: f64 = weights.iter().sum();
+        let sum_w2: f64 = weights.iter().map(|w| w * w).sum();
+        let mean = DATA.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>() / sum_w;
+        let sum_sq_dev = DATA
+            .iter()
+            .zip(&weights)
+            .map(|(x, w)| w * (x - mean) * (x - mean))
+            .sum::<f64>();
+        let variance = sum_sq_dev / (sum_w - sum_w2 / sum_w);
+        let n_eff = sum_w * sum_w / sum_w2;
+        assert_abs_diff_eq!(ewma.mean(), mean, epsilon = 1e-10);
+        assert_abs_diff_eq!(ewma.variance(), variance, epsilon = 1e-10);
+        assert_abs_diff_eq!(ewma.effective_sample_size(), n_eff, epsilon = 1e-10);
+
+        let sem = (variance / n_eff).sqrt();
+        let t = distributions::t_value(confidence, n_eff - 1.)?;
+        assert_abs_diff_eq!(
+            ewma.ci(confidence)?,
+            Interval::new(mean - t * sem, mean + t * sem)?,
+            epsilon = 1e-10
+        );
+        let upper = ewma.ci(Confidence::new_upper(0.975))?;
+        assert_abs_diff_eq!(upper.low_f(), mean - t * sem, epsilon = 1e-10);
+
+        // asymptotic effective sample size
+        for _ in 0..1000 {
+            ewma.push(1.)?;
+        }
+        assert_abs_diff_eq!(ewma.effective_sample_size(), 9., epsilon = 1e-10);
+
+        // invalid data leaves the state unchanged
+        let before = ewma;
+        assert!(ewma.push(f64::NAN).is_err());
+        assert_eq!(ewma, before);
+
+        // half-life
+        let ewma = EwmaAccumulator::<f64>::with_half_life(10.);
+        assert_abs_diff_eq!((1. - ewma.alpha()).powi(10), 0.5, epsilon = 1e-12);
+
+        // no smoothing: only the last sample matters
+        let mut ewma = EwmaAccumulator::new(1.);
+        ewma.extend(&[1., 2., 3.])?;
+        assert_eq!(ewma.mean(), 3.);
+        assert!(ewma.ci(confidence).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ewma_invalid_alpha() {
+        EwmaAccumulator::<f64>::new(0.);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() -> CIResult<()> {
+        let mut acc = MeanAccumulator::new();
+        acc.extend(&DATA)?;
+        let serialized = toml::to_string(&acc).unwrap();
+        let deserialized: MeanAccumulator<f64> = toml::from_str(&serialized).unwrap();
+        assert_eq!(acc, deserialized);
+        Ok(())
+    }
+}
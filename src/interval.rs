@@ -11,6 +11,8 @@ use core::ops::{RangeFrom, RangeInclusive, RangeToInclusive};
 use num_traits::float::FloatCore;
 use num_traits::Num;
 
+use crate::error::ConversionError;
+
 /// Interval over a partially ordered type (NB: floating point numbers are only partially ordered because of `NaN` values).
 /// The interval is defined by its lower and upper bounds. One-sided intervals (with a single concrete bound) are also supported.
 /// In this crate, intervals are considered inclusive of their (finite) bounds.
@@ -156,6 +158,17 @@ use num_traits::Num;
 /// # Ok::<(),stats_ci::error::IntervalError>(())
 /// ```
 ///
+/// ## Serialization
+///
+/// With the feature `serde`, intervals are serialized with the name of the variant as a key,
+/// followed by the bounds, e.g., in JSON:
+/// ```text
+/// {"TwoSided":[2.0,4.0]}
+/// {"UpperOneSided":2.0}
+/// {"LowerOneSided":3.0}
+/// ```
+/// This representation is part of the public API and is kept stable across versions.
+///
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Interval<T>
@@ -441,6 +454,82 @@ impl<T: num_traits::Float> Interval<T> {
         }
     }
 
+    ///
+    /// Compute the midpoint of the interval, i.e., the center between its bounds.
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(48., 59.)?;
+    /// assert_eq!(interval.midpoint(), Some(53.5));
+    /// assert_eq!(interval.radius(), Some(5.5));
+    /// assert_eq!(interval.width(), Some(11.));
+    /// assert_eq!(interval.relative_width(), Some(11. / 53.5));
+    /// assert_eq!(Interval::new_upper(48.).midpoint(), None);
+    ///
+    /// // stopping rule: the half-width is under 2% of the mean
+    /// let interval = Interval::new(99., 101.)?;
+    /// assert!(interval.relative_width().unwrap() / 2. < 0.02);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn midpoint(&self) -> Option<T> {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            &Interval::TwoSided(low, high) => Some(low + (high - low) / (T::one() + T::one())),
+        }
+    }
+
+    ///
+    /// Compute the radius of the interval, i.e., half of its width (also known as the margin of error).
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    pub fn radius(&self) -> Option<T> {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            &Interval::TwoSided(low, high) => Some((high - low) / (T::one() + T::one())),
+        }
+    }
+
+    ///
+    /// Format the interval as its midpoint plus or minus its radius (e.g., `53.67 ± 5.58`) rather than with its
+    /// bounds in brackets. One-sided intervals have no midpoint and are formatted with brackets (e.g., `[48.09,->)`).
+    ///
+    /// The format options (width, precision, sign, etc.) are applied to each of the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(48.0912, 59.2544)?;
+    /// assert_eq!(format!("{:.2}", interval.display_pm()), "53.67 ± 5.58");
+    /// assert_eq!(format!("{:.1}", Interval::new_upper(48.0912).display_pm()), "[48.1,->)");
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn display_pm(&self) -> impl Display + '_
+    where
+        T: Display,
+    {
+        PlusMinus(self)
+    }
+
+    ///
+    /// Compute the width of the interval relative to the absolute value of its midpoint,
+    /// i.e., \( (b - a) / |(a + b) / 2| \) for the interval \( [a, b] \).
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    /// If the midpoint is zero, the relative width is infinite (or `NaN` if the width is also zero).
+    ///
+    pub fn relative_width(&self) -> Option<T> {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            &Interval::TwoSided(low, high) => Some((high - low) / self.midpoint()?.abs()),
+        }
+    }
+
     ///
     /// Given two intervals, compute the relative interval compared to the reference (argument).
     /// The relative interval is defined as the interval of the ratios of the two intervals.
@@ -546,23 +635,95 @@ impl<T: PartialOrd> Interval<T> {
         self.right()
     }
 }
-impl<T: PartialOrd + Copy> Interval<T> {
-    fn applied<F>(&self, f_low: F, f_high: F) -> Self
+impl<T: PartialOrd> Interval<T> {
+    ///
+    /// Transform the bounds of the interval with a monotonically non-decreasing function,
+    /// e.g., to convert an interval from seconds to milliseconds or from log space to linear space.
+    /// The kind of the interval (two-sided, upper or lower one-sided) is preserved.
+    ///
+    /// The function must be non-decreasing for the result to be a valid interval;
+    /// use [`Self::map_antitone`] for non-increasing functions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let log_interval = Interval::new(0., 2.)?;
+    /// let interval = log_interval.map(f64::exp);
+    /// assert_eq!(interval, Interval::new(1., 2_f64.exp())?);
+    /// let seconds = Interval::new_upper(1.5);
+    /// assert_eq!(seconds.map(|s| s * 1000.), Interval::new_upper(1500.));
+    /// let ranks = Interval::new(2, 5)?.map(|i| ["a", "b", "c", "d", "e", "f"][i]);
+    /// assert_eq!(ranks, Interval::new("c", "f")?);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn map<U, F>(self, mut f: F) -> Interval<U>
+    where
+        U: PartialOrd,
+        F: FnMut(T) -> U,
+    {
+        match self {
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(low), f(high)),
+            Interval::UpperOneSided(low) => Interval::UpperOneSided(f(low)),
+            Interval::LowerOneSided(high) => Interval::LowerOneSided(f(high)),
+        }
+    }
+
+    ///
+    /// Transform the bounds of the interval with a monotonically non-increasing function,
+    /// e.g., to convert an interval on a rate into an interval on a mean time between events.
+    /// The bounds are swapped, so that an upper one-sided interval becomes a lower one-sided interval
+    /// and vice versa.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let rate = Interval::new(2., 4.)?;
+    /// assert_eq!(rate.map_antitone(|x| 1. / x), Interval::new(0.25, 0.5)?);
+    /// let rate = Interval::new_upper(2.);
+    /// assert_eq!(rate.map_antitone(|x| 1. / x), Interval::new_lower(0.5));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn map_antitone<U, F>(self, mut f: F) -> Interval<U>
     where
-        F: FnOnce(T) -> T,
+        U: PartialOrd,
+        F: FnMut(T) -> U,
     {
         match self {
-            Interval::TwoSided(low, high) => Interval::TwoSided(f_low(*low), f_high(*high)),
-            Interval::LowerOneSided(low) => Interval::UpperOneSided(f_low(*low)),
-            Interval::UpperOneSided(high) => Interval::LowerOneSided(f_high(*high)),
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(high), f(low)),
+            Interval::UpperOneSided(low) => Interval::LowerOneSided(f(low)),
+            Interval::LowerOneSided(high) => Interval::UpperOneSided(f(high)),
         }
     }
 
-    fn applied_both<F>(&self, f: F) -> Self
+    ///
+    /// Transform the bounds of the interval with a fallible monotonically non-decreasing function
+    /// (see [`Self::map`]). The first error returned by the function is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(1_000_u64, 3_000)?;
+    /// let narrow = interval.try_map(u16::try_from)?;
+    /// assert_eq!(narrow, Interval::new(1_000_u16, 3_000)?);
+    /// assert!(Interval::new(1_000_u64, 100_000)?.try_map(u16::try_from).is_err());
+    /// # Ok::<(),Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<Interval<U>, E>
     where
-        F: Fn(T) -> T,
+        U: PartialOrd,
+        F: FnMut(T) -> Result<U, E>,
     {
-        self.applied(&f, &f)
+        Ok(match self {
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(low)?, f(high)?),
+            Interval::UpperOneSided(low) => Interval::UpperOneSided(f(low)?),
+            Interval::LowerOneSided(high) => Interval::LowerOneSided(f(high)?),
+        })
     }
 }
 
@@ -644,19 +805,27 @@ where
     }
 }
 
-impl<F: Mul<F, Output = F> + PartialOrd + Copy> Mul<F> for Interval<F> {
+impl<F: Mul<F, Output = F> + num_traits::Zero + PartialOrd + Copy> Mul<F> for Interval<F> {
     type Output = Self;
 
     fn mul(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x * rhs)
+        if rhs < F::zero() {
+            self.map_antitone(|x| x * rhs)
+        } else {
+            self.map(|x| x * rhs)
+        }
     }
 }
 
-impl<F: Div<F, Output = F> + PartialOrd + Copy> Div<F> for Interval<F> {
+impl<F: Div<F, Output = F> + num_traits::Zero + PartialOrd + Copy> Div<F> for Interval<F> {
     type Output = Self;
 
     fn div(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x / rhs)
+        if rhs < F::zero() {
+            self.map_antitone(|x| x / rhs)
+        } else {
+            self.map(|x| x / rhs)
+        }
     }
 }
 
@@ -664,7 +833,7 @@ impl<F: Add<F, Output = F> + PartialOrd + Copy> Add<F> for Interval<F> {
     type Output = Self;
 
     fn add(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x + rhs)
+        self.map(|x| x + rhs)
     }
 }
 
@@ -672,7 +841,7 @@ impl<F: Sub<F, Output = F> + PartialOrd + Copy> Sub<F> for Interval<F> {
     type Output = Self;
 
     fn sub(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x - rhs)
+        self.map(|x| x - rhs)
     }
 }
 
@@ -680,7 +849,7 @@ impl<F: Neg<Output = F> + PartialOrd + Copy> Neg for Interval<F> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        self.applied_both(|x| -x)
+        self.map_antitone(|x| -x)
     }
 }
 
@@ -878,6 +1047,74 @@ impl<T: PartialOrd> From<RangeToInclusive<T>> for Interval<T> {
     }
 }
 
+impl<T: PartialOrd> TryFrom<Interval<T>> for RangeInclusive<T> {
+    type Error = ConversionError;
+
+    ///
+    /// Convert a two-sided interval into an inclusive range.
+    /// One-sided intervals cannot be converted and return an error
+    /// (see the conversion into a pair of [`Bound`]s for the general case).
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let range = std::ops::RangeInclusive::try_from(Interval::new(1, 5)?)?;
+    /// assert_eq!(range, 1..=5);
+    /// assert!(std::ops::RangeInclusive::try_from(Interval::new_upper(1)).is_err());
+    /// # Ok::<(),Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    fn try_from(interval: Interval<T>) -> Result<Self, Self::Error> {
+        match interval {
+            Interval::TwoSided(low, high) => Ok(low..=high),
+            _ => Err(ConversionError::SingleBoundError),
+        }
+    }
+}
+
+impl<T: PartialOrd> From<Interval<T>> for (Bound<T>, Bound<T>) {
+    ///
+    /// Convert an interval into a pair of bounds, which can be used as an owned range
+    /// (e.g., to query a [`BTreeMap`](std::collections::BTreeMap) or to slice a [`Vec`]).
+    /// Concrete bounds are included and the missing bound of a one-sided interval is unbounded.
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::ops::Bound;
+    /// let bounds: (Bound<usize>, Bound<usize>) = Interval::new_upper(3).into();
+    /// assert_eq!(bounds, (Bound::Included(3), Bound::Unbounded));
+    /// let data = [1, 2, 3, 4, 5, 6];
+    /// assert_eq!(data[bounds], [4, 5, 6]);
+    /// ```
+    ///
+    fn from(interval: Interval<T>) -> Self {
+        match interval {
+            Interval::TwoSided(low, high) => (Bound::Included(low), Bound::Included(high)),
+            Interval::UpperOneSided(low) => (Bound::Included(low), Bound::Unbounded),
+            Interval::LowerOneSided(high) => (Bound::Unbounded, Bound::Included(high)),
+        }
+    }
+}
+
+///
+/// Intervals are ranges with included bounds: a two-sided interval behaves as an inclusive range `low..=high`,
+/// an upper one-sided interval as `low..`, and a lower one-sided interval as `..=high`.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use std::collections::BTreeMap;
+/// use std::ops::RangeBounds;
+/// let events = BTreeMap::from([(1, "a"), (3, "b"), (5, "c"), (7, "d")]);
+/// let interval = Interval::new(3, 5)?;
+/// let selected: Vec<_> = events.range(interval).map(|(_, &v)| v).collect();
+/// assert_eq!(selected, ["b", "c"]);
+/// let selected: Vec<_> = events.range(Interval::new_lower(3)).map(|(_, &v)| v).collect();
+/// assert_eq!(selected, ["a", "b"]);
+/// assert!(RangeBounds::contains(&interval, &5));
+/// # Ok::<(),error::IntervalError>(())
+/// ```
+///
 impl<T: PartialOrd> RangeBounds<T> for Interval<T> {
     fn start_bound(&self) -> Bound<&T> {
         match self.left() {
@@ -888,18 +1125,33 @@ impl<T: PartialOrd> RangeBounds<T> for Interval<T> {
 
     fn end_bound(&self) -> Bound<&T> {
         match self.right() {
-            Some(high) => Bound::Excluded(high),
+            Some(high) => Bound::Included(high),
             None => Bound::Unbounded,
         }
     }
 }
 
-impl<T: PartialOrd + Sub<Output = T> + num_traits::Zero + Clone> Interval<T> {
+impl<T: PartialOrd + Sub + Clone> Interval<T> {
     ///
     /// Compute the width of the interval.
     /// If the interval is one-sided, the function returns `None`.
     ///
-    pub fn width(&self) -> Option<T> {
+    /// The width has the type of the difference between two bounds, which can differ from the type of the bounds,
+    /// e.g., the width of an interval over timestamps (such as `time::OffsetDateTime` or `chrono::DateTime`) is a duration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::time::{Duration, Instant};
+    /// assert_eq!(Interval::new(2., 5.)?.width(), Some(3.));
+    /// let start = Instant::now();
+    /// let interval = Interval::new(start, start + Duration::from_secs(2))?;
+    /// assert_eq!(interval.width(), Some(Duration::from_secs(2)));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn width(&self) -> Option<T::Output> {
         match self {
             Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
             Interval::TwoSided(low, high) => Some(high.clone() - low.clone()),
@@ -920,12 +1172,73 @@ impl<T: PartialOrd + Clone> Clone for Interval<T> {
 impl<T: PartialOrd + Copy> Copy for Interval<T> {}
 
 use core::fmt::Display;
+///
+/// Formats the interval with its bounds in brackets (e.g., `[48.09, 59.25]`, or `[48.09,->)` for a one-sided interval).
+/// Intervals over floating point numbers can also be formatted as their midpoint plus or minus their radius
+/// with [`Interval::display_pm`].
+///
+/// The format options (width, precision, sign, etc.) are applied to each of the values.
+///
+/// # Notes
+///
+/// The alternate flag `{:#}` does not select the midpoint form, which would require distinguishing floating point
+/// bounds within this implementation for all types of bounds (including borrowed ones, such as `&str`).
+/// It formats the interval with brackets, like `{}`; use [`Interval::display_pm`] for the midpoint form.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let interval = Interval::new(48.0912, 59.2544)?;
+/// assert_eq!(format!("{}", interval), "[48.0912, 59.2544]");
+/// assert_eq!(format!("{:.2}", interval), "[48.09, 59.25]");
+/// assert_eq!(format!("{:6.1}", interval), "[  48.1,   59.3]");
+/// assert_eq!(format!("{:#.2}", interval), "[48.09, 59.25]");
+/// assert_eq!(format!("{:.2}", interval.display_pm()), "53.67 ± 5.58");
+/// assert_eq!(format!("{:.1}", Interval::new_upper(48.0912)), "[48.1,->)");
+/// assert_eq!(format!("{:.1}", Interval::new_lower(59.2544)), "(<-,59.3]");
+/// assert_eq!(format!("{}", Interval::new("E", "L")?), "[E, L]");
+/// # Ok::<(),error::IntervalError>(())
+/// ```
+///
 impl<T: PartialOrd + Display> Display for Interval<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Interval::TwoSided(low, high) => write!(f, "[{}, {}]", low, high),
-            Interval::UpperOneSided(low) => write!(f, "[{},->)", low),
-            Interval::LowerOneSided(high) => write!(f, "(<-,{}]", high),
+            Interval::TwoSided(low, high) => {
+                f.write_str("[")?;
+                low.fmt(f)?;
+                f.write_str(", ")?;
+                high.fmt(f)?;
+                f.write_str("]")
+            }
+            Interval::UpperOneSided(low) => {
+                f.write_str("[")?;
+                low.fmt(f)?;
+                f.write_str(",->)")
+            }
+            Interval::LowerOneSided(high) => {
+                f.write_str("(<-,")?;
+                high.fmt(f)?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+///
+/// Formats a two-sided interval as its midpoint plus or minus its radius (see [`Interval::display_pm`]).
+///
+struct PlusMinus<'a, T: PartialOrd>(&'a Interval<T>);
+
+impl<T: num_traits::Float + Display> Display for PlusMinus<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.0.midpoint(), self.0.radius()) {
+            (Some(midpoint), Some(radius)) => {
+                midpoint.fmt(f)?;
+                f.write_str(" ± ")?;
+                radius.fmt(f)
+            }
+            _ => self.0.fmt(f),
         }
     }
 }
@@ -1070,7 +1383,7 @@ mod tests {
         let interval = Interval::new_lower(10_i64);
         assert_eq!(interval.low(), None);
         assert_eq!(interval.high(), Some(10));
-        assert_eq!(interval.low_i(), std::i64::MIN);
+        assert_eq!(interval.low_i(), i64::MIN);
         assert_eq!(interval.high_i(), 10);
         assert!(!interval.is_degenerate());
         assert!(!interval.is_two_sided());
@@ -1176,6 +1489,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_range_bounds() {
+        let interval = Interval::new(2, 4).unwrap();
+        assert_eq!(interval.start_bound(), Bound::Included(&2));
+        assert_eq!(interval.end_bound(), Bound::Included(&4));
+        assert!(RangeBounds::contains(&interval, &4));
+        assert!(!RangeBounds::contains(&interval, &5));
+        assert_eq!(RangeInclusive::try_from(interval).unwrap(), 2..=4);
+        let data = [0, 1, 2, 3, 4, 5];
+        let bounds: (Bound<usize>, Bound<usize>) = Interval::new(2, 4).unwrap().into();
+        assert_eq!(data[bounds], [2, 3, 4]);
+
+        let interval = Interval::new_lower(4);
+        assert_eq!(interval.start_bound(), Bound::Unbounded);
+        assert_eq!(interval.end_bound(), Bound::Included(&4));
+        assert!(RangeInclusive::try_from(interval).is_err());
+        let bounds: (Bound<usize>, Bound<usize>) = Interval::new_lower(4).into();
+        assert_eq!(data[bounds], [0, 1, 2, 3, 4]);
+
+        let interval = Interval::new_upper(4);
+        assert_eq!(interval.start_bound(), Bound::Included(&4));
+        assert_eq!(interval.end_bound(), Bound::Unbounded);
+        assert!(RangeInclusive::try_from(interval).is_err());
+    }
+
     #[test]
     fn test_interval_from_range() -> Result<(), IntervalError> {
         let interval = Interval::try_from(0..=3)?;
@@ -1257,6 +1595,25 @@ mod tests {
         assert_eq!(Interval::new(-10, 0)?.width(), Some(10));
         assert_eq!(Interval::new(-10, -10)?.width(), Some(0));
 
+        let interval = Interval::new(-6., -2.)?;
+        assert_eq!(interval.midpoint(), Some(-4.));
+        assert_eq!(interval.radius(), Some(2.));
+        assert_eq!(interval.relative_width(), Some(1.));
+        assert_eq!(
+            Interval::new(-1., 1.)?.relative_width(),
+            Some(f64::INFINITY)
+        );
+        assert!(Interval::new(0., 0.)?.relative_width().unwrap().is_nan());
+        assert_eq!(
+            Interval::new(f64::MAX / 2., f64::MAX)?.midpoint(),
+            Some(f64::MAX * 0.75)
+        );
+        for interval in [Interval::new_upper(1.), Interval::new_lower(1.)] {
+            assert_eq!(interval.midpoint(), None);
+            assert_eq!(interval.radius(), None);
+            assert_eq!(interval.relative_width(), None);
+        }
+
         Ok(())
     }
 
@@ -1291,6 +1648,75 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_map() -> Result<(), IntervalError> {
+        let interval = Interval::new(1.5, 2.5)?;
+        assert_eq!(interval.map(|s| s * 1000.), Interval::new(1500., 2500.)?);
+        assert_eq!(
+            Interval::new_upper(1.).map(f64::ln),
+            Interval::new_upper(0.)
+        );
+        assert_eq!(
+            Interval::new_lower(0.).map(f64::exp),
+            Interval::new_lower(1.)
+        );
+        assert_eq!(
+            Interval::new(2., 4.)?.map_antitone(|x| 1. / x),
+            Interval::new(0.25, 0.5)?
+        );
+        assert_eq!(
+            Interval::new_upper(2.).map_antitone(|x| -x),
+            Interval::new_lower(-2.)
+        );
+        assert_eq!(
+            Interval::new_lower(2.).map_antitone(|x| -x),
+            Interval::new_upper(-2.)
+        );
+        assert_eq!(Interval::new(-1_i64, 300)?.try_map(u8::try_from).ok(), None);
+        assert_eq!(
+            Interval::new_lower(200_i64).try_map(u8::try_from).ok(),
+            Some(Interval::new_lower(200_u8))
+        );
+
+        // arithmetic with scalars preserves the kind of interval
+        assert_eq!(Interval::new_upper(2.) * 3., Interval::new_upper(6.));
+        assert_eq!(Interval::new_lower(2.) + 1., Interval::new_lower(3.));
+        assert_eq!(Interval::new(1., 2.)? * -2., Interval::new(-4., -2.)?);
+        assert_eq!(Interval::new_upper(2.) / -2., Interval::new_lower(-1.));
+        assert_eq!(-Interval::new(1., 2.)?, Interval::new(-2., -1.)?);
+        assert_eq!(-Interval::new_upper(2.), Interval::new_lower(-2.));
+        assert_eq!(Interval::new_lower(2.) * -3., Interval::new_upper(-6.));
+        assert_eq!(Interval::new_upper(4.) / 2., Interval::new_upper(2.));
+        assert_eq!(Interval::new_lower(4.) - 1., Interval::new_lower(3.));
+        assert_eq!(Interval::new(-2., 6.)? / -2., Interval::new(-3., 1.)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<(), IntervalError> {
+        let interval = Interval::new(48.09482399055084, 59.24517600944916)?;
+        assert_eq!(format!("{:.2}", interval), "[48.09, 59.25]");
+        assert_eq!(format!("{:.2}", interval.display_pm()), "53.67 ± 5.58");
+        assert_eq!(format!("{:+.1}", interval), "[+48.1, +59.2]");
+        assert_eq!(
+            format!("{:.3}", Interval::new(1f32, 2f32)?.display_pm()),
+            "1.500 ± 0.500"
+        );
+        assert_eq!(format!("{:>3}", Interval::new(4, 12)?), "[  4,  12]");
+        assert_eq!(
+            format!("{:.2}", Interval::new_upper(48.094).display_pm()),
+            "[48.09,->)"
+        );
+        assert_eq!(format!("{:<4}", Interval::new_lower('L')), "(<-,L   ]");
+        // borrowed bounds
+        let (low, high) = (String::from("E"), String::from("L"));
+        assert_eq!(
+            format!("{}", Interval::new(low.as_str(), high.as_str())?),
+            "[E, L]"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
@@ -7,23 +7,63 @@
 #![warn(missing_docs)]
 #![cfg_attr(all(not(test), not(feature = "std")), no_std)]
 
+#[cfg(any(test, feature = "std"))]
+pub mod agreement;
+#[cfg(any(test, feature = "std"))]
+pub mod anova;
+#[cfg(any(test, feature = "std"))]
+pub mod autocorr;
+#[cfg(any(test, feature = "std"))]
+pub mod classification;
 pub mod comparison;
+#[cfg(any(test, feature = "std"))]
+pub mod correlation;
+pub mod decimal;
+pub mod distributions;
+pub mod duration;
+pub mod effect_size;
 pub mod error;
+pub mod hdi;
+pub mod hypothesis;
+pub mod incremental;
+#[cfg(any(test, feature = "std"))]
+pub mod location;
+#[cfg(any(test, feature = "std"))]
+pub mod mcmc;
 pub mod mean;
+pub mod meta;
+pub mod power;
 pub mod proportion;
 pub mod quantile;
+pub mod rate;
+pub mod ratio;
+pub mod sequential;
+#[cfg(any(test, feature = "std"))]
+pub mod survey;
+#[cfg(any(test, feature = "std"))]
+pub mod survival;
+pub mod tolerance;
 
 pub mod utils;
 
 mod confidence;
+mod estimate;
+mod ext;
 mod interval;
+mod special;
 mod stats;
 
-pub use confidence::Confidence;
+pub use confidence::{Confidence, Correction};
 pub use error::CIResult;
+pub use estimate::{CiEstimate, Statistic};
+pub use ext::StatisticsExt;
 pub use interval::Interval;
 pub use mean::MeanCI;
+#[cfg(feature = "parallel")]
+pub use mean::ParMeanCI;
 pub use mean::StatisticsOps;
+pub use mean::WeightedMeanCI;
+pub use utils::NanPolicy;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,879 @@
+//!
+//! Distribution-free confidence intervals for location
+//!
+//! The functions of this module estimate the location (center) of a population, or the shift between
+//! two populations, without assuming any particular distribution. They are robust alternatives to the
+//! intervals on the mean (see [`mean`]) when the data is skewed or contains outliers.
+//!
+//! * [`hodges_lehmann`] and [`ci_hodges_lehmann`] - the Hodges–Lehmann estimator of the pseudomedian
+//!   (i.e., the median of the pairwise averages) and its confidence interval, derived from the signed-rank statistic.
+//!   Assumes that the distribution is symmetric, in which case the pseudomedian is the median (and the mean, if it exists).
+//! * [`ci_signed_rank`] - the one-sample interval obtained by inverting the Wilcoxon signed-rank test
+//!   (the nonparametric counterpart of the t interval on the mean); it is the same interval as [`ci_hodges_lehmann`].
+//! * [`hodges_lehmann_shift`] and [`ci_shift`] - the shift between two independent samples
+//!   (i.e., the median of the pairwise differences) and its confidence interval obtained by inverting the Mann–Whitney test.
+//!   Assumes that the two distributions have the same shape, up to a shift.
+//! * [`ci_median`] - the interval on the median obtained by inverting the sign test, which only assumes
+//!   that the samples are independent and identically distributed (no symmetry).
+//! * [`ci_median_difference`] - the interval on the difference between the medians of two independent samples,
+//!   using the method of Bonett and Price, which does not assume that the distributions have the same shape.
+//!
+//! Rank statistics use their exact distribution for up to [`EXACT_LIMIT`] samples, and a normal approximation above.
+//!
+//! All functions require a copy of the data and are only available with the `std` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // differences in zinc concentration between bottom and surface water
+//! let data = [0.015, 0.028, 0.177, 0.121, 0.102, 0.107, 0.019, 0.066, 0.058, 0.111];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let estimate = location::hodges_lehmann(&data)?;
+//! let interval = location::ci_hodges_lehmann(confidence, &data)?;
+//! assert_abs_diff_eq!(estimate, 0.08, epsilon = 1e-10);
+//! assert_abs_diff_eq!(interval, Interval::new(0.0405, 0.116)?, epsilon = 1e-10);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// This constant defines the largest number of samples for which the distribution of rank statistics
+/// is computed exactly. Above that size, a normal approximation (with continuity correction) is used instead.
+///
+pub const EXACT_LIMIT: usize = 200;
+
+///
+/// Compute the Hodges–Lehmann estimator of the location of a sample, i.e., the median of the
+/// \\( n(n+1)/2 \\) Walsh averages \\( (x_i + x_j)/2 \\) for \\( i \leq j \\).
+///
+/// The estimator is robust (with a breakdown point of about 29%) while being nearly as efficient as the mean
+/// for normal data (about 95%), and much more efficient than the sample median.
+/// It estimates the pseudomedian of the population, which is the median when the distribution is symmetric.
+///
+/// Complexity: \\( O(n^2) \\) in time and memory, where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `data` - the sample data
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the data is empty
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [1., 2., 3., 4., 100.];
+/// // Walsh averages: 1, 1.5, 2, 2, 2.5, 2.5, 3, 3, 3.5, 4, 50.5, 51, 51.5, 52, 100
+/// assert_eq!(location::hodges_lehmann(&data)?, 3.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * J.L. Hodges and E.L. Lehmann. Estimates of location based on rank tests. The Annals of Mathematical Statistics, 34(2):598-611, 1963.
+/// * [Wikipedia - Hodges–Lehmann estimator](https://en.wikipedia.org/wiki/Hodges%E2%80%93Lehmann_estimator)
+///
+pub fn hodges_lehmann<F, I>(data: &I) -> CIResult<F>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let data = collect_sample(data)?;
+    let mut averages = walsh_averages(&data);
+    Ok(median_mut(&mut averages))
+}
+
+///
+/// Compute the confidence interval on the location of a sample (its pseudomedian) around the
+/// Hodges–Lehmann estimator (see [`hodges_lehmann`]).
+///
+/// This is the interval obtained by inverting the Wilcoxon signed-rank test; see [`ci_signed_rank`] for details.
+///
+/// Complexity: \\( O(n^2) \\) in time and memory, where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the sample data
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are too few samples to reach the confidence level
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Notes
+///
+/// The interval is valid for continuous distributions that are symmetric around their center.
+/// Without symmetry, use the median instead (e.g., [`quantile::ci`]).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [
+///     82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+///     15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = location::ci_hodges_lehmann(confidence, &data)?;
+/// assert_eq!(interval, Interval::new(43.5, 67.)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_hodges_lehmann<F, I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    ci_signed_rank(confidence, data)
+}
+
+///
+/// Compute the confidence interval on the location of a sample by inverting the Wilcoxon signed-rank test.
+///
+/// The interval contains all the values \\( \theta \\) for which the signed-rank test does not reject the hypothesis
+/// that the sample is centered on \\( \theta \\). Its bounds are the \\( d \\)-th smallest and the \\( d \\)-th largest
+/// Walsh averages \\( (x_i + x_j)/2 \\) for \\( i \leq j \\), where \\( d - 1 \\) is the largest value \\( k \\) such that
+/// \\( P(W^+ \leq k) \leq \alpha \\) for the signed-rank statistic \\( W^+ \\), and \\( \alpha \\) is the probability of each tail.
+///
+/// The distribution of \\( W^+ \\) is computed exactly for up to [`EXACT_LIMIT`] samples, so that the coverage
+/// of the interval is guaranteed to be at least the confidence level. For larger samples, it is approximated by
+/// a normal distribution with mean \\( n(n+1)/4 \\) and variance \\( n(n+1)(2n+1)/24 \\), with a continuity correction.
+///
+/// Complexity: \\( O(n^2) \\) in time and memory, where \\( n \\) is the number of samples
+/// (\\( O(n^3) \\) in time for the exact distribution, when \\( n \\) is at most [`EXACT_LIMIT`]).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the sample data
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are too few samples to reach the confidence level
+///   (e.g., at least 6 samples are needed for a two-sided 95% interval)
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Notes
+///
+/// The interval is valid for continuous distributions that are symmetric around their center.
+/// Applied to the differences of paired observations, it gives an interval on the (pseudo)median of the differences.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [0.015, 0.028, 0.177, 0.121, 0.102, 0.107, 0.019, 0.066, 0.058, 0.111];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = location::ci_signed_rank(confidence, &data)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.0405, 0.116)?, epsilon = 1e-10);
+///
+/// // too few samples for a two-sided 95% interval
+/// assert!(location::ci_signed_rank(confidence, &data[..5].to_vec()).is_err());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * M. Hollander, D.A. Wolfe, and E. Chicken. Nonparametric Statistical Methods (3rd edition), Section 3.3. Wiley, 2014.
+/// * [Wikipedia - Wilcoxon signed-rank test](https://en.wikipedia.org/wiki/Wilcoxon_signed-rank_test)
+///
+pub fn ci_signed_rank<F, I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let data = collect_sample(data)?;
+    let depth = signed_rank_depth(confidence, data.len())?;
+    let mut averages = walsh_averages(&data);
+    order_statistics_interval(confidence, &mut averages, depth)
+}
+
+///
+/// Compute the Hodges–Lehmann estimator of the shift between two independent samples, i.e., the median of the
+/// \\( m \times n \\) pairwise differences \\( a_i - b_j \\).
+///
+/// Complexity: \\( O(mn) \\) in time and memory, where \\( m \\) and \\( n \\) are the sizes of the samples.
+///
+/// # Arguments
+///
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if either sample is empty
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data_a = [11., 12., 13., 14., 15.];
+/// let data_b = [1., 2., 3., 4., 5., 1000.];
+/// // the outlier barely affects the estimate (10 without it)
+/// assert_eq!(location::hodges_lehmann_shift(&data_a, &data_b)?, 9.5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn hodges_lehmann_shift<F, Ia, Ib>(data_a: &Ia, data_b: &Ib) -> CIResult<F>
+where
+    F: Float,
+    for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+{
+    let data_a = collect_sample(data_a)?;
+    let data_b = collect_sample(data_b)?;
+    let mut differences = pairwise_differences(&data_a, &data_b);
+    Ok(median_mut(&mut differences))
+}
+
+///
+/// Compute the confidence interval on the shift between two independent samples (i.e., the location of
+/// the first one minus that of the second one) by inverting the Mann–Whitney (Wilcoxon rank-sum) test.
+///
+/// The interval contains all the shifts \\( \Delta \\) for which the Mann–Whitney test does not reject the hypothesis
+/// that the first sample minus \\( \Delta \\) and the second sample come from the same distribution.
+/// Its bounds are the \\( d \\)-th smallest and the \\( d \\)-th largest pairwise differences \\( a_i - b_j \\),
+/// where \\( d - 1 \\) is the largest value \\( k \\) such that \\( P(U \leq k) \leq \alpha \\) for the Mann–Whitney
+/// statistic \\( U \\), and \\( \alpha \\) is the probability of each tail.
+/// The estimate of the shift is given by [`hodges_lehmann_shift`].
+///
+/// The distribution of \\( U \\) is computed exactly when there are at most [`EXACT_LIMIT`] samples in total,
+/// so that the coverage of the interval is guaranteed to be at least the confidence level. Above that, it is approximated by
+/// a normal distribution with mean \\( mn/2 \\) and variance \\( mn(m+n+1)/12 \\), with a continuity correction.
+///
+/// Complexity: \\( O(mn) \\) in time and memory, where \\( m \\) and \\( n \\) are the sizes of the samples
+/// (\\( O(m^2 n) \\) in time for the exact distribution).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are too few samples to reach the confidence level
+///   (e.g., a two-sided 95% interval cannot be computed with samples of sizes 3 and 4)
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Notes
+///
+/// The interval is valid for continuous distributions that differ only by their location.
+/// If the interval is strictly positive (resp. negative), the first sample tends to be significantly
+/// larger (resp. smaller) than the second one.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // latencies (in ms) of two configurations
+/// let config_a = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+/// let config_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = location::ci_shift(confidence, &config_a, &config_b)?;
+/// assert_abs_diff_eq!(interval, Interval::new(1.0, 4.2)?, epsilon = 1e-10);
+/// let shift = location::hodges_lehmann_shift(&config_a, &config_b)?;
+/// assert_abs_diff_eq!(shift, 2.55, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * M. Hollander, D.A. Wolfe, and E. Chicken. Nonparametric Statistical Methods (3rd edition), Section 4.3. Wiley, 2014.
+/// * [Wikipedia - Mann–Whitney U test](https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test)
+///
+pub fn ci_shift<F, Ia, Ib>(
+    confidence: Confidence,
+    data_a: &Ia,
+    data_b: &Ib,
+) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+{
+    let data_a = collect_sample(data_a)?;
+    let data_b = collect_sample(data_b)?;
+    let depth = rank_sum_depth(confidence, data_a.len(), data_b.len())?;
+    let mut differences = pairwise_differences(&data_a, &data_b);
+    order_statistics_interval(confidence, &mut differences, depth)
+}
+
+///
+/// Compute the confidence interval on the median of a sample by inverting the sign test.
+///
+/// The number of samples below the median follows a binomial distribution \\( B(n, 1/2) \\).
+/// The bounds are the \\( d \\)-th smallest and the \\( d \\)-th largest samples, where \\( d - 1 \\) is the largest value
+/// \\( k \\) such that \\( P(B \leq k) \leq \alpha \\), and \\( \alpha \\) is the probability of each tail.
+/// The coverage of the interval is guaranteed to be at least the confidence level, for any number of samples.
+///
+/// Unlike the intervals based on ranks (e.g., [`ci_signed_rank`]), this interval makes no assumption on the shape
+/// of the distribution (e.g., symmetry), only that the samples are independent and identically distributed.
+/// It is the same as the exact interval on the median computed by [`quantile::Stats::ci_exact`], except that the
+/// exact distribution is used regardless of the number of samples.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data` - the sample data
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are too few samples to reach the confidence level
+///   (e.g., at least 6 samples are needed for a two-sided 95% interval)
+///
+/// # Panics
+///
+/// * if the data contains elements that are not comparable (with their partial ordering).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [8, 11, 12, 13, 15, 17, 19, 20, 21, 21, 22, 23, 25, 26, 28];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = location::ci_median(confidence, &data)?;
+/// assert_eq!(interval, Interval::new(13, 23)?);
+///
+/// let confidence = Confidence::new_upper(0.9);
+/// let interval = location::ci_median(confidence, &data)?;
+/// assert_eq!(interval, Interval::new_upper(15));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.J. Conover. Practical Nonparametric Statistics (3rd edition), Section 3.4. Wiley, 1999.
+/// * [Wikipedia - Sign test](https://en.wikipedia.org/wiki/Sign_test)
+///
+pub fn ci_median<T, I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let mut data: Vec<T> = data.into_iter().copied().collect();
+    let depth = sign_test_depth(confidence, data.len())?;
+    order_statistics_interval(confidence, &mut data, depth)
+}
+
+///
+/// Compute the confidence interval on the difference between the medians of two independent samples
+/// (i.e., the median of the first one minus that of the second one), using the method of Bonett and Price.
+///
+/// The interval is \\( \hat{\eta}_a - \hat{\eta}_b \pm z \sqrt{SE_a^2 + SE_b^2} \\), where \\( \hat{\eta} \\) is the
+/// sample median, and its standard error is estimated by Price and Bonett from a pair of order statistics:
+/// \\[
+/// SE = \frac{Y_{(n-c+1)} - Y_{(c)}}{2 z_c}
+/// \\]
+/// with \\( c = \max(1, \mathrm{round}(n/2 - \sqrt{n})) \\), \\( z_c = \Phi^{-1}(1 - p_c) \\), and
+/// \\( p_c = P(B \leq c-1) \\) for \\( B \sim B(n, 1/2) \\).
+///
+/// Unlike [`ci_shift`], the method does not assume that the two distributions have the same shape,
+/// and does not require a resampling procedure like the bootstrap.
+///
+/// Complexity: \\( O(m + n) \\) where \\( m \\) and \\( n \\) are the sizes of the samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if either sample has fewer than 2 values
+/// * `InvalidInputData` - if the data contains NaN values
+///
+/// # Notes
+///
+/// The interval relies on a normal approximation and is recommended for samples of at least 10 values each,
+/// although its coverage remains close to the nominal level for smaller samples.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let config_a = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+/// let config_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = location::ci_median_difference(confidence, &config_a, &config_b)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.722905, 4.677095)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R.M. Price and D.G. Bonett. Estimating the variance of the sample median. Journal of Statistical Computation and Simulation, 68(3):295-305, 2001.
+/// * D.G. Bonett and R.M. Price. Statistical inference for a linear function of medians: Confidence intervals, hypothesis testing, and sample size requirements. Psychological Methods, 7(3):370-383, 2002.
+///
+pub fn ci_median_difference<F, Ia, Ib>(
+    confidence: Confidence,
+    data_a: &Ia,
+    data_b: &Ib,
+) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+{
+    let mut data_a = collect_sample(data_a)?;
+    let mut data_b = collect_sample(data_b)?;
+    let (median_a, sem_a) = median_with_sem(&mut data_a)?;
+    let (median_b, sem_b) = median_with_sem(&mut data_b)?;
+
+    let difference = median_a - median_b;
+    let std_err = (sem_a * sem_a + sem_b * sem_b).sqrt();
+    let z = distributions::z_value(confidence)?;
+    let (lo, hi) = (difference - z * std_err, difference + z * std_err);
+    let (lo, hi) = (F::from(lo).convert("lo")?, F::from(hi).convert("hi")?);
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+    }
+}
+
+///
+/// Copy the sample into a vector, checking that it is not empty and does not contain NaN values.
+///
+fn collect_sample<F, I>(data: &I) -> CIResult<Vec<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let data: Vec<F> = data.into_iter().copied().collect();
+    if data.is_empty() {
+        return Err(CIError::TooFewSamples(0));
+    }
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(CIError::InvalidInputData);
+    }
+    Ok(data)
+}
+
+///
+/// Return the Walsh averages \\( (x_i + x_j)/2 \\) for \\( i \leq j \\) of the sample (in unspecified order).
+///
+fn walsh_averages<F: Float>(data: &[F]) -> Vec<F> {
+    let two = F::one() + F::one();
+    let mut averages = Vec::with_capacity(data.len() * (data.len() + 1) / 2);
+    for (i, &x) in data.iter().enumerate() {
+        averages.extend(data[i..].iter().map(|&y| (x + y) / two));
+    }
+    averages
+}
+
+///
+/// Return the pairwise differences \\( a_i - b_j \\) (in unspecified order).
+///
+fn pairwise_differences<F: Float>(data_a: &[F], data_b: &[F]) -> Vec<F> {
+    let mut differences = Vec::with_capacity(data_a.len() * data_b.len());
+    for &a in data_a {
+        differences.extend(data_b.iter().map(|&b| a - b));
+    }
+    differences
+}
+
+///
+/// Return the depth \\( d \geq 1 \\) of the bounds of the confidence interval obtained by inverting the signed-rank test
+/// on \\( n \\) samples, i.e., \\( d = k + 1 \\) for the largest \\( k \\) such that \\( P(W^+ \leq k) \leq \alpha \\),
+/// where \\( \alpha \\) is the probability of each tail.
+///
+fn signed_rank_depth(confidence: Confidence, n: usize) -> CIResult<usize> {
+    let alpha = 1. - distributions::checked_quantile(confidence)?;
+    let k = if n <= EXACT_LIMIT {
+        // number of values k such that P(W+ <= k) <= alpha
+        let below = signed_rank_cdf(n)
+            .into_iter()
+            .take_while(|&cdf| cdf <= alpha)
+            .count();
+        below as f64 - 1.
+    } else {
+        let n_f = n as f64;
+        let mean = n_f * (n_f + 1.) / 4.;
+        let std_dev = (n_f * (n_f + 1.) * (2. * n_f + 1.) / 24.).sqrt();
+        let z = distributions::z_value(confidence)?;
+        // with continuity correction
+        (mean - z * std_dev - 0.5).floor()
+    };
+    if k < 0. {
+        return Err(CIError::TooFewSamples(n));
+    }
+    Ok(k as usize + 1)
+}
+
+///
+/// Return the cumulative distribution function of the signed-rank statistic \\( W^+ \\) on \\( n \\) samples,
+/// i.e., \\( P(W^+ \leq k) \\) for \\( k = 0, \ldots, n(n+1)/2 \\).
+///
+/// Under the null hypothesis, \\( W^+ \\) is the sum of a random subset of the ranks \\( \{1, \ldots, n\} \\).
+/// Its distribution is obtained by adding the ranks one at a time (dynamic programming).
+///
+fn signed_rank_cdf(n: usize) -> Vec<f64> {
+    let max = n * (n + 1) / 2;
+    let mut pmf = vec![0.; max + 1];
+    pmf[0] = 1.;
+    for rank in 1..=n {
+        let top = rank * (rank + 1) / 2;
+        for sum in (0..=top).rev() {
+            let with_rank = if sum >= rank { pmf[sum - rank] } else { 0. };
+            pmf[sum] = (pmf[sum] + with_rank) / 2.;
+        }
+    }
+    let mut cumulative = 0.;
+    pmf.iter()
+        .map(|p| {
+            cumulative += p;
+            cumulative
+        })
+        .collect()
+}
+
+///
+/// Return the depth \\( d \geq 1 \\) of the bounds of the confidence interval obtained by inverting the sign test
+/// on \\( n \\) samples, i.e., \\( d = k + 1 \\) for the largest \\( k \\) such that \\( P(B \leq k) \leq \alpha \\)
+/// for \\( B \sim B(n, 1/2) \\), where \\( \alpha \\) is the probability of each tail.
+///
+fn sign_test_depth(confidence: Confidence, n: usize) -> CIResult<usize> {
+    let alpha = 1. - distributions::checked_quantile(confidence)?;
+    let n = n as u64;
+    let below = quantile::partition_point(n, |k| stats::binomial_cdf(k, n, 0.5) <= alpha);
+    if below == 0 {
+        return Err(CIError::TooFewSamples(n as usize));
+    }
+    Ok(below as usize)
+}
+
+///
+/// Return the depth \\( d \geq 1 \\) of the bounds of the confidence interval obtained by inverting the Mann–Whitney test
+/// on samples of sizes \\( m \\) and \\( n \\), i.e., \\( d = k + 1 \\) for the largest \\( k \\) such that
+/// \\( P(U \leq k) \leq \alpha \\), where \\( \alpha \\) is the probability of each tail.
+///
+fn rank_sum_depth(confidence: Confidence, m: usize, n: usize) -> CIResult<usize> {
+    let alpha = 1. - distributions::checked_quantile(confidence)?;
+    let k = if m + n <= EXACT_LIMIT {
+        // number of values k such that P(U <= k) <= alpha
+        let below = rank_sum_cdf(m, n)
+            .into_iter()
+            .take_while(|&cdf| cdf <= alpha)
+            .count();
+        below as f64 - 1.
+    } else {
+        let (m, n) = (m as f64, n as f64);
+        let mean = m * n / 2.;
+        let std_dev = (m * n * (m + n + 1.) / 12.).sqrt();
+        let z = distributions::z_value(confidence)?;
+        // with continuity correction
+        (mean - z * std_dev - 0.5).floor()
+    };
+    if k < 0. {
+        return Err(CIError::TooFewSamples(m.min(n)));
+    }
+    Ok(k as usize + 1)
+}
+
+///
+/// Return the cumulative distribution function of the Mann–Whitney statistic \\( U \\) on samples of sizes
+/// \\( m \\) and \\( n \\), i.e., \\( P(U \leq k) \\) for \\( k = 0, \ldots, mn \\).
+///
+/// The number of arrangements with \\( U = k \\) is the coefficient of \\( q^k \\) in the Gaussian binomial coefficient
+/// \\( \binom{m+n}{m}_q = \prod_{i=1}^{m} \frac{1 - q^{n+i}}{1 - q^i} \\), which is computed one factor at a time.
+///
+fn rank_sum_cdf(m: usize, n: usize) -> Vec<f64> {
+    let mut counts = vec![0.; m * n + 1];
+    counts[0] = 1.;
+    for i in 1..=m {
+        // multiply by (1 - q^(n+i)), then divide by (1 - q^i); the degree grows from (i-1)n to i n
+        let degree = i * n;
+        for k in (n + i..=degree + i).rev() {
+            if k <= degree {
+                counts[k] -= counts[k - n - i];
+            }
+        }
+        for k in i..=degree {
+            counts[k] += counts[k - i];
+        }
+    }
+    let total: f64 = counts.iter().sum();
+    let mut cumulative = 0.;
+    counts
+        .iter()
+        .map(|count| {
+            cumulative += count / total;
+            cumulative
+        })
+        .collect()
+}
+
+///
+/// Return the confidence interval bounded by the `depth`-th smallest and the `depth`-th largest values (1-based).
+/// The values are reordered in place.
+///
+fn order_statistics_interval<T: PartialOrd + Copy>(
+    confidence: Confidence,
+    values: &mut [T],
+    depth: usize,
+) -> CIResult<Interval<T>> {
+    let len = values.len();
+    if depth == 0 || depth > len {
+        return Err(CIError::TooFewSamples(len));
+    }
+    let compare = |a: &T, b: &T| a.partial_cmp(b).unwrap();
+    let (_, &mut low, _) = values.select_nth_unstable_by(depth - 1, compare);
+    let (_, &mut high, _) = values.select_nth_unstable_by(len - depth, compare);
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(low, high).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(low)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(high)),
+    }
+}
+
+///
+/// Return the median of the sample and the Price–Bonett estimate of its standard error (see [`ci_median_difference`]).
+/// The sample is sorted in place.
+///
+fn median_with_sem<F: Float>(sorted: &mut [F]) -> CIResult<(f64, f64)> {
+    let n = sorted.len();
+    if n < 2 {
+        return Err(CIError::TooFewSamples(n));
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median =
+        ((sorted[(n - 1) / 2] + sorted[n / 2]) / (F::one() + F::one())).try_f64("median")?;
+
+    let depth = ((n as f64) / 2. - (n as f64).sqrt()).round().max(1.) as usize;
+    let p = stats::binomial_cdf(depth as u64 - 1, n as u64, 0.5);
+    let z = distributions::normal_inverse_cdf(1. - p)?;
+    let (low, high) = (
+        sorted[depth - 1].try_f64("low")?,
+        sorted[n - depth].try_f64("high")?,
+    );
+    Ok((median, (high - low) / (2. * z)))
+}
+
+///
+/// Return the median of the (non-empty) values, which are reordered in place.
+///
+fn median_mut<F: Float>(values: &mut [F]) -> F {
+    let len = values.len();
+    let compare = |a: &F, b: &F| a.partial_cmp(b).unwrap();
+    let (lower, &mut upper_mid, _) = values.select_nth_unstable_by(len / 2, compare);
+    if len % 2 == 1 {
+        upper_mid
+    } else {
+        let lower_mid = lower.iter().copied().fold(F::neg_infinity(), F::max);
+        (lower_mid + upper_mid) / (F::one() + F::one())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_hodges_lehmann() -> CIResult<()> {
+        let data = [
+            0.015, 0.028, 0.177, 0.121, 0.102, 0.107, 0.019, 0.066, 0.058, 0.111,
+        ];
+        assert_abs_diff_eq!(hodges_lehmann(&data)?, 0.08, epsilon = 1e-12);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let interval = ci_hodges_lehmann(confidence, &data)?;
+        assert_abs_diff_eq!(interval, Interval::new(0.0405, 0.116)?, epsilon = 1e-12);
+
+        let confidence = Confidence::new_upper(0.9);
+        let interval = ci_hodges_lehmann(confidence, &data)?;
+        assert!(interval.is_upper());
+        assert_abs_diff_eq!(interval.low_f(), 0.0605, epsilon = 1e-12);
+
+        let confidence = Confidence::new_lower(0.9);
+        let interval = ci_hodges_lehmann(confidence, &data)?;
+        assert_abs_diff_eq!(interval.high_f(), 0.1065, epsilon = 1e-12);
+
+        // even number of Walsh averages
+        assert_eq!(hodges_lehmann(&[1., 2., 4.])?, 2.25);
+        assert_eq!(hodges_lehmann(&[5.])?, 5.);
+
+        // errors
+        let confidence = Confidence::new_two_sided(0.95);
+        assert!(matches!(
+            ci_hodges_lehmann(confidence, &[1., 2., 3., 4., 5.]),
+            Err(CIError::TooFewSamples(5))
+        ));
+        let empty: [f64; 0] = [];
+        assert!(matches!(
+            hodges_lehmann(&empty),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            hodges_lehmann(&[1., f64::NAN]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_rank() -> CIResult<()> {
+        // exact distribution
+        let cdf = signed_rank_cdf(3);
+        let expected = [1., 2., 3., 5., 6., 7., 8.].map(|count| count / 8.);
+        assert_abs_diff_eq!(cdf.as_slice(), expected.as_slice(), epsilon = 1e-15);
+        assert_abs_diff_eq!(signed_rank_cdf(10)[8], 0.0244140625, epsilon = 1e-15);
+
+        // critical values from tables (two-sided 5% and 10%)
+        let confidence = Confidence::new_two_sided(0.95);
+        let depths = [6, 7, 8, 10, 20].map(|n| signed_rank_depth(confidence, n).unwrap());
+        assert_eq!(depths, [1, 3, 4, 9, 53]);
+        let confidence = Confidence::new_two_sided(0.9);
+        let depths = [5, 6, 7, 8, 10, 20].map(|n| signed_rank_depth(confidence, n).unwrap());
+        assert_eq!(depths, [1, 3, 4, 6, 11, 61]);
+        assert!(matches!(
+            signed_rank_depth(Confidence::new_two_sided(0.95), 5),
+            Err(CIError::TooFewSamples(5))
+        ));
+
+        // exact at the limit, then normal approximation (which would give 8444 at the limit)
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(signed_rank_depth(confidence, EXACT_LIMIT)?, 8445);
+        assert_eq!(signed_rank_depth(confidence, EXACT_LIMIT + 1)?, 8532);
+
+        // symmetric data centered on 10
+        let data = (1..=50)
+            .flat_map(|i| [10. - i as f64 / 10., 10. + i as f64 / 10.])
+            .collect::<Vec<_>>();
+        let interval = ci_signed_rank(confidence, &data)?;
+        assert!(interval.contains(&10.));
+        assert_abs_diff_eq!(hodges_lehmann(&data)?, 10., epsilon = 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift() -> CIResult<()> {
+        // exact distribution: U on samples of sizes 2 and 2 takes values 0..=4 with counts 1, 1, 2, 1, 1
+        let cdf = rank_sum_cdf(2, 2);
+        let expected = [1., 2., 4., 5., 6.].map(|count| count / 6.);
+        assert_abs_diff_eq!(cdf.as_slice(), expected.as_slice(), epsilon = 1e-15);
+        assert_abs_diff_eq!(
+            rank_sum_cdf(3, 1).as_slice(),
+            [0.25, 0.5, 0.75, 1.].as_slice(),
+            epsilon = 1e-15
+        );
+
+        // critical values from tables
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(rank_sum_depth(confidence, 10, 10)?, 24);
+        assert_eq!(rank_sum_depth(confidence, 5, 5)?, 3);
+        assert_eq!(rank_sum_depth(confidence, 40, 40)?, 597);
+        assert_eq!(rank_sum_depth(confidence, 100, 100)?, 4198);
+        // normal approximation
+        assert_eq!(rank_sum_depth(confidence, 100, 101)?, 4242);
+        assert_eq!(rank_sum_depth(Confidence::new_upper(0.95), 8, 6)?, 11);
+        assert!(matches!(
+            rank_sum_depth(confidence, 3, 4),
+            Err(CIError::TooFewSamples(3))
+        ));
+
+        let data_a = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_b = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        assert_abs_diff_eq!(
+            hodges_lehmann_shift(&data_a, &data_b)?,
+            0.093,
+            epsilon = 1e-12
+        );
+        let interval = ci_shift(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(interval, Interval::new(-0.054, 0.231)?, epsilon = 1e-12);
+        let interval = ci_shift(Confidence::new_upper(0.9), &data_a, &data_b)?;
+        assert!(interval.is_upper());
+        assert_abs_diff_eq!(interval.low_f(), 0.015, epsilon = 1e-12);
+
+        // swapping the samples negates the interval
+        let swapped = ci_shift(confidence, &data_b, &data_a)?;
+        assert_abs_diff_eq!(swapped, Interval::new(-0.231, 0.054)?, epsilon = 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_median() -> CIResult<()> {
+        // P(B <= 1) = 11/1024 and P(B <= 2) = 56/1024 for n = 10
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(sign_test_depth(confidence, 10)?, 2);
+        assert_eq!(sign_test_depth(Confidence::new_two_sided(0.9), 10)?, 2);
+        assert_eq!(sign_test_depth(Confidence::new_two_sided(0.8), 10)?, 3);
+        assert!(matches!(
+            sign_test_depth(confidence, 5),
+            Err(CIError::TooFewSamples(5))
+        ));
+
+        let data = [5., 1., 9., 3., 7., 2., 10., 4., 8., 6.];
+        assert_eq!(ci_median(confidence, &data)?, Interval::new(2., 9.)?);
+        assert_eq!(
+            ci_median(Confidence::new_lower(0.9), &data)?,
+            Interval::new_lower(8.)
+        );
+
+        // same as the exact quantile interval, including above its size limit
+        for n in [15, 100, quantile::EXACT_LIMIT + 1] {
+            let data = (0..n).rev().collect::<Vec<_>>();
+            let expected = quantile::Stats::new(n).ci_exact(confidence, 0.5)?;
+            assert_eq!(ci_median(confidence, &data)?, expected);
+        }
+
+        // arbitrary ordered data
+        let data = ["b", "d", "a", "f", "c", "e", "h", "g", "j", "i"];
+        assert_eq!(ci_median(confidence, &data)?, Interval::new("b", "i")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_median_difference() -> CIResult<()> {
+        let data_a = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_b = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        let (median, sem) = median_with_sem(&mut data_a.to_vec())?;
+        assert_abs_diff_eq!(median, 0.578, epsilon = 1e-12);
+        assert_abs_diff_eq!(sem, 0.06219115628776566, epsilon = 1e-9);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let interval = ci_median_difference(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(
+            interval,
+            Interval::new(-0.04530461160584898, 0.26330461160584895)?,
+            epsilon = 1e-9
+        );
+
+        let data_a = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+        let data_b = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1];
+        let interval = ci_median_difference(Confidence::new_upper(0.9), &data_a, &data_b)?;
+        assert!(interval.is_upper());
+        assert_abs_diff_eq!(interval.low_f(), 1.4072471816810066, epsilon = 1e-9);
+
+        assert!(matches!(
+            ci_median_difference(confidence, &data_a, &[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//!
+//! Summary of the draws of Markov chain Monte Carlo (MCMC) chains.
+//!
+//! The successive draws of an MCMC sampler are autocorrelated, so the Monte Carlo error on the posterior mean is much larger
+//! than \\( s / \sqrt{n} \\). Besides, the draws are only valid once the chains have converged to the same distribution.
+//!
+//! * [`ChainSummary`] - the posterior mean with its Monte Carlo standard error based on the integrated autocorrelation time,
+//!   the effective sample size of each chain, and the split-\\( \hat{R} \\) convergence diagnostic across chains.
+//!
+//! All functions require a copy of the draws and are only available with the `std` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // three chains of draws of the same parameter
+//! let chains = [
+//!     [1.76, 1.47, 1.23, 0.59, 0.79, 1.96, 2.12, 2.7, 2.42, 2.37, 2.17, 0.57],
+//!     [2.57, 2.6, 0.87, -0.21, -0.18, 0.18, 1.01, 1.23, 1.81, 1.23, 1.65, 1.96],
+//!     [2.78, 2.83, 3.38, 2.25, 1.48, 1.33, 1.43, 2.09, 2.17, 1.66, 0.95, 0.88],
+//! ];
+//! let summary = mcmc::ChainSummary::from_chains(&chains)?;
+//! assert_abs_diff_eq!(summary.mean(), 1.613889, epsilon = 1e-6);
+//! assert_abs_diff_eq!(summary.mc_std_err(), 0.208806, epsilon = 1e-6);
+//! // the 36 draws are worth about 17 independent ones
+//! assert_abs_diff_eq!(summary.effective_sample_size(), 17.01, epsilon = 1e-2);
+//! // the chains have not quite converged yet (the usual threshold is 1.01)
+//! assert!(summary.split_rhat().unwrap() > 1.01);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * A. Gelman, J.B. Carlin, H.S. Stern, D.B. Dunson, A. Vehtari, D.B. Rubin. Bayesian Data Analysis (3rd ed.), Section 11.4-11.5. CRC Press, 2013.
+//! * C.J. Geyer. "Practical Markov chain Monte Carlo." Statistical Science 7(4): 473-483 (1992).
+//! * Stan Reference Manual, Section "Effective sample size". [Online](https://mc-stan.org/docs/reference-manual/analysis.html#effective-sample-size.section)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Summary of the draws of one or several MCMC chains of the same parameter.
+///
+/// * The posterior mean \\( \bar{\theta} \\) and standard deviation \\( s \\) are computed over all the draws.
+/// * The effective sample size of each chain is \\( n / \tau \\), where \\( \tau \\) is its integrated autocorrelation time
+///   (see [`autocorr::integrated_autocorrelation_time`]), and the total effective sample size \\( n_{\text{eff}} \\) is their sum.
+/// * The Monte Carlo standard error of the posterior mean is \\( s / \sqrt{n_{\text{eff}}} \\).
+/// * The split-\\( \hat{R} \\) splits each chain in two halves (dropping the middle draw of chains of odd length), and compares
+///   the within-half variance \\( W \\) with the variance between the means of the halves \\( B / n \\):
+///   \\[
+///   \hat{R} = \sqrt{\frac{\frac{n - 1}{n} W + \frac{B}{n}}{W}}
+///   \\]
+///   where \\( n \\) is the length of the halves. Values close to 1 indicate that the chains have converged (e.g., \\( \hat{R} < 1.01 \\)).
+///   Splitting the chains detects non-stationary chains, even with a single chain.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainSummary {
+    draw_count: usize,
+    mean: f64,
+    std_dev: f64,
+    chain_ess: Vec<f64>,
+    split_rhat: Option<f64>,
+}
+
+impl ChainSummary {
+    ///
+    /// Summarize a single chain.
+    ///
+    /// Complexity: \\( O(n K) \\), where \\( n \\) is the number of draws and \\( K \\) the number of lags included.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - the draws of the chain, in order
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if the chain has less than 2 draws
+    /// * [`CIError::InvalidInputData`] - if the chain contains values that are not finite
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    pub fn from_chain<F, I>(chain: &I) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_series(vec![autocorr::series(chain)?])
+    }
+
+    ///
+    /// Summarize several chains of the same length.
+    ///
+    /// Complexity: \\( O(n K) \\), where \\( n \\) is the total number of draws and \\( K \\) the number of lags included.
+    ///
+    /// # Arguments
+    ///
+    /// * `chains` - the chains, each with its draws in order
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there is no chain, or if the chains have less than 2 draws
+    /// * [`CIError::DifferentSampleSizes`] - if the chains do not all have the same length
+    /// * [`CIError::InvalidInputData`] - if some chain contains values that are not finite
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let chains = vec![vec![0.1, 0.4, 0.3, 0.5], vec![0.2, 0.6]];
+    /// assert!(matches!(
+    ///     mcmc::ChainSummary::from_chains(&chains),
+    ///     Err(error::CIError::DifferentSampleSizes(4, 2))
+    /// ));
+    /// ```
+    ///
+    pub fn from_chains<F, C, I>(chains: &C) -> CIResult<Self>
+    where
+        F: Float,
+        for<'a> &'a C: IntoIterator<Item = &'a I>,
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let chains = chains
+            .into_iter()
+            .map(autocorr::series)
+            .collect::<CIResult<Vec<_>>>()?;
+        if let Some(first) = chains.first() {
+            if let Some(other) = chains.iter().find(|chain| chain.len() != first.len()) {
+                return Err(CIError::DifferentSampleSizes(first.len(), other.len()));
+            }
+        }
+        Self::from_series(chains)
+    }
+
+    fn from_series(chains: Vec<Vec<f64>>) -> CIResult<Self> {
+        if chains.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let draw_count = chains.iter().map(Vec::len).sum::<usize>();
+        let mean = chains.iter().flatten().sum::<f64>() / draw_count as f64;
+        let std_dev = (chains
+            .iter()
+            .flatten()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / (draw_count - 1) as f64)
+            .sqrt();
+        let chain_ess = chains
+            .iter()
+            .map(|chain| {
+                let (_, deviations) = autocorr::center(chain);
+                chain.len() as f64 / autocorr::geyer_time(&deviations)
+            })
+            .collect();
+        Ok(Self {
+            draw_count,
+            mean,
+            std_dev,
+            chain_ess,
+            split_rhat: split_rhat(&chains),
+        })
+    }
+
+    ///
+    /// Number of chains.
+    ///
+    pub fn chain_count(&self) -> usize {
+        self.chain_ess.len()
+    }
+
+    ///
+    /// Total number of draws, over all the chains.
+    ///
+    pub fn draw_count(&self) -> usize {
+        self.draw_count
+    }
+
+    ///
+    /// Posterior mean, over all the draws.
+    ///
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    ///
+    /// Posterior standard deviation, over all the draws.
+    ///
+    pub fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    ///
+    /// Effective sample size of each chain, in the order of the chains.
+    ///
+    pub fn chain_effective_sample_sizes(&self) -> &[f64] {
+        &self.chain_ess
+    }
+
+    ///
+    /// Total effective sample size, i.e., the sum of the effective sample sizes of the chains.
+    ///
+    pub fn effective_sample_size(&self) -> f64 {
+        self.chain_ess.iter().sum()
+    }
+
+    ///
+    /// Monte Carlo standard error of the posterior mean.
+    ///
+    pub fn mc_std_err(&self) -> f64 {
+        self.std_dev / self.effective_sample_size().sqrt()
+    }
+
+    ///
+    /// Split-\\( \hat{R} \\) convergence diagnostic, or `None` if the chains have less than 4 draws.
+    ///
+    /// The value is `NaN` if all the draws are identical.
+    ///
+    pub fn split_rhat(&self) -> Option<f64> {
+        self.split_rhat
+    }
+
+    ///
+    /// Compute the confidence interval on the posterior mean, i.e., the Monte Carlo error of the posterior mean,
+    /// with the Student t distribution with \\( n_{\text{eff}} - 1 \\) degrees of freedom.
+    ///
+    /// This is not a credible interval of the parameter: its width shrinks as the chains get longer.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidDegreesOfFreedom`] - if the effective sample size is not greater than 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let chains = [
+    ///     [1.76, 1.47, 1.23, 0.59, 0.79, 1.96, 2.12, 2.7, 2.42, 2.37, 2.17, 0.57],
+    ///     [2.57, 2.6, 0.87, -0.21, -0.18, 0.18, 1.01, 1.23, 1.81, 1.23, 1.65, 1.96],
+    ///     [2.78, 2.83, 3.38, 2.25, 1.48, 1.33, 1.43, 2.09, 2.17, 1.66, 0.95, 0.88],
+    /// ];
+    /// let summary = mcmc::ChainSummary::from_chains(&chains)?;
+    /// let ci = summary.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// assert_abs_diff_eq!(ci, Interval::new(1.171273, 2.056505)?, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        let (lo, hi) = stats::interval_bounds(
+            confidence,
+            self.mean,
+            self.mc_std_err(),
+            self.effective_sample_size() - 1.,
+        )?;
+        match confidence {
+            Confidence::TwoSided(_) => Ok(Interval::new(lo, hi)?),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+impl Statistic<f64> for ChainSummary {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<f64>> {
+        Ok(CiEstimate::new(
+            self.mean,
+            self.ci_mean(confidence)?,
+            confidence,
+            self.draw_count,
+            self.mc_std_err(),
+        ))
+    }
+}
+
+///
+/// Split-\\( \hat{R} \\) of chains of the same length (see [`ChainSummary`]), or `None` if the halves have less than 2 draws.
+///
+fn split_rhat(chains: &[Vec<f64>]) -> Option<f64> {
+    let half = chains[0].len() / 2;
+    if half < 2 {
+        return None;
+    }
+    let halves = chains
+        .iter()
+        .flat_map(|chain| [&chain[..half], &chain[chain.len() - half..]])
+        .map(|half| {
+            let (mean, deviations) = autocorr::center(half);
+            let variance = deviations.iter().map(|x| x * x).sum::<f64>() / (half.len() - 1) as f64;
+            (mean, variance)
+        })
+        .collect::<Vec<_>>();
+    let m = halves.len() as f64;
+    let n = half as f64;
+    let grand_mean = halves.iter().map(|(mean, _)| mean).sum::<f64>() / m;
+    let within = halves.iter().map(|(_, variance)| variance).sum::<f64>() / m;
+    let between = n * halves
+        .iter()
+        .map(|(mean, _)| (mean - grand_mean) * (mean - grand_mean))
+        .sum::<f64>()
+        / (m - 1.);
+    Some((((n - 1.) / n * within + between / n) / within).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const CHAINS: [[f64; 12]; 3] = [
+        [
+            1.76, 1.47, 1.23, 0.59, 0.79, 1.96, 2.12, 2.7, 2.42, 2.37, 2.17, 0.57,
+        ],
+        [
+            2.57, 2.6, 0.87, -0.21, -0.18, 0.18, 1.01, 1.23, 1.81, 1.23, 1.65, 1.96,
+        ],
+        [
+            2.78, 2.83, 3.38, 2.25, 1.48, 1.33, 1.43, 2.09, 2.17, 1.66, 0.95, 0.88,
+        ],
+    ];
+
+    #[test]
+    fn test_chain_summary() -> CIResult<()> {
+        // reference values computed in python
+        let summary = ChainSummary::from_chains(&CHAINS)?;
+        assert_eq!(summary.chain_count(), 3);
+        assert_eq!(summary.draw_count(), 36);
+        assert_abs_diff_eq!(summary.mean(), 1.613888888888889, epsilon = 1e-12);
+        assert_abs_diff_eq!(summary.std_dev(), 0.8613022624500572, epsilon = 1e-12);
+        let expected = [6.100159812455555, 5.521253987822342, 5.393337339002754];
+        for (ess, expected) in summary.chain_effective_sample_sizes().iter().zip(expected) {
+            assert_abs_diff_eq!(*ess, expected, epsilon = 1e-9);
+        }
+        assert_abs_diff_eq!(
+            summary.effective_sample_size(),
+            17.01475113928065,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(summary.mc_std_err(), 0.2088059102982913, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            summary.split_rhat().unwrap(),
+            1.115948180969232,
+            epsilon = 1e-9
+        );
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let estimate = summary.estimate(confidence)?;
+        assert_abs_diff_eq!(
+            *estimate.interval(),
+            Interval::new(1.171273267529246, 2.056504510248532)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(estimate.std_err(), summary.mc_std_err());
+        let ci = summary.ci_mean(Confidence::new_upper(0.975))?;
+        assert_abs_diff_eq!(ci.low_f(), 1.171273267529246, epsilon = 1e-9);
+        assert!(ci.is_upper());
+
+        // a single chain is split in two
+        let summary = ChainSummary::from_chain(&CHAINS[0])?;
+        assert_abs_diff_eq!(
+            summary.effective_sample_size(),
+            6.100159812455555,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            summary.split_rhat().unwrap(),
+            1.22453913544964,
+            epsilon = 1e-9
+        );
+        // the split-rhat drops the middle draw of chains of odd length
+        let odd = [1., 2., 100., 3., 4.];
+        let even = [1., 2., 3., 4.];
+        assert_eq!(
+            ChainSummary::from_chain(&odd)?.split_rhat(),
+            ChainSummary::from_chain(&even)?.split_rhat()
+        );
+        assert_eq!(ChainSummary::from_chain(&[1., 2., 3.])?.split_rhat(), None);
+
+        assert!(matches!(
+            ChainSummary::from_chains(&Vec::<Vec<f64>>::new()),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            ChainSummary::from_chain(&[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            ChainSummary::from_chains(&[vec![1., 2.], vec![f64::NAN, 2.]]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 //!
-//! Confidence intervals over the mean (arithmetic, geometric, harmonic) of a given sample.
+//! Confidence intervals over the mean (arithmetic, geometric, harmonic, quadratic, power, trimmed) of a given sample.
 //!
 //! The premise on which confidence intervals are computed is that the sample data is a random
 //! sample from a population following some (unknown) distribution. The confidence interval
@@ -24,7 +24,11 @@
 //! * The sample data is a random sample from a population following some (unknown) distribution.
 //! * The sample data is independent and identically distributed (iid).
 //! * The standard approaches a normal distribution.
-//! * For geometric / harmonic means, the sample data is strictly positive.
+//! * For geometric / harmonic means, the sample data is strictly positive (see [`NonPositivePolicy`] otherwise).
+//! * For power means, the sample data is non-negative (strictly positive when the exponent is not positive).
+//!
+//! Samples of integers (e.g., counters or byte sizes) are best handled by [`Integer`], which
+//! accumulates sums exactly and converts to floating point only when computing the statistics.
 //!
 //! # Examples
 //!
@@ -90,10 +94,10 @@
 //! ```
 //!
 use super::*;
-use crate::utils;
+use crate::utils::{self, NanAction};
 
 use error::*;
-use num_traits::Float;
+use num_traits::{Float, PrimInt};
 
 ///
 /// Trait for incremental statistics.
@@ -101,6 +105,7 @@ use num_traits::Float;
 /// - [`mean::Arithmetic`] for arithmetic calculations
 /// - [`mean::Geometric`] for geometric calculations (logarithmic space)
 /// - [`mean::Harmonic`] for harmonic calculations (reciprocal space)
+/// - [`mean::Quadratic`] for quadratic calculations (squares, aka. root mean square)
 ///
 /// # Example
 /// ```
@@ -247,6 +252,43 @@ pub trait StatisticsOps<F: Float>: Default {
     fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
     where
         for<'a> &'a I: IntoIterator<Item = &'a F>;
+
+    ///
+    /// Compute the confidence interval on the mean of a sample, handling NaN values according to the given policy
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    /// * `nan_policy` - The policy for NaN values (see [`NanPolicy`])
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the input data contains NaN values and the policy is [`NanPolicy::Error`]
+    /// * [`CIError::TooFewSamples`] - If the input data has too few (non-ignored) samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    fn ci_with_nan_policy<I>(
+        confidence: Confidence,
+        data: &I,
+        nan_policy: NanPolicy,
+    ) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut stats = Self::default();
+        for &x in data {
+            match nan_policy.apply(x)? {
+                NanAction::Keep => stats.append(x)?,
+                NanAction::Ignore => {}
+                NanAction::Propagate => return Ok(NanPolicy::nan_interval(confidence)),
+            }
+        }
+        stats.ci_mean(confidence)
+    }
 }
 
 macro_rules! impl_statistics_ops_for {
@@ -286,6 +328,7 @@ macro_rules! impl_statistics_ops_for {
 impl_statistics_ops_for!(Arithmetic<F>);
 impl_statistics_ops_for!(Harmonic<F>);
 impl_statistics_ops_for!(Geometric<F>);
+impl_statistics_ops_for!(Quadratic<F>);
 
 ///
 /// Represents the state of the computation of the arithmetic mean.
@@ -384,160 +427,205 @@ impl<F: Float> Arithmetic<F> {
     /// Complexity: \\( O(1) \\)
     ///
     pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let n = self.count as f64;
-        let mean = self.sample_mean().try_f64("stats.mean")?;
-        let std_dev = self.sample_std_dev().try_f64("stats.std_dev")?;
-        let std_err_mean = std_dev / n.sqrt();
-        let degrees_of_freedom = n - 1.;
-        let (lo, hi) = stats::interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom);
-        let (lo, hi) = (F::from(lo).convert("lo")?, F::from(hi).convert("hi")?);
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
-        }
+        let n = F::from(self.count).convert("count")?;
+        self.ci_mean_with_count(confidence, n)
     }
 
     ///
-    /// Number of samples
-    ///
-    /// Complexity: \\( O(1) \\)
-    ///
-    pub fn sample_count(&self) -> usize {
-        self.count
-    }
-
+    /// Confidence interval of the sample mean of correlated samples, where the number of samples is deflated to
+    /// the given effective sample size \\( n_{\text{eff}} \\): the standard error is \\( s / \sqrt{n_{\text{eff}}} \\)
+    /// and the interval has \\( n_{\text{eff}} - 1 \\) degrees of freedom.
     ///
-    /// Combine two states
+    /// The effective sample size of a series can be estimated with [`autocorr::effective_sample_size`],
+    /// or directly with [`autocorr::ci_mean_ess`].
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(self, rhs: Self) -> Self {
-        let mut sum = self.sum;
-        let mut sum_sq = self.sum_sq;
-        sum += rhs.sum;
-        sum_sq += rhs.sum_sq;
-        let count = self.count + rhs.count;
-        Self { sum, sum_sq, count }
-    }
-
-    ///
-    /// Compute the confidence interval on the mean of a sample
-    ///
     /// # Arguments
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// * `confidence` - the confidence level
+    /// * `effective_count` - the effective sample size (must be greater than 1)
     ///
-    /// # Output
+    /// # Errors
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    /// * [`CIError::InvalidInputData`] - if the effective sample size is not finite or not greater than 1
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
-    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
-    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // with all the samples, this is the usual interval
+    /// assert_eq!(stats.ci_mean_effective(confidence, 10.)?, stats.ci_mean(confidence)?);
+    /// assert!(stats.ci_mean(confidence)?.is_included_in(&stats.ci_mean_effective(confidence, 4.)?));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
     ///
-    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a F>,
-    {
-        Self::from_iter(data)?.ci_mean(confidence)
-    }
-}
-
-impl<F: Float> core::ops::Add for Arithmetic<F> {
-    type Output = Self;
-
-    #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add(rhs)
+    pub fn ci_mean_effective(
+        &self,
+        confidence: Confidence,
+        effective_count: F,
+    ) -> CIResult<Interval<F>> {
+        if !effective_count.is_finite() || effective_count <= F::one() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.ci_mean_with_count(confidence, effective_count)
     }
-}
 
-impl<F: Float> core::ops::AddAssign for Arithmetic<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.add(rhs);
+    fn ci_mean_with_count(&self, confidence: Confidence, n: F) -> CIResult<Interval<F>> {
+        let std_err_mean = self.sample_std_dev() / n.sqrt();
+        let degrees_of_freedom = n - F::one();
+        let (lo, hi) = stats::float_interval_bounds(
+            confidence,
+            self.sample_mean(),
+            std_err_mean,
+            degrees_of_freedom,
+        )?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
     }
-}
-
-///
-/// Represents the state of the computation related to the harmonic mean.
-/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
-/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the reciprocals of the samples.
-///
-/// It is best used through the [`StatisticsOps`] trait.
-///
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Harmonic<F: Float> {
-    recip_space: Arithmetic<F>,
-}
 
-impl<F: Float> Harmonic<F> {
     ///
-    /// Create a new empty state
+    /// Prediction interval for a single future observation drawn from the same (normal) population.
+    ///
+    /// Unlike [`Arithmetic::ci_mean`], which bounds the unknown population mean and shrinks as samples accumulate,
+    /// the prediction interval bounds the next observation: it accounts for both the uncertainty on the mean
+    /// and the spread of the population, so it never gets narrower than the population itself.
+    /// The standard error is \\( s \sqrt{1 + 1/n} \\) with \\( n-1 \\) degrees of freedom.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+    ///
+    /// # Examples
     ///
-    /// # Example
     /// ```
-    /// use stats_ci::*;
-    /// let mut stats = mean::Harmonic::new();
-    /// stats.append(10.);
-    /// assert_eq!(stats.sample_count(), 1);
-    /// assert_eq!(stats.sample_mean(), 10.);
+    /// # use stats_ci::*;
+    /// let data = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let prediction = stats.prediction_interval(confidence)?;
+    /// // the next observation is much less certain than the mean
+    /// assert!(stats.ci_mean(confidence)?.is_included_in(&prediction));
+    /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    pub fn new() -> Self {
-        Default::default()
+    pub fn prediction_interval(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.prediction_interval_mean(confidence, 1)
     }
 
     ///
-    /// Append a new sample to the data
+    /// Prediction interval for the mean of the next `future_count` observations drawn from the same (normal) population
+    /// (e.g., next week's average load given the past daily loads).
+    ///
+    /// The standard error is \\( s \sqrt{1/m + 1/n} \\) with \\( n-1 \\) degrees of freedom, where \\( m \\) is the number
+    /// of future observations. With \\( m = 1 \\), this is [`Arithmetic::prediction_interval`], and the interval tends
+    /// to [`Arithmetic::ci_mean`] as \\( m \\) grows.
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    pub fn append(&mut self, x: F) -> CIResult<()> {
-        if x <= F::zero() {
-            return Err(error::CIError::NonPositiveValue(
-                x.to_f64().unwrap_or(f64::NAN),
-            ));
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the interval
+    /// * `future_count` - the number \\( m \\) of future observations to average
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+    /// * [`CIError::InvalidInputData`] - if the number of future observations is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let daily_load = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+    /// let stats = mean::Arithmetic::from_iter(&daily_load)?;
+    /// let confidence = Confidence::new_upper(0.95);
+    /// let next_day = stats.prediction_interval(confidence)?;
+    /// let next_week = stats.prediction_interval_mean(confidence, 7)?;
+    /// assert!(next_week.is_included_in(&next_day));
+    /// assert!(stats.ci_mean(confidence)?.is_included_in(&next_week));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn prediction_interval_mean(
+        &self,
+        confidence: Confidence,
+        future_count: usize,
+    ) -> CIResult<Interval<F>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        if future_count == 0 {
+            return Err(CIError::InvalidInputData);
+        }
+        let n = F::from(self.count).convert("count")?;
+        let m = F::from(future_count).convert("future_count")?;
+        let std_err = self.sample_std_dev() * (m.recip() + n.recip()).sqrt();
+        let (lo, hi) =
+            stats::float_interval_bounds(confidence, self.sample_mean(), std_err, n - F::one())?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
         }
-        self.recip_space.append(F::one() / x)?;
-        Ok(())
     }
 
     ///
-    /// Harmonic mean of the sample
-    /// \\( H = \left( \frac{1}{n} \sum_i \frac{1}{x_i} \right)^{-1} \\)
+    /// One-sample t-test of the mean against a reference value, dual to [`Arithmetic::ci_mean`]
+    /// (see [`hypothesis`]).
+    ///
+    /// The statistic is \\( t = \frac{\bar{x} - \mu_0}{s / \sqrt{n}} \\) with \\( n-1 \\) degrees of freedom.
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    pub fn sample_mean(&self) -> F {
-        F::one() / self.recip_space.sample_mean()
-    }
-
+    /// # Arguments
     ///
-    /// Standard error of the harmonic mean
-    /// \\( s_H = \frac{1}{\alpha^2} \frac{s_{1/x_i}}{\sqrt{n-1}} \\)
+    /// * `confidence` - the direction and the significance level of the test
+    /// * `mean` - the mean \\( \mu_0 \\) under the null hypothesis
     ///
-    /// where
-    /// * the estimate of \\( \alpha \\) is given by \\( \alpha = \frac{1}{n} \sum_i 1/x_i \\);
-    /// * \\( s_{1/x_i} \\) is the estimate of the standard deviation of the reciprocals of the samples;
-    /// * and \\( n-1 \\) is the degree of freedom of the sample data.
+    /// # Errors
     ///
-    /// # Reference
+    /// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+    /// * [`CIError::InvalidReference`] - if the reference mean is not finite
+    /// * [`CIError::InvalidInputData`] - if the statistic is undefined (i.e., all samples are equal to the reference mean)
     ///
-    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    /// # Examples
     ///
-    pub fn sample_sem(&self) -> F {
-        let harm_mean = self.sample_mean();
-        let recip_std_dev = self.recip_space.sample_std_dev();
-        harm_mean * harm_mean * recip_std_dev
-            / F::from(self.recip_space.sample_count() - 1).unwrap().sqrt()
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// // is the mean greater than 10?
+    /// let test = stats.t_test(Confidence::new_upper(0.95), 10.)?;
+    /// assert!(test.is_significant());
+    /// assert!(test.p_value() < 0.05);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn t_test(&self, confidence: Confidence, mean: F) -> CIResult<hypothesis::TestResult> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let mean = mean.try_f64("mean")?;
+        if !mean.is_finite() {
+            return Err(CIError::InvalidReference(mean));
+        }
+        let n = self.count as f64;
+        let sample_mean = self.sample_mean().try_f64("sample_mean")?;
+        let std_dev = self.sample_std_dev().try_f64("std_dev")?;
+        hypothesis::TestResult::t(
+            confidence,
+            (sample_mean - mean) / (std_dev / n.sqrt()),
+            n - 1.,
+        )
     }
 
     ///
@@ -546,20 +634,7 @@ impl<F: Float> Harmonic<F> {
     /// Complexity: \\( O(1) \\)
     ///
     pub fn sample_count(&self) -> usize {
-        self.recip_space.sample_count()
-    }
-
-    ///
-    /// Confidence interval for the harmonic mean
-    ///
-    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let arith_ci = self.recip_space.ci_mean(confidence.flipped())?;
-        let (lo, hi) = (F::one() / arith_ci.high_f(), F::one() / arith_ci.low_f());
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
-        }
+        self.count
     }
 
     ///
@@ -569,9 +644,12 @@ impl<F: Float> Harmonic<F> {
     ///
     #[allow(clippy::should_implement_trait)]
     pub fn add(self, rhs: Self) -> Self {
-        Self {
-            recip_space: self.recip_space + rhs.recip_space,
-        }
+        let mut sum = self.sum;
+        let mut sum_sq = self.sum_sq;
+        sum += rhs.sum;
+        sum_sq += rhs.sum_sq;
+        let count = self.count + rhs.count;
+        Self { sum, sum_sq, count }
     }
 
     ///
@@ -599,17 +677,59 @@ impl<F: Float> Harmonic<F> {
     {
         Self::from_iter(data)?.ci_mean(confidence)
     }
-}
 
-impl<F: Float> Default for Harmonic<F> {
-    fn default() -> Self {
-        Self {
-            recip_space: Arithmetic::default(),
+    ///
+    /// Smallest total number of samples for which the confidence interval on the mean is expected
+    /// to have at most the given half-width, using the current state as a pilot sample to estimate
+    /// the standard deviation (see [`required_sample_size`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `target_half_width` - The desired half-width of the interval (in the unit of the samples)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the pilot sample has fewer than two samples
+    /// * [`CIError::InvalidHalfWidth`] - If the target half-width is not strictly positive and finite
+    /// * [`CIError::InvalidInputData`] - If the standard deviation of the pilot sample is not finite
+    ///
+    /// # Notes
+    ///
+    /// The result is the total sample size, including the pilot samples.
+    /// The standard deviation of a small pilot sample is itself uncertain, so the result should be
+    /// taken as an approximation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let pilot = [251., 238., 262., 244., 259., 247., 255., 241., 266., 237.];
+    /// let stats = mean::Arithmetic::from_iter(&pilot)?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // how many runs for a ±1% interval?
+    /// let n = stats.required_sample_size(confidence, 0.01 * stats.sample_mean())?;
+    /// assert_eq!(n, 68);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn required_sample_size(
+        &self,
+        confidence: Confidence,
+        target_half_width: F,
+    ) -> CIResult<usize> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
         }
+        required_sample_size(
+            confidence,
+            target_half_width.to_f64().convert("target_half_width")?,
+            self.sample_std_dev().to_f64().convert("std_dev")?,
+        )
     }
 }
 
-impl<F: Float> core::ops::Add for Harmonic<F> {
+impl<F: Float> core::ops::Add for Arithmetic<F> {
     type Output = Self;
 
     #[inline]
@@ -618,7 +738,7 @@ impl<F: Float> core::ops::Add for Harmonic<F> {
     }
 }
 
-impl<F: Float> core::ops::AddAssign for Harmonic<F> {
+impl<F: Float> core::ops::AddAssign for Arithmetic<F> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
         *self = self.add(rhs);
@@ -626,92 +746,2131 @@ impl<F: Float> core::ops::AddAssign for Harmonic<F> {
 }
 
 ///
-/// Represents the state of the computation of the geometric mean.
-/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
-/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the logarithms of the samples.
+/// Prediction interval for a single future observation, given a sample of past observations
+/// assumed to be drawn from a normal population (see [`Arithmetic::prediction_interval`]).
 ///
-/// It is best used through the [`StatisticsOps`] trait.
+/// This is *not* a confidence interval on the mean: a confidence interval on the mean says where the
+/// population mean is likely to be, while the prediction interval says where the next observation is
+/// likely to fall. Using [`Arithmetic::ci_mean`] to predict individual observations is a common mistake
+/// that grossly understates the uncertainty.
 ///
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Geometric<F: Float> {
-    log_space: Arithmetic<F>,
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `data` - the past observations
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the data contains invalid values (e.g., NaN)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let latencies = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let prediction = mean::prediction_interval(confidence, &latencies)?;
+/// assert_abs_diff_eq!(prediction.low_f(), 9.77811004442008, epsilon = 1e-10);
+/// assert_abs_diff_eq!(prediction.high_f(), 10.79688995557992, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn prediction_interval<F: Float, I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    Arithmetic::from_iter(data)?.prediction_interval(confidence)
 }
 
-impl<F: Float> Geometric<F> {
-    ///
-    /// Create a new empty state
-    ///
-    /// # Example
-    /// ```
-    /// # use stats_ci::*;
-    /// # use approx::*;
-    /// let mut stats = mean::Geometric::new();
+///
+/// Prediction interval for the mean of the next `future_count` observations, given a sample of past observations
+/// assumed to be drawn from a normal population (see [`Arithmetic::prediction_interval_mean`]).
+///
+/// This is the interval to use for capacity planning questions such as "what will next week's average load be?".
+/// It is narrower than [`prediction_interval`] (a single observation) but wider than [`Arithmetic::ci_mean`]
+/// (the population mean).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `data` - the past observations
+/// * `future_count` - the number of future observations to average
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+/// * [`CIError::InvalidInputData`] - if the data contains invalid values (e.g., NaN) or the number of future observations is zero
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let daily_load = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let prediction = mean::prediction_interval_mean(confidence, &daily_load, 4)?;
+/// assert_abs_diff_eq!(prediction.low_f(), 9.993403572023442, epsilon = 1e-10);
+/// assert_abs_diff_eq!(prediction.high_f(), 10.581596427976558, epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn prediction_interval_mean<F: Float, I>(
+    confidence: Confidence,
+    data: &I,
+    future_count: usize,
+) -> CIResult<Interval<F>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    Arithmetic::from_iter(data)?.prediction_interval_mean(confidence, future_count)
+}
+
+///
+/// Smallest number of samples for which the confidence interval on the arithmetic mean
+/// is expected to have at most the given half-width (i.e., margin of error), given an estimate of the
+/// standard deviation of the population.
+///
+/// This is the inverse of [`Arithmetic::ci_mean`]: it returns the smallest \\( n \\geq 2 \\) such that
+/// \\( t_{n-1} \\frac{\\sigma}{\\sqrt{n}} \\leq h \\), where \\( t_{n-1} \\) is the critical value of
+/// Student's t distribution with \\( n-1 \\) degrees of freedom.
+/// For one-sided confidence levels, the half-width is the distance between the sample mean and the bound.
+///
+/// # Arguments
+///
+/// * `confidence` - The confidence level of the interval
+/// * `target_half_width` - The desired half-width of the interval (in the unit of the samples)
+/// * `std_dev_estimate` - An estimate of the standard deviation of the population (e.g., from a pilot sample, see [`Arithmetic::required_sample_size`])
+///
+/// # Errors
+///
+/// * [`CIError::InvalidHalfWidth`] - If the target half-width is not strictly positive and finite
+/// * [`CIError::InvalidInputData`] - If the standard deviation is negative or not finite
+/// * [`CIError::Overflow`] - If the required sample size cannot be represented
+///
+/// # Notes
+///
+/// The actual half-width of the resulting interval depends on the standard deviation of the sample,
+/// so the target is only met on average when the estimate is accurate.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // runs needed for a ±0.1 interval when the standard deviation is about 1
+/// assert_eq!(mean::required_sample_size(confidence, 0.1, 1.)?, 387);
+/// // runs needed for a ±1% interval around a mean of about 250 with a standard deviation of about 12.5
+/// assert_eq!(mean::required_sample_size(confidence, 0.01 * 250., 12.5)?, 99);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn required_sample_size(
+    confidence: Confidence,
+    target_half_width: f64,
+    std_dev_estimate: f64,
+) -> CIResult<usize> {
+    if !(target_half_width.is_finite() && target_half_width > 0.) {
+        return Err(CIError::InvalidHalfWidth(target_half_width));
+    }
+    if !(std_dev_estimate.is_finite() && std_dev_estimate >= 0.) {
+        return Err(CIError::InvalidInputData);
+    }
+    let half_width = |n: usize| -> CIResult<f64> {
+        let n = n as f64;
+        let (_, hi) = stats::interval_bounds(confidence, 0., std_dev_estimate / n.sqrt(), n - 1.)?;
+        Ok(hi)
+    };
+    // the normal approximation gives a lower bound, from which the sample size is increased
+    let z = distributions::z_value(confidence)?;
+    let n_z = (z * std_dev_estimate / target_half_width).powi(2).ceil();
+    let mut n = utils::sample_size_from_f64(n_z)?.max(2);
+    while half_width(n)? > target_half_width {
+        n += 1;
+    }
+    Ok(n)
+}
+
+///
+/// Compute confidence intervals on the arithmetic means of several samples at once, with simultaneous coverage.
+///
+/// Computing, e.g., 20 intervals at 95% each, the probability that at least one of them misses its mean
+/// can be as high as 64%. This function instead guarantees that __all__ intervals simultaneously contain their
+/// respective mean with (at least) the given confidence level, by computing each of the \( m \) intervals
+/// at the individual confidence level given by the correction (see [`Confidence::adjusted`]).
+///
+/// Complexity: \( O(n) \) where \( n \) is the total number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the joint confidence level
+/// * `correction` - the correction of the confidence level (Bonferroni or Šidák)
+/// * `samples` - the samples, one per interval
+///
+/// # Output
+///
+/// * the confidence intervals, in the same order as `samples`
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - If some sample has too few values to compute the confidence interval
+/// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+/// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let metrics = [
+///     vec![1.2, 1.4, 1.1, 1.3, 1.5, 1.2],
+///     vec![10., 12., 11., 13., 9., 10.],
+///     vec![0.5, 0.7, 0.6, 0.4, 0.6, 0.5],
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let intervals = mean::ci_many(confidence, Correction::Bonferroni, &metrics)?;
+/// assert_eq!(intervals.len(), 3);
+/// // each interval is wider than an individual 95% interval
+/// for (interval, data) in intervals.iter().zip(&metrics) {
+///     assert!(interval.includes(&mean::Arithmetic::ci(confidence, data)?));
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_many<F: Float, S>(
+    confidence: Confidence,
+    correction: Correction,
+    samples: &[S],
+) -> CIResult<Vec<Interval<F>>>
+where
+    for<'a> &'a S: IntoIterator<Item = &'a F>,
+{
+    let individual = confidence.adjusted(samples.len(), correction);
+    samples
+        .iter()
+        .map(|data| Arithmetic::ci(individual, data))
+        .collect()
+}
+
+///
+/// Compute confidence intervals on the arithmetic means of the selected samples among a family of samples,
+/// controlling the false coverage-statement rate (FCR) among the selected intervals (Benjamini and Yekutieli, 2005).
+///
+/// When screening many metrics and reporting intervals only for a few of them (e.g., those that moved), the individual
+/// intervals of the selected metrics are biased by the selection, while family-wise corrections (see [`ci_many`])
+/// are too conservative. Instead, with \( R \) samples selected among \( m \), each selected interval is computed at
+/// the level \( 1 - R\alpha/m \) (see [`Confidence::fcr_adjusted`]), which guarantees that the expected proportion
+/// of selected intervals missing their mean is at most \( \alpha \).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level \( 1 - \alpha \), where \( \alpha \) is the target FCR
+/// * `samples` - the whole family of samples
+/// * `selected` - the (distinct) indices of the selected samples
+///
+/// # Output
+///
+/// * the confidence intervals of the selected samples, in the same order as `selected`
+///
+/// # Errors
+///
+/// * [`CIError::IndexError`] - If some selected index is out of range
+/// * [`CIError::TooFewSamples`] - If some selected sample has too few values to compute the confidence interval
+/// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// The guarantee holds for any selection rule when the samples are independent.
+/// This function is only available with the `std` feature enabled.
+///
+/// # References
+///
+/// * Yoav Benjamini and Daniel Yekutieli. "False discovery rate–adjusted multiple confidence intervals for selected parameters." Journal of the American Statistical Association 100(469): 71-81 (2005).
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_many_fcr<F: Float, S>(
+    confidence: Confidence,
+    samples: &[S],
+    selected: &[usize],
+) -> CIResult<Vec<Interval<F>>>
+where
+    for<'a> &'a S: IntoIterator<Item = &'a F>,
+{
+    let individual = confidence.fcr_adjusted(samples.len(), selected.len());
+    selected
+        .iter()
+        .map(|&i| {
+            let data = samples
+                .get(i)
+                .ok_or(CIError::IndexError(i as f64, samples.len()))?;
+            Arithmetic::ci(individual, data)
+        })
+        .collect()
+}
+
+///
+/// Select the samples whose mean differs from zero with the Benjamini–Hochberg procedure, and compute their
+/// FCR-adjusted confidence intervals (see [`ci_many_fcr`]).
+///
+/// The Benjamini–Hochberg procedure controls the false discovery rate at \( \alpha \) among the samples
+/// declared different from zero (based on the t-test of each mean). With this selection, the adjusted
+/// intervals exclude zero for exactly the selected samples.
+/// For one-sided confidence levels, the tests are one-sided in the same direction as the intervals.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level \( 1 - \alpha \), where \( \alpha \) is the target FDR and FCR
+/// * `samples` - the whole family of samples
+///
+/// # Output
+///
+/// * the indices of the selected samples (in increasing order) with their confidence intervals
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - If some sample has less than two values
+/// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // differences observed on 6 metrics: only the second and fifth really moved
+/// let metrics = [
+///     vec![0.1, -0.2, 0.05, 0.0, -0.1, 0.15],
+///     vec![2.1, 1.8, 2.4, 2.0, 2.2, 1.9],
+///     vec![-0.3, 0.2, 0.1, -0.1, 0.0, 0.05],
+///     vec![0.2, -0.1, -0.2, 0.1, 0.05, -0.05],
+///     vec![-1.1, -0.9, -1.3, -1.0, -1.2, -0.8],
+///     vec![0.0, 0.1, -0.1, 0.2, -0.2, 0.05],
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let discoveries = mean::ci_discoveries_fcr(confidence, &metrics)?;
+/// let indices = discoveries.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+/// assert_eq!(indices, [1, 4]);
+/// for (_, interval) in &discoveries {
+///     assert!(!interval.contains(&0.));
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_discoveries_fcr<F: Float, S>(
+    confidence: Confidence,
+    samples: &[S],
+) -> CIResult<Vec<(usize, Interval<F>)>>
+where
+    for<'a> &'a S: IntoIterator<Item = &'a F>,
+{
+    let level = confidence.level();
+    if !(level > 0. && level < 1.) {
+        return Err(CIError::InvalidConfidenceLevel(level));
+    }
+    let alpha = 1. - level;
+    let m = samples.len();
+    let mut p_values = samples
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            Ok((
+                i,
+                zero_mean_p_value(confidence, &Arithmetic::from_iter(data)?)?,
+            ))
+        })
+        .collect::<CIResult<Vec<_>>>()?;
+    p_values.sort_by(|a, b| a.1.total_cmp(&b.1));
+    // Benjamini-Hochberg: largest r such that the r-th smallest p-value is at most r alpha / m
+    let selected_count = p_values
+        .iter()
+        .enumerate()
+        .filter(|(rank, (_, p))| *p <= (rank + 1) as f64 * alpha / m as f64)
+        .map(|(rank, _)| rank + 1)
+        .max()
+        .unwrap_or(0);
+    let mut selected = p_values[..selected_count]
+        .iter()
+        .map(|&(i, _)| i)
+        .collect::<Vec<_>>();
+    selected.sort_unstable();
+    let intervals = ci_many_fcr(confidence, samples, &selected)?;
+    Ok(selected.into_iter().zip(intervals).collect())
+}
+
+///
+/// p-value of the t-test of a zero mean, in the direction(s) given by the confidence
+///
+#[cfg(any(test, feature = "std"))]
+fn zero_mean_p_value<F: Float>(confidence: Confidence, stats: &Arithmetic<F>) -> CIResult<f64> {
+    match stats.t_test(confidence, F::zero()) {
+        // constant zero sample: no evidence against a zero mean
+        Err(CIError::InvalidInputData) => Ok(1.),
+        result => Ok(result?.p_value()),
+    }
+}
+
+///
+/// Represents the state of the computation of the arithmetic mean of a sample of integers
+/// (e.g., counters, byte sizes, durations in nanoseconds).
+///
+/// The sum and the sum of squares are accumulated exactly in `i128`/`u128` and the samples
+/// are converted to `f64` only when computing the statistics.
+/// This removes all summation errors, and is faster than the compensated (Kahan) summation
+/// used by [`Arithmetic`].
+/// The accumulators only overflow when the sum of squares exceeds \\( 2^{128} \\),
+/// e.g., never before \\( 2^{64} \\) samples of magnitude below \\( 2^{32} \\).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data: [u64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// let stats = mean::Integer::from_iter(&data)?;
+/// assert_eq!(stats.sample_count(), 10);
+/// assert_eq!(stats.sample_sum(), 55);
+/// assert_eq!(stats.sample_mean(), 5.5);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(stats.ci_mean(confidence)?, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+///
+/// // no summation error, even when the sum cannot be represented exactly as a float
+/// let data: [i64; 4] = [1 << 53, 1, 1, -(1 << 53)];
+/// let stats = mean::Integer::from_iter(&data)?;
+/// assert_eq!(stats.sample_sum(), 2);
+/// assert_eq!(stats.sample_mean(), 0.5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Integer<T: PrimInt> {
+    sum: i128,
+    sum_sq: u128,
+    count: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T: PrimInt> Default for Integer<T> {
+    fn default() -> Self {
+        Self {
+            sum: 0,
+            sum_sq: 0,
+            count: 0,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: PrimInt> Integer<T> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let mut stats = mean::Integer::new();
+    /// stats.append(10_u32)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_eq!(stats.sample_mean(), 10.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state and "populates" it with data from an iterator
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to populate the state with
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Extend the state with additional samples from an iterator
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to append
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+    {
+        for &x in data {
+            self.append(x)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows (the state is then left unchanged)
+    ///
+    pub fn append(&mut self, x: T) -> CIResult<()> {
+        let x = x
+            .to_i128()
+            .ok_or_else(|| CIError::Overflow("sample".to_string()))?;
+        let sum = self
+            .sum
+            .checked_add(x)
+            .ok_or_else(|| CIError::Overflow("sum".to_string()))?;
+        let sum_sq = x
+            .unsigned_abs()
+            .checked_mul(x.unsigned_abs())
+            .and_then(|x2| self.sum_sq.checked_add(x2))
+            .ok_or_else(|| CIError::Overflow("sum of squares".to_string()))?;
+        self.sum = sum;
+        self.sum_sq = sum_sq;
+        self.count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Exact sum of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sum(&self) -> i128 {
+        self.sum
+    }
+
+    ///
+    /// Mean of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> f64 {
+        self.sum as f64 / self.count as f64
+    }
+
+    ///
+    /// Variance of the sample
+    /// \\( \frac{1}{n(n-1)}\left(n \sum_{i=1}^n x_i^2 - \left(\sum_{i=1}^n x_i\right)^2 \right) \\)
+    ///
+    /// The numerator is computed exactly, unless it overflows `u128`.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_variance(&self) -> f64 {
+        let n = self.count as f64;
+        let numerator = (self.count as u128)
+            .checked_mul(self.sum_sq)
+            .zip(self.sum.unsigned_abs().checked_pow(2))
+            .map(|(n_sum_sq, sum_2)| (n_sum_sq - sum_2) as f64)
+            .unwrap_or_else(|| n * (self.sum_sq as f64 - self.sample_mean() * self.sum as f64));
+        numerator / (n * (n - 1.))
+    }
+
+    ///
+    /// Standard deviation of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_std_dev(&self) -> f64 {
+        self.sample_variance().sqrt()
+    }
+
+    ///
+    /// Standard error of the sample mean \\( s / \sqrt{n} \\)
+    ///
+    /// It is NaN with fewer than two samples.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> f64 {
+        self.sample_std_dev() / (self.count as f64).sqrt()
+    }
+
+    ///
+    /// Confidence interval of the sample mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are fewer than two samples
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let n = self.count as f64;
+        let std_err_mean = self.sample_std_dev() / n.sqrt();
+        let (lo, hi) =
+            stats::interval_bounds(confidence, self.sample_mean(), std_err_mean, n - 1.)?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::Overflow`] - If the combined sum or sum of squares overflows
+    ///
+    pub fn checked_add(self, rhs: Self) -> CIResult<Self> {
+        Ok(Self {
+            sum: self
+                .sum
+                .checked_add(rhs.sum)
+                .ok_or_else(|| CIError::Overflow("sum".to_string()))?,
+            sum_sq: self
+                .sum_sq
+                .checked_add(rhs.sum_sq)
+                .ok_or_else(|| CIError::Overflow("sum of squares".to_string()))?,
+            count: self.count + rhs.count,
+            _marker: core::marker::PhantomData,
+        })
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::Overflow`] - If the sum or the sum of squares overflows
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f64>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+impl<T: PrimInt> core::ops::Add for Integer<T> {
+    type Output = Self;
+
+    ///
+    /// # Panics
+    ///
+    /// If the combined sum or sum of squares overflows (see [`Integer::checked_add`]).
+    ///
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when combining integer states")
+    }
+}
+
+impl<T: PrimInt> core::ops::AddAssign for Integer<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+///
+/// Policy for handling samples that are not strictly positive (zero or negative) when computing geometric and harmonic means,
+/// which are only defined for strictly positive values.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [0., 2., 4., 8., 16.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // by default, the zero is rejected
+/// assert!(mean::Geometric::ci(confidence, &data).is_err());
+/// // ignore the zero
+/// let stats = mean::Geometric::from_iter_with_policy(&data, mean::NonPositivePolicy::Skip)?;
+/// assert_abs_diff_eq!(stats.sample_mean(), 1024_f64.powf(0.25), epsilon = 1e-10);
+/// assert_eq!(stats.skipped_count(), 1);
+/// // shifted geometric mean: exp(mean(log(x + 1))) - 1
+/// let stats = mean::Geometric::from_iter_with_policy(&data, mean::NonPositivePolicy::Offset(1.))?;
+/// assert_abs_diff_eq!(stats.sample_mean(), (3. * 5. * 9. * 17_f64).powf(0.2) - 1., epsilon = 1e-10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonPositivePolicy<F> {
+    ///
+    /// Reject the sample with [`CIError::NonPositiveValue`] (default).
+    ///
+    #[default]
+    Error,
+
+    ///
+    /// Ignore the sample (see `skipped_count`).
+    ///
+    Skip,
+
+    ///
+    /// Add the given offset \\( \varepsilon > 0 \\) to __all__ samples, and subtract it from the resulting mean and interval
+    /// (e.g., \\( \exp\left(\frac{1}{n}\sum_i \log(x_i + \varepsilon)\right) - \varepsilon \\) for the geometric mean).
+    /// Samples that remain non-positive after the offset are rejected with [`CIError::NonPositiveValue`].
+    ///
+    Offset(F),
+}
+
+impl<F: Float> NonPositivePolicy<F> {
+    ///
+    /// Applies the policy to a sample, returning the (possibly offset) value to use, or `None` if the sample must be skipped.
+    ///
+    fn apply(&self, x: F) -> CIResult<Option<F>> {
+        match *self {
+            Self::Error => Ok(Some(x)),
+            Self::Skip if x <= F::zero() => Ok(None),
+            Self::Skip => Ok(Some(x)),
+            Self::Offset(offset) => Ok(Some(x + offset)),
+        }
+    }
+
+    ///
+    /// Offset to subtract from the resulting mean (zero unless [`NonPositivePolicy::Offset`]).
+    ///
+    fn offset(&self) -> F {
+        match *self {
+            Self::Offset(offset) => offset,
+            _ => F::zero(),
+        }
+    }
+
+    ///
+    /// Subtracts the offset from the bounds of an interval (unchanged unless [`NonPositivePolicy::Offset`]).
+    ///
+    fn shifted(&self, ci: Interval<F>) -> Interval<F> {
+        let offset = self.offset();
+        match ci {
+            Interval::TwoSided(lo, hi) => Interval::TwoSided(lo - offset, hi - offset),
+            Interval::UpperOneSided(lo) => Interval::UpperOneSided(lo - offset),
+            Interval::LowerOneSided(hi) => Interval::LowerOneSided(hi - offset),
+        }
+    }
+}
+
+///
+/// Represents the state of the computation related to the harmonic mean.
+/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the reciprocals of the samples.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Harmonic<F: Float> {
+    recip_space: Arithmetic<F>,
+    policy: NonPositivePolicy<F>,
+    skipped: usize,
+}
+
+impl<F: Float> Harmonic<F> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let mut stats = mean::Harmonic::new();
+    /// stats.append(10.);
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_eq!(stats.sample_mean(), 10.);
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the sample is not strictly positive (depending on the policy, see [`Harmonic::with_policy`])
+    ///
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        match self.policy.apply(x)? {
+            Some(x) => self.recip_space.append(to_recip_space(x)?)?,
+            None => self.skipped += 1,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Create a new empty state with the given policy for samples that are not strictly positive
+    ///
+    pub fn with_policy(policy: NonPositivePolicy<F>) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Create a new state with the given policy for samples that are not strictly positive, and populate it with data
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the input data contains values that are not strictly positive (depending on the policy)
+    ///
+    pub fn from_iter_with_policy<I>(data: &I, policy: NonPositivePolicy<F>) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut stats = Self::with_policy(policy);
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Policy for samples that are not strictly positive
+    ///
+    pub fn policy(&self) -> NonPositivePolicy<F> {
+        self.policy
+    }
+
+    ///
+    /// Number of samples that were ignored by the policy [`NonPositivePolicy::Skip`]
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn skipped_count(&self) -> usize {
+        self.skipped
+    }
+
+    ///
+    /// Harmonic mean of the sample
+    /// \\( H = \left( \frac{1}{n} \sum_i \frac{1}{x_i} \right)^{-1} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        F::one() / self.recip_space.sample_mean() - self.policy.offset()
+    }
+
+    ///
+    /// Standard error of the harmonic mean
+    /// \\( s_H = \frac{1}{\alpha^2} \frac{s_{1/x_i}}{\sqrt{n-1}} \\)
+    ///
+    /// where
+    /// * the estimate of \\( \alpha \\) is given by \\( \alpha = \frac{1}{n} \sum_i 1/x_i \\);
+    /// * \\( s_{1/x_i} \\) is the estimate of the standard deviation of the reciprocals of the samples;
+    /// * and \\( n-1 \\) is the degree of freedom of the sample data.
+    ///
+    /// # Reference
+    ///
+    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let harm_mean = F::one() / self.recip_space.sample_mean();
+        let recip_std_dev = self.recip_space.sample_std_dev();
+        harm_mean * harm_mean * recip_std_dev
+            / F::from(self.recip_space.sample_count() - 1).unwrap().sqrt()
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.recip_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the harmonic mean
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let arith_ci = self.recip_space.ci_mean(confidence.flipped())?;
+        from_recip_space(confidence, arith_ci).map(|ci| self.policy.shifted(ci))
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the two states have different policies for non-positive values
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        assert!(
+            self.policy == rhs.policy,
+            "cannot combine means with different policies for non-positive values"
+        );
+        Self {
+            recip_space: self.recip_space + rhs.recip_space,
+            policy: self.policy,
+            skipped: self.skipped + rhs.skipped,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample, with the given policy for samples that are not strictly positive
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    /// * `policy` - The policy for samples that are not strictly positive
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few (non-skipped) samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains values that are not strictly positive (depending on the policy)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci_with_policy<I>(
+        confidence: Confidence,
+        data: &I,
+        policy: NonPositivePolicy<F>,
+    ) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter_with_policy(data, policy)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float> Default for Harmonic<F> {
+    fn default() -> Self {
+        Self {
+            recip_space: Arithmetic::default(),
+            policy: NonPositivePolicy::default(),
+            skipped: 0,
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Harmonic<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Harmonic<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+///
+/// Represents the state of the computation of the geometric mean.
+/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the logarithms of the samples.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geometric<F: Float> {
+    log_space: Arithmetic<F>,
+    policy: NonPositivePolicy<F>,
+    skipped: usize,
+}
+
+impl<F: Float> Geometric<F> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Geometric::new();
+    /// stats.append(10.)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the sample is not strictly positive (depending on the policy, see [`Geometric::with_policy`])
+    ///
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        match self.policy.apply(x)? {
+            Some(x) => self.log_space.append(to_log_space(x)?)?,
+            None => self.skipped += 1,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Create a new empty state with the given policy for samples that are not strictly positive
+    ///
+    pub fn with_policy(policy: NonPositivePolicy<F>) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Create a new state with the given policy for samples that are not strictly positive, and populate it with data
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the input data contains values that are not strictly positive (depending on the policy)
+    ///
+    pub fn from_iter_with_policy<I>(data: &I, policy: NonPositivePolicy<F>) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut stats = Self::with_policy(policy);
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Policy for samples that are not strictly positive
+    ///
+    pub fn policy(&self) -> NonPositivePolicy<F> {
+        self.policy
+    }
+
+    ///
+    /// Number of samples that were ignored by the policy [`NonPositivePolicy::Skip`]
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn skipped_count(&self) -> usize {
+        self.skipped
+    }
+
+    ///
+    /// Geometric mean of the sample
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.log_space.sample_mean().exp() - self.policy.offset()
+    }
+
+    ///
+    /// Standard error of the geometric mean
+    ///
+    /// Computed as: \\( G \frac{s_{\log x_i}}{\sqrt{n-1}} \\)
+    /// where \\( G \\) is the geometric mean of the sample;
+    /// \\( s_{\log x_i} \\) is the estimate of the standard deviation of the logarithms of the samples;
+    /// and \\( n-1 \\) is the degree of freedom of the sample data.
+    ///
+    ///  # Reference
+    ///
+    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let geom_mean = self.log_space.sample_mean().exp();
+        let log_std_dev = self.log_space.sample_std_dev();
+        geom_mean * log_std_dev / F::from(self.log_space.sample_count() - 1).unwrap().sqrt()
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.log_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the geometric mean
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let arith_ci = self.log_space.ci_mean(confidence)?;
+        from_log_space(confidence, arith_ci).map(|ci| self.policy.shifted(ci))
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the two states have different policies for non-positive values
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        assert!(
+            self.policy == rhs.policy,
+            "cannot combine means with different policies for non-positive values"
+        );
+        Self {
+            log_space: self.log_space + rhs.log_space,
+            policy: self.policy,
+            skipped: self.skipped + rhs.skipped,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample, with the given policy for samples that are not strictly positive
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    /// * `policy` - The policy for samples that are not strictly positive
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few (non-skipped) samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains values that are not strictly positive (depending on the policy)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci_with_policy<I>(
+        confidence: Confidence,
+        data: &I,
+        policy: NonPositivePolicy<F>,
+    ) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter_with_policy(data, policy)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float> Default for Geometric<F> {
+    fn default() -> Self {
+        Self {
+            log_space: Arithmetic::default(),
+            policy: NonPositivePolicy::default(),
+            skipped: 0,
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Geometric<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Geometric<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+///
+/// Represents the state of the computation of the quadratic mean (aka. root mean square, RMS).
+/// \\[
+/// Q = \sqrt{\frac{1}{n} \sum_i x_i^2}
+/// \\]
+///
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the squares of the samples.
+/// The confidence interval is computed on the mean of the squares and mapped back through the square root.
+/// Unlike [`Power`] with \\( p = 2 \\), negative samples are allowed (e.g., signals or errors).
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // prediction errors
+/// let errors = [0.5, -1.2, 0.3, 2.1, -0.7, -1.5, 0.9, 0.1, -0.4, 1.8];
+/// let stats = mean::Quadratic::from_iter(&errors)?;
+/// assert_abs_diff_eq!(stats.sample_mean(), 1.1467345, epsilon = 1e-6);
+/// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.4774511, 1.5498518)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * [Wikipedia - Root mean square](https://en.wikipedia.org/wiki/Root_mean_square)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quadratic<F: Float> {
+    square_space: Arithmetic<F>,
+}
+
+impl<F: Float> Quadratic<F> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Quadratic::new();
+    /// stats.append(-10.)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        if x.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.square_space.append(x * x)
+    }
+
+    ///
+    /// Quadratic mean (root mean square) of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.square_space.sample_mean().sqrt()
+    }
+
+    ///
+    /// Standard error of the quadratic mean (delta method)
+    /// \\( s_Q = \frac{1}{2Q} \frac{s_{x_i^2}}{\sqrt{n-1}} \\)
+    ///
+    /// where \\( s_{x_i^2} \\) is the estimate of the standard deviation of the squares of the samples.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let rms = self.sample_mean();
+        self.square_space.sample_sem() / (rms + rms)
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.square_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the quadratic mean
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let square_ci = self.square_space.ci_mean(confidence)?;
+        // the mean of the squares cannot be negative
+        let (lo, hi) = (
+            square_ci.low_f().max(F::zero()).sqrt(),
+            square_ci.high_f().max(F::zero()).sqrt(),
+        );
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        Self {
+            square_space: self.square_space + rhs.square_space,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float> Default for Quadratic<F> {
+    fn default() -> Self {
+        Self {
+            square_space: Arithmetic::default(),
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Quadratic<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Quadratic<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+///
+/// Represents the state of the computation of the power mean (aka. generalized mean) with exponent \\( p \\):
+/// \\[
+/// M_p = \left( \frac{1}{n} \sum_i x_i^p \right)^{1/p}
+/// \\]
+///
+/// The arithmetic, geometric, and harmonic means are the power means with \\( p = 1 \\), \\( p = 0 \\) (as a limit), and \\( p = -1 \\),
+/// respectively, and the root mean square is the power mean with \\( p = 2 \\).
+///
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the samples raised to the power \\( p \\)
+/// (or of their logarithms when \\( p = 0 \\)). The confidence interval is computed in that space and mapped back through the
+/// \\( p \\)-th root, which is decreasing when \\( p < 0 \\) (see [`Confidence::flipped`]).
+///
+/// Unlike the other means, the exponent is part of the state, so this type does not implement [`StatisticsOps`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // the power mean with p = -1 is the harmonic mean
+/// let stats = mean::Power::from_iter(&data, -1.)?;
+/// assert_abs_diff_eq!(stats.sample_mean(), mean::Harmonic::from_iter(&data)?.sample_mean(), epsilon = 1e-10);
+/// assert_abs_diff_eq!(stats.ci_mean(confidence)?, mean::Harmonic::ci(confidence, &data)?, epsilon = 1e-10);
+/// // the power mean with p = 3 (cubic mean)
+/// let ci = mean::Power::ci(confidence, &data, 3.)?;
+/// assert!(ci.contains(&mean::Power::from_iter(&data, 3.)?.sample_mean()));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * [Wikipedia - Generalized mean](https://en.wikipedia.org/wiki/Generalized_mean)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Power<F: Float> {
+    exponent: F,
+    power_space: Arithmetic<F>,
+}
+
+impl<F: Float> Power<F> {
+    ///
+    /// Create a new empty state for the power mean with the given exponent
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Power::new(2.);
     /// stats.append(10.)?;
     /// assert_eq!(stats.sample_count(), 1);
     /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    pub fn new() -> Self {
-        Default::default()
+    pub fn new(exponent: F) -> Self {
+        Self {
+            exponent,
+            power_space: Arithmetic::default(),
+        }
+    }
+
+    ///
+    /// Create a new state for the power mean with the given exponent, and populate it with data
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the input data contains negative values, or zero values when \\( p \leq 0 \\)
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I, exponent: F) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut stats = Self::new(exponent);
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Exponent \\( p \\) of the power mean
+    ///
+    pub fn exponent(&self) -> F {
+        self.exponent
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the value is negative, or zero when \\( p \leq 0 \\)
+    /// * [`CIError::InvalidInputData`] - If the value is invalid (e.g. NaN)
+    ///
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        if x.is_nan() {
+            return Err(CIError::InvalidInputData);
+        }
+        let x = if self.exponent == F::zero() {
+            to_log_space(x)?
+        } else if x < F::zero() || (x == F::zero() && self.exponent < F::zero()) {
+            return Err(CIError::NonPositiveValue(x.to_f64().unwrap_or(f64::NAN)));
+        } else {
+            x.powf(self.exponent)
+        };
+        self.power_space.append(x)
+    }
+
+    ///
+    /// Extend the data with additional sample data.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the input data contains negative values, or zero values when \\( p \leq 0 \\)
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        for x_i in data {
+            self.append(*x_i)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Power mean of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        if self.exponent == F::zero() {
+            self.power_space.sample_mean().exp()
+        } else {
+            self.power_space.sample_mean().powf(self.exponent.recip())
+        }
+    }
+
+    ///
+    /// Standard error of the power mean (delta method)
+    /// \\( s_{M_p} = \left| \frac{M_p^{1-p}}{p} \right| \frac{s_{x_i^p}}{\sqrt{n-1}} \\)
+    ///
+    /// where \\( s_{x_i^p} \\) is the estimate of the standard deviation of the samples raised to the power \\( p \\).
+    /// When \\( p = 0 \\), this is \\( M_0 \frac{s_{\log x_i}}{\sqrt{n-1}} \\).
+    /// This coincides with the standard errors of the arithmetic, geometric, and harmonic means for \\( p = 1, 0, -1 \\).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let mean = self.sample_mean();
+        let sem = self.power_space.sample_sem();
+        if self.exponent == F::zero() {
+            mean * sem
+        } else {
+            (mean.powf(F::one() - self.exponent) / self.exponent).abs() * sem
+        }
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.power_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the power mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are too few samples to compute the confidence interval
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let p = self.exponent;
+        if p == F::zero() {
+            let log_ci = self.power_space.ci_mean(confidence)?;
+            return from_log_space(confidence, log_ci);
+        }
+        // the mean of the powers of non-negative values cannot be negative
+        let root = |x: F| x.max(F::zero()).powf(p.recip());
+        let (lo, hi) = if p > F::zero() {
+            let power_ci = self.power_space.ci_mean(confidence)?;
+            (root(power_ci.low_f()), root(power_ci.high_f()))
+        } else {
+            let power_ci = self.power_space.ci_mean(confidence.flipped())?;
+            (root(power_ci.high_f()), root(power_ci.low_f()))
+        };
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the two states have different exponents
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        assert!(
+            self.exponent == rhs.exponent,
+            "cannot combine power means with different exponents"
+        );
+        Self {
+            exponent: self.exponent,
+            power_space: self.power_space + rhs.power_space,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the power mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    /// * `exponent` - The exponent \\( p \\) of the power mean
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains negative values, or zero values when \\( p \leq 0 \\)
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I, exponent: F) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data, exponent)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float> core::ops::Add for Power<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Power<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+///
+/// Represents the state of the computation of a weighted arithmetic mean, where each sample carries
+/// a non-negative weight (e.g., an importance weight, the inverse of a sampling rate, or a duration).
+///
+/// The weighted mean is \\( \bar{x}_w = \sum_i w_i x_i / \sum_i w_i \\). Its standard error is estimated from the
+/// unbiased weighted variance (with reliability weights) and Kish's effective sample size
+/// \\[
+/// n_{\text{eff}} = \frac{\left(\sum_i w_i\right)^2}{\sum_i w_i^2}
+/// \\]
+/// as \\( SE = s_w / \sqrt{n_{\text{eff}}} \\), and the interval uses the Student t distribution with
+/// \\( n_{\text{eff}} - 1 \\) degrees of freedom. With equal weights, this is the usual interval on the arithmetic mean.
+///
+/// The weights are treated as relative: scaling all weights by the same factor does not change the result.
+/// In particular, integer weights are __not__ interpreted as frequencies (i.e., repeated samples).
+///
+/// Weighted geometric and harmonic means are available through the [`WeightedMeanCI`] trait.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // (value, weight) pairs
+/// let data = [(10.6, 1.), (6.6, 2.), (26.7, 3.), (0.4, 4.), (5.7, 1.), (0.3, 2.), (1.1, 3.)];
+/// let stats = mean::Weighted::from_iter(&data)?;
+/// assert_abs_diff_eq!(stats.sample_mean(), 7.19375, epsilon = 1e-10);
+/// assert_abs_diff_eq!(stats.effective_sample_size(), 256. / 44., epsilon = 1e-10);
+/// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+/// assert!(ci.contains(&7.19375));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * L. Kish. Survey Sampling. Wiley, 1965.
+/// * [Wikipedia - Weighted arithmetic mean](https://en.wikipedia.org/wiki/Weighted_arithmetic_mean#Reliability_weights)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weighted<F: Float> {
+    count: usize,
+    mean: F,
+    sum_sq_dev: F,
+    sum_weights: utils::KahanSum<F>,
+    sum_sq_weights: utils::KahanSum<F>,
+}
+
+impl<F: Float> Default for Weighted<F> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: F::zero(),
+            sum_sq_dev: F::zero(),
+            sum_weights: utils::KahanSum::default(),
+            sum_sq_weights: utils::KahanSum::default(),
+        }
+    }
+}
+
+impl<F: Float> Weighted<F> {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state from (value, weight) pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some weight is negative or not finite
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append a new sample with its weight (West's algorithm)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the value is not finite, or the weight is negative or not finite;
+    ///   the state is then left unchanged
+    ///
+    pub fn append(&mut self, x: F, weight: F) -> CIResult<()> {
+        if !x.is_finite() || !weight.is_finite() || weight < F::zero() {
+            return Err(CIError::InvalidInputData);
+        }
+        if weight == F::zero() {
+            return Ok(());
+        }
+        self.count += 1;
+        self.sum_weights += weight;
+        self.sum_sq_weights += weight * weight;
+        let delta = x - self.mean;
+        self.mean = self.mean + delta * weight / self.sum_weights.value();
+        self.sum_sq_dev = self.sum_sq_dev + weight * delta * (x - self.mean);
+        Ok(())
+    }
+
+    ///
+    /// Append (value, weight) pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some weight is negative or not finite;
+    ///   the pairs before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        for &(x, weight) in data {
+            self.append(x, weight)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of samples with a positive weight
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Sum of the weights
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sum_weights(&self) -> F {
+        self.sum_weights.value()
+    }
+
+    ///
+    /// Weighted mean of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.mean
+    }
+
+    ///
+    /// Unbiased weighted variance of the sample (using reliability weights)
+    /// \\( \frac{\sum_i w_i (x_i - \bar{x}_w)^2}{\sum_i w_i - \sum_i w_i^2 / \sum_i w_i} \\)
+    ///
+    /// The result is NaN if less than two samples have a positive weight.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_variance(&self) -> F {
+        if self.count < 2 {
+            return F::nan();
+        }
+        let sum_weights = self.sum_weights.value();
+        self.sum_sq_dev / (sum_weights - self.sum_sq_weights.value() / sum_weights)
+    }
+
+    ///
+    /// Effective sample size implied by the weights
+    /// \\( n_{\text{eff}} = \left(\sum_i w_i\right)^2 / \sum_i w_i^2 \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn effective_sample_size(&self) -> F {
+        if self.count == 0 {
+            return F::zero();
+        }
+        let sum_weights = self.sum_weights.value();
+        sum_weights * sum_weights / self.sum_sq_weights.value()
+    }
+
+    ///
+    /// Standard error of the weighted mean
+    /// \\( s_w / \sqrt{n_{\text{eff}}} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> F {
+        (self.sample_variance() / self.effective_sample_size()).sqrt()
     }
 
     ///
-    /// Append a new sample to the data
+    /// Confidence interval of the weighted mean
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    pub fn append(&mut self, x: F) -> CIResult<()> {
-        if x <= F::zero() {
-            return Err(error::CIError::NonPositiveValue(
-                x.to_f64().unwrap_or(f64::NAN),
-            ));
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have a positive weight
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let degrees_of_freedom = self.effective_sample_size() - F::one();
+        let (lo, hi) = stats::float_interval_bounds(
+            confidence,
+            self.sample_mean(),
+            self.sample_sem(),
+            degrees_of_freedom,
+        )?;
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
         }
-        self.log_space.append(x.ln())?;
-        Ok(())
     }
 
     ///
-    /// Geometric mean of the sample
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        if rhs.count == 0 {
+            return self;
+        }
+        if self.count == 0 {
+            return rhs;
+        }
+        let (w_a, w_b) = (self.sum_weights.value(), rhs.sum_weights.value());
+        let mut sum_weights = self.sum_weights;
+        sum_weights += rhs.sum_weights;
+        let mut sum_sq_weights = self.sum_sq_weights;
+        sum_sq_weights += rhs.sum_sq_weights;
+        let delta = rhs.mean - self.mean;
+        let total = sum_weights.value();
+        Self {
+            count: self.count + rhs.count,
+            mean: self.mean + delta * w_b / total,
+            sum_sq_dev: self.sum_sq_dev + rhs.sum_sq_dev + delta * delta * w_a * w_b / total,
+            sum_weights,
+            sum_sq_weights,
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Weighted<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Weighted<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+///
+/// Represents the statistics of a sample needed to compute the confidence interval on its trimmed mean,
+/// using Yuen's method.
+///
+/// The \\( \gamma \\)-trimmed mean discards the \\( g = \lfloor \gamma n \rfloor \\) smallest and the \\( g \\) largest
+/// samples, and averages the \\( h = n - 2g \\) remaining ones. It is robust to outliers, while being much more
+/// efficient than the median for nearly normal data; trimming 10% to 20% on each side is a common recommendation.
+///
+/// The standard error of the trimmed mean is estimated from the winsorized variance \\( s_w^2 \\)
+/// (i.e., the variance of the sample where the trimmed samples are replaced by the nearest remaining ones):
+/// \\[
+/// SE = \sqrt{\frac{(n-1)\ s_w^2}{h(h-1)}}
+/// \\]
+/// and the interval uses the Student t distribution with \\( h-1 \\) degrees of freedom.
+/// With no trimming, this is the usual interval on the arithmetic mean.
+///
+/// Unlike the other means, the computation requires the whole sample (to sort it), so this is not an incremental
+/// statistic. It is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 100.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // 20% trimmed mean: the outlier does not affect the interval
+/// let ci = mean::Trimmed::ci(confidence, &data, 0.2)?;
+/// assert_abs_diff_eq!(ci, Interval::new(2.979466, 9.020534)?, epsilon = 1e-6);
+///
+/// let stats = mean::Trimmed::from_iter(&data, 0.2)?;
+/// assert_eq!(stats.sample_mean(), 6.);
+/// assert_eq!(stats.trimmed_count(), 7);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * K.K. Yuen. The two-sample trimmed t for unequal population variances. Biometrika, 61(1):165-170, 1974.
+/// * R.R. Wilcox. Introduction to Robust Estimation and Hypothesis Testing (4th edition), Section 4.4. Academic Press, 2017.
+/// * [Wikipedia - Truncated mean](https://en.wikipedia.org/wiki/Truncated_mean)
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trimmed<F: Float> {
+    count: usize,
+    trimmed_count: usize,
+    mean: F,
+    winsorized_variance: F,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<F: Float> Trimmed<F> {
+    ///
+    /// Compute the statistics of the trimmed mean of a sample.
+    ///
+    /// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to compute the statistics on
+    /// * `trim_fraction` - The fraction \\( \gamma \\) of the samples to trim on __each__ side (must be in [0, 0.5))
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidTrimFraction`] - If the trim fraction is not in [0, 0.5)
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::TooFewSamples`] - If fewer than two samples remain after trimming
+    ///
+    pub fn from_iter<I>(data: &I, trim_fraction: f64) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        if !(0. ..0.5).contains(&trim_fraction) {
+            return Err(CIError::InvalidTrimFraction(trim_fraction));
+        }
+        let mut sorted: Vec<F> = data.into_iter().copied().collect();
+        if sorted.iter().any(|x| x.is_nan()) {
+            return Err(CIError::InvalidInputData);
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let count = sorted.len();
+        let trim = (trim_fraction * count as f64).floor() as usize;
+        let trimmed_count = count.saturating_sub(2 * trim);
+        if trimmed_count < 2 {
+            return Err(CIError::TooFewSamples(count));
+        }
+        let kept = &sorted[trim..count - trim];
+        let (lowest, highest) = (kept[0], kept[trimmed_count - 1]);
+        let n_trim = F::from(trim).convert("trim")?;
+
+        let mut sum = utils::KahanSum::default();
+        kept.iter().for_each(|&x| sum += x);
+        let mean = sum.value() / F::from(trimmed_count).convert("trimmed_count")?;
+
+        let mut winsorized_sum = sum;
+        winsorized_sum += n_trim * (lowest + highest);
+        let winsorized_mean = winsorized_sum.value() / F::from(count).convert("count")?;
+        let mut sum_sq_dev = utils::KahanSum::default();
+        kept.iter()
+            .for_each(|&x| sum_sq_dev += (x - winsorized_mean).powi(2));
+        sum_sq_dev += n_trim * (lowest - winsorized_mean).powi(2);
+        sum_sq_dev += n_trim * (highest - winsorized_mean).powi(2);
+        let winsorized_variance = sum_sq_dev.value() / F::from(count - 1).convert("count")?;
+
+        Ok(Self {
+            count,
+            trimmed_count,
+            mean,
+            winsorized_variance,
+        })
+    }
+
+    ///
+    /// Trimmed mean of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
     ///
     pub fn sample_mean(&self) -> F {
-        self.log_space.sample_mean().exp()
+        self.mean
     }
 
     ///
-    /// Standard error of the geometric mean
+    /// Winsorized variance of the sample
     ///
-    /// Computed as: \\( G \frac{s_{\log x_i}}{\sqrt{n-1}} \\)
-    /// where \\( G \\) is the geometric mean of the sample;
-    /// \\( s_{\log x_i} \\) is the estimate of the standard deviation of the logarithms of the samples;
-    /// and \\( n-1 \\) is the degree of freedom of the sample data.
+    /// Complexity: \\( O(1) \\)
     ///
-    ///  # Reference
+    pub fn sample_winsorized_variance(&self) -> F {
+        self.winsorized_variance
+    }
+
     ///
-    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    /// Standard error of the trimmed mean
+    /// \\( \sqrt{\frac{(n-1)\ s_w^2}{h(h-1)}} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
     ///
     pub fn sample_sem(&self) -> F {
-        let geom_mean = self.sample_mean();
-        let log_std_dev = self.log_space.sample_std_dev();
-        geom_mean * log_std_dev / F::from(self.log_space.sample_count() - 1).unwrap().sqrt()
+        let n = F::from(self.count).unwrap();
+        let h = F::from(self.trimmed_count).unwrap();
+        ((n - F::one()) * self.winsorized_variance / (h * (h - F::one()))).sqrt()
     }
 
     ///
-    /// Number of samples
+    /// Number of samples (before trimming)
     ///
     /// Complexity: \\( O(1) \\)
     ///
     pub fn sample_count(&self) -> usize {
-        self.log_space.sample_count()
+        self.count
     }
 
     ///
-    /// Confidence interval for the geometric mean
+    /// Number of samples remaining after trimming
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn trimmed_count(&self) -> usize {
+        self.trimmed_count
+    }
+
+    ///
+    /// Confidence interval of the trimmed mean
+    ///
+    /// Complexity: \\( O(1) \\)
     ///
     pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let arith_ci = self.log_space.ci_mean(confidence)?;
-        let (lo, hi) = (arith_ci.low_f().exp(), arith_ci.high_f().exp());
+        let degrees_of_freedom = F::from(self.trimmed_count - 1).convert("trimmed_count")?;
+        let (lo, hi) = stats::float_interval_bounds(
+            confidence,
+            self.sample_mean(),
+            self.sample_sem(),
+            degrees_of_freedom,
+        )?;
         match confidence {
             Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
             Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
@@ -720,65 +2879,180 @@ impl<F: Float> Geometric<F> {
     }
 
     ///
-    /// Combine two states
+    /// Compute the confidence interval on the trimmed mean of a sample
     ///
-    /// Complexity: \\( O(1) \\)
+    /// # Arguments
     ///
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(self, rhs: Self) -> Self {
-        Self {
-            log_space: self.log_space + rhs.log_space,
-        }
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    /// * `trim_fraction` - The fraction of the samples to trim on __each__ side (must be in [0, 0.5))
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidTrimFraction`] - If the trim fraction is not in [0, 0.5)
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::TooFewSamples`] - If fewer than two samples remain after trimming
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I, trim_fraction: f64) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data, trim_fraction)?.ci_mean(confidence)
     }
+}
+
+///
+/// Number of consecutive samples aggregated sequentially by a single task in parallel computations.
+///
+#[cfg(feature = "parallel")]
+const PAR_CHUNK_SIZE: usize = 1 << 14;
 
+///
+/// Trait for computing confidence intervals on the mean from a parallel iterator
+/// (requires the feature `parallel`).
+///
+/// The data is split into fixed-size chunks of consecutive samples, each aggregated by a single task
+/// into a compensated partial state. The partial states are then merged in the order of the chunks.
+/// Since neither the chunk boundaries nor the merge order depend on the scheduling of the tasks,
+/// the result is deterministic: it is the same regardless of the number of threads.
+///
+/// This trait is implemented for [`mean::Arithmetic`], [`mean::Geometric`], and [`mean::Harmonic`].
+/// The data must be given as an indexed parallel iterator over values (e.g., `vec.into_par_iter()`
+/// or `slice.par_iter().copied()`).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// use rayon::prelude::*;
+/// let data = (1..=1_000_000).map(|x| x as f64).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = mean::Arithmetic::par_ci(confidence, data.par_iter().copied())?;
+/// assert_abs_diff_eq!(ci, mean::Arithmetic::ci(confidence, &data)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(feature = "parallel")]
+pub trait ParMeanCI<F: Float + Send>:
+    StatisticsOps<F> + core::ops::Add<Output = Self> + Send
+{
     ///
-    /// Compute the confidence interval on the mean of a sample
+    /// Create a new state and populates it with data from a parallel iterator
     ///
-    /// # Arguments
+    /// Complexity: \\( O(n/p) \\), where \\( n \\) is the number of elements in `data` and \\( p \\) the number of threads
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// # Errors
     ///
-    /// # Output
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    fn par_from_iter<I>(data: I) -> CIResult<Self>
+    where
+        I: rayon::iter::IntoParallelIterator<Item = F>,
+        I::Iter: rayon::iter::IndexedParallelIterator,
+    {
+        use rayon::prelude::*;
+        let partials: Vec<CIResult<Self>> = data
+            .into_par_iter()
+            .fold_chunks(
+                PAR_CHUNK_SIZE,
+                || Ok(Self::default()),
+                |state: CIResult<Self>, x| {
+                    let mut state = state?;
+                    state.append(x)?;
+                    Ok(state)
+                },
+            )
+            .collect();
+        partials
+            .into_iter()
+            .try_fold(Self::default(), |acc, partial| Ok(acc + partial?))
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample given as a parallel iterator
     ///
     /// # Errors
     ///
     /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
     /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
     /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
     ///
-    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    fn par_ci<I>(confidence: Confidence, data: I) -> CIResult<Interval<F>>
     where
-        for<'a> &'a I: IntoIterator<Item = &'a F>,
+        I: rayon::iter::IntoParallelIterator<Item = F>,
+        I::Iter: rayon::iter::IndexedParallelIterator,
     {
-        Self::from_iter(data)?.ci_mean(confidence)
+        Self::par_from_iter(data)?.ci_mean(confidence)
     }
 }
 
-impl<F: Float> Default for Geometric<F> {
-    fn default() -> Self {
-        Self {
-            log_space: Arithmetic::default(),
-        }
+#[cfg(feature = "parallel")]
+impl<F: Float + Send> ParMeanCI<F> for Arithmetic<F> {}
+#[cfg(feature = "parallel")]
+impl<F: Float + Send> ParMeanCI<F> for Harmonic<F> {}
+#[cfg(feature = "parallel")]
+impl<F: Float + Send> ParMeanCI<F> for Geometric<F> {}
+
+///
+/// Maps a sample value to the reciprocal space used for harmonic means.
+///
+/// # Errors
+///
+/// * [`CIError::NonPositiveValue`] - If the value is not strictly positive
+///
+pub(crate) fn to_recip_space<F: Float>(x: F) -> CIResult<F> {
+    if x <= F::zero() {
+        return Err(CIError::NonPositiveValue(x.to_f64().unwrap_or(f64::NAN)));
     }
+    Ok(F::one() / x)
 }
 
-impl<F: Float> core::ops::Add for Geometric<F> {
-    type Output = Self;
+///
+/// Maps a sample value to the logarithmic space used for geometric means.
+///
+/// # Errors
+///
+/// * [`CIError::NonPositiveValue`] - If the value is not strictly positive
+///
+pub(crate) fn to_log_space<F: Float>(x: F) -> CIResult<F> {
+    if x <= F::zero() {
+        return Err(CIError::NonPositiveValue(x.to_f64().unwrap_or(f64::NAN)));
+    }
+    Ok(x.ln())
+}
 
-    #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add(rhs)
+///
+/// Maps a confidence interval on the arithmetic mean of the reciprocals back to the harmonic mean.
+///
+/// The interval in reciprocal space must have been computed with the flipped confidence
+/// (see [`Confidence::flipped`]) since the reciprocal reverses the order of the bounds.
+///
+pub(crate) fn from_recip_space<F: Float>(
+    confidence: Confidence,
+    recip_ci: Interval<F>,
+) -> CIResult<Interval<F>> {
+    let (lo, hi) = (F::one() / recip_ci.high_f(), F::one() / recip_ci.low_f());
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
     }
 }
 
-impl<F: Float> core::ops::AddAssign for Geometric<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.add(rhs);
+///
+/// Maps a confidence interval on the arithmetic mean of the logarithms back to the geometric mean.
+///
+pub(crate) fn from_log_space<F: Float>(
+    confidence: Confidence,
+    log_ci: Interval<F>,
+) -> CIResult<Interval<F>> {
+    let (lo, hi) = (log_ci.low_f().exp(), log_ci.high_f().exp());
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
     }
 }
 
@@ -811,47 +3085,190 @@ impl<F: Float> core::ops::AddAssign for Geometric<F> {
 /// # Ok::<(),error::CIError>(())
 /// ```
 ///
-pub trait MeanCI<T: PartialOrd> {
+pub trait MeanCI<T: PartialOrd> {
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>;
+}
+
+macro_rules! impl_mean_ci_for {
+    ( $x:ty ) => {
+        impl<F: Float> MeanCI<F> for $x {
+            fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+            where
+                for<'a> &'a I: IntoIterator<Item = &'a F>,
+            {
+                <$x>::ci(confidence, data)
+            }
+        }
+    };
+}
+
+impl_mean_ci_for!(Arithmetic<F>);
+impl_mean_ci_for!(Harmonic<F>);
+impl_mean_ci_for!(Geometric<F>);
+impl_mean_ci_for!(Quadratic<F>);
+
+estimate::impl_statistic_for_mean!(Arithmetic<F>, sem_over_n_minus_one);
+estimate::impl_statistic_for_mean!(Harmonic<F>, sem_over_n_minus_one);
+estimate::impl_statistic_for_mean!(Geometric<F>, sem_over_n_minus_one);
+estimate::impl_statistic_for_mean!(Quadratic<F>, sem_over_n_minus_one);
+estimate::impl_statistic_for_mean!(Power<F>, sem_over_n_minus_one);
+estimate::impl_statistic_for_mean!(Weighted<F>);
+estimate::impl_statistic_for_mean!(Trimmed<F>);
+
+///
+/// Trait for computing weighted means, and confidence intervals on them, from (value, weight) pairs.
+///
+/// The weighted means are computed in the same space as the corresponding unweighted means:
+/// - [`mean::Arithmetic`]: \\( \sum_i w_i x_i / \sum_i w_i \\) (see [`mean::Weighted`])
+/// - [`mean::Geometric`]: \\( \exp\left(\sum_i w_i \log x_i / \sum_i w_i\right) \\)
+/// - [`mean::Harmonic`]: \\( \sum_i w_i / \sum_i (w_i / x_i) \\)
+///
+/// The intervals are based on the effective sample size of the weights (see [`mean::Weighted`]).
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// // average speed over segments: the harmonic mean weighted by the distance of each segment
+/// let data = [(60., 10.), (30., 5.), (90., 30.), (50., 20.), (70., 15.)]; // (speed, distance)
+/// let mean = mean::Harmonic::weighted_mean(&data)?;
+/// assert_abs_diff_eq!(mean, 80. / (10. / 60. + 5. / 30. + 30. / 90. + 20. / 50. + 15. / 70.), epsilon = 1e-10);
+/// let ci = mean::Harmonic::ci_weighted(Confidence::new_two_sided(0.95), &data)?;
+/// assert!(ci.contains(&mean));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub trait WeightedMeanCI<T: PartialOrd> {
     ///
-    /// Compute the confidence interval on the mean of a sample
+    /// Compute the weighted mean of a sample
     ///
     /// # Arguments
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// * `data` - The (value, weight) pairs
     ///
-    /// # Output
+    /// # Errors
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    /// * [`CIError::TooFewSamples`] - If no sample has a positive weight
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some weight is negative or not finite
+    ///
+    fn weighted_mean<I>(data: &I) -> CIResult<T>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (T, T)>;
+
+    ///
+    /// Compute the confidence interval on the weighted mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The (value, weight) pairs
     ///
     /// # Errors
     ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::TooFewSamples`] - If less than two samples have a positive weight
     /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some weight is negative or not finite
     /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
     ///
-    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
+    fn ci_weighted<I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
     where
-        for<'a> &'a I: IntoIterator<Item = &'a T>;
+        for<'a> &'a I: IntoIterator<Item = &'a (T, T)>;
 }
 
-macro_rules! impl_mean_ci_for {
-    ( $x:ty ) => {
-        impl<F: Float> MeanCI<F> for $x {
-            fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-            where
-                for<'a> &'a I: IntoIterator<Item = &'a F>,
-            {
-                <$x>::ci(confidence, data)
-            }
+///
+/// Accumulate the weighted mean of the samples mapped to some space (e.g., logarithmic space).
+///
+fn weighted_in_space<F, I, M>(data: &I, to_space: M) -> CIResult<Weighted<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    M: Fn(F) -> CIResult<F>,
+{
+    let mut state = Weighted::new();
+    for &(x, weight) in data {
+        if x.is_nan() {
+            return Err(CIError::InvalidInputData);
         }
-    };
+        state.append(to_space(x)?, weight)?;
+    }
+    if state.sample_count() == 0 {
+        return Err(CIError::TooFewSamples(0));
+    }
+    Ok(state)
 }
 
-impl_mean_ci_for!(Arithmetic<F>);
-impl_mean_ci_for!(Harmonic<F>);
-impl_mean_ci_for!(Geometric<F>);
+impl<F: Float> WeightedMeanCI<F> for Arithmetic<F> {
+    fn weighted_mean<I>(data: &I) -> CIResult<F>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        Ok(weighted_in_space(data, Ok)?.sample_mean())
+    }
+
+    fn ci_weighted<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        weighted_in_space(data, Ok)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float> WeightedMeanCI<F> for Geometric<F> {
+    fn weighted_mean<I>(data: &I) -> CIResult<F>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        Ok(weighted_in_space(data, to_log_space)?.sample_mean().exp())
+    }
+
+    fn ci_weighted<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let log_ci = weighted_in_space(data, to_log_space)?.ci_mean(confidence)?;
+        from_log_space(confidence, log_ci)
+    }
+}
+
+impl<F: Float> WeightedMeanCI<F> for Harmonic<F> {
+    fn weighted_mean<I>(data: &I) -> CIResult<F>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        Ok(F::one() / weighted_in_space(data, to_recip_space)?.sample_mean())
+    }
+
+    fn ci_weighted<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let recip_ci = weighted_in_space(data, to_recip_space)?.ci_mean(confidence.flipped())?;
+        from_recip_space(confidence, recip_ci)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1014,6 +3431,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn test_arithmetic_add() {
         const VALUE: f32 = 0.1;
         let size = 1_000_000;
@@ -1052,6 +3470,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn test_geometric_add() {
         const VALUE: f32 = 0.1;
         let size = 1_000_000;
@@ -1080,6 +3499,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn test_harmonic_add() {
         const VALUE: f32 = 0.1;
         let size = 1_000_000;
@@ -1119,4 +3539,763 @@ mod tests {
         assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
         Ok(())
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_mean_ci() -> CIResult<()> {
+        use rayon::prelude::*;
+        let data = (0..100_000)
+            .map(|i| 1. + ((i * 7919) % 1000) as f64 / 10.)
+            .collect::<Vec<_>>();
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let par = Arithmetic::par_from_iter(data.par_iter().copied())?;
+        let seq = Arithmetic::from_iter(&data)?;
+        assert_eq!(par.sample_count(), seq.sample_count());
+        assert_abs_diff_eq!(
+            Arithmetic::par_ci(confidence, data.par_iter().copied())?,
+            seq.ci_mean(confidence)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            Geometric::par_ci(confidence, data.par_iter().copied())?,
+            Geometric::ci(confidence, &data)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            Harmonic::par_ci(confidence, data.clone().into_par_iter())?,
+            Harmonic::ci(confidence, &data)?,
+            epsilon = 1e-8
+        );
+
+        // deterministic regardless of the number of threads
+        let single = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(|| Arithmetic::par_from_iter(data.par_iter().copied()))?;
+        assert_eq!(single, par);
+
+        let mut invalid = data.clone();
+        invalid[54_321] = 0.;
+        assert!(matches!(
+            Geometric::par_ci(confidence, invalid.par_iter().copied()),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_ci() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // special cases
+        let cases: [(f64, Interval<f64>); 3] = [
+            (1., Arithmetic::ci(confidence, &data)?),
+            (0., Geometric::ci(confidence, &data)?),
+            (-1., Harmonic::ci(confidence, &data)?),
+        ];
+        for (p, expected) in cases {
+            assert_abs_diff_eq!(Power::ci(confidence, &data, p)?, expected, epsilon = 1e-8);
+        }
+        assert_abs_diff_eq!(
+            Power::ci(Confidence::new_upper(0.9), &data, -1.)?,
+            Harmonic::ci(Confidence::new_upper(0.9), &data)?,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            Power::ci(Confidence::new_lower(0.9), &data, -1.)?,
+            Harmonic::ci(Confidence::new_lower(0.9), &data)?,
+            epsilon = 1e-8
+        );
+
+        assert_abs_diff_eq!(
+            Power::from_iter(&data, 0.)?.sample_sem(),
+            Geometric::from_iter(&data)?.sample_sem(),
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Power::from_iter(&data, -1.)?.sample_sem(),
+            Harmonic::from_iter(&data)?.sample_sem(),
+            epsilon = 1e-10
+        );
+
+        // reference values computed in python
+        let stats = Power::from_iter(&data, 2.)?;
+        assert_eq!(stats.exponent(), 2.);
+        assert_abs_diff_eq!(stats.sample_mean(), 60.51487420461188, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats.sample_sem(), 2.559175502347068, epsilon = 1e-8);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Interval::new(55.23175256514212, 65.37242162091684)?,
+            epsilon = 1e-8
+        );
+        let stats = Power::from_iter(&data, 0.5)?;
+        assert_abs_diff_eq!(stats.sample_sem(), 3.0031801688509674, epsilon = 1e-8);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Interval::new(43.42577311757389, 55.28395578339801)?,
+            epsilon = 1e-8
+        );
+
+        // combining states
+        let (left, right) = data.split_at(40);
+        let combined =
+            Power::from_iter(&left.to_vec(), 3.)? + Power::from_iter(&right.to_vec(), 3.)?;
+        assert_abs_diff_eq!(combined.sample_mean(), 65.42485798589414, epsilon = 1e-8);
+        assert_abs_diff_eq!(
+            combined.ci_mean(confidence)?,
+            Interval::new(60.381521060938226, 69.79148948864254)?,
+            epsilon = 1e-8
+        );
+
+        // domain
+        assert!(Power::ci(confidence, &[0., 1., 2.], 2.).is_ok());
+        assert!(matches!(
+            Power::ci(confidence, &[0., 1., 2.], -2.),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            Power::ci(confidence, &[-1., 1., 2.], 2.),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            Power::ci(confidence, &[f64::NAN, 1., 2.], 2.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_quadratic_ci() -> CIResult<()> {
+        let errors = [0.5, -1.2, 0.3, 2.1, -0.7, -1.5, 0.9, 0.1, -0.4, 1.8];
+        let confidence = Confidence::new_two_sided(0.95);
+        let stats = Quadratic::from_iter(&errors)?;
+        // reference values computed in python
+        assert_abs_diff_eq!(stats.sample_mean(), 1.1467344941179716, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_sem(), 0.2208558011224794, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Interval::new(0.47745107829180644, 1.5498517567296528)?,
+            epsilon = 1e-8
+        );
+        let upper = Quadratic::ci(Confidence::new_upper(0.9), &errors)?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), 0.806479683104213, epsilon = 1e-8);
+
+        // same as the power mean with p = 2 for non-negative data
+        let magnitudes = errors.map(f64::abs);
+        assert_abs_diff_eq!(
+            Quadratic::ci(confidence, &magnitudes)?,
+            Power::ci(confidence, &magnitudes, 2.)?,
+            epsilon = 1e-10
+        );
+
+        // combining states
+        let (left, right) = errors.split_at(4);
+        let combined =
+            Quadratic::from_iter(&left.to_vec())? + Quadratic::from_iter(&right.to_vec())?;
+        assert_abs_diff_eq!(combined.sample_mean(), stats.sample_mean(), epsilon = 1e-10);
+
+        assert!(matches!(
+            Quadratic::ci(confidence, &[1., f64::NAN, 2.]),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_positive_policy() -> CIResult<()> {
+        let data = [0., 3.2, 0., 5.1, 1.7, 8.4, 2.2, 0.9, 4.6, 6.3];
+        let positive = [3.2, 5.1, 1.7, 8.4, 2.2, 0.9, 4.6, 6.3];
+        let shifted = data.map(|x| x + 0.5);
+        let confidence = Confidence::new_two_sided(0.95);
+
+        assert!(matches!(
+            Geometric::ci(confidence, &data),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            Harmonic::ci_with_policy(confidence, &data, NonPositivePolicy::Error),
+            Err(CIError::NonPositiveValue(_))
+        ));
+
+        // skip
+        let stats = Geometric::from_iter_with_policy(&data, NonPositivePolicy::Skip)?;
+        assert_eq!(stats.policy(), NonPositivePolicy::Skip);
+        assert_eq!(stats.sample_count(), 8);
+        assert_eq!(stats.skipped_count(), 2);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Geometric::ci(confidence, &positive)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Harmonic::ci_with_policy(confidence, &data, NonPositivePolicy::Skip)?,
+            Harmonic::ci(confidence, &positive)?,
+            epsilon = 1e-10
+        );
+
+        // offset
+        let offset = NonPositivePolicy::Offset(0.5);
+        let stats = Geometric::from_iter_with_policy(&data, offset)?;
+        assert_eq!(stats.sample_count(), 10);
+        assert_abs_diff_eq!(
+            stats.sample_mean(),
+            Geometric::from_iter(&shifted)?.sample_mean() - 0.5,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            stats.sample_sem(),
+            Geometric::from_iter(&shifted)?.sample_sem(),
+            epsilon = 1e-10
+        );
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+        ] {
+            assert_abs_diff_eq!(
+                Geometric::ci_with_policy(confidence, &data, offset)?,
+                offset.shifted(Geometric::ci(confidence, &shifted)?),
+                epsilon = 1e-10
+            );
+            assert_abs_diff_eq!(
+                Harmonic::ci_with_policy(confidence, &data, offset)?,
+                offset.shifted(Harmonic::ci(confidence, &shifted)?),
+                epsilon = 1e-10
+            );
+        }
+        assert!(matches!(
+            Harmonic::ci_with_policy(confidence, &[-1., 1., 2.], offset),
+            Err(CIError::NonPositiveValue(_))
+        ));
+
+        // combining states
+        let (left, right) = data.split_at(5);
+        let combined = Harmonic::from_iter_with_policy(&left.to_vec(), NonPositivePolicy::Skip)?
+            + Harmonic::from_iter_with_policy(&right.to_vec(), NonPositivePolicy::Skip)?;
+        assert_eq!(combined.skipped_count(), 2);
+        assert_abs_diff_eq!(
+            combined.sample_mean(),
+            Harmonic::from_iter(&positive)?.sample_mean(),
+            epsilon = 1e-10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_trimmed_ci() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let stats = Trimmed::from_iter(&data, 0.2)?;
+        assert_eq!(stats.sample_count(), 100);
+        assert_eq!(stats.trimmed_count(), 60);
+        assert_abs_diff_eq!(stats.sample_mean(), 53.4, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.sample_winsorized_variance(),
+            498.41454545454,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(stats.sample_sem(), 3.7334584321252, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Interval::new(45.92936693303282, 60.87063306696718)?,
+            epsilon = 1e-6
+        );
+
+        let ci = Trimmed::ci(confidence, &data, 0.1)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(47.121116659655, 60.253883340345)?,
+            epsilon = 1e-6
+        );
+
+        // no trimming: same as the arithmetic mean
+        let ci = Trimmed::ci(confidence, &data, 0.)?;
+        assert_abs_diff_eq!(ci, Arithmetic::ci(confidence, &data)?, epsilon = 1e-8);
+
+        // one-sided
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 100.];
+        let ci = Trimmed::ci(Confidence::new_upper(0.95), &data, 0.2)?;
+        assert_abs_diff_eq!(ci.low_f(), 3.6012861850896924, epsilon = 1e-6);
+        assert!(ci.is_upper());
+
+        // errors
+        assert!(matches!(
+            Trimmed::ci(confidence, &data, 0.5),
+            Err(CIError::InvalidTrimFraction(_))
+        ));
+        assert!(matches!(
+            Trimmed::ci(confidence, &data, -0.1),
+            Err(CIError::InvalidTrimFraction(_))
+        ));
+        assert!(matches!(
+            Trimmed::ci(confidence, &[1., 2., 3.], 0.4),
+            Err(CIError::TooFewSamples(3))
+        ));
+        assert!(matches!(
+            Trimmed::ci(confidence, &[1., f64::NAN, 3.], 0.1),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted() -> CIResult<()> {
+        let values = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+            0.4, 10.1, 4.5, 7.1,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // equal weights: same as the unweighted means
+        let data = values.map(|x| (x, 2.5));
+        assert_abs_diff_eq!(
+            Arithmetic::ci_weighted(confidence, &data)?,
+            Arithmetic::ci(confidence, &values)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Geometric::ci_weighted(confidence, &data)?,
+            Geometric::ci(confidence, &values)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Harmonic::ci_weighted(confidence, &data)?,
+            Harmonic::ci(confidence, &values)?,
+            epsilon = 1e-10
+        );
+
+        // unequal weights (reference values computed in python)
+        let data = values
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (x, (i % 4 + 1) as f64))
+            .collect::<Vec<_>>();
+        let stats = Weighted::from_iter(&data)?;
+        assert_eq!(stats.sample_count(), 20);
+        assert_abs_diff_eq!(stats.sum_weights(), 50., epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_mean(), 8.122, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_variance(), 78.69927234042552, epsilon = 1e-9);
+        assert_abs_diff_eq!(stats.effective_sample_size(), 50. / 3., epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            Arithmetic::ci_weighted(confidence, &data)?,
+            Interval::new(3.507453889916622, 12.736546110083374)?,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            Geometric::weighted_mean(&data)?,
+            3.558795331228914,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Geometric::ci_weighted(confidence, &data)?,
+            Interval::new(1.5732230883258926, 8.050367620178964)?,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            Harmonic::weighted_mean(&data)?,
+            1.1881577566996127,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            Harmonic::ci_weighted(confidence, &data)?,
+            Interval::new(0.681618043370607, 4.625756740681507)?,
+            epsilon = 1e-6
+        );
+
+        // one-sided
+        let upper = Harmonic::ci_weighted(Confidence::new_upper(0.975), &data)?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), 0.681618043370607, epsilon = 1e-6);
+
+        // merging states
+        let (left, right) = data.split_at(7);
+        let merged = Weighted::from_iter(&left.to_vec())? + Weighted::from_iter(&right.to_vec())?;
+        assert_abs_diff_eq!(merged.sample_mean(), stats.sample_mean(), epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            merged.sample_variance(),
+            stats.sample_variance(),
+            epsilon = 1e-9
+        );
+        assert_eq!(merged + Weighted::new(), merged);
+
+        // zero weights are ignored; invalid weights are rejected
+        let mut padded = data.clone();
+        padded.push((1000., 0.));
+        assert_abs_diff_eq!(
+            Arithmetic::ci_weighted(confidence, &padded)?,
+            Arithmetic::ci_weighted(confidence, &data)?,
+            epsilon = 1e-10
+        );
+        assert!(matches!(
+            Arithmetic::ci_weighted(confidence, &[(1., 1.), (2., -1.)]),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            Arithmetic::ci_weighted(confidence, &[(1., 1.), (2., 0.)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            Geometric::ci_weighted(confidence, &[(1., 1.), (-2., 1.)]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        let empty: [(f64, f64); 0] = [];
+        assert!(matches!(
+            Harmonic::weighted_mean(&empty),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nan_policy() -> CIResult<()> {
+        let data = [1., 2., f64::NAN, 3., 4., 5., f64::NAN, 6., 7., 8., 9., 10.];
+        let clean = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+        ] {
+            assert!(matches!(
+                Arithmetic::ci_with_nan_policy(confidence, &data, NanPolicy::Error),
+                Err(CIError::InvalidInputData)
+            ));
+            assert_abs_diff_eq!(
+                Geometric::ci_with_nan_policy(confidence, &data, NanPolicy::Ignore)?,
+                Geometric::ci(confidence, &clean)?,
+                epsilon = 1e-10
+            );
+            assert_abs_diff_eq!(
+                Quadratic::ci_with_nan_policy(confidence, &data, NanPolicy::Ignore)?,
+                Quadratic::ci(confidence, &clean)?,
+                epsilon = 1e-10
+            );
+            let ci = Harmonic::ci_with_nan_policy(confidence, &data, NanPolicy::Propagate)?;
+            assert_eq!(ci.is_two_sided(), confidence.is_two_sided());
+            assert_eq!(
+                ci.is_upper(),
+                confidence.kind() == Confidence::new_upper(0.9).kind()
+            );
+            assert!(ci.low_f().is_nan() || ci.high_f().is_nan());
+            // without NaN values, all policies give the same result
+            for policy in [NanPolicy::Error, NanPolicy::Ignore, NanPolicy::Propagate] {
+                assert_eq!(
+                    Arithmetic::ci_with_nan_policy(confidence, &clean, policy)?,
+                    Arithmetic::ci(confidence, &clean)?
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_integer_mean() -> CIResult<()> {
+        let data: Vec<i64> = (0..1000).map(|i| (i * 7919) % 1013 - 200).collect();
+        let float_data = data.iter().map(|&x| x as f64).collect::<Vec<_>>();
+        let stats = Integer::from_iter(&data)?;
+        let reference = Arithmetic::from_iter(&float_data)?;
+        assert_eq!(stats.sample_count(), reference.sample_count());
+        assert_eq!(stats.sample_sum(), data.iter().map(|&x| x as i128).sum());
+        assert_abs_diff_eq!(
+            stats.sample_mean(),
+            reference.sample_mean(),
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            stats.sample_std_dev(),
+            reference.sample_std_dev(),
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            stats.sample_sem(),
+            reference.sample_std_dev() / (data.len() as f64).sqrt(),
+            epsilon = 1e-8
+        );
+        assert!(Integer::<i32>::new().sample_sem().is_nan());
+        assert!(Integer::from_iter(&[3])?.sample_sem().is_nan());
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            assert_abs_diff_eq!(
+                stats.ci_mean(confidence)?,
+                reference.ci_mean(confidence)?,
+                epsilon = 1e-8
+            );
+        }
+
+        // merging states
+        let (left, right) = data.split_at(300);
+        let merged = Integer::from_iter(&left.to_vec())? + Integer::from_iter(&right.to_vec())?;
+        assert_eq!(merged, stats);
+
+        // exact variance of large values with a small spread
+        let data: [u64; 3] = [(1 << 53) + 1, (1 << 53) + 2, (1 << 53) + 3];
+        let stats = Integer::from_iter(&data)?;
+        assert_eq!(stats.sample_variance(), 1.);
+
+        // overflow leaves the state unchanged
+        let mut stats = Integer::from_iter(&[i64::MIN; 3])?;
+        assert!(matches!(stats.append(i64::MIN), Err(CIError::Overflow(_))));
+        assert_eq!(stats.sample_count(), 3);
+        assert_eq!(stats.sample_sum(), 3 * i64::MIN as i128);
+        let mut stats = Integer::<u128>::new();
+        assert!(matches!(stats.append(u128::MAX), Err(CIError::Overflow(_))));
+        assert!(matches!(
+            Integer::<i32>::new().ci_mean(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_sample_size() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(required_sample_size(confidence, 0.1, 1.)?, 387);
+        assert_eq!(required_sample_size(confidence, 1., 1.)?, 7);
+        assert_eq!(required_sample_size(confidence, 2., 1.)?, 4);
+        assert_eq!(required_sample_size(confidence, 0.5, 1.)?, 18);
+        assert_eq!(
+            required_sample_size(Confidence::new_two_sided(0.99), 0.01, 1.)?,
+            66353
+        );
+        assert_eq!(
+            required_sample_size(Confidence::new_upper(0.9), 0.5, 2.)?,
+            28
+        );
+        assert_eq!(
+            required_sample_size(Confidence::new_lower(0.9), 0.5, 2.)?,
+            28
+        );
+        assert_eq!(required_sample_size(confidence, 1., 0.)?, 2);
+
+        // the resulting interval meets the target
+        for (h, sd) in [(0.1, 1.), (3., 7.), (0.02, 0.5)] {
+            let n = required_sample_size(confidence, h, sd)?;
+            let bounds = |n: usize| {
+                let n = n as f64;
+                stats::interval_bounds(confidence, 0., sd / n.sqrt(), n - 1.)
+            };
+            assert!(bounds(n)?.1 <= h);
+            assert!(bounds(n - 1)?.1 > h);
+        }
+
+        // from a pilot sample
+        let pilot = [251., 238., 262., 244., 259., 247., 255., 241., 266., 237.];
+        let stats = Arithmetic::from_iter(&pilot)?;
+        assert_eq!(stats.required_sample_size(confidence, 2.5)?, 68);
+        assert!(matches!(
+            Arithmetic::from_iter(&[1.])?.required_sample_size(confidence, 2.5),
+            Err(CIError::TooFewSamples(1))
+        ));
+
+        assert!(matches!(
+            required_sample_size(confidence, 0., 1.),
+            Err(CIError::InvalidHalfWidth(_))
+        ));
+        assert!(matches!(
+            required_sample_size(confidence, f64::INFINITY, 1.),
+            Err(CIError::InvalidHalfWidth(_))
+        ));
+        assert!(matches!(
+            required_sample_size(confidence, 1., -1.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_many() -> CIResult<()> {
+        let samples = [
+            vec![1.2, 1.4, 1.1, 1.3, 1.5, 1.2],
+            vec![10., 12., 11., 13., 9., 10.],
+            vec![0.5, 0.7, 0.6, 0.4, 0.6, 0.5],
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+        for correction in [Correction::Bonferroni, Correction::Sidak] {
+            let intervals = ci_many(confidence, correction, &samples)?;
+            let individual = confidence.adjusted(3, correction);
+            for (interval, data) in intervals.iter().zip(&samples) {
+                assert_eq!(*interval, Arithmetic::ci(individual, data)?);
+            }
+        }
+        // Sidak is less conservative than Bonferroni
+        let bonferroni = ci_many(confidence, Correction::Bonferroni, &samples)?;
+        let sidak = ci_many(confidence, Correction::Sidak, &samples)?;
+        for (b, s) in bonferroni.iter().zip(&sidak) {
+            assert!(b.includes(s) && b != s);
+        }
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            confidence.adjusted(3, Correction::Bonferroni).level(),
+            0.9833333333333333,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            confidence.adjusted(3, Correction::Sidak).level(),
+            0.9830475724915585,
+            epsilon = 1e-12
+        );
+        assert_eq!(
+            Confidence::new_upper(0.9).adjusted(0, Correction::Sidak),
+            Confidence::new_upper(0.9)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_many_fcr() -> CIResult<()> {
+        let samples = [
+            vec![0.1, -0.2, 0.05, 0.0, -0.1, 0.15],
+            vec![2.1, 1.8, 2.4, 2.0, 2.2, 1.9],
+            vec![-0.3, 0.2, 0.1, -0.1, 0.0, 0.05],
+            vec![0.2, -0.1, -0.2, 0.1, 0.05, -0.05],
+            vec![-1.1, -0.9, -1.3, -1.0, -1.2, -0.8],
+            vec![0.0, 0.1, -0.1, 0.2, -0.2, 0.05],
+        ];
+        let confidence = Confidence::new_two_sided(0.9);
+        let intervals = ci_many_fcr(confidence, &samples, &[1, 4])?;
+        let individual = Confidence::new_two_sided(1. - 2. * 0.1 / 6.);
+        assert_eq!(intervals[0], Arithmetic::ci(individual, &samples[1])?);
+        assert_eq!(intervals[1], Arithmetic::ci(individual, &samples[4])?);
+        assert!(matches!(
+            ci_many_fcr(confidence, &samples, &[6]),
+            Err(CIError::IndexError(_, 6))
+        ));
+
+        let discoveries = ci_discoveries_fcr(confidence, &samples)?;
+        assert_eq!(discoveries.len(), 2);
+        assert_eq!(discoveries[0], (1, intervals[0]));
+        assert_eq!(discoveries[1], (4, intervals[1]));
+
+        // one-sided: only the increase is selected
+        let discoveries = ci_discoveries_fcr(Confidence::new_upper(0.9), &samples)?;
+        assert_eq!(discoveries.len(), 1);
+        assert_eq!(discoveries[0].0, 1);
+        assert!(discoveries[0].1.low_f() > 0.);
+
+        // nothing to discover
+        let discoveries =
+            ci_discoveries_fcr(confidence, &[samples[0].clone(), samples[2].clone()])?;
+        assert!(discoveries.is_empty());
+
+        // reference values computed in python
+        let stats = Arithmetic::from_iter(&samples[0])?;
+        assert_abs_diff_eq!(zero_mean_p_value(confidence, &stats)?, 1., epsilon = 1e-9);
+        let stats = Arithmetic::from_iter(&samples[2])?;
+        assert_abs_diff_eq!(
+            zero_mean_p_value(confidence, &stats)?,
+            0.9113834471752339,
+            epsilon = 1e-9
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_t_test() -> CIResult<()> {
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+            0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+        ];
+        let stats = Arithmetic::from_iter(&data)?;
+
+        // reference values computed in python
+        let test = stats.t_test(Confidence::new_two_sided(0.95), 5.)?;
+        assert_abs_diff_eq!(test.statistic(), 2.7216490654895313, epsilon = 1e-9);
+        assert_abs_diff_eq!(test.p_value(), 0.010871502445993757, epsilon = 1e-9);
+        assert_eq!(test.degrees_of_freedom(), Some(29.));
+
+        // duality with the confidence intervals
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let interval = stats.ci_mean(confidence)?;
+            for mean in [2., 5., 6.5, 8., 10., 12., 14., 18.] {
+                let test = stats.t_test(confidence, mean)?;
+                assert_eq!(test.is_significant(), !interval.contains(&mean));
+            }
+        }
+
+        assert!(matches!(
+            Arithmetic::from_iter(&[1.])?.t_test(Confidence::new(0.95), 0.),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            stats.t_test(Confidence::new(0.95), f64::NAN),
+            Err(CIError::InvalidReference(_))
+        ));
+        assert!(matches!(
+            Arithmetic::from_iter(&[2., 2., 2.])?.t_test(Confidence::new(0.95), 2.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prediction_interval() -> CIResult<()> {
+        let data = [10.4, 10.1, 10.6, 10.2, 10.0, 10.5, 10.3, 10.2];
+        let stats = Arithmetic::from_iter(&data)?;
+
+        // reference values computed in python
+        let prediction = prediction_interval(Confidence::new_two_sided(0.95), &data)?;
+        assert_abs_diff_eq!(prediction.low_f(), 9.77811004442008, epsilon = 1e-10);
+        assert_abs_diff_eq!(prediction.high_f(), 10.79688995557992, epsilon = 1e-10);
+        let prediction = stats.prediction_interval(Confidence::new_upper(0.95))?;
+        assert_abs_diff_eq!(prediction.low_f(), 9.879367794285118, epsilon = 1e-10);
+        assert!(prediction.high_f().is_infinite());
+        let prediction = stats.prediction_interval(Confidence::new_lower(0.95))?;
+        assert_abs_diff_eq!(prediction.high_f(), 10.695632205714882, epsilon = 1e-10);
+
+        // wider than the confidence interval on the mean
+        let confidence = Confidence::new_two_sided(0.9);
+        assert!(stats
+            .ci_mean(confidence)?
+            .is_included_in(&stats.prediction_interval(confidence)?));
+
+        // mean of several future observations
+        let prediction = prediction_interval_mean(Confidence::new_two_sided(0.95), &data, 4)?;
+        assert_abs_diff_eq!(prediction.low_f(), 9.993403572023442, epsilon = 1e-10);
+        assert_abs_diff_eq!(prediction.high_f(), 10.581596427976558, epsilon = 1e-10);
+        let prediction = stats.prediction_interval_mean(Confidence::new_upper(0.95), 4)?;
+        assert_abs_diff_eq!(prediction.low_f(), 10.051864761165557, epsilon = 1e-10);
+        assert_eq!(
+            stats.prediction_interval_mean(confidence, 1)?,
+            stats.prediction_interval(confidence)?
+        );
+        assert!(stats
+            .ci_mean(confidence)?
+            .is_included_in(&stats.prediction_interval_mean(confidence, 1000)?));
+
+        assert!(matches!(
+            prediction_interval(Confidence::new(0.95), &[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            stats.prediction_interval_mean(confidence, 0),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,647 @@
+//!
+//! Meta-analysis: combining independent estimates of the same quantity into a pooled estimate.
+//!
+//! When the same experiment is run independently at several sites (e.g., an A/B test in each datacenter), each site reports an
+//! estimate with its standard error or confidence interval. Averaging the estimates, let alone the bounds of the intervals,
+//! ignores that some sites are much more precise than others.
+//!
+//! * [`FixedEffect`] - inverse-variance weighted pooling, assuming that all the sites estimate the same true value
+//! * [`RandomEffects`] - random-effects pooling (DerSimonian–Laird), when the true value genuinely differs between sites,
+//!   with the heterogeneity statistics \\( \tau^2 \\) and \\( I^2 \\)
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // reduction of the latency (in ms) measured in each datacenter, with its standard error
+//! let results = [(2.1, 0.6), (1.4, 0.4), (2.9, 1.1), (1.8, 0.5)];
+//! let pooled = meta::FixedEffect::from_iter(&results)?;
+//! let ci = pooled.ci(Confidence::new_two_sided(0.95))?;
+//! assert_abs_diff_eq!(pooled.pooled_estimate(), 1.745318, epsilon = 1e-6);
+//! assert_abs_diff_eq!(ci, Interval::new(1.218747, 2.271889)?, epsilon = 1e-6);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * M. Borenstein, L.V. Hedges, J.P.T. Higgins, H.R. Rothstein. Introduction to Meta-Analysis. Wiley, 2009.
+//! * [Wikipedia - Inverse-variance weighting](https://en.wikipedia.org/wiki/Inverse-variance_weighting)
+//!
+use super::*;
+use error::*;
+
+///
+/// Represents the state of the computation of a fixed-effect meta-analysis.
+///
+/// Each study \\( i \\) reports an estimate \\( y_i \\) with a standard error \\( s_i \\). Assuming that all the studies estimate
+/// the same true value, the pooled estimate is the inverse-variance weighted mean
+/// \\[
+/// \hat{\theta} = \frac{\sum_i w_i y_i}{\sum_i w_i} \qquad \text{where } w_i = 1 / s_i^2
+/// \\]
+/// with standard error \\( 1 / \sqrt{\sum_i w_i} \\), and the interval uses the normal distribution.
+///
+/// # Notes
+///
+/// The estimates must be on a scale where they are approximately normal with symmetric intervals; for instance, ratios must be given
+/// on a logarithmic scale (e.g., log response ratios), and the pooled interval transformed back.
+///
+/// When the studies do not estimate the same value (e.g., the effect genuinely differs between sites), the fixed-effect interval is
+/// too narrow.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut pooled = meta::FixedEffect::new();
+/// // results reported as intervals
+/// pooled.append_interval(confidence, &Interval::new(0.8, 3.4)?)?;
+/// pooled.append_interval(confidence, &Interval::new(0.9, 2.5)?)?;
+/// // and as an estimate with its standard error
+/// pooled.append(1.8, 0.5)?;
+/// let estimate = pooled.estimate(confidence)?;
+/// assert_eq!(estimate.sample_count(), 3);
+/// assert!(estimate.interval().is_included_in(&Interval::new(0.9, 2.5)?));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedEffect {
+    count: usize,
+    sum_weights: f64,
+    mean: f64,
+}
+
+impl FixedEffect {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state from `(estimate, std_err)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if an estimate is not finite or a standard error is not strictly positive and finite
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append the result of a study given as an estimate with its standard error
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the estimate is not finite or the standard error is not strictly positive and finite;
+    ///   the state is then left unchanged
+    ///
+    pub fn append(&mut self, estimate: f64, std_err: f64) -> CIResult<()> {
+        check_study(estimate, std_err)?;
+        let weight = 1. / (std_err * std_err);
+        self.count += 1;
+        self.sum_weights += weight;
+        self.mean += (estimate - self.mean) * weight / self.sum_weights;
+        Ok(())
+    }
+
+    ///
+    /// Append the result of a study given as a two-sided confidence interval, assumed to be a symmetric normal interval:
+    /// the estimate is the midpoint and the standard error is the half-width divided by \\( z_{1 - \alpha/2} \\).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the interval reported by the study
+    /// * `interval` - the interval reported by the study
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the interval or the confidence is not two-sided, or the interval is degenerate or not finite
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn append_interval(
+        &mut self,
+        confidence: Confidence,
+        interval: &Interval<f64>,
+    ) -> CIResult<()> {
+        let (estimate, std_err) = from_interval(confidence, interval)?;
+        self.append(estimate, std_err)
+    }
+
+    ///
+    /// Append the results of studies given as `(estimate, std_err)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if an estimate is not finite or a standard error is not strictly positive and finite;
+    ///   the results before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        for &(estimate, std_err) in data {
+            self.append(estimate, std_err)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of studies
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn study_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Pooled estimate, i.e., the inverse-variance weighted mean of the estimates
+    /// (NaN if there are no studies)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn pooled_estimate(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    ///
+    /// Standard error of the pooled estimate, \\( 1 / \sqrt{\sum_i w_i} \\)
+    /// (infinite if there are no studies)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn pooled_std_err(&self) -> f64 {
+        1. / self.sum_weights.sqrt()
+    }
+
+    ///
+    /// Confidence interval of the pooled estimate
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no studies
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        normal_interval(confidence, self.mean, self.pooled_std_err())
+    }
+}
+
+impl Statistic<f64> for FixedEffect {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<f64>> {
+        Ok(CiEstimate::new(
+            self.pooled_estimate(),
+            self.ci(confidence)?,
+            confidence,
+            self.count,
+            self.pooled_std_err(),
+        ))
+    }
+}
+
+///
+/// Represents the state of the computation of a random-effects meta-analysis, with the DerSimonian–Laird estimator of the
+/// between-study variance.
+///
+/// Unlike [`FixedEffect`], the studies are not assumed to estimate the same value: the true value of study \\( i \\) is
+/// \\( \theta_i \sim N(\theta, \tau^2) \\), where \\( \tau^2 \\) is the between-study variance (heterogeneity), and
+/// the parameter of interest is the mean \\( \theta \\) of the distribution of the effects.
+///
+/// The heterogeneity is measured by Cochran's statistic \\( Q = \sum_i w_i (y_i - \hat{\theta}_{FE})^2 \\) with
+/// \\( w_i = 1 / s_i^2 \\), from which:
+/// * the DerSimonian–Laird estimate of the between-study variance is
+///   \\( \hat{\tau}^2 = \max\left(0, \frac{Q - (k - 1)}{\sum_i w_i - \sum_i w_i^2 / \sum_i w_i}\right) \\) for \\( k \\) studies;
+/// * the statistic \\( I^2 = \max(0, (Q - (k - 1)) / Q) \\) is the proportion of the total variation of the estimates that is due to heterogeneity
+///   rather than chance (roughly, 25%, 50%, and 75% are considered low, moderate, and high).
+///
+/// The pooled estimate is the weighted mean with the weights \\( w_i^* = 1 / (s_i^2 + \hat{\tau}^2) \\), with standard error
+/// \\( 1 / \sqrt{\sum_i w_i^*} \\), and the interval uses the normal distribution.
+/// Without heterogeneity (\\( \hat{\tau}^2 = 0 \\)), this is the fixed-effect interval; otherwise, the interval is wider.
+///
+/// # Notes
+///
+/// With few studies, the estimate of \\( \tau^2 \\) is imprecise and the interval tends to be too narrow.
+///
+/// This structure is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // reduction of the latency (in ms) measured in each datacenter, with its standard error
+/// let results = [(2.1, 0.3), (0.4, 0.2), (2.9, 0.5), (1.1, 0.25), (1.7, 0.35)];
+/// let random = meta::RandomEffects::from_iter(&results)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(random.i_squared(), 0.896782, epsilon = 1e-6);
+/// assert_abs_diff_eq!(random.pooled_estimate(), 1.577538, epsilon = 1e-6);
+/// // the fixed-effect interval is overconfident
+/// let fixed = meta::FixedEffect::from_iter(&results)?;
+/// assert!(fixed.ci(confidence)?.is_included_in(&random.ci(confidence)?));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R. DerSimonian, N. Laird. "Meta-analysis in clinical trials." Controlled Clinical Trials 7(3): 177-188 (1986).
+/// * J.P.T. Higgins, S.G. Thompson. "Quantifying heterogeneity in a meta-analysis." Statistics in Medicine 21(11): 1539-1558 (2002).
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomEffects {
+    studies: Vec<(f64, f64)>, // (estimate, variance)
+}
+
+#[cfg(any(test, feature = "std"))]
+impl RandomEffects {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state from `(estimate, std_err)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if an estimate is not finite or a standard error is not strictly positive and finite
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append the result of a study given as an estimate with its standard error
+    ///
+    /// Complexity: \\( O(1) \\) amortized
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the estimate is not finite or the standard error is not strictly positive and finite;
+    ///   the state is then left unchanged
+    ///
+    pub fn append(&mut self, estimate: f64, std_err: f64) -> CIResult<()> {
+        check_study(estimate, std_err)?;
+        self.studies.push((estimate, std_err * std_err));
+        Ok(())
+    }
+
+    ///
+    /// Append the result of a study given as a two-sided confidence interval (see [`FixedEffect::append_interval`])
+    ///
+    /// Complexity: \\( O(1) \\) amortized
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the interval or the confidence is not two-sided, or the interval is degenerate or not finite
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn append_interval(
+        &mut self,
+        confidence: Confidence,
+        interval: &Interval<f64>,
+    ) -> CIResult<()> {
+        let (estimate, std_err) = from_interval(confidence, interval)?;
+        self.append(estimate, std_err)
+    }
+
+    ///
+    /// Append the results of studies given as `(estimate, std_err)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if an estimate is not finite or a standard error is not strictly positive and finite;
+    ///   the results before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        for &(estimate, std_err) in data {
+            self.append(estimate, std_err)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of studies
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn study_count(&self) -> usize {
+        self.studies.len()
+    }
+
+    ///
+    /// Fixed-effect meta-analysis of the same studies
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn fixed_effect(&self) -> FixedEffect {
+        let mut fixed = FixedEffect::new();
+        for &(estimate, variance) in &self.studies {
+            fixed
+                .append(estimate, variance.sqrt())
+                .expect("studies are checked when appended");
+        }
+        fixed
+    }
+
+    ///
+    /// Cochran's heterogeneity statistic \\( Q \\), which follows a chi-squared distribution with \\( k - 1 \\) degrees of freedom
+    /// in the absence of heterogeneity
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn q_statistic(&self) -> f64 {
+        let mean = self.fixed_effect().pooled_estimate();
+        self.studies
+            .iter()
+            .map(|&(estimate, variance)| (estimate - mean).powi(2) / variance)
+            .sum()
+    }
+
+    ///
+    /// DerSimonian–Laird estimate of the between-study variance \\( \tau^2 \\) (zero with less than two studies)
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn tau_squared(&self) -> f64 {
+        if self.studies.len() < 2 {
+            return 0.;
+        }
+        let excess = self.q_statistic() - (self.studies.len() - 1) as f64;
+        let (sum_weights, sum_sq_weights) =
+            self.studies
+                .iter()
+                .fold((0., 0.), |(sum, sum_sq), &(_, variance)| {
+                    (sum + 1. / variance, sum_sq + 1. / (variance * variance))
+                });
+        (excess / (sum_weights - sum_sq_weights / sum_weights)).max(0.)
+    }
+
+    ///
+    /// Proportion \\( I^2 \\) of the variation of the estimates due to heterogeneity rather than chance, in \\( [0, 1] \\)
+    /// (zero with less than two studies)
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn i_squared(&self) -> f64 {
+        let q = self.q_statistic();
+        if q > 0. {
+            ((q - (self.studies.len() as f64 - 1.)) / q).max(0.)
+        } else {
+            0.
+        }
+    }
+
+    ///
+    /// Pooled estimate of the mean effect, with the random-effects weights \\( 1 / (s_i^2 + \hat{\tau}^2) \\)
+    /// (NaN if there are no studies)
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn pooled_estimate(&self) -> f64 {
+        self.random_effect().pooled_estimate()
+    }
+
+    ///
+    /// Standard error of the pooled estimate, \\( 1 / \sqrt{\sum_i w_i^*} \\)
+    /// (infinite if there are no studies)
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    pub fn pooled_std_err(&self) -> f64 {
+        self.random_effect().pooled_std_err()
+    }
+
+    ///
+    /// Confidence interval of the mean effect
+    ///
+    /// Complexity: \\( O(k) \\), where \\( k \\) is the number of studies
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no studies
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        self.random_effect().ci(confidence)
+    }
+
+    ///
+    /// Fixed-effect pooling of the studies with the variances inflated by \\( \hat{\tau}^2 \\)
+    ///
+    fn random_effect(&self) -> FixedEffect {
+        let tau_squared = self.tau_squared();
+        let mut pooled = FixedEffect::new();
+        for &(estimate, variance) in &self.studies {
+            pooled
+                .append(estimate, (variance + tau_squared).sqrt())
+                .expect("studies are checked when appended");
+        }
+        pooled
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl Statistic<f64> for RandomEffects {
+    fn estimate(&self, confidence: Confidence) -> CIResult<CiEstimate<f64>> {
+        self.random_effect().estimate(confidence)
+    }
+}
+
+///
+/// Check the result of a study given as an estimate with its standard error.
+///
+fn check_study(estimate: f64, std_err: f64) -> CIResult<()> {
+    if !estimate.is_finite() || !std_err.is_finite() || std_err <= 0. {
+        return Err(CIError::InvalidInputData);
+    }
+    Ok(())
+}
+
+///
+/// Return the estimate and the standard error of a study given as a two-sided normal confidence interval.
+///
+fn from_interval(confidence: Confidence, interval: &Interval<f64>) -> CIResult<(f64, f64)> {
+    if !confidence.is_two_sided() {
+        return Err(CIError::InvalidInputData);
+    }
+    let z = distributions::z_value(confidence)?;
+    match (interval.midpoint(), interval.radius()) {
+        (Some(midpoint), Some(radius)) => Ok((midpoint, radius / z)),
+        _ => Err(CIError::InvalidInputData),
+    }
+}
+
+///
+/// Return the interval \\( \hat{\theta} \pm z ~ s \\) (or its one-sided counterpart).
+///
+fn normal_interval(confidence: Confidence, estimate: f64, std_err: f64) -> CIResult<Interval<f64>> {
+    let span = distributions::z_value(confidence)? * std_err;
+    match confidence {
+        Confidence::TwoSided(_) => Ok(Interval::new(estimate - span, estimate + span)?),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(estimate - span)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(estimate + span)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_fixed_effect() -> CIResult<()> {
+        let results = [(2.1, 0.6), (1.4, 0.4), (2.9, 1.1), (1.8, 0.5)];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let pooled = FixedEffect::from_iter(&results)?;
+        assert_eq!(pooled.study_count(), 4);
+        assert_abs_diff_eq!(pooled.pooled_estimate(), 1.745318066579397, epsilon = 1e-12);
+        assert_abs_diff_eq!(pooled.pooled_std_err(), 0.268663641222992, epsilon = 1e-12);
+        let ci = pooled.ci(confidence)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(1.218747005826941, 2.271889127331852)?,
+            epsilon = 1e-9
+        );
+        let ci = pooled.ci(Confidence::new_upper(0.975))?;
+        assert_abs_diff_eq!(ci.low_f(), 1.218747005826941, epsilon = 1e-9);
+        assert!(ci.is_upper());
+
+        // a single study gives back its own interval
+        let single = FixedEffect::from_iter(&[(2.1, 0.6)])?;
+        let z = distributions::z_value(confidence)?;
+        assert_abs_diff_eq!(
+            single.ci(confidence)?,
+            Interval::new(2.1 - z * 0.6, 2.1 + z * 0.6)?,
+            epsilon = 1e-12
+        );
+
+        // intervals are converted back to the same estimates and standard errors
+        let mut from_intervals = FixedEffect::new();
+        for &(estimate, std_err) in &results {
+            from_intervals.append_interval(
+                confidence,
+                &Interval::new(estimate - z * std_err, estimate + z * std_err)?,
+            )?;
+        }
+        assert_abs_diff_eq!(
+            from_intervals.ci(confidence)?,
+            pooled.ci(confidence)?,
+            epsilon = 1e-12
+        );
+
+        // equal standard errors give the arithmetic mean
+        let equal = FixedEffect::from_iter(&[(1., 0.5), (2., 0.5), (6., 0.5)])?;
+        assert_abs_diff_eq!(equal.pooled_estimate(), 3., epsilon = 1e-12);
+        assert_abs_diff_eq!(equal.pooled_std_err(), 0.5 / 3_f64.sqrt(), epsilon = 1e-12);
+
+        assert!(matches!(
+            FixedEffect::new().ci(confidence),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(FixedEffect::new().pooled_estimate().is_nan());
+        assert!(matches!(
+            FixedEffect::new().append(1., 0.),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            FixedEffect::new()
+                .append_interval(Confidence::new_upper(0.95), &Interval::new(0., 1.)?),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            FixedEffect::new().append_interval(confidence, &Interval::new_upper(0.)),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_effects() -> CIResult<()> {
+        let results = [(2.1, 0.3), (0.4, 0.2), (2.9, 0.5), (1.1, 0.25), (1.7, 0.35)];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let random = RandomEffects::from_iter(&results)?;
+        assert_eq!(random.study_count(), 5);
+        assert_abs_diff_eq!(random.q_statistic(), 38.75278744046569, epsilon = 1e-9);
+        assert_abs_diff_eq!(random.tau_squared(), 0.733784668009487, epsilon = 1e-9);
+        assert_abs_diff_eq!(random.i_squared(), 0.896781618454026, epsilon = 1e-9);
+        assert_abs_diff_eq!(random.pooled_estimate(), 1.577537792170702, epsilon = 1e-9);
+        assert_abs_diff_eq!(random.pooled_std_err(), 0.41008378158716, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            random.ci(confidence)?,
+            Interval::new(0.773788349615878, 2.381287234725526)?,
+            epsilon = 1e-9
+        );
+        let estimate = random.estimate(confidence)?;
+        assert_eq!(estimate.sample_count(), 5);
+        assert_eq!(estimate.interval(), &random.ci(confidence)?);
+
+        // homogeneous studies: same as the fixed effect
+        let homogeneous = [(2.1, 0.6), (1.4, 0.4), (2.9, 1.1), (1.8, 0.5)];
+        let random = RandomEffects::from_iter(&homogeneous)?;
+        let fixed = FixedEffect::from_iter(&homogeneous)?;
+        assert_eq!(random.tau_squared(), 0.);
+        assert_eq!(random.i_squared(), 0.);
+        assert_abs_diff_eq!(
+            random.ci(confidence)?,
+            fixed.ci(confidence)?,
+            epsilon = 1e-12
+        );
+        assert_eq!(random.fixed_effect(), fixed);
+
+        let single = RandomEffects::from_iter(&[(2.1, 0.6)])?;
+        assert_eq!(single.tau_squared(), 0.);
+        assert_eq!(single.i_squared(), 0.);
+        assert!(matches!(
+            RandomEffects::new().ci(confidence),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            RandomEffects::new().append(f64::NAN, 1.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
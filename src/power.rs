@@ -0,0 +1,344 @@
+//!
+//! Power calculations for the design of experiments (e.g., A/B tests): minimum detectable effect (MDE)
+//! given the sample sizes, and conversely the sample size required to detect a given effect.
+//!
+//! The calculations apply to the comparison of two independent groups (see [`comparison::Unpaired`] and
+//! [`proportion`]), for a difference in means or in proportions, and rely on the normal approximation.
+//! The confidence level of the test plays the role of \\( 1 - \alpha \\), i.e., a two-sided 95% confidence
+//! corresponds to a two-sided test at the significance level \\( \alpha = 0.05 \\).
+//! The power is the probability of detecting an effect when the true effect equals the MDE (typically 0.8).
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! # use approx::*;
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let power = 0.8;
+//!
+//! // smallest difference in means detectable with 100 samples per group, when the standard deviation is about 1
+//! let mde = power::mde_mean(confidence, power, 1., 100, 100)?;
+//! assert_abs_diff_eq!(mde, 0.3962, epsilon = 1e-4);
+//! // samples needed per group to detect a difference of 0.5
+//! assert_eq!(power::required_sample_size_mean(confidence, power, 1., 0.5)?, 63);
+//!
+//! // samples needed per group to detect an increase of a conversion rate from 10% to 12%
+//! assert_eq!(power::required_sample_size_proportion(confidence, power, 0.1, 0.02)?, 3841);
+//! // conversely, the smallest increase detectable with these sample sizes
+//! let mde = power::mde_proportion(confidence, power, 0.1, 3841, 3841)?;
+//! assert!(mde <= 0.02);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * [Wikipedia - Power of a test](https://en.wikipedia.org/wiki/Power_of_a_test)
+//! * Joseph L. Fleiss, Bruce Levin, Myunghee Cho Paik. Statistical Methods for Rates and Proportions (3rd ed.), John Wiley & Sons, 2003.
+//!
+use super::*;
+use crate::distributions::{normal_inverse_cdf, z_value};
+use error::*;
+
+///
+/// Minimum detectable difference between the means of two independent groups:
+/// \\[
+/// \delta = (z_{1-\alpha} + z_{1-\beta}) ~ \sigma \sqrt{\frac{1}{n_a} + \frac{1}{n_b}}
+/// \\]
+/// where \\( 1-\beta \\) is the power and \\( \sigma \\) the standard deviation (assumed equal in both groups).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \alpha = 0.05 \\))
+/// * `power` - the desired power of the test (must be in (0, 1), typically 0.8)
+/// * `std_dev` - an estimate of the standard deviation of the population
+/// * `n_a` - the number of samples in the first group
+/// * `n_b` - the number of samples in the second group
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if the power is not in (0, 1)
+/// * [`CIError::InvalidInputData`] - if the standard deviation is negative or not finite
+/// * [`CIError::TooFewSamples`] - if one of the groups is empty
+///
+pub fn mde_mean(
+    confidence: Confidence,
+    power: f64,
+    std_dev: f64,
+    n_a: usize,
+    n_b: usize,
+) -> CIResult<f64> {
+    let z = z_sum(confidence, power)?;
+    check_std_dev(std_dev)?;
+    check_group_sizes(n_a, n_b)?;
+    Ok(z * std_dev * (1. / n_a as f64 + 1. / n_b as f64).sqrt())
+}
+
+///
+/// Number of samples required in each of two independent groups of equal size to detect
+/// a given difference between their means:
+/// \\[
+/// n = \left\lceil \frac{2 ~ (z_{1-\alpha} + z_{1-\beta})^2 ~ \sigma^2}{\delta^2} \right\rceil
+/// \\]
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \alpha = 0.05 \\))
+/// * `power` - the desired power of the test (must be in (0, 1), typically 0.8)
+/// * `std_dev` - an estimate of the standard deviation of the population
+/// * `mde` - the minimum difference in means to detect
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if the power is not in (0, 1)
+/// * [`CIError::InvalidInputData`] - if the standard deviation is negative or not finite
+/// * [`CIError::InvalidEffectSize`] - if the MDE is not strictly positive and finite
+/// * [`CIError::Overflow`] - if the required sample size cannot be represented
+///
+pub fn required_sample_size_mean(
+    confidence: Confidence,
+    power: f64,
+    std_dev: f64,
+    mde: f64,
+) -> CIResult<usize> {
+    let z = z_sum(confidence, power)?;
+    check_std_dev(std_dev)?;
+    check_effect_size(mde)?;
+    utils::sample_size_from_f64((2. * (z * std_dev / mde).powi(2)).ceil())
+}
+
+///
+/// Minimum detectable (absolute) increase of a proportion between two independent groups, from a baseline
+/// proportion \\( p_a \\) in the first group to \\( p_b = p_a + \delta \\) in the second group.
+///
+/// The MDE is the smallest \\( \delta \\) such that
+/// \\[
+/// \delta = z_{1-\alpha} \sqrt{\bar{p} (1-\bar{p}) \left(\frac{1}{n_a} + \frac{1}{n_b}\right)} + z_{1-\beta} \sqrt{\frac{p_a (1-p_a)}{n_a} + \frac{p_b (1-p_b)}{n_b}}
+/// \\]
+/// where \\( \bar{p} = \frac{n_a p_a + n_b p_b}{n_a + n_b} \\) is the pooled proportion.
+/// It is found by bisection.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \alpha = 0.05 \\))
+/// * `power` - the desired power of the test (must be in (0, 1), typically 0.8)
+/// * `p_baseline` - the proportion in the first group (must be in (0, 1))
+/// * `n_a` - the number of samples in the first group
+/// * `n_b` - the number of samples in the second group
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if the power or the baseline proportion is not in (0, 1)
+/// * [`CIError::TooFewSamples`] - if one of the groups is empty, or if the groups are too small to detect any increase
+///
+/// # Notes
+///
+/// A decrease of the proportion can be handled by swapping successes and failures (i.e., with the baseline \\( 1 - p_a \\)).
+///
+pub fn mde_proportion(
+    confidence: Confidence,
+    power: f64,
+    p_baseline: f64,
+    n_a: usize,
+    n_b: usize,
+) -> CIResult<f64> {
+    let z_alpha = z_value(confidence)?;
+    let z_beta = z_power(power)?;
+    check_proportion(p_baseline)?;
+    check_group_sizes(n_a, n_b)?;
+    let (n_a, n_b) = (n_a as f64, n_b as f64);
+    let excess = |delta: f64| {
+        let p_b = p_baseline + delta;
+        let p_pooled = (n_a * p_baseline + n_b * p_b) / (n_a + n_b);
+        delta
+            - z_alpha * (p_pooled * (1. - p_pooled) * (1. / n_a + 1. / n_b)).sqrt()
+            - z_beta * (p_baseline * (1. - p_baseline) / n_a + p_b * (1. - p_b) / n_b).sqrt()
+    };
+    let (mut lo, mut hi) = (0., 1. - p_baseline);
+    if excess(hi) < 0. {
+        return Err(CIError::TooFewSamples(n_a.min(n_b) as usize));
+    }
+    while hi - lo > f64::EPSILON * hi {
+        let mid = (lo + hi) / 2.;
+        if excess(mid) < 0. {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(hi)
+}
+
+///
+/// Number of samples required in each of two independent groups of equal size to detect
+/// an (absolute) increase of a proportion from \\( p_a \\) to \\( p_b = p_a + \delta \\):
+/// \\[
+/// n = \left\lceil \frac{\left(z_{1-\alpha} \sqrt{2 \bar{p} (1 - \bar{p})} + z_{1-\beta} \sqrt{p_a (1-p_a) + p_b (1-p_b)}\right)^2}{\delta^2} \right\rceil
+/// \\]
+/// where \\( \bar{p} = \frac{p_a + p_b}{2} \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the test (e.g., two-sided 95% for \\( \alpha = 0.05 \\))
+/// * `power` - the desired power of the test (must be in (0, 1), typically 0.8)
+/// * `p_baseline` - the proportion in the first group (must be in (0, 1))
+/// * `mde` - the minimum increase of the proportion to detect
+///
+/// # Errors
+///
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if the power or the baseline proportion is not in (0, 1)
+/// * [`CIError::InvalidEffectSize`] - if the MDE is not strictly positive, or if \\( p_a + \delta \geq 1 \\)
+/// * [`CIError::Overflow`] - if the required sample size cannot be represented
+///
+pub fn required_sample_size_proportion(
+    confidence: Confidence,
+    power: f64,
+    p_baseline: f64,
+    mde: f64,
+) -> CIResult<usize> {
+    let z_alpha = z_value(confidence)?;
+    let z_beta = z_power(power)?;
+    check_proportion(p_baseline)?;
+    check_effect_size(mde)?;
+    let p_b = p_baseline + mde;
+    if p_b >= 1. {
+        return Err(CIError::InvalidEffectSize(mde));
+    }
+    let p_mean = (p_baseline + p_b) / 2.;
+    let numerator = z_alpha * (2. * p_mean * (1. - p_mean)).sqrt()
+        + z_beta * (p_baseline * (1. - p_baseline) + p_b * (1. - p_b)).sqrt();
+    utils::sample_size_from_f64((numerator / mde).powi(2).ceil())
+}
+
+fn z_power(power: f64) -> CIResult<f64> {
+    if !(power > 0. && power < 1.) {
+        return Err(CIError::InvalidProbability(power));
+    }
+    normal_inverse_cdf(power)
+}
+
+fn z_sum(confidence: Confidence, power: f64) -> CIResult<f64> {
+    Ok(z_value(confidence)? + z_power(power)?)
+}
+
+fn check_std_dev(std_dev: f64) -> CIResult<()> {
+    if std_dev.is_finite() && std_dev >= 0. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidInputData)
+    }
+}
+
+fn check_proportion(p: f64) -> CIResult<()> {
+    if p > 0. && p < 1. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidProbability(p))
+    }
+}
+
+fn check_effect_size(mde: f64) -> CIResult<()> {
+    if mde.is_finite() && mde > 0. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidEffectSize(mde))
+    }
+}
+
+fn check_group_sizes(n_a: usize, n_b: usize) -> CIResult<()> {
+    if n_a == 0 || n_b == 0 {
+        return Err(CIError::TooFewSamples(n_a.min(n_b)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_power_mean() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            mde_mean(confidence, 0.8, 1., 100, 100)?,
+            0.3962039811599343,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            mde_mean(Confidence::new_upper(0.95), 0.9, 2., 50, 200)?,
+            0.9254105764831074,
+            epsilon = 1e-9
+        );
+        assert_eq!(required_sample_size_mean(confidence, 0.8, 1., 0.5)?, 63);
+        assert_eq!(required_sample_size_mean(confidence, 0.8, 2., 0.1)?, 6280);
+        // the MDE at the required sample size is at most the target
+        let n = required_sample_size_mean(confidence, 0.8, 2., 0.1)?;
+        assert!(mde_mean(confidence, 0.8, 2., n, n)? <= 0.1);
+        assert!(mde_mean(confidence, 0.8, 2., n - 1, n - 1)? > 0.1);
+
+        assert!(matches!(
+            mde_mean(confidence, 1., 1., 100, 100),
+            Err(CIError::InvalidProbability(_))
+        ));
+        assert!(matches!(
+            mde_mean(confidence, 0.8, 1., 0, 100),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            required_sample_size_mean(confidence, 0.8, 1., 0.),
+            Err(CIError::InvalidEffectSize(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_proportion() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            required_sample_size_proportion(confidence, 0.8, 0.1, 0.02)?,
+            3841
+        );
+        assert_eq!(
+            required_sample_size_proportion(confidence, 0.8, 0.5, 0.05)?,
+            1565
+        );
+        assert_eq!(
+            required_sample_size_proportion(Confidence::new_upper(0.95), 0.9, 0.03, 0.005)?,
+            21541
+        );
+        assert_abs_diff_eq!(
+            mde_proportion(confidence, 0.8, 0.1, 1000, 1000)?,
+            0.04074515431182416,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            mde_proportion(Confidence::new_upper(0.95), 0.9, 0.03, 10000, 20000)?,
+            0.0064339276517424646,
+            epsilon = 1e-9
+        );
+        // consistency between the two functions
+        let mde = mde_proportion(confidence, 0.8, 0.1, 3841, 3841)?;
+        assert!(mde <= 0.02);
+        assert!(mde > 0.0199);
+
+        assert!(matches!(
+            mde_proportion(confidence, 0.8, 0.5, 1, 1),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            required_sample_size_proportion(confidence, 0.8, 0.9, 0.1),
+            Err(CIError::InvalidEffectSize(_))
+        ));
+        assert!(matches!(
+            required_sample_size_proportion(confidence, 0.8, 0., 0.1),
+            Err(CIError::InvalidProbability(_))
+        ));
+        Ok(())
+    }
+}
@@ -32,15 +32,29 @@
 //! # Ok::<(),error::CIError>(())
 //! ```
 //!
+//! Bayesian credible intervals (equal-tailed or highest posterior density) combine the observations with a beta prior
+//! (see [`credible_interval`] and [`Prior`]), and remain defined with few or no successes.
+//!
+//! The proportions of all the categories of a multinomial distribution (e.g., market shares, histogram bins) can be
+//! bounded jointly with [`ci_multinomial`].
+//!
+//! The difference between two proportions measured on the same subjects (e.g., before and after a change) is estimated
+//! from the discordant pairs with [`ci_paired_difference`].
+//!
+//! Two proportions (e.g., the conversion rates of an A/B test) can be compared sequentially, with an always-valid
+//! interval on their difference, using [`sequential::ProportionComparison`].
+//!
 //! # References
 //!
 //! * [Wikipedia - Confidence interval](https://en.wikipedia.org/wiki/Confidence_interval)
 //! * [Wikipedia - Binomial proportion confidence interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval)
 //! * <https://influentialpoints.com/Training/confidence_intervals_of_proportions-principles-properties-assumptions.htm>
+//! * [Wikipedia - Multinomial distribution](https://en.wikipedia.org/wiki/Multinomial_distribution)
 //!
 use super::*;
-use crate::stats::z_value;
+use crate::distributions::z_value;
 use error::*;
+use utils::NanAction;
 
 ///
 /// Represents the state of the computation of a confidence interval for a proportion.
@@ -203,6 +217,33 @@ impl Stats {
         ci(confidence, self.population, self.successes)
     }
 
+    ///
+    /// Score test of the proportion against a reference value, dual to [`Stats::ci`] (see [`hypothesis`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the direction and the significance level of the test
+    /// * `proportion` - the proportion \\( p_0 \\) under the null hypothesis (must be in (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if the population is empty
+    /// * `InvalidReference` - if the reference proportion is not in (0, 1)
+    ///
+    /// # Notes
+    ///
+    /// The test is computed using the function [`z_test`].
+    ///
+    pub fn z_test(
+        &self,
+        confidence: Confidence,
+        proportion: f64,
+    ) -> CIResult<hypothesis::TestResult> {
+        z_test(confidence, self.population, self.successes, proportion)
+    }
+
     ///
     /// Extend the data with additional sample data.
     ///
@@ -266,6 +307,74 @@ impl Stats {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl Stats {
+    ///
+    /// Extend the data with additional sample data given as a parallel iterator (requires the feature `parallel`).
+    ///
+    /// Complexity: \\( O(n/p) \\) where \\( n \\) is the number of samples in `data` and \\( p \\) the number of threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as a parallel iterator over Boolean values
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// use rayon::prelude::*;
+    /// let data = (0..1_000_000).map(|i| i % 4 == 0).collect::<Vec<_>>();
+    /// let mut stats = proportion::Stats::default();
+    /// stats.par_extend(data.par_iter());
+    /// assert_eq!(stats, proportion::Stats::new(1_000_000, 250_000));
+    /// ```
+    pub fn par_extend<I>(&mut self, data: I)
+    where
+        I: rayon::iter::IntoParallelIterator,
+        I::Item: core::borrow::Borrow<bool>,
+    {
+        self.par_extend_if(data, |x| *core::borrow::Borrow::<bool>::borrow(x));
+    }
+
+    ///
+    /// Extend the data with additional sample data given as a parallel iterator and a condition that must be
+    /// satisfied to be counted as a success (requires the feature `parallel`).
+    ///
+    /// Complexity: \\( O(n/p) \\) where \\( n \\) is the number of samples in `data` and \\( p \\) the number of threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as a parallel iterator
+    /// * `is_success` - a function that returns `true` if a sample value is a success
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// use rayon::prelude::*;
+    /// let data = (1..=1_000_000).collect::<Vec<_>>();
+    /// let mut stats = proportion::Stats::default();
+    /// stats.par_extend_if(data.par_iter(), |&&x| x <= 100_000);
+    /// assert_eq!(stats, proportion::Stats::new(1_000_000, 100_000));
+    /// ```
+    pub fn par_extend_if<I, F>(&mut self, data: I, is_success: F)
+    where
+        I: rayon::iter::IntoParallelIterator,
+        F: Fn(&I::Item) -> bool + Sync + Send,
+    {
+        use rayon::prelude::*;
+        *self += data
+            .into_par_iter()
+            .fold(Stats::default, |mut stats, x| {
+                if is_success(&x) {
+                    stats.add_success();
+                } else {
+                    stats.add_failure();
+                }
+                stats
+            })
+            .reduce(Stats::default, |s1, s2| s1 + s2);
+    }
+}
+
 impl core::ops::Add for Stats {
     type Output = Self;
 
@@ -395,6 +504,67 @@ where
     stats.ci(confidence)
 }
 
+///
+/// Computes the confidence interval over the proportion of a given floating point sample that satisfies a given condition,
+/// handling NaN values according to the given policy.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample given as an iterator or slice
+/// * `condition` - the condition that must be satisfied to be counted as a success
+/// * `nan_policy` - the policy for NaN values (see [`NanPolicy`])
+///
+/// # Errors
+///
+/// * `InvalidInputData` - if the data contains NaN values and the policy is [`NanPolicy::Error`]
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10., f64::NAN, 11., 12., 13., 14., 15., 16., 17., 18., 19., 20.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = proportion::ci_if_with_nan_policy(confidence, &data, |&x| x <= 10., NanPolicy::Ignore)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.299, 0.701)?, epsilon = 1e-2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_if_with_nan_policy<T, I, F>(
+    confidence: Confidence,
+    data: &I,
+    cond: F,
+    nan_policy: NanPolicy,
+) -> CIResult<Interval<f64>>
+where
+    T: num_traits::Float,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+    F: Fn(&T) -> bool,
+{
+    let mut stats = Stats::default();
+    for x in data {
+        match nan_policy.apply(*x)? {
+            NanAction::Keep if cond(x) => stats.add_success(),
+            NanAction::Keep => stats.add_failure(),
+            NanAction::Ignore => {}
+            NanAction::Propagate => {
+                return match confidence {
+                    Confidence::TwoSided(_) => Ok(Interval::TwoSided(f64::NAN, f64::NAN)),
+                    Confidence::UpperOneSided(_) => Ok(Interval::TwoSided(f64::NAN, 1.)),
+                    Confidence::LowerOneSided(_) => Ok(Interval::TwoSided(0., f64::NAN)),
+                };
+            }
+        }
+    }
+    stats.ci(confidence)
+}
+
 ///
 /// Computes the (two sided) confidence interval over the proportion of successes a given sample.
 ///
@@ -434,6 +604,65 @@ pub fn ci(confidence: Confidence, population: usize, successes: usize) -> CIResu
     ci_wilson(confidence, population, successes)
 }
 
+///
+/// Score test of a proportion against a reference value \\( p_0 \\), dual to the Wilson score interval
+/// (see [`ci_wilson`] and [`hypothesis`]).
+///
+/// The statistic follows approximately the standard normal distribution under the null hypothesis:
+/// \\[
+/// z = \frac{\hat{p} - p_0}{\sqrt{p_0 (1 - p_0) / n}}
+/// \\]
+/// where \\( \hat{p} \\) is the observed proportion of successes and \\( n \\) is the population size.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the direction and the significance level of the test
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+/// * `proportion` - the proportion \\( p_0 \\) under the null hypothesis (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidReference` - if the reference proportion is not in (0, 1)
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // has the failure rate increased beyond the usual 5%?
+/// let test = proportion::z_test(Confidence::new_upper(0.95), 500, 36, 0.05)?;
+/// assert_abs_diff_eq!(test.statistic(), 2.2572, epsilon = 1e-4);
+/// assert!(test.is_significant());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn z_test(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+    proportion: f64,
+) -> CIResult<hypothesis::TestResult> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if successes > population {
+        return Err(CIError::InvalidSuccesses(successes, population));
+    }
+    if !(proportion > 0. && proportion < 1.) {
+        return Err(CIError::InvalidReference(proportion));
+    }
+    let n = population as f64;
+    let p_hat = successes as f64 / n;
+    let statistic = (p_hat - proportion) / (proportion * (1. - proportion) / n).sqrt();
+    hypothesis::TestResult::z(confidence, statistic)
+}
+
 ///
 /// Check if the conditions for the validity of the Wilson score interval are met.
 /// The conditions for the validity of hypothesis tests (from which the Wilson score is derived) are stated as follows:
@@ -542,7 +771,7 @@ pub fn ci_wilson(
         ));
     }
 
-    let z = z_value(confidence);
+    let z = z_value(confidence)?;
     let z_sq = z * z;
 
     let mean = (n_s + z_sq / 2.) / (n + z_sq);
@@ -654,7 +883,7 @@ pub fn ci_z_normal(
     }
 
     let std_dev = (p * q / n).sqrt();
-    let z = z_value(confidence);
+    let z = z_value(confidence)?;
     let mean = p;
     let span = z * std_dev;
     match confidence {
@@ -664,11 +893,863 @@ pub fn ci_z_normal(
     }
 }
 
+///
+/// Method for the simultaneous confidence intervals over the proportions of the categories of a multinomial
+/// distribution (see [`ci_multinomial`]).
+///
+/// # References
+///
+/// * L.A. Goodman. "On simultaneous confidence intervals for multinomial proportions." Technometrics 7(2): 247-254 (1965).
+/// * C.P. Sison, J. Glaz. "Simultaneous confidence intervals and sample size determination for multinomial proportions."
+///   Journal of the American Statistical Association 90(429): 366-369 (1995).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Multinomial {
+    ///
+    /// Goodman's method: the Wilson score interval of each category with the chi-square critical value of
+    /// the Bonferroni-corrected level \\( 1 - \alpha/k \\) for \\( k \\) categories.
+    /// It is accurate when every category has a reasonable count, and conservative when there are many categories.
+    ///
+    #[default]
+    Goodman,
+
+    ///
+    /// Method of Sison and Glaz: the intervals \\( [\hat{p}_i - c/n, \hat{p}_i + (c + 2\gamma)/n] \\) with a common width,
+    /// obtained from an Edgeworth approximation of the multinomial distribution as truncated Poisson variables.
+    /// It is less conservative than Goodman's method with many categories or small counts.
+    ///
+    SisonGlaz,
+}
+
+///
+/// Computes simultaneous confidence intervals over the proportions of all the categories of a multinomial distribution
+/// (e.g., the market shares of several products, or the bins of a histogram), given the count of observations in each category.
+///
+/// The intervals hold jointly with the given confidence, i.e., all the proportions are contained in their respective
+/// interval with probability \\( 1 - \alpha \\).
+/// By contrast, the individual Wilson intervals of [`ci_wilson`] hold one at a time, so that some of the \\( k \\) intervals
+/// are likely to miss their proportion when there are many categories.
+///
+/// With one-sided confidence, Goodman's method uses the one-sided critical value, while the method of Sison and Glaz keeps
+/// the corresponding side of the two-sided intervals (which is conservative).
+///
+/// # Arguments
+///
+/// * `confidence` - the joint confidence level (must be in (0, 1))
+/// * `counts` - the number of observations in each category
+/// * `method` - the method used to compute the intervals (see [`Multinomial`])
+///
+/// # Errors
+///
+/// * `InvalidInputData` - if there are less than 2 categories
+/// * `TooFewSamples` - if there are no observations at all
+/// * `Overflow` - if the total number of observations cannot be represented
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let counts = [56, 72, 73, 59, 62, 87, 58];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let intervals = proportion::ci_multinomial(confidence, &counts, proportion::Multinomial::Goodman)?;
+/// assert_abs_diff_eq!(intervals[5], Interval::new(0.142749, 0.239416)?, epsilon = 1e-6);
+///
+/// let intervals = proportion::ci_multinomial(confidence, &counts, proportion::Multinomial::SisonGlaz)?;
+/// assert_abs_diff_eq!(intervals[5], Interval::new(0.145610, 0.230737)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_multinomial(
+    confidence: Confidence,
+    counts: &[usize],
+    method: Multinomial,
+) -> CIResult<Vec<Interval<f64>>> {
+    if counts.len() < 2 {
+        return Err(CIError::InvalidInputData);
+    }
+    let total = counts
+        .iter()
+        .try_fold(0_usize, |total, &count| total.checked_add(count))
+        .ok_or_else(|| CIError::Overflow("number of observations".to_string()))?;
+    if total == 0 {
+        return Err(CIError::TooFewSamples(0));
+    }
+    match method {
+        Multinomial::Goodman => goodman(confidence, counts, total),
+        Multinomial::SisonGlaz => sison_glaz(confidence, counts, total),
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+fn goodman(confidence: Confidence, counts: &[usize], total: usize) -> CIResult<Vec<Interval<f64>>> {
+    let z = z_value(confidence.adjusted(counts.len(), Correction::Bonferroni))?;
+    let a = z * z;
+    let n = total as f64;
+    counts
+        .iter()
+        .map(|&count| {
+            let x = count as f64;
+            let center = a + 2. * x;
+            let span = (a * (a + 4. * x * (n - x) / n)).sqrt();
+            let lo = ((center - span) / (2. * (n + a))).max(0.);
+            let hi = ((center + span) / (2. * (n + a))).min(1.);
+            match confidence {
+                Confidence::TwoSided(_) => Interval::new(lo, hi),
+                Confidence::UpperOneSided(_) => Interval::new(lo, 1.),
+                Confidence::LowerOneSided(_) => Interval::new(0., hi),
+            }
+            .map_err(|e| e.into())
+        })
+        .collect()
+}
+
+#[cfg(any(test, feature = "std"))]
+fn sison_glaz(
+    confidence: Confidence,
+    counts: &[usize],
+    total: usize,
+) -> CIResult<Vec<Interval<f64>>> {
+    distributions::checked_quantile(confidence)?;
+    let level = confidence.level();
+    // smallest half-width c (in number of observations) whose coverage exceeds the level,
+    // then interpolation between c - 1 and c
+    let mut previous = 0.;
+    let mut width = (total, 0.);
+    for c in 1..=total {
+        let coverage = truncated_poisson_coverage(c, counts, total);
+        if coverage > level && previous < level {
+            width = (c - 1, (level - previous) / (coverage - previous));
+            break;
+        }
+        previous = coverage;
+    }
+    let (c, gamma) = width;
+    let n = total as f64;
+    let c = c as f64;
+    counts
+        .iter()
+        .map(|&count| {
+            let p = count as f64 / n;
+            let lo = (p - c / n).max(0.);
+            let hi = (p + (c + 2. * gamma) / n).min(1.);
+            match confidence {
+                Confidence::TwoSided(_) => Interval::new(lo, hi),
+                Confidence::UpperOneSided(_) => Interval::new(lo, 1.),
+                Confidence::LowerOneSided(_) => Interval::new(0., hi),
+            }
+            .map_err(|e| e.into())
+        })
+        .collect()
+}
+
+///
+/// Edgeworth approximation of the probability that every count lies within `c` of its observed value,
+/// i.e., \\( P(x_i - c \leq X_i \leq x_i + c, \forall i) \\), where the multinomial counts \\( X_i \\) are represented
+/// as independent Poisson variables conditioned on their sum (Sison and Glaz, 1995).
+///
+#[cfg(any(test, feature = "std"))]
+fn truncated_poisson_coverage(c: usize, counts: &[usize], total: usize) -> f64 {
+    let mut cumulants = [0.; 4];
+    let mut truncation = 1.;
+    for &count in counts {
+        let (moments, probability) = truncated_poisson_moments(c, count);
+        cumulants[0] += moments[0];
+        cumulants[1] += moments[1];
+        cumulants[2] += moments[2];
+        cumulants[3] += moments[3] - 3. * moments[1] * moments[1];
+        truncation *= probability;
+    }
+    let n = total as f64;
+    // 1 / P(N = n) for N ~ Poisson(n)
+    let poisson_mode = (n * n.ln() - n - special::ln_gamma(n + 1.)).exp();
+    let variance = cumulants[1];
+    let z = (n - cumulants[0]) / variance.sqrt();
+    let skewness = cumulants[2] / variance.powf(1.5);
+    let kurtosis = cumulants[3] / (variance * variance);
+    let z2 = z * z;
+    let polynomial = 1.
+        + skewness * (z2 * z - 3. * z) / 6.
+        + kurtosis * (z2 * z2 - 6. * z2 + 3.) / 24.
+        + skewness * skewness * (z2 * z2 * z2 - 15. * z2 * z2 + 45. * z2 - 15.) / 72.;
+    let density = polynomial * (-z2 / 2.).exp() / (2. * core::f64::consts::PI).sqrt();
+    truncation * density / variance.sqrt() / poisson_mode
+}
+
+///
+/// Mean and central moments of order 2 to 4 of a Poisson variable with mean `count`, truncated to
+/// `[count - c, count + c]`, together with the probability of the truncation interval.
+///
+#[cfg(any(test, feature = "std"))]
+fn truncated_poisson_moments(c: usize, count: usize) -> ([f64; 4], f64) {
+    let lambda = count as f64;
+    // P(X <= k) for X ~ Poisson(lambda), with k < 0 for the empty set
+    let cdf = |k: i64| {
+        if k < 0 {
+            0.
+        } else {
+            special::gamma_q(k as f64 + 1., lambda)
+        }
+    };
+    let a = (count + c) as i64;
+    let b = count.saturating_sub(c) as i64;
+    let probability = cdf(a) - cdf(b - 1);
+    // factorial moments of the truncated variable
+    let mut mu = [0.; 5];
+    for (r, moment) in mu.iter_mut().enumerate().skip(1) {
+        let r = r as i64;
+        let upper = cdf(a) - cdf(a - r);
+        let lower = cdf(b - 1) - cdf(b - r - 1);
+        *moment = lambda.powi(r as i32) * (1. - (upper - lower) / probability);
+    }
+    let m = mu[1];
+    let moments = [
+        m,
+        mu[2] + m - m * m,
+        mu[3] + mu[2] * (3. - 3. * m) + (m - 3. * m * m + 2. * m * m * m),
+        mu[4] + mu[3] * (6. - 4. * m) + mu[2] * (7. - 12. * m + 6. * m * m) + m - 4. * m * m
+            + 6. * m * m * m
+            - 3. * m * m * m * m,
+    ];
+    (moments, probability)
+}
+
+///
+/// Method for the confidence interval over the difference of two paired proportions (see [`ci_paired_difference`]).
+///
+/// # References
+///
+/// * T. Tango. "Equivalence test and confidence interval for the difference in proportions for the paired-sample design."
+///   Statistics in Medicine 17(8): 891-908 (1998).
+/// * R.G. Newcombe. "Improved confidence intervals for the difference between binomial proportions based on paired data."
+///   Statistics in Medicine 17(22): 2635-2650 (1998).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PairedDifference {
+    ///
+    /// Wald interval with continuity correction:
+    /// \\( \hat{\Delta} \pm \left( \frac{z}{n} \sqrt{b + c - (b - c)^2 / n} + \frac{1}{n} \right) \\).
+    /// It is simple, but it can be too narrow when there are few discordant pairs.
+    ///
+    Wald,
+
+    ///
+    /// Score interval of Tango, which inverts the score test of the difference (the McNemar test when the difference is zero),
+    /// using the maximum likelihood estimate of the discordant proportions constrained by the difference.
+    /// It has good coverage even with few discordant pairs and never exceeds \\( [-1, 1] \\).
+    ///
+    #[default]
+    Tango,
+}
+
+///
+/// Computes the confidence interval over the difference \\( p_1 - p_2 \\) of two proportions measured on the same subjects
+/// (e.g., the conversion rate of the same users before and after enabling a feature), from the counts of discordant pairs.
+///
+/// Out of \\( n \\) pairs, \\( b \\) are successes in the first measurement only, and \\( c \\) are successes in the second
+/// measurement only, so that \\( \hat{\Delta} = (b - c) / n \\).
+/// The pairs where both measurements agree do not contribute to the difference, but they reduce its variance.
+/// Unlike the difference of two independent proportions, this accounts for the correlation between the two measurements,
+/// which typically makes the interval much narrower.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the number of pairs \\( n \\)
+/// * `only_first` - the number of pairs \\( b \\) with a success in the first measurement only
+/// * `only_second` - the number of pairs \\( c \\) with a success in the second measurement only
+/// * `method` - the method used to compute the interval (see [`PairedDifference`])
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are no pairs
+/// * `InvalidSuccesses` - if the number of discordant pairs is larger than the number of pairs
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // 44 users: 9 converted only before the change, 2 only after
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let method = proportion::PairedDifference::Tango;
+/// let interval = proportion::ci_paired_difference(confidence, 44, 9, 2, method)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.013177, 0.311607)?, epsilon = 1e-6);
+/// // the conversion rate dropped
+/// assert!(interval.low_f() > 0.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_paired_difference(
+    confidence: Confidence,
+    population: usize,
+    only_first: usize,
+    only_second: usize,
+    method: PairedDifference,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(0));
+    }
+    let discordant = only_first.saturating_add(only_second);
+    if discordant > population {
+        return Err(CIError::InvalidSuccesses(discordant, population));
+    }
+    let z = z_value(confidence)?;
+    let n = population as f64;
+    let b = only_first as f64;
+    let c = only_second as f64;
+    let (lo, hi) = match method {
+        PairedDifference::Wald => {
+            let difference = (b - c) / n;
+            let span = z * (b + c - (b - c) * (b - c) / n).sqrt() / n + 1. / n;
+            ((difference - span).max(-1.), (difference + span).min(1.))
+        }
+        PairedDifference::Tango => (tango_bound(n, b, c, z), tango_bound(n, b, c, -z)),
+    };
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+        Confidence::LowerOneSided(_) => Interval::new(-1., hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Difference \\( \Delta \\) at which the score statistic of Tango equals `target`, found by bisection
+/// since the statistic decreases with \\( \Delta \\).
+///
+fn tango_bound(n: f64, b: f64, c: f64, target: f64) -> f64 {
+    let score = |delta: f64| {
+        // maximum likelihood estimate of the proportion of pairs with a success in the second measurement only,
+        // under the constraint that the difference is delta
+        let qa = 2. * n;
+        let qb = -b - c + (2. * n - b + c) * delta;
+        let qc = -c * delta * (1. - delta);
+        let p21 = ((qb * qb - 4. * qa * qc).sqrt() - qb) / (2. * qa);
+        let numerator = b - c - n * delta;
+        let variance = n * (2. * p21 + delta * (1. - delta));
+        if variance > 0. {
+            numerator / variance.sqrt()
+        } else if numerator == 0. {
+            0.
+        } else {
+            numerator.signum() * f64::INFINITY
+        }
+    };
+    let (mut lo, mut hi) = (-1., 1.);
+    while hi - lo > 1e-12 {
+        let mid = (lo + hi) / 2.;
+        if score(mid) > target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.
+}
+
+///
+/// Beta prior on a proportion, for the Bayesian credible intervals of [`credible_interval`] and [`credible_interval_hpd`].
+///
+/// With a prior \\( \operatorname{Beta}(a, b) \\), the posterior after observing \\( n_S \\) successes out of \\( n \\) is
+/// \\( \operatorname{Beta}(a + n_S, b + n - n_S) \\). The prior counts as \\( a + b \\) pseudo-observations,
+/// \\( a \\) of which are successes (e.g., `Beta(2., 18.)` encodes a historical conversion rate of about 10%).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Prior {
+    ///
+    /// Uniform prior \\( \operatorname{Beta}(1, 1) \\).
+    ///
+    Uniform,
+
+    ///
+    /// Jeffreys prior \\( \operatorname{Beta}(1/2, 1/2) \\), whose equal-tailed interval has good frequentist coverage.
+    ///
+    Jeffreys,
+
+    ///
+    /// Informative prior \\( \operatorname{Beta}(a, b) \\), where both parameters must be strictly positive.
+    ///
+    Beta(f64, f64),
+}
+
+impl Prior {
+    ///
+    /// Parameters \\( (a, b) \\) of the beta distribution of the prior.
+    ///
+    pub fn parameters(&self) -> (f64, f64) {
+        match self {
+            Prior::Uniform => (1., 1.),
+            Prior::Jeffreys => (0.5, 0.5),
+            Prior::Beta(a, b) => (*a, *b),
+        }
+    }
+
+    ///
+    /// Parameters of the beta distribution of the posterior after observing `successes` out of `population`.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSuccesses` - if the number of successes is larger than the population size
+    /// * `NonPositiveValue` - if some parameter of the prior is not strictly positive and finite
+    ///
+    pub fn posterior(&self, population: usize, successes: usize) -> CIResult<(f64, f64)> {
+        if successes > population {
+            return Err(CIError::InvalidSuccesses(successes, population));
+        }
+        let (a, b) = self.parameters();
+        for shape in [a, b] {
+            if shape.is_nan() || shape <= 0. || shape.is_infinite() {
+                return Err(CIError::NonPositiveValue(shape));
+            }
+        }
+        Ok((a + successes as f64, b + (population - successes) as f64))
+    }
+}
+
+///
+/// Computes the equal-tailed Bayesian credible interval over a proportion, given a beta prior (see [`Prior`]).
+///
+/// The interval is delimited by the quantiles of the posterior distribution \\( \operatorname{Beta}(a + n_S, b + n - n_S) \\),
+/// with the probability \\( \alpha/2 \\) outside on each side for a two-sided confidence \\( 1 - \alpha \\).
+/// One-sided intervals extend to 0 or 1.
+///
+/// Unlike the confidence intervals of this module, the credible interval is defined for any number of successes, including
+/// none, and an informative prior combines the observations with prior knowledge (e.g., the conversion rate of previous campaigns),
+/// which matters with small samples.
+/// With the Jeffreys prior, the interval is also a good frequentist confidence interval.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the credibility level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+/// * `prior` - the prior distribution of the proportion
+///
+/// # Errors
+///
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `NonPositiveValue` - if some parameter of the prior is not strictly positive and finite
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // 3 conversions out of 20 visitors, and previous campaigns converted about 20%
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = proportion::credible_interval(confidence, 20, 3, proportion::Prior::Beta(2., 8.))?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.058456, 0.316641)?, epsilon = 1e-6);
+///
+/// // no conversion at all
+/// let interval = proportion::credible_interval(confidence, 20, 0, proportion::Prior::Jeffreys)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.000024, 0.116639)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * L.D. Brown, T.T. Cai, A. DasGupta. "Interval estimation for a binomial proportion." Statistical Science 16(2): 101-133 (2001).
+/// * [Wikipedia - Beta-binomial conjugate prior](https://en.wikipedia.org/wiki/Conjugate_prior)
+///
+pub fn credible_interval(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+    prior: Prior,
+) -> CIResult<Interval<f64>> {
+    let (a, b) = prior.posterior(population, successes)?;
+    let quantile = distributions::checked_quantile(confidence)?;
+    match confidence {
+        Confidence::TwoSided(_) => Ok(Interval::new(
+            distributions::beta_inverse_cdf(1. - quantile, a, b)?,
+            distributions::beta_inverse_cdf(quantile, a, b)?,
+        )?),
+        Confidence::UpperOneSided(_) => Ok(Interval::new(
+            distributions::beta_inverse_cdf(1. - quantile, a, b)?,
+            1.,
+        )?),
+        Confidence::LowerOneSided(_) => Ok(Interval::new(
+            0.,
+            distributions::beta_inverse_cdf(quantile, a, b)?,
+        )?),
+    }
+}
+
+///
+/// Computes the highest posterior density (HPD) credible interval over a proportion, given a beta prior (see [`Prior`]).
+///
+/// The HPD interval is the narrowest interval containing the requested posterior probability. It is narrower than the
+/// equal-tailed interval of [`credible_interval`] when the posterior is skewed, e.g., for proportions close to 0 or 1,
+/// where it extends to the boundary.
+/// For one-sided confidences, the interval is the same as the one-sided interval of [`credible_interval`].
+///
+/// Complexity: \\( O(1) \\), with a fixed number of evaluations of the quantile function of the beta distribution (see [`hdi::ci_inverse_cdf`]).
+///
+/// # Arguments
+///
+/// * `confidence` - the credibility level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+/// * `prior` - the prior distribution of the proportion
+///
+/// # Errors
+///
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `NonPositiveValue` - if some parameter of the prior is not strictly positive and finite
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let prior = proportion::Prior::Beta(2., 8.);
+/// let hpd = proportion::credible_interval_hpd(confidence, 20, 3, prior)?;
+/// assert_abs_diff_eq!(hpd, Interval::new(0.047546, 0.298913)?, epsilon = 1e-6);
+///
+/// let equal_tailed = proportion::credible_interval(confidence, 20, 3, prior)?;
+/// assert!(hpd.width().unwrap() < equal_tailed.width().unwrap());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn credible_interval_hpd(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+    prior: Prior,
+) -> CIResult<Interval<f64>> {
+    if !confidence.is_two_sided() {
+        return credible_interval(confidence, population, successes, prior);
+    }
+    let (a, b) = prior.posterior(population, successes)?;
+    hdi::ci_inverse_cdf(confidence, |p| distributions::beta_inverse_cdf(p, a, b))
+}
+
+///
+/// Smallest sample size for which the confidence interval over a proportion is expected to have at most
+/// the given half-width (i.e., margin of error), using the normal approximation:
+/// \\[
+/// n = \left\lceil \frac{z^2 ~ p ~ (1-p)}{h^2} \right\rceil
+/// \\]
+/// where \\( p \\) is the expected proportion and \\( h \\) the target half-width.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `target_half_width` - the desired half-width of the interval (e.g., `0.03` for ±3 percentage points)
+/// * `p_estimate` - an estimate of the proportion; `None` uses the conservative value \\( p = 0.5 \\), which maximizes the required sample size
+///
+/// # Errors
+///
+/// * `InvalidHalfWidth` - if the target half-width is not strictly positive and finite
+/// * `InvalidProbability` - if the estimate of the proportion is not in [0, 1]
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `Overflow` - if the required sample size cannot be represented
+///
+/// # Notes
+///
+/// The normal approximation underestimates the sample size when the proportion is close to 0 or 1.
+/// The function [`required_sample_size_wilson`] is then preferable, as it matches the interval computed by [`ci`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // classic poll: ±3 percentage points
+/// assert_eq!(proportion::required_sample_size(confidence, 0.03, None)?, 1068);
+/// // A/B test with a baseline conversion rate of about 10%
+/// assert_eq!(proportion::required_sample_size(confidence, 0.03, Some(0.1))?, 385);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn required_sample_size(
+    confidence: Confidence,
+    target_half_width: f64,
+    p_estimate: Option<f64>,
+) -> CIResult<usize> {
+    let (h, p) = check_sample_size_args(target_half_width, p_estimate)?;
+    let z = z_value(confidence)?;
+    utils::sample_size_from_f64((z * z * p * (1. - p) / (h * h)).ceil())
+}
+
+///
+/// Smallest sample size for which the Wilson score interval over a proportion (see [`ci_wilson`]) is
+/// expected to have at most the given half-width (i.e., margin of error).
+///
+/// The half-width of the Wilson score interval is \\( \frac{z}{n+z^2} \sqrt{n ~ p ~ (1-p)+\frac{z^2}{4}} \\),
+/// which is solved exactly for \\( n \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `target_half_width` - the desired half-width of the interval (e.g., `0.03` for ±3 percentage points)
+/// * `p_estimate` - an estimate of the proportion; `None` uses the conservative value \\( p = 0.5 \\)
+///
+/// # Errors
+///
+/// * `InvalidHalfWidth` - if the target half-width is not strictly positive and finite
+/// * `InvalidProbability` - if the estimate of the proportion is not in [0, 1]
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `Overflow` - if the required sample size cannot be represented
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // rare events require larger samples than predicted by the normal approximation
+/// assert_eq!(proportion::required_sample_size(confidence, 0.02, Some(0.01))?, 96);
+/// assert_eq!(proportion::required_sample_size_wilson(confidence, 0.02, Some(0.01))?, 150);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn required_sample_size_wilson(
+    confidence: Confidence,
+    target_half_width: f64,
+    p_estimate: Option<f64>,
+) -> CIResult<usize> {
+    let (h, p) = check_sample_size_args(target_half_width, p_estimate)?;
+    let z = z_value(confidence)?;
+    let z_sq = z * z;
+    let pq = p * (1. - p);
+    // h^2 (n + z^2)^2 = z^2 (n p q + z^2 / 4), as a quadratic equation in u = n + z^2
+    let u = (z_sq * pq + z_sq * (pq * pq + h * h * (1. - 4. * pq)).sqrt()) / (2. * h * h);
+    utils::sample_size_from_f64((u - z_sq).ceil())
+}
+
+fn check_sample_size_args(target_half_width: f64, p_estimate: Option<f64>) -> CIResult<(f64, f64)> {
+    if !(target_half_width.is_finite() && target_half_width > 0.) {
+        return Err(CIError::InvalidHalfWidth(target_half_width));
+    }
+    let p = p_estimate.unwrap_or(0.5);
+    if !(0. ..=1.).contains(&p) {
+        return Err(CIError::InvalidProbability(p));
+    }
+    Ok((target_half_width, p))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use approx::*;
 
+    #[test]
+    fn test_ci_multinomial() -> CIResult<()> {
+        // reference values computed in python
+        let counts = [56, 72, 73, 59, 62, 87, 58];
+        let confidence = Confidence::new_two_sided(0.95);
+        let intervals = ci_multinomial(confidence, &counts, Multinomial::Goodman)?;
+        assert_eq!(intervals.len(), counts.len());
+        assert_abs_diff_eq!(
+            intervals[0],
+            Interval::new(0.08516723913874685, 0.16626140813046544)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            intervals[5],
+            Interval::new(0.14274910916305503, 0.239415943371816)?,
+            epsilon = 1e-9
+        );
+        let intervals = ci_multinomial(Confidence::new_upper(0.95), &counts, Multinomial::Goodman)?;
+        assert_abs_diff_eq!(intervals[1].low_f(), 0.11764804189054293, epsilon = 1e-9);
+        assert_eq!(intervals[1].high_f(), 1.);
+
+        let intervals = ci_multinomial(confidence, &counts, Multinomial::SisonGlaz)?;
+        assert_abs_diff_eq!(
+            intervals[0],
+            Interval::new(0.07922912205567452, 0.16435573404022133)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            intervals[5],
+            Interval::new(0.145610278372591, 0.23073689035713782)?,
+            epsilon = 1e-9
+        );
+
+        // empty category and small counts
+        let counts = [0, 3, 12, 5];
+        let confidence = Confidence::new_two_sided(0.9);
+        let intervals = ci_multinomial(confidence, &counts, Multinomial::Goodman)?;
+        assert_abs_diff_eq!(
+            intervals[0],
+            Interval::new(0., 0.20076362838724857)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            intervals[2],
+            Interval::new(0.3595005640546428, 0.8003467102679075)?,
+            epsilon = 1e-9
+        );
+        let intervals = ci_multinomial(confidence, &counts, Multinomial::SisonGlaz)?;
+        assert_abs_diff_eq!(
+            intervals[0],
+            Interval::new(0., 0.1991219340176648)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            intervals[2],
+            Interval::new(0.45, 0.7991219340176648)?,
+            epsilon = 1e-9
+        );
+
+        // joint intervals are wider than the individual ones
+        let wilson = ci_wilson(confidence, 20, 12)?;
+        assert!(
+            wilson.is_included_in(&ci_multinomial(confidence, &counts, Multinomial::Goodman)?[2])
+        );
+
+        assert!(matches!(
+            ci_multinomial(confidence, &[10], Multinomial::Goodman),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_multinomial(confidence, &[0, 0, 0], Multinomial::SisonGlaz),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_paired_difference() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 44, 9, 2, PairedDifference::Tango)?,
+            Interval::new(0.013176674090853274, 0.31160671035770277)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 44, 9, 2, PairedDifference::Wald)?,
+            Interval::new(-0.003696229075274725, 0.3218780472570929)?,
+            epsilon = 1e-9
+        );
+        let upper = Confidence::new_upper(0.95);
+        assert_abs_diff_eq!(
+            ci_paired_difference(upper, 44, 9, 2, PairedDifference::Tango)?,
+            Interval::new(0.03940850627897362, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ci_paired_difference(upper, 44, 9, 2, PairedDifference::Wald)?,
+            Interval::new(0.01882169198367826, 1.)?,
+            epsilon = 1e-9
+        );
+        let confidence = Confidence::new_two_sided(0.9);
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 200, 14, 31, PairedDifference::Tango)?,
+            Interval::new(-0.14086689643911254, -0.030683650755998243)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 200, 14, 31, PairedDifference::Wald)?,
+            Interval::new(-0.14427705434691665, -0.02572294565308334)?,
+            epsilon = 1e-9
+        );
+
+        // no discordant pairs, or only discordant pairs
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 20, 0, 0, PairedDifference::Tango)?,
+            Interval::new(-0.16112515805281938, 0.16112515805281938)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            ci_paired_difference(confidence, 10, 10, 0, PairedDifference::Tango)?,
+            Interval::new(0.4449344002742215, 1.)?,
+            epsilon = 1e-9
+        );
+
+        assert!(matches!(
+            ci_paired_difference(confidence, 10, 6, 5, PairedDifference::Tango),
+            Err(CIError::InvalidSuccesses(11, 10))
+        ));
+        assert!(matches!(
+            ci_paired_difference(confidence, 0, 0, 0, PairedDifference::Wald),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_credible_interval() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        let prior = Prior::Beta(2., 8.);
+        assert_abs_diff_eq!(
+            credible_interval(confidence, 20, 3, prior)?,
+            Interval::new(0.0584560829726344, 0.3166406094391376)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            credible_interval(confidence, 20, 3, Prior::Uniform)?,
+            Interval::new(0.0544635681784068, 0.363423987016981)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            credible_interval(confidence, 20, 3, Prior::Jeffreys)?,
+            Interval::new(0.04413134197515587, 0.3485777108584541)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            credible_interval(Confidence::new_upper(0.95), 20, 3, prior)?,
+            Interval::new(0.07049392284132051, 1.)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            credible_interval(Confidence::new_lower(0.95), 20, 3, prior)?,
+            Interval::new(0., 0.2883720089910314)?,
+            epsilon = 1e-9
+        );
+
+        assert_abs_diff_eq!(
+            credible_interval_hpd(confidence, 20, 3, prior)?,
+            Interval::new(0.04754569386985473, 0.2989130888423892)?,
+            epsilon = 1e-7
+        );
+        // the posterior density is decreasing: the interval extends to 0
+        assert_abs_diff_eq!(
+            credible_interval_hpd(confidence, 20, 0, Prior::Jeffreys)?,
+            Interval::new(0., 0.09047642653787232)?,
+            epsilon = 1e-7
+        );
+        assert_eq!(
+            credible_interval_hpd(Confidence::new_upper(0.95), 20, 3, prior)?,
+            credible_interval(Confidence::new_upper(0.95), 20, 3, prior)?
+        );
+
+        assert_eq!(Prior::Beta(2., 8.).posterior(20, 3)?, (5., 25.));
+        assert!(matches!(
+            credible_interval(confidence, 20, 21, prior),
+            Err(CIError::InvalidSuccesses(21, 20))
+        ));
+        assert!(matches!(
+            credible_interval(confidence, 20, 3, Prior::Beta(0., 1.)),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            credible_interval_hpd(Confidence::TwoSided(1.), 20, 3, prior),
+            Err(CIError::InvalidConfidenceLevel(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_proportion_ci() -> CIResult<()> {
         let population = 500;
@@ -737,4 +1818,106 @@ mod tests {
         let ci = proportion::ci(confidence, messages, losses).unwrap();
         println!("Loss rate less than: {}", ci);
     }
+
+    #[test]
+    fn test_nan_policy() -> CIResult<()> {
+        let data = [
+            0.2,
+            f64::NAN,
+            0.7,
+            0.4,
+            0.9,
+            0.1,
+            0.6,
+            f64::NAN,
+            0.3,
+            0.8,
+            0.5,
+            0.95,
+        ];
+        let clean = [0.2, 0.7, 0.4, 0.9, 0.1, 0.6, 0.3, 0.8, 0.5, 0.95];
+        let confidence = Confidence::new_upper(0.95);
+        assert!(matches!(
+            ci_if_with_nan_policy(confidence, &data, |&x| x > 0.5, NanPolicy::Error),
+            Err(CIError::InvalidInputData)
+        ));
+        // NaN values must not be counted as failures
+        assert_eq!(
+            ci_if_with_nan_policy(confidence, &data, |&x| x > 0.5, NanPolicy::Ignore)?,
+            ci_if(confidence, &clean, |&x| x > 0.5)?
+        );
+        let nan = ci_if_with_nan_policy(confidence, &data, |&x| x > 0.5, NanPolicy::Propagate)?;
+        assert!(nan.low_f().is_nan());
+        assert_eq!(nan.high_f(), 1.);
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_sample_size() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        for (confidence, h, p, wald, wilson) in [
+            (confidence, 0.03, 0.5, 1068, 1064),
+            (confidence, 0.03, 0.1, 385, 388),
+            (Confidence::new_two_sided(0.99), 0.01, 0.02, 1301, 1368),
+            (Confidence::new_upper(0.9), 0.05, 0.3, 138, 137),
+            (confidence, 0.1, 0.5, 97, 93),
+            (confidence, 0.02, 0.01, 96, 150),
+        ] {
+            assert_eq!(required_sample_size(confidence, h, Some(p))?, wald);
+            assert_eq!(required_sample_size_wilson(confidence, h, Some(p))?, wilson);
+        }
+        assert_eq!(required_sample_size(confidence, 0.03, None)?, 1068);
+        assert_eq!(required_sample_size_wilson(confidence, 0.03, None)?, 1064);
+        assert_eq!(required_sample_size(confidence, 0.03, Some(0.))?, 1);
+        assert_eq!(required_sample_size_wilson(confidence, 0.6, None)?, 1);
+
+        assert!(matches!(
+            required_sample_size(confidence, 0., None),
+            Err(CIError::InvalidHalfWidth(_))
+        ));
+        assert!(matches!(
+            required_sample_size_wilson(confidence, 0.03, Some(1.5)),
+            Err(CIError::InvalidProbability(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_z_test() -> CIResult<()> {
+        // reference values computed in python
+        let test = z_test(Confidence::new_two_sided(0.95), 500, 36, 0.05)?;
+        assert_abs_diff_eq!(test.statistic(), 2.257152374587338, epsilon = 1e-9);
+        assert_abs_diff_eq!(test.p_value(), 0.0239985551562588, epsilon = 1e-9);
+
+        // duality with the Wilson score interval
+        let stats = Stats::new(80, 23);
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let interval = stats.ci(confidence)?;
+            for p in [0.1, 0.15, 0.2, 0.25, 0.3, 0.35, 0.4, 0.45, 0.5] {
+                let test = stats.z_test(confidence, p)?;
+                assert_eq!(test.is_significant(), !interval.contains(&p));
+            }
+        }
+
+        assert!(matches!(
+            z_test(Confidence::new(0.95), 0, 0, 0.5),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            z_test(Confidence::new(0.95), 10, 11, 0.5),
+            Err(CIError::InvalidSuccesses(11, 10))
+        ));
+        for p in [0., 1., f64::NAN] {
+            assert!(matches!(
+                z_test(Confidence::new(0.95), 10, 5, p),
+                Err(CIError::InvalidReference(_))
+            ));
+        }
+        Ok(())
+    }
 }
@@ -10,11 +10,11 @@
 //! let confidence = Confidence::new_two_sided(0.95);
 //! let quantile = 0.5; // median
 //! let interval = quantile::ci(confidence, &data, quantile)?;
-//! assert_eq!(interval, Interval::new(5, 12)?);
+//! assert_eq!(interval, Interval::new(4, 12)?);
 //!
 //! let confidence = Confidence::new_two_sided(0.8);
 //! let interval = quantile::ci(confidence, &data, quantile)?;
-//! assert_eq!(interval, Interval::new(6, 11)?);
+//! assert_eq!(interval, Interval::new(5, 11)?);
 //!
 //! let confidence = Confidence::new_two_sided(0.5);
 //! let quantile = 0.4; // 40th percentile
@@ -22,7 +22,15 @@
 //! assert_eq!(interval, Interval::new(5, 8)?);
 //! # Ok::<(),error::CIError>(())
 //! ```
+//!
+//! Since the computation only relies on the ordering of the data (`PartialOrd + Clone`), the intervals
+//! apply to any ordered type without additional features, such as durations or timestamps
+//! (e.g., `time::OffsetDateTime` or `chrono::DateTime<Utc>`). The width of an interval over timestamps
+//! is then a duration (see [`Interval::width`]).
+//!
 use super::*;
+use num_traits::Float;
+use utils::NanAction;
 
 ///
 /// This constant defines the maximum number of samples that can be handled
@@ -31,10 +39,18 @@ use super::*;
 /// and `no_alloc` environments (unlike [`ci`]]).
 pub const DATA_CAP: usize = 1024;
 
+///
+/// This constant defines the largest population for which confidence intervals on quantiles are computed
+/// with the exact (binomial) method by default. Above that size, the Wilson score method is used instead,
+/// which is then practically indistinguishable. See [`Stats::ci`].
+///
+pub const EXACT_LIMIT: usize = 1_000;
+
 ///
 /// Running statistics for quantiles
 ///
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stats {
     population: usize,
 }
@@ -62,9 +78,15 @@ impl Stats {
     /// # Errors
     ///
     /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
-    /// * `InvalidQuantile` - if the quantile is not in the range [0, 1]
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    /// * `InvalidQuantile` - if the quantile is not in the range (0, 1)
     /// * `IndexError` - if the confidence interval falls outside the range of the data
     ///
+    /// # Notes
+    ///
+    /// The interval is computed with the exact method (see [`Self::ci_exact`]) when the population is at most
+    /// [`EXACT_LIMIT`], and with the Wilson score method (see [`Self::ci_wilson`]) for larger populations.
+    ///
     /// # Examples
     ///
     /// ```
@@ -74,13 +96,133 @@ impl Stats {
     /// let quantile = 0.5; // median
     /// let stats = quantile::Stats::new(data.len());
     /// let interval = stats.ci(confidence, quantile)?;
-    /// assert_eq!(interval, Interval::new(3, 6)?);
+    /// assert_eq!(interval, Interval::new(2, 6)?);
     /// # Ok::<(),error::CIError>(())
     /// ```
     pub fn ci(&self, confidence: Confidence, quantile: f64) -> CIResult<Interval<usize>> {
-        if quantile <= 0. || 1. <= quantile {
-            return Err(error::CIError::InvalidQuantile(quantile));
+        if self.population <= EXACT_LIMIT {
+            self.ci_exact(confidence, quantile)
+        } else {
+            self.ci_wilson(confidence, quantile)
+        }
+    }
+
+    ///
+    /// Return the confidence interval on the indices for a given quantile, using the exact (binomial) method.
+    ///
+    /// The number of samples smaller than the true quantile \\( q \\) follows a binomial distribution
+    /// \\( B(n, q) \\). The bounds are the order statistics whose ranks are obtained by inverting
+    /// the cumulative distribution function of that binomial distribution, so that the coverage of the interval
+    /// is guaranteed to be at least the confidence level (the method is conservative), regardless of the number
+    /// of samples or of the quantile. It does not rely on any assumption on the distribution of the data,
+    /// except for its continuity.
+    ///
+    /// Complexity: \\( O(\log n) \\) where \\( n \\) is the population
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `quantile` - the quantile (must be in the range (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if the population is empty
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    /// * `InvalidQuantile` - if the quantile is not in the range (0, 1)
+    /// * `IndexError` - if the confidence interval falls outside the range of the data, i.e., if there are too few
+    ///   samples to guarantee the confidence level for the quantile (typically, an extreme quantile with few samples)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // 95th percentile with 100 samples
+    /// let interval = quantile::Stats::new(100).ci_exact(confidence, 0.95)?;
+    /// assert_eq!(interval, Interval::new(89, 99)?);
+    /// // 99th percentile with 100 samples: the upper bound cannot be guaranteed
+    /// assert!(quantile::Stats::new(100).ci_exact(confidence, 0.99).is_err());
+    /// // ... but a one-sided interval can
+    /// let confidence = Confidence::new_upper(0.975);
+    /// let interval = quantile::Stats::new(100).ci_exact(confidence, 0.99)?;
+    /// assert_eq!(interval, Interval::new_upper(96));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * W.J. Conover. Practical Nonparametric Statistics (3rd edition), Section 3.2. Wiley, 1999.
+    /// * [Wikipedia - Order statistic](https://en.wikipedia.org/wiki/Order_statistic#Confidence_intervals_for_quantiles)
+    ///
+    pub fn ci_exact(&self, confidence: Confidence, quantile: f64) -> CIResult<Interval<usize>> {
+        check_arguments(confidence, quantile)?;
+        if self.population == 0 {
+            return Err(error::CIError::TooFewSamples(self.population));
+        }
+
+        let n = self.population as u64;
+        let alpha = 1. - distributions::checked_quantile(confidence)?;
+        let cdf = |k: u64| stats::binomial_cdf(k, n, quantile);
+        // number of ranks i such that cdf(i) <= alpha (cdf is non-decreasing)
+        let below = partition_point(n, |i| cdf(i) <= alpha);
+        // first rank j such that cdf(j) >= 1 - alpha
+        let above = partition_point(n, |j| cdf(j) < 1. - alpha);
+
+        // ranks are clamped to the data: none on a side where the interval would fall outside of it
+        let lo_index = below.checked_sub(1).map(|lo| lo as usize);
+        let hi_index = (above < n).then_some(above as usize);
+
+        match (confidence, lo_index, hi_index) {
+            (Confidence::TwoSided(_), Some(lo), Some(hi)) => {
+                Interval::new(lo, hi).map_err(|e| e.into())
+            }
+            (Confidence::TwoSided(_) | Confidence::UpperOneSided(_), None, _) => {
+                Err(error::CIError::IndexError(-1., self.population))
+            }
+            (Confidence::UpperOneSided(_), Some(lo), _) => Ok(Interval::new_upper(lo)),
+            (_, _, None) => Err(error::CIError::IndexError(
+                self.population as f64,
+                self.population,
+            )),
+            (Confidence::LowerOneSided(_), _, Some(hi)) => Ok(Interval::new_lower(hi)),
         }
+    }
+
+    ///
+    /// Return the confidence interval on the indices for a given quantile, using the Wilson score method.
+    ///
+    /// The ranks of the bounds are obtained from the Wilson score interval on the proportion of samples
+    /// smaller than the quantile (see [`proportion::ci_wilson`]). This relies on a normal approximation,
+    /// which is accurate for large populations but can be poor for small populations and extreme quantiles.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `quantile` - the quantile (must be in the range (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+    /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+    /// * `InvalidQuantile` - if the quantile is not in the range (0, 1)
+    /// * `IndexError` - if the confidence interval falls outside the range of the data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// let confidence = Confidence::new_two_sided(0.8);
+    /// let quantile = 0.5; // median
+    /// let stats = quantile::Stats::new(data.len());
+    /// let interval = stats.ci_wilson(confidence, quantile)?;
+    /// assert_eq!(interval, Interval::new(3, 6)?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn ci_wilson(&self, confidence: Confidence, quantile: f64) -> CIResult<Interval<usize>> {
+        check_arguments(confidence, quantile)?;
 
         if self.population < 4 {
             // too few samples to compute
@@ -185,11 +327,6 @@ impl core::ops::AddAssign for Stats {
 /// * `sorted` - the sorted sample
 /// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
 ///
-/// # Output
-///
-/// * `Interval` - the confidence interval for the quantile
-/// * `None` - if the number of samples is too small to compute a confidence interval, or if the interval falls outside the range of the data.
-///
 /// # Errors
 ///
 /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
@@ -204,11 +341,11 @@ impl core::ops::AddAssign for Stats {
 /// let confidence = Confidence::new_two_sided(0.95);
 /// let quantile = 0.5; // median
 /// let interval = quantile::ci_sorted_unchecked(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(5, 12)?);
+/// assert_eq!(interval, Interval::new(4, 12)?);
 ///
 /// let confidence = Confidence::new_two_sided(0.8);
 /// let interval = quantile::ci_sorted_unchecked(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(6, 11)?);
+/// assert_eq!(interval, Interval::new(5, 11)?);
 ///
 /// let confidence = Confidence::new_two_sided(0.5);
 /// let quantile = 0.4; // 40th percentile
@@ -224,8 +361,6 @@ pub fn ci_sorted_unchecked<T>(
 where
     T: PartialOrd + Clone,
 {
-    assert!(quantile > 0. && quantile < 1.);
-
     ci_indices(confidence, sorted.len(), quantile).and_then(|indices| match indices.into() {
         (Some(lo), Some(hi)) => {
             Interval::new(sorted[lo].clone(), sorted[hi].clone()).map_err(|e| e.into())
@@ -242,7 +377,7 @@ where
 /// Compute the confidence interval for a given quantile.
 /// Use [`ci_sorted_unchecked`] instead if the data is already sorted.
 ///
-/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples.
 ///
 /// # Arguments
 ///
@@ -268,7 +403,7 @@ where
 /// let confidence = Confidence::new_two_sided(0.95);
 /// let quantile = 0.5; // median
 /// let interval = quantile::ci(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(5, 12)?);
+/// assert_eq!(interval, Interval::new(4, 12)?);
 ///
 /// let data2 = [2, 14, 13, 6, 8, 4, 15, 9, 3, 11, 10, 7, 1, 12, 5];
 /// let interval2 = quantile::ci(confidence, &data2, quantile)?;
@@ -276,7 +411,7 @@ where
 ///
 /// let confidence = Confidence::new_two_sided(0.8);
 /// let interval = quantile::ci(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(6, 11)?);
+/// assert_eq!(interval, Interval::new(5, 11)?);
 ///
 /// let confidence = Confidence::new_two_sided(0.5);
 /// let quantile = 0.4; // 40th percentile
@@ -288,24 +423,81 @@ where
 /// Notes:
 ///
 /// This function is only available with the `std` feature enabled.
-/// This is because it uses a sorted [Vec] when finding the quantile,
+/// This is because it copies the data into a [Vec] when finding the quantile,
 /// which is not available in `no_std` environments.
+/// Use [`ci_mut`] to avoid the copy, or if the data can be reordered in place.
 #[cfg(any(test, feature = "std"))]
 pub fn ci<T, I>(confidence: Confidence, data: &I, quantile: f64) -> CIResult<Interval<T>>
 where
     T: PartialOrd + Copy,
     for<'a> &'a I: IntoIterator<Item = &'a T>,
 {
-    let mut sorted: Vec<T> = data.into_iter().copied().collect();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    ci_sorted_unchecked(confidence, &sorted, quantile)
+    let mut buffer: Vec<T> = data.into_iter().copied().collect();
+    ci_mut(confidence, &mut buffer, quantile)
+}
+
+///
+/// Compute the confidence interval for a given quantile of floating point data, handling NaN values according to the given policy.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample data
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+/// * `nan_policy` - the policy for NaN values (see [`NanPolicy`])
+///
+/// # Errors
+///
+/// * `InvalidInputData` - if the data contains NaN values and the policy is [`NanPolicy::Error`]
+/// * `TooFewSamples` - if the number of (non-ignored) samples is too small to compute a confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., f64::NAN, 8., 9., 10., 11., 12., 13., 14., 15.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = quantile::ci_with_nan_policy(confidence, &data, 0.5, NanPolicy::Ignore)?;
+/// assert_eq!(interval, Interval::new(4., 12.)?);
+/// assert!(quantile::ci_with_nan_policy(confidence, &data, 0.5, NanPolicy::Error).is_err());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// Notes:
+///
+/// Unlike [`ci`], which panics on NaN values since they are not comparable, this function never panics.
+/// This function is only available with the `std` feature enabled.
+#[cfg(any(test, feature = "std"))]
+pub fn ci_with_nan_policy<F, I>(
+    confidence: Confidence,
+    data: &I,
+    quantile: f64,
+    nan_policy: NanPolicy,
+) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let mut buffer = Vec::new();
+    for &x in data {
+        match nan_policy.apply(x)? {
+            NanAction::Keep => buffer.push(x),
+            NanAction::Ignore => {}
+            NanAction::Propagate => return Ok(NanPolicy::nan_interval(confidence)),
+        }
+    }
+    ci_mut(confidence, &mut buffer, quantile)
 }
 
 ///
 /// Compute the confidence interval for a given quantile.
 /// Use [`ci_sorted_unchecked`] instead if the data is already sorted.
 ///
-/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples.
 ///
 /// # Arguments
 ///
@@ -332,7 +524,7 @@ where
 /// let confidence = Confidence::new_two_sided(0.95);
 /// let quantile = 0.5; // median
 /// let interval = quantile::ci(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(5, 12)?);
+/// assert_eq!(interval, Interval::new(4, 12)?);
 ///
 /// let data2 = [2, 14, 13, 6, 8, 4, 15, 9, 3, 11, 10, 7, 1, 12, 5];
 /// let interval2 = quantile::ci(confidence, &data2, quantile)?;
@@ -340,7 +532,7 @@ where
 ///
 /// let confidence = Confidence::new_two_sided(0.8);
 /// let interval = quantile::ci(confidence, &data, quantile)?;
-/// assert_eq!(interval, Interval::new(6, 11)?);
+/// assert_eq!(interval, Interval::new(5, 11)?);
 ///
 /// let confidence = Confidence::new_two_sided(0.5);
 /// let quantile = 0.4; // 40th percentile
@@ -363,104 +555,903 @@ where
     for<'a> &'a I: IntoIterator<Item = &'a T>,
 {
     use arrayvec::ArrayVec;
-    let mut sorted: ArrayVec<T, CAP> = data.into_iter().copied().collect();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    ci_sorted_unchecked(confidence, &sorted, quantile)
+    let mut buffer: ArrayVec<T, CAP> = data.into_iter().copied().collect();
+    ci_mut(confidence, &mut buffer, quantile)
 }
 
 ///
-/// Compute the indices of the confidence interval for a given quantile.
-/// The function returns the indices of the lower and upper bounds of the interval.
+/// Compute the confidence interval for a given quantile, reordering the data in place.
+/// Unlike [`ci`], this function does not allocate any memory; it is hence available
+/// in `no_std` and `no_alloc` environments, without limitation on the number of samples.
 ///
-/// Complexity: \\( O(1) \\)
+/// Only the two order statistics bounding the interval are located, using a selection algorithm
+/// (see [`slice::select_nth_unstable_by`]) rather than a full sort.
+/// When the function returns, the data is partially reordered: the elements are partitioned
+/// around the bounds of the interval, but are otherwise in unspecified order.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples.
 ///
 /// # Arguments
 ///
 /// * `confidence` - the confidence level (must be in (0, 1))
-/// * `data_len` - the number of samples
+/// * `data` - the sample data, which will be reordered
 /// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
 ///
-/// # Output
-///
-/// * `Interval` - the confidence interval for the quantile
-/// * `None` - if the number of samples is too small to compute a confidence interval, or if the interval falls outside the range of the data.
-///
 /// # Errors
 ///
 /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
 /// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
 /// * `InvalidQuantile` - if the quantile is not in (0, 1)
 ///
+/// # Panics
+///
+/// * if the data contains elements that are not comparable (with their partial ordering).
+///
 /// # Examples
 ///
 /// ```
 /// # use stats_ci::*;
-/// let data = ["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O"];
+/// let mut data = [2, 14, 13, 6, 8, 4, 15, 9, 3, 11, 10, 7, 1, 12, 5];
 /// let confidence = Confidence::new_two_sided(0.95);
 /// let quantile = 0.5; // median
-/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
-/// assert_eq!(interval, Interval::new(4, 11)?);
+/// let interval = quantile::ci_mut(confidence, &mut data, quantile)?;
+/// assert_eq!(interval, Interval::new(4, 12)?);
+/// // the data is partitioned around the bounds of the interval
+/// assert_eq!(data[3], 4);
+/// assert_eq!(data[11], 12);
+/// assert!(data[..3].iter().all(|&x| x < 4));
+/// assert!(data[12..].iter().all(|&x| x > 12));
+/// # Ok::<(),error::CIError>(())
+/// ```
 ///
-/// let confidence = Confidence::new_two_sided(0.8);
-/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
-/// assert_eq!(interval, Interval::new(5, 10)?);
+pub fn ci_mut<T>(confidence: Confidence, data: &mut [T], quantile: f64) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Clone,
+{
+    let compare = |a: &T, b: &T| a.partial_cmp(b).unwrap();
+    let indices = ci_indices(confidence, data.len(), quantile)?;
+    match indices.into() {
+        (Some(lo), Some(hi)) => {
+            let (_, low, rest) = data.select_nth_unstable_by(lo, compare);
+            let low = low.clone();
+            let high = if hi == lo {
+                low.clone()
+            } else {
+                let (_, high, _) = rest.select_nth_unstable_by(hi - lo - 1, compare);
+                high.clone()
+            };
+            Interval::new(low, high).map_err(|e| e.into())
+        }
+        (Some(lo), None) => {
+            let (_, low, _) = data.select_nth_unstable_by(lo, compare);
+            Ok(Interval::new_upper(low.clone()))
+        }
+        (None, Some(hi)) => {
+            let (_, high, _) = data.select_nth_unstable_by(hi, compare);
+            Ok(Interval::new_lower(high.clone()))
+        }
+        _ => Err(error::CIError::IntervalError(
+            interval::IntervalError::EmptyInterval,
+        )),
+    }
+}
+
 ///
-/// let confidence = Confidence::new_two_sided(0.5);
-/// let quantile = 0.4; // 40th percentile
-/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
-/// assert_eq!(interval, Interval::new(4, 7)?);
+/// Compute confidence intervals for several quantiles at once, with simultaneous coverage.
+///
+/// Reporting several quantiles (e.g., p50, p95, and p99) with individually valid intervals overstates
+/// the joint confidence: with three 95% intervals, the probability that at least one of them misses its quantile
+/// can be as high as 15%. This function instead guarantees that __all__ intervals simultaneously contain their
+/// respective quantile with (at least) the given confidence level.
+/// This is done with the Bonferroni correction, i.e., each of the \\( k \\) intervals is computed with a confidence
+/// level of \\( 1 - \alpha/k \\) where \\( 1-\alpha \\) is the requested confidence level.
+///
+/// Complexity: \\( O(n \log n + k) \\) where \\( n \\) is the number of samples and \\( k \\) the number of quantiles.
+///
+/// # Arguments
+///
+/// * `confidence` - the joint confidence level (must be in (0, 1))
+/// * `data` - the sample data
+/// * `quantiles` - the quantiles to compute the confidence intervals for (each must be in (0, 1))
+///
+/// # Output
+///
+/// * the confidence intervals, in the same order as `quantiles`
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute some confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if some quantile is not in (0, 1)
+/// * `IndexError` - if some confidence interval falls outside the range of the data
+/// * `InvalidInputData` - if the data contains elements that are not comparable (with their partial ordering), e.g., NaN
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = (1..=1000).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let intervals = quantile::ci_many(confidence, &data, &[0.5, 0.95, 0.99])?;
+/// assert_eq!(intervals.len(), 3);
+/// // each interval is wider than an individual 95% interval
+/// for (interval, quantile) in intervals.iter().zip([0.5, 0.95, 0.99]) {
+///     let individual = quantile::ci(confidence, &data, quantile)?;
+///     assert!(interval.includes(&individual));
+/// }
 /// # Ok::<(),error::CIError>(())
 /// ```
-pub fn ci_indices(
+///
+/// # Notes
+///
+/// The Bonferroni correction is conservative: the actual joint coverage is typically larger than the
+/// requested confidence level. This function is only available with the `std` feature enabled.
+///
+/// # References
+///
+/// * [Wikipedia - Bonferroni correction](https://en.wikipedia.org/wiki/Bonferroni_correction)
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_many<T, I>(
     confidence: Confidence,
-    data_len: usize,
-    quantile: f64,
-) -> CIResult<Interval<usize>> {
-    let stats = Stats::new(data_len);
-    stats.ci(confidence, quantile)
+    data: &I,
+    quantiles: &[f64],
+) -> CIResult<Vec<Interval<T>>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let sorted = sorted_copy(data)?;
+    ci_many_sorted_unchecked(confidence, &sorted, quantiles)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::thread_rng;
-
-    #[test]
-    fn test_median_ci() -> CIResult<()> {
-        let data = [
-            8., 11., 12., 13., 15., 17., 19., 20., 21., 21., 22., 23., 25., 26., 28.,
-        ];
-        let confidence = Confidence::new_two_sided(0.95);
-        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
-        assert_eq!(median_ci, Interval::new(15., 23.)?);
-
-        let confidence = Confidence::new_lower(0.975);
-        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
-        assert_eq!(median_ci, Interval::new_lower(23.));
-
-        let confidence = Confidence::new_upper(0.975);
-        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
-        assert_eq!(median_ci, Interval::new_upper(15.));
+///
+/// Compute confidence intervals for several quantiles at once, with simultaneous coverage,
+/// assuming that the data is __already sorted__ (see [`ci_many`] and [`ci_sorted_unchecked`]).
+///
+/// Complexity: \\( O(k) \\) where \\( k \\) is the number of quantiles.
+///
+/// # Arguments
+///
+/// * `confidence` - the joint confidence level (must be in (0, 1))
+/// * `sorted` - the sorted sample
+/// * `quantiles` - the quantiles to compute the confidence intervals for (each must be in (0, 1))
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute some confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if some quantile is not in (0, 1)
+/// * `IndexError` - if some confidence interval falls outside the range of the data
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let sorted = (1..=100).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.9);
+/// let intervals = quantile::ci_many_sorted_unchecked(confidence, &sorted, &[0.25, 0.5, 0.75])?;
+/// // each interval is computed at the 96.67% level
+/// let individual = Confidence::new_two_sided(1. - 0.1 / 3.);
+/// assert_eq!(intervals[1], quantile::ci_sorted_unchecked(individual, &sorted, 0.5)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_many_sorted_unchecked<T>(
+    confidence: Confidence,
+    sorted: &[T],
+    quantiles: &[f64],
+) -> CIResult<Vec<Interval<T>>>
+where
+    T: PartialOrd + Clone,
+{
+    for &quantile in quantiles {
+        check_arguments(confidence, quantile)?;
+    }
+    let individual = confidence.adjusted(quantiles.len(), Correction::Bonferroni);
+    quantiles
+        .iter()
+        .map(|&quantile| ci_sorted_unchecked(individual, sorted, quantile))
+        .collect()
+}
 
-        Ok(())
+///
+/// Compute the weighted quantile of a sample where each value carries a non-negative weight
+/// (e.g., an importance weight or the inverse of a sampling rate).
+///
+/// The weighted quantile is the smallest value \\( x_i \\) (in sorted order) such that the cumulative weight
+/// of the values up to and including \\( x_i \\) reaches the fraction `quantile` of the total weight.
+/// With equal weights, this is the usual (lower) empirical quantile.
+///
+/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `data` - the sample data, as pairs of value and weight
+/// * `quantile` - the quantile (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `InvalidInputData` - if some weight is negative or not finite, or some values are not comparable (e.g., NaN)
+/// * `TooFewSamples` - if the total weight is zero (e.g., the data is empty)
+///
+/// # Panics
+///
+/// * if the data contains values that are not comparable (with their partial ordering).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // value 1 was sampled at a rate of 1/4, values 2 and 3 at a rate of 1/2
+/// let data = [(1, 4.), (2, 2.), (3, 2.)];
+/// assert_eq!(quantile::weighted_quantile(&data, 0.5)?, 1);
+/// assert_eq!(quantile::weighted_quantile(&data, 0.6)?, 2);
+/// assert_eq!(quantile::weighted_quantile(&data, 0.9)?, 3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+#[cfg(any(test, feature = "std"))]
+pub fn weighted_quantile<T, I>(data: &I, quantile: f64) -> CIResult<T>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a (T, f64)>,
+{
+    if quantile <= 0. || 1. <= quantile || quantile.is_nan() {
+        return Err(error::CIError::InvalidQuantile(quantile));
     }
+    let weighted = WeightedSample::new(data)?;
+    Ok(weighted.value(weighted.index_reaching(quantile)))
+}
 
-    #[test]
+///
+/// Compute the confidence interval for a given quantile of a sample where each value carries
+/// a non-negative weight (e.g., an importance weight or the inverse of a sampling rate).
+///
+/// The interval is computed like the Wilson score method of [`Stats::ci_wilson`], except that the
+/// number of samples is replaced by Kish's effective sample size
+/// \\[
+/// n_{\text{eff}} = \frac{\left(\sum_i w_i\right)^2}{\sum_i w_i^2}
+/// \\]
+/// which accounts for the loss of precision due to unequal weights.
+/// The bounds of the resulting interval on the cumulative weight fraction are then mapped back to values
+/// through the weighted empirical distribution (see [`weighted_quantile`]).
+/// With equal weights, the result is the same as with the Wilson score method on the unweighted data.
+///
+/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample data, as pairs of value and weight
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `InvalidInputData` - if some weight is negative or not finite, or some values are not comparable (e.g., NaN)
+/// * `TooFewSamples` - if the effective sample size is too small to compute a confidence interval (less than 4)
+///
+/// # Panics
+///
+/// * if the data contains values that are not comparable (with their partial ordering).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // latencies sampled at different rates: the weight of each sample is the inverse of its sampling rate
+/// let data = (1..=100)
+///     .map(|latency| (latency, if latency <= 50 { 10. } else { 1. }))
+///     .collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = quantile::ci_weighted(confidence, &data, 0.5)?;
+/// let median = quantile::weighted_quantile(&data, 0.5)?;
+/// assert!(interval.contains(&median));
+/// assert!(median < 50);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Notes
+///
+/// The method relies on a normal approximation and on the weights being fixed (i.e., not estimated from the data).
+/// This function is only available with the `std` feature enabled.
+///
+/// # References
+///
+/// * L. Kish. Survey Sampling. Wiley, 1965.
+/// * [Wikipedia - Effective sample size](https://en.wikipedia.org/wiki/Effective_sample_size)
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_weighted<T, I>(confidence: Confidence, data: &I, quantile: f64) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a (T, f64)>,
+{
+    check_arguments(confidence, quantile)?;
+    let weighted = WeightedSample::new(data)?;
+
+    let n_eff = weighted.effective_sample_size();
+    if n_eff < 4. {
+        // too few samples to compute
+        return Err(error::CIError::TooFewSamples(n_eff as usize));
+    }
+
+    // Wilson score interval on the fraction of the weight below the quantile
+    let z = distributions::z_value(confidence)?;
+    let z_sq = z * z;
+    let mean = (quantile + z_sq / (2. * n_eff)) / (1. + z_sq / n_eff);
+    let span = (z / (1. + z_sq / n_eff))
+        * (quantile * (1. - quantile) / n_eff + z_sq / (4. * n_eff * n_eff)).sqrt();
+
+    let low = weighted.value(weighted.index_reaching(mean - span));
+    let high = weighted.value(weighted.index_exceeding(mean + span));
+
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(low, high).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(low)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(high)),
+    }
+}
+
+///
+/// Weighted sample sorted by value, with the cumulative weights.
+///
+#[cfg(any(test, feature = "std"))]
+struct WeightedSample<T> {
+    values: Vec<T>,
+    cumulative: Vec<f64>,
+    sum_sq_weights: f64,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Copy> WeightedSample<T> {
+    fn new<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (T, f64)>,
+    {
+        let mut pairs: Vec<(T, f64)> = data.into_iter().copied().collect();
+        if pairs
+            .iter()
+            .any(|&(x, w)| x.partial_cmp(&x).is_none() || !w.is_finite() || w < 0.)
+        {
+            return Err(error::CIError::InvalidInputData);
+        }
+        pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut total = utils::KahanSum::default();
+        let mut sum_sq_weights = utils::KahanSum::default();
+        let mut values = Vec::with_capacity(pairs.len());
+        let mut cumulative = Vec::with_capacity(pairs.len());
+        for (value, weight) in pairs {
+            total += weight;
+            sum_sq_weights += weight * weight;
+            values.push(value);
+            cumulative.push(total.value());
+        }
+        if total.value() <= 0. {
+            return Err(error::CIError::TooFewSamples(0));
+        }
+        Ok(Self {
+            values,
+            cumulative,
+            sum_sq_weights: sum_sq_weights.value(),
+        })
+    }
+
+    fn total(&self) -> f64 {
+        *self.cumulative.last().unwrap()
+    }
+
+    fn effective_sample_size(&self) -> f64 {
+        let total = self.total();
+        total * total / self.sum_sq_weights
+    }
+
+    fn value(&self, index: usize) -> T {
+        self.values[index.min(self.values.len() - 1)]
+    }
+
+    /// index of the first value at which the cumulative weight reaches the given fraction of the total
+    fn index_reaching(&self, fraction: f64) -> usize {
+        let target = fraction * self.total();
+        self.cumulative.partition_point(|&c| c < target)
+    }
+
+    /// index of the first value at which the cumulative weight exceeds the given fraction of the total
+    fn index_exceeding(&self, fraction: f64) -> usize {
+        let target = fraction * self.total();
+        self.cumulative.partition_point(|&c| c <= target)
+    }
+}
+
+///
+/// Compute a simultaneous confidence band for the cumulative distribution function (CDF) of the population,
+/// around the empirical CDF of the sample, using the Dvoretzky–Kiefer–Wolfowitz (DKW) inequality.
+///
+/// Unlike pointwise intervals (e.g., [`ci`] for a single quantile), the band contains the __whole__ CDF with
+/// (at least) the given confidence level, which makes it suitable to draw an uncertainty band around a distribution.
+/// For a sample of size \\( n \\), the band is \\( \hat{F}_n(x) \pm \varepsilon \\) (clamped to [0, 1]) where
+/// \\[
+/// \varepsilon = \sqrt{\frac{\ln(2/\alpha)}{2n}}
+/// \\]
+/// for a two-sided band, and \\( \varepsilon = \sqrt{\ln(1/\alpha) / 2n} \\) for a one-sided band
+/// (see [`dkw_half_width`]).
+///
+/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample data
+///
+/// # Output
+///
+/// For each distinct value \\( x \\) of the sample (in increasing order), the pair of \\( x \\) and the confidence
+/// interval on \\( F(x) \\). Between two consecutive values, the band is that of the smaller value.
+/// An upper one-sided band bounds the CDF from below (i.e., intervals \\( [\hat{F}_n(x) - \varepsilon, 1] \\)),
+/// and a lower one-sided band bounds it from above (i.e., intervals \\( [0, \hat{F}_n(x) + \varepsilon] \\)).
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the data is empty
+/// * `InvalidInputData` - if the data contains elements that are not comparable (with their partial ordering), e.g., NaN
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = (1..=100).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let band = quantile::ecdf_band(confidence, &data)?;
+/// assert_eq!(band.len(), 100);
+/// let (value, interval) = band[49];
+/// assert_eq!(value, 50);
+/// assert_abs_diff_eq!(interval.low_f(), 0.5 - 0.13581, epsilon = 1e-5);
+/// assert_abs_diff_eq!(interval.high_f(), 0.5 + 0.13581, epsilon = 1e-5);
+/// // the band is clamped to [0, 1]
+/// assert_eq!(band[0].1.low_f(), 0.);
+/// assert_eq!(band[99].1.high_f(), 1.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Notes
+///
+/// The DKW bound (with Massart's tight constant) holds for any sample size and is slightly conservative.
+/// See [`ecdf_band_ks`] for a band based on the critical values of the Kolmogorov–Smirnov statistic.
+/// This function is only available with the `std` feature enabled.
+///
+/// # References
+///
+/// * P. Massart. The Tight Constant in the Dvoretzky-Kiefer-Wolfowitz Inequality. The Annals of Probability, 18(3):1269-1283, 1990.
+/// * [Wikipedia - Dvoretzky–Kiefer–Wolfowitz inequality](https://en.wikipedia.org/wiki/Dvoretzky%E2%80%93Kiefer%E2%80%93Wolfowitz_inequality)
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ecdf_band<T, I>(confidence: Confidence, data: &I) -> CIResult<Vec<(T, Interval<f64>)>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let sorted = sorted_copy(data)?;
+    let half_width = dkw_half_width(confidence, sorted.len())?;
+    Ok(ecdf_band_sorted(confidence, &sorted, half_width))
+}
+
+///
+/// Compute a simultaneous confidence band for the cumulative distribution function (CDF) of the population,
+/// around the empirical CDF of the sample, using the critical values of the Kolmogorov–Smirnov statistic.
+///
+/// This is the same as [`ecdf_band`] except that the half-width of the band is given by [`ks_half_width`],
+/// which is slightly narrower than the DKW bound for small samples.
+///
+/// Complexity: \\( O(n \log n) \\) where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample data
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the data is empty
+/// * `InvalidInputData` - if the data contains elements that are not comparable (with their partial ordering), e.g., NaN
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [3.1, 4.5, 2.2, 5.0, 3.3, 4.1, 2.9, 3.8, 4.4, 3.0];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let band = quantile::ecdf_band_ks(confidence, &data)?;
+/// let dkw = quantile::ecdf_band(confidence, &data)?;
+/// for ((_, ks), (_, dkw)) in band.iter().zip(&dkw) {
+///     assert!(dkw.includes(ks));
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ecdf_band_ks<T, I>(confidence: Confidence, data: &I) -> CIResult<Vec<(T, Interval<f64>)>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let sorted = sorted_copy(data)?;
+    let half_width = ks_half_width(confidence, sorted.len())?;
+    Ok(ecdf_band_sorted(confidence, &sorted, half_width))
+}
+
+///
+/// Return the half-width of the confidence band on the CDF given by the Dvoretzky–Kiefer–Wolfowitz inequality
+/// (see [`ecdf_band`]).
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data_len` - the number of samples
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is zero
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_abs_diff_eq!(quantile::dkw_half_width(confidence, 100)?, 0.13581, epsilon = 1e-5);
+/// // number of samples needed for a band of +/- 1%
+/// assert!(quantile::dkw_half_width(confidence, 18_445)? <= 0.01);
+/// assert!(quantile::dkw_half_width(confidence, 18_444)? > 0.01);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn dkw_half_width(confidence: Confidence, data_len: usize) -> CIResult<f64> {
+    if data_len == 0 {
+        return Err(error::CIError::TooFewSamples(data_len));
+    }
+    distributions::checked_quantile(confidence)?;
+    let alpha = 1. - confidence.level();
+    let tails = if confidence.is_two_sided() { 2. } else { 1. };
+    Ok(((tails / alpha).ln() / (2. * data_len as f64)).sqrt())
+}
+
+///
+/// Return the half-width of the confidence band on the CDF given by the critical value of the
+/// Kolmogorov–Smirnov statistic (see [`ecdf_band_ks`]).
+///
+/// The critical value \\( \lambda \\) of the asymptotic Kolmogorov distribution is scaled with
+/// Stephens' small-sample correction, i.e., \\( \varepsilon = \lambda / (\sqrt{n} + 0.12 + 0.11/\sqrt{n}) \\),
+/// which is accurate to within a few units in the third decimal for \\( n \geq 5 \\).
+/// For a one-sided band, the asymptotic critical value is \\( \lambda = \sqrt{\ln(1/\alpha)/2} \\).
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data_len` - the number of samples
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is zero
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // the exact critical value for n = 10 is 0.40925
+/// assert_abs_diff_eq!(quantile::ks_half_width(confidence, 10)?, 0.40925, epsilon = 1e-3);
+/// assert!(quantile::ks_half_width(confidence, 10)? < quantile::dkw_half_width(confidence, 10)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * M.A. Stephens. EDF Statistics for Goodness of Fit and Some Comparisons. Journal of the American Statistical Association, 69(347):730-737, 1974.
+/// * [Wikipedia - Kolmogorov–Smirnov test](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test)
+///
+pub fn ks_half_width(confidence: Confidence, data_len: usize) -> CIResult<f64> {
+    if data_len == 0 {
+        return Err(error::CIError::TooFewSamples(data_len));
+    }
+    distributions::checked_quantile(confidence)?;
+    let alpha = 1. - confidence.level();
+    let lambda = if confidence.is_two_sided() {
+        kolmogorov_inverse_sf(alpha)
+    } else {
+        ((1. / alpha).ln() / 2.).sqrt()
+    };
+    let sqrt_n = (data_len as f64).sqrt();
+    Ok(lambda / (sqrt_n + 0.12 + 0.11 / sqrt_n))
+}
+
+///
+/// Return \\( \lambda \\) such that \\( P(K > \lambda) = \alpha \\) where \\( K \\) follows the Kolmogorov distribution,
+/// i.e., \\( P(K > \lambda) = 2 \sum_{k \geq 1} (-1)^{k-1} e^{-2k^2\lambda^2} \\) (bisection).
+///
+fn kolmogorov_inverse_sf(alpha: f64) -> f64 {
+    let survival = |lambda: f64| {
+        2. * (1..=100)
+            .map(|k| {
+                let k = k as f64;
+                let sign = if k as u64 % 2 == 1 { 1. } else { -1. };
+                sign * (-2. * k * k * lambda * lambda).exp()
+            })
+            .sum::<f64>()
+    };
+    let (mut lo, mut hi) = (0.1_f64, 10.);
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.;
+        if survival(mid) > alpha {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.
+}
+
+///
+/// Copy the sample into a sorted vector, checking that all the values are comparable.
+///
+/// # Errors
+///
+/// * `InvalidInputData` - if the data contains elements that are not comparable (e.g., NaN)
+///
+#[cfg(any(test, feature = "std"))]
+pub(crate) fn sorted_copy<T, I>(data: &I) -> CIResult<Vec<T>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let mut sorted: Vec<T> = data.into_iter().copied().collect();
+    if sorted.iter().any(|x| x.partial_cmp(x).is_none()) {
+        return Err(error::CIError::InvalidInputData);
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+#[cfg(any(test, feature = "std"))]
+fn ecdf_band_sorted<T>(
+    confidence: Confidence,
+    sorted: &[T],
+    half_width: f64,
+) -> Vec<(T, Interval<f64>)>
+where
+    T: PartialOrd + Copy,
+{
+    let n = sorted.len() as f64;
+    let mut band = Vec::new();
+    for (i, &value) in sorted.iter().enumerate() {
+        if sorted.get(i + 1).is_some_and(|next| *next == value) {
+            // only keep the last of tied values
+            continue;
+        }
+        let ecdf = (i + 1) as f64 / n;
+        let low = (ecdf - half_width).max(0.);
+        let high = (ecdf + half_width).min(1.);
+        let interval = match confidence {
+            Confidence::TwoSided(_) => Interval::new(low, high),
+            Confidence::UpperOneSided(_) => Interval::new(low, 1.),
+            Confidence::LowerOneSided(_) => Interval::new(0., high),
+        };
+        band.push((value, interval.expect("band bounds are ordered")));
+    }
+    band
+}
+
+///
+/// Compute the indices of the confidence interval for a given quantile.
+/// The function returns the indices of the lower and upper bounds of the interval.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data_len` - the number of samples
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+///
+/// # Output
+///
+/// * `Interval` - the interval of the indices (ranks) in the sorted data of the bounds of the confidence interval
+///
+/// The data itself is not needed, which is useful when it is kept in some external storage (e.g., memory-mapped
+/// or columnar) where only the bounding elements need to be fetched. See also [`ci_index_range`] to obtain
+/// the indices as a range within the data, including for one-sided intervals.
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `IndexError` - if the confidence interval falls outside the range of the data
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = ["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O"];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let quantile = 0.5; // median
+/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
+/// assert_eq!(interval, Interval::new(3, 11)?);
+///
+/// let confidence = Confidence::new_two_sided(0.8);
+/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
+/// assert_eq!(interval, Interval::new(4, 10)?);
+///
+/// let confidence = Confidence::new_two_sided(0.5);
+/// let quantile = 0.4; // 40th percentile
+/// let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
+/// assert_eq!(interval, Interval::new(4, 7)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+pub fn ci_indices(
+    confidence: Confidence,
+    data_len: usize,
+    quantile: f64,
+) -> CIResult<Interval<usize>> {
+    let stats = Stats::new(data_len);
+    stats.ci(confidence, quantile)
+}
+
+///
+/// Compute the range of indices (ranks) in the sorted data that bound the confidence interval for a given quantile.
+///
+/// This is the same as [`ci_indices`] except that the result is always a range of valid indices into the sorted data:
+/// for a one-sided confidence interval, the unbounded side extends to the first or the last element of the data.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data_len` - the number of samples
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `IndexError` - if the confidence interval falls outside the range of the data
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // sorted column stored elsewhere: only the number of rows is needed
+/// let rows = 15;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ranks = quantile::ci_index_range(confidence, rows, 0.5)?;
+/// assert_eq!(ranks, 3..=11);
+/// // ... then fetch rows `*ranks.start()` and `*ranks.end()` from the storage
+///
+/// let confidence = Confidence::new_upper(0.975);
+/// let ranks = quantile::ci_index_range(confidence, rows, 0.5)?;
+/// assert_eq!(ranks, 3..=14);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_index_range(
+    confidence: Confidence,
+    data_len: usize,
+    quantile: f64,
+) -> CIResult<core::ops::RangeInclusive<usize>> {
+    let indices = ci_indices(confidence, data_len, quantile)?;
+    match indices.into() {
+        (Some(lo), Some(hi)) => Ok(lo..=hi),
+        (Some(lo), None) => Ok(lo..=data_len - 1),
+        (None, Some(hi)) => Ok(0..=hi),
+        _ => Err(error::CIError::IntervalError(
+            interval::IntervalError::EmptyInterval,
+        )),
+    }
+}
+
+///
+/// Check that the confidence level and the quantile are both in the range (0, 1).
+///
+/// # Errors
+///
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+///
+fn check_arguments(confidence: Confidence, quantile: f64) -> CIResult<()> {
+    let level = confidence.level();
+    if !(level > 0. && level < 1.) {
+        return Err(error::CIError::InvalidConfidenceLevel(level));
+    }
+    if !(quantile > 0. && quantile < 1.) {
+        return Err(error::CIError::InvalidQuantile(quantile));
+    }
+    Ok(())
+}
+
+///
+/// Return the number of leading values in `0..n` that satisfy `pred`,
+/// assuming that `pred` is true for a prefix of the range and false afterwards (binary search).
+///
+pub(crate) fn partition_point<P: Fn(u64) -> bool>(n: u64, pred: P) -> u64 {
+    let (mut lo, mut hi) = (0, n);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_median_ci() -> CIResult<()> {
+        let data = [
+            8., 11., 12., 13., 15., 17., 19., 20., 21., 21., 22., 23., 25., 26., 28.,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
+        assert_eq!(median_ci, Interval::new(13., 23.)?);
+
+        let confidence = Confidence::new_lower(0.975);
+        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
+        assert_eq!(median_ci, Interval::new_lower(23.));
+
+        let confidence = Confidence::new_upper(0.975);
+        let median_ci = ci_sorted_unchecked(confidence, &data, 0.5)?;
+        assert_eq!(median_ci, Interval::new_upper(13.));
+
+        Ok(())
+    }
+
+    #[test]
     fn test_quantile_ci() -> CIResult<()> {
         let data = [
             8., 11., 12., 13., 15., 17., 19., 20., 21., 21., 22., 23., 25., 26., 28.,
         ];
         let confidence = Confidence::new_two_sided(0.95);
         let quantile_ci = ci_sorted_unchecked(confidence, &data, 0.4).unwrap();
-        assert_eq!(quantile_ci, Interval::new(12., 21.)?);
+        assert_eq!(quantile_ci, Interval::new(11., 22.)?);
 
+        // too few samples to bound extreme quantiles with the exact method
         let confidence = Confidence::new_two_sided(0.999);
+        assert!(ci_sorted_unchecked(confidence, &data, 0.867).is_err());
+        assert!(ci_sorted_unchecked(confidence, &data, 0.133).is_err());
+        let confidence = Confidence::new_upper(0.99);
         let quantile_ci = ci_sorted_unchecked(confidence, &data, 0.867).unwrap();
-        assert_eq!(quantile_ci, Interval::new(19., 28.)?);
-
-        let confidence = Confidence::new_two_sided(0.999);
+        assert_eq!(quantile_ci, Interval::new_upper(21.));
+        let confidence = Confidence::new_lower(0.99);
         let quantile_ci = ci_sorted_unchecked(confidence, &data, 0.133).unwrap();
-        assert_eq!(quantile_ci, Interval::new(8., 21.)?);
+        assert_eq!(quantile_ci, Interval::new_lower(17.));
 
         let data = [
             "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O",
@@ -468,11 +1459,11 @@ mod tests {
         let confidence = Confidence::new_two_sided(0.95);
         let quantile = 0.5; // median
         let interval = quantile::ci_indices(confidence, data.len(), quantile).unwrap();
-        assert_eq!(interval, Interval::new(4, 11)?);
+        assert_eq!(interval, Interval::new(3, 11)?);
 
         let confidence = Confidence::new_two_sided(0.8);
         let interval = quantile::ci_indices(confidence, data.len(), quantile).unwrap();
-        assert_eq!(interval, Interval::new(5, 10)?);
+        assert_eq!(interval, Interval::new(4, 10)?);
 
         let confidence = Confidence::new_two_sided(0.5);
         let quantile = 0.4; // 40th percentile
@@ -485,7 +1476,7 @@ mod tests {
         let confidence = Confidence::new_two_sided(0.95);
         let quantile = 0.5; // median
         let interval = quantile::ci_sorted_unchecked(confidence, &data, quantile)?;
-        assert_eq!(interval, Interval::new("E", "L")?);
+        assert_eq!(interval, Interval::new("D", "L")?);
 
         let data = [
             'J', 'E', 'M', 'G', 'K', 'H', 'N', 'A', 'C', 'L', 'F', 'O', 'D', 'B', 'I',
@@ -493,17 +1484,17 @@ mod tests {
         let confidence = Confidence::new_two_sided(0.95);
         let quantile = 0.5; // median
         let interval = quantile::ci(confidence, &data, quantile)?;
-        assert_eq!(interval, Interval::new('E', 'L')?);
+        assert_eq!(interval, Interval::new('D', 'L')?);
 
         let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
         let confidence = Confidence::new_two_sided(0.95);
         let quantile = 0.5; // median
         let interval = quantile::ci(confidence, &data, quantile)?;
-        assert_eq!(interval, Interval::new(5, 12)?);
+        assert_eq!(interval, Interval::new(4, 12)?);
 
         let confidence = Confidence::new_two_sided(0.8);
         let interval = quantile::ci(confidence, &data, quantile)?;
-        assert_eq!(interval, Interval::new(6, 11)?);
+        assert_eq!(interval, Interval::new(5, 11)?);
 
         let confidence = Confidence::new_two_sided(0.5);
         let quantile = 0.4; // 40th percentile
@@ -520,11 +1511,11 @@ mod tests {
         ];
         let confidence = Confidence::new_upper(0.975);
         let quantile_ci = ci_sorted_unchecked(confidence, &data, 0.4).unwrap();
-        assert_eq!(quantile_ci, Interval::new_upper(12.));
+        assert_eq!(quantile_ci, Interval::new_upper(11.));
 
         let confidence = Confidence::new_lower(0.975);
         let quantile_ci = ci_sorted_unchecked(confidence, &data, 0.4).unwrap();
-        assert_eq!(quantile_ci, Interval::new_lower(21.));
+        assert_eq!(quantile_ci, Interval::new_lower(22.));
 
         let data = [
             "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O",
@@ -532,7 +1523,7 @@ mod tests {
         let confidence = Confidence::new_upper(0.975);
         let quantile = 0.5; // median
         let interval = quantile::ci_indices(confidence, data.len(), quantile).unwrap();
-        assert_eq!(interval, Interval::new_upper(4));
+        assert_eq!(interval, Interval::new_upper(3));
 
         let confidence = Confidence::new_lower(0.975);
         let interval = quantile::ci_indices(confidence, data.len(), quantile).unwrap();
@@ -547,11 +1538,11 @@ mod tests {
         let confidence = Confidence::new_two_sided(0.95);
         let quantile = 0.5; // median
         let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
-        assert_eq!(interval, Interval::new(4, 11)?);
+        assert_eq!(interval, Interval::new(3, 11)?);
 
         let confidence = Confidence::new_two_sided(0.8);
         let interval = quantile::ci_indices(confidence, data.len(), quantile)?;
-        assert_eq!(interval, Interval::new(5, 10)?);
+        assert_eq!(interval, Interval::new(4, 10)?);
 
         let confidence = Confidence::new_two_sided(0.5);
         let quantile = 0.4; // 40th percentile
@@ -580,6 +1571,30 @@ mod tests {
         Fifteen,
     }
 
+    #[test]
+    fn test_timestamps() -> CIResult<()> {
+        use time::{Duration, OffsetDateTime};
+        // arrival times of events, in seconds after some origin
+        let origin = OffsetDateTime::UNIX_EPOCH;
+        let arrivals = [
+            3, 7, 8, 12, 15, 15, 21, 24, 30, 31, 38, 42, 47, 55, 61, 64, 70, 78, 83, 90,
+        ]
+        .map(|s| origin + Duration::seconds(s));
+        let confidence = Confidence::new_two_sided(0.95);
+        let interval = ci(confidence, &arrivals, 0.5)?;
+        assert_eq!(
+            interval,
+            Interval::new(
+                origin + Duration::seconds(15),
+                origin + Duration::seconds(61)
+            )?
+        );
+        assert_eq!(interval.width(), Some(Duration::seconds(46)));
+        let interval = ci(Confidence::new_upper(0.95), &arrivals, 0.5)?;
+        assert_eq!(interval.low(), Some(origin + Duration::seconds(15)));
+        Ok(())
+    }
+
     #[test]
     fn test_median_unordered() -> CIResult<()> {
         use Numbers::*;
@@ -589,8 +1604,8 @@ mod tests {
         ];
         let confidence = Confidence::new_two_sided(0.95);
         let median_ci = ci_indices(confidence, data.len(), 0.5)?;
-        assert_eq!(median_ci, Interval::new(4, 11)?);
-        assert_eq!(median_ci.left(), Some(&4));
+        assert_eq!(median_ci, Interval::new(3, 11)?);
+        assert_eq!(median_ci.left(), Some(&3));
         assert_eq!(median_ci.right(), Some(&11));
 
         Ok(())
@@ -606,7 +1621,7 @@ mod tests {
             let mut shuffled = data.to_vec();
             shuffled.shuffle(&mut thread_rng());
             let interval = ci(confidence, &shuffled, quantile)?;
-            assert_eq!(interval, Interval::new(5, 12)?);
+            assert_eq!(interval, Interval::new(4, 12)?);
         }
         Ok(())
     }
@@ -622,4 +1637,438 @@ mod tests {
         stats += quantile::Stats::new(250);
         assert_eq!(stats, quantile::Stats::new(350));
     }
+
+    #[test]
+    fn test_ci_mut() -> CIResult<()> {
+        use rand::Rng;
+        let mut rng = thread_rng();
+        for len in [4, 5, 10, 15, 100, 1000] {
+            for _ in 0..20 {
+                // small range of values to include many duplicates
+                let data = (0..len)
+                    .map(|_| rng.gen_range(0..len / 2 + 1))
+                    .collect::<Vec<_>>();
+                let mut sorted = data.clone();
+                sorted.sort();
+                for confidence in [
+                    Confidence::new_two_sided(0.95),
+                    Confidence::new_two_sided(0.5),
+                    Confidence::new_upper(0.9),
+                    Confidence::new_lower(0.9),
+                ] {
+                    for quantile in [0.1, 0.25, 0.5, 0.9] {
+                        let expected = ci_sorted_unchecked(confidence, &sorted, quantile);
+                        let mut buffer = data.clone();
+                        let actual = ci_mut(confidence, &mut buffer, quantile);
+                        match (expected, actual) {
+                            (Ok(expected), Ok(actual)) => assert_eq!(expected, actual),
+                            (Err(_), Err(_)) => {}
+                            (expected, actual) => {
+                                panic!("mismatch: {:?} vs. {:?}", expected, actual)
+                            }
+                        }
+                        if let Ok(expected) = ci_sorted_unchecked(confidence, &sorted, quantile) {
+                            assert_eq!(ci(confidence, &data, quantile)?, expected);
+                            assert_eq!(
+                                ci_max_size::<_, _, DATA_CAP>(confidence, &data, quantile)?,
+                                expected
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        let mut data = [1., 2., 3.];
+        assert!(ci_mut(Confidence::new_two_sided(0.95), &mut data, 0.5).is_err());
+        let mut data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        assert!(matches!(
+            ci_mut(Confidence::new_two_sided(0.95), &mut data, 1.5),
+            Err(error::CIError::InvalidQuantile(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let confidence = Confidence::new_two_sided(0.95);
+        for quantile in [0., 1., -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                ci_sorted_unchecked(confidence, &data, quantile),
+                Err(error::CIError::InvalidQuantile(_))
+            ));
+            assert!(matches!(
+                ci(confidence, &data, quantile),
+                Err(error::CIError::InvalidQuantile(_))
+            ));
+        }
+        for confidence in [
+            Confidence::TwoSided(0.),
+            Confidence::UpperOneSided(1.),
+            Confidence::LowerOneSided(f64::NAN),
+        ] {
+            assert!(matches!(
+                ci_sorted_unchecked(confidence, &data, 0.5),
+                Err(error::CIError::InvalidConfidenceLevel(_))
+            ));
+            assert!(matches!(
+                Stats::new(5_000).ci(confidence, 0.5),
+                Err(error::CIError::InvalidConfidenceLevel(_))
+            ));
+            assert!(matches!(
+                ci_many(confidence, &data, &[0.5]),
+                Err(error::CIError::InvalidConfidenceLevel(_))
+            ));
+        }
+        let empty: [f64; 0] = [];
+        assert!(matches!(
+            ci_sorted_unchecked(confidence, &empty, 0.5),
+            Err(error::CIError::TooFewSamples(0))
+        ));
+    }
+
+    #[test]
+    fn test_ci_index_range() -> CIResult<()> {
+        let data = [
+            8., 11., 12., 13., 15., 17., 19., 20., 21., 21., 22., 23., 25., 26., 28.,
+        ];
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.975),
+            Confidence::new_lower(0.975),
+        ] {
+            let ranks = ci_index_range(confidence, data.len(), 0.4)?;
+            let interval = ci_sorted_unchecked(confidence, &data, 0.4)?;
+            if let Some(&low) = interval.left() {
+                assert_eq!(data[*ranks.start()], low);
+            } else {
+                assert_eq!(*ranks.start(), 0);
+            }
+            if let Some(&high) = interval.right() {
+                assert_eq!(data[*ranks.end()], high);
+            } else {
+                assert_eq!(*ranks.end(), data.len() - 1);
+            }
+        }
+        assert!(ci_index_range(Confidence::new_two_sided(0.95), 3, 0.5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wilson() -> CIResult<()> {
+        let stats = Stats::new(15);
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(stats.ci_wilson(confidence, 0.5)?, Interval::new(4, 11)?);
+        let confidence = Confidence::new_two_sided(0.8);
+        assert_eq!(stats.ci_wilson(confidence, 0.5)?, Interval::new(5, 10)?);
+        let confidence = Confidence::new_two_sided(0.5);
+        assert_eq!(stats.ci_wilson(confidence, 0.4)?, Interval::new(4, 7)?);
+        let confidence = Confidence::new_two_sided(0.999);
+        assert_eq!(stats.ci_wilson(confidence, 0.867)?, Interval::new(6, 14)?);
+        assert_eq!(stats.ci_wilson(confidence, 0.133)?, Interval::new(0, 8)?);
+
+        // large populations use the Wilson score method by default
+        let stats = Stats::new(EXACT_LIMIT + 1);
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            stats.ci(confidence, 0.9)?,
+            stats.ci_wilson(confidence, 0.9)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact() -> CIResult<()> {
+        // reference values computed in python (binomial cdf from math.comb)
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            Stats::new(15).ci_exact(confidence, 0.5)?,
+            Interval::new(3, 11)?
+        );
+        assert_eq!(
+            Stats::new(30).ci_exact(confidence, 0.5)?,
+            Interval::new(9, 20)?
+        );
+        assert_eq!(
+            Stats::new(100).ci_exact(confidence, 0.95)?,
+            Interval::new(89, 99)?
+        );
+        let confidence = Confidence::new_two_sided(0.9);
+        assert_eq!(
+            Stats::new(50).ci_exact(confidence, 0.9)?,
+            Interval::new(40, 48)?
+        );
+        let confidence = Confidence::new_upper(0.95);
+        assert_eq!(
+            Stats::new(30).ci_exact(confidence, 0.5)?,
+            Interval::new_upper(10)
+        );
+        let confidence = Confidence::new_lower(0.95);
+        assert_eq!(
+            Stats::new(30).ci_exact(confidence, 0.5)?,
+            Interval::new_lower(19)
+        );
+
+        let confidence = Confidence::new_two_sided(0.95);
+        assert!(matches!(
+            Stats::new(100).ci_exact(confidence, 0.99),
+            Err(error::CIError::IndexError(_, 100))
+        ));
+        assert!(matches!(
+            Stats::new(100).ci_exact(confidence, 0.01),
+            Err(error::CIError::IndexError(_, 100))
+        ));
+        assert!(Stats::new(5).ci_exact(confidence, 0.5).is_err());
+        assert!(Stats::new(0).ci_exact(confidence, 0.5).is_err());
+        assert!(Stats::new(10).ci_exact(confidence, 0.).is_err());
+        assert!(Stats::new(10).ci_exact(confidence, f64::NAN).is_err());
+
+        // coverage is guaranteed: check against the binomial distribution for many cases
+        for n in [10, 20, 50, 100, 500] {
+            for quantile in [0.05, 0.25, 0.5, 0.75, 0.95] {
+                if let Ok(interval) = Stats::new(n).ci_exact(confidence, quantile) {
+                    let (lo, hi): (usize, usize) = interval.into();
+                    let coverage = stats::binomial_cdf(hi as u64, n as u64, quantile)
+                        - stats::binomial_cdf(lo as u64, n as u64, quantile);
+                    assert!(coverage >= 0.95 - 1e-12, "n={n} q={quantile}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_rank_bounds() {
+        // property: for any (n, q, confidence), either the ranks are valid indices into the data
+        // or a typed error is returned (no panic, no underflow)
+        let sizes = (0..=120).chain([999, 1_000, 1_001, 2_500, 10_000]);
+        let quantiles = [
+            1e-6,
+            0.001,
+            0.01,
+            0.025,
+            0.05,
+            0.1,
+            0.25,
+            0.333,
+            0.5,
+            0.667,
+            0.75,
+            0.9,
+            0.95,
+            0.975,
+            0.99,
+            0.999,
+            1. - 1e-6,
+        ];
+        let levels = [0.5, 0.8, 0.9, 0.95, 0.99, 0.999];
+        for n in sizes {
+            let data = (0..n).collect::<Vec<_>>();
+            for quantile in quantiles {
+                for level in levels {
+                    for confidence in [
+                        Confidence::new_two_sided(level),
+                        Confidence::new_upper(level),
+                        Confidence::new_lower(level),
+                    ] {
+                        let context = format!("n={n} q={quantile} {confidence:?}");
+                        match ci_indices(confidence, n, quantile) {
+                            Ok(indices) => {
+                                match indices {
+                                    Interval::TwoSided(lo, hi) => {
+                                        assert!(lo <= hi && hi < n, "{context}")
+                                    }
+                                    Interval::UpperOneSided(lo) => assert!(lo < n, "{context}"),
+                                    Interval::LowerOneSided(hi) => assert!(hi < n, "{context}"),
+                                }
+                                let ranks = ci_index_range(confidence, n, quantile).unwrap();
+                                assert!(ranks.start() <= ranks.end(), "{context}");
+                                assert!(*ranks.end() < n, "{context}");
+                                assert_eq!(
+                                    ci_sorted_unchecked(confidence, &data, quantile).unwrap(),
+                                    indices,
+                                    "{context}"
+                                );
+                            }
+                            Err(err) => assert!(
+                                matches!(
+                                    err,
+                                    error::CIError::TooFewSamples(_)
+                                        | error::CIError::IndexError(..)
+                                        | error::CIError::TooFewSuccesses(..)
+                                        | error::CIError::TooFewFailures(..)
+                                ),
+                                "{context}: {err:?}"
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ci_many() -> CIResult<()> {
+        use rand::seq::SliceRandom;
+        let sorted = (0..500).collect::<Vec<_>>();
+        let mut data = sorted.clone();
+        data.shuffle(&mut thread_rng());
+
+        let quantiles = [0.1, 0.5, 0.9];
+        let confidence = Confidence::new_two_sided(0.95);
+        let intervals = ci_many(confidence, &data, &quantiles)?;
+        let individual = Confidence::new_two_sided(1. - 0.05 / 3.);
+        for (interval, &quantile) in intervals.iter().zip(&quantiles) {
+            assert_eq!(*interval, ci(individual, &data, quantile)?);
+            assert!(interval.includes(&ci(confidence, &data, quantile)?));
+        }
+
+        let confidence = Confidence::new_upper(0.9);
+        let intervals = ci_many(confidence, &data, &quantiles)?;
+        assert!(intervals.iter().all(|interval| interval.is_upper()));
+        assert_eq!(
+            intervals[2],
+            ci_sorted_unchecked(Confidence::new_upper(1. - 0.1 / 3.), &sorted, 0.9)?
+        );
+
+        assert!(ci_many(confidence, &data, &[]).unwrap().is_empty());
+        assert!(matches!(
+            ci_many(confidence, &[1., f64::NAN, 3., 4., 5.], &[0.5]),
+            Err(error::CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_many(confidence, &data, &[0.5, 1.5]),
+            Err(error::CIError::InvalidQuantile(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // equal weights: same as the Wilson score method
+        let data = (1..=100).map(|x| (x, 1.)).collect::<Vec<_>>();
+        assert_eq!(weighted_quantile(&data, 0.5)?, 50);
+        let interval = ci_weighted(confidence, &data, 0.5)?;
+        assert_eq!(interval, Interval::new(41, 60)?);
+        let (lo, hi): (usize, usize) = Stats::new(100).ci_wilson(confidence, 0.5)?.into();
+        assert_eq!(interval, Interval::new(lo + 1, hi + 1)?);
+
+        // scaling the weights has no effect
+        let scaled = (1..=100).map(|x| (x, 0.25)).collect::<Vec<_>>();
+        assert_eq!(ci_weighted(confidence, &scaled, 0.5)?, interval);
+
+        // unequal weights reduce the effective sample size, hence widen the interval
+        let unequal = (1..=100_usize)
+            .map(|x| (x, if x % 2 == 0 { 1. } else { 3. }))
+            .collect::<Vec<_>>();
+        let wider = ci_weighted(confidence, &unequal, 0.5)?;
+        assert!(wider.width().unwrap() > interval.width().unwrap());
+
+        // zero weights are ignored
+        let padded = (1..=100)
+            .map(|x| (x, 1.))
+            .chain((101..=200).map(|x| (x, 0.)))
+            .collect::<Vec<_>>();
+        assert_eq!(weighted_quantile(&padded, 0.9)?, 90);
+        assert_eq!(ci_weighted(confidence, &padded, 0.5)?, interval);
+
+        // one-sided
+        let upper = ci_weighted(Confidence::new_upper(0.975), &data, 0.5)?;
+        assert_eq!(upper, Interval::new_upper(41));
+        let lower = ci_weighted(Confidence::new_lower(0.975), &data, 0.5)?;
+        assert_eq!(lower, Interval::new_lower(60));
+
+        // errors
+        assert!(matches!(
+            ci_weighted(confidence, &[(1, 1.), (2, -1.)], 0.5),
+            Err(error::CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_weighted(confidence, &[(f64::NAN, 1.), (2., 1.), (3., 1.)], 0.5),
+            Err(error::CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_weighted(confidence, &[(1, 1.), (2, 1.), (3, 100.)], 0.5),
+            Err(error::CIError::TooFewSamples(_))
+        ));
+        assert!(matches!(
+            weighted_quantile(&[(1, 0.), (2, 0.)], 0.5),
+            Err(error::CIError::TooFewSamples(_))
+        ));
+        assert!(matches!(
+            weighted_quantile(&data, 1.),
+            Err(error::CIError::InvalidQuantile(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ecdf_band() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(dkw_half_width(confidence, 100)?, 0.1358102, epsilon = 1e-6);
+        assert_abs_diff_eq!(ks_half_width(confidence, 100)?, 0.1340538, epsilon = 1e-6);
+        assert_abs_diff_eq!(ks_half_width(confidence, 10)?, 0.4094281, epsilon = 1e-6);
+        let confidence = Confidence::new_two_sided(0.8);
+        assert_abs_diff_eq!(dkw_half_width(confidence, 10)?, 0.3393070, epsilon = 1e-6);
+        assert_abs_diff_eq!(ks_half_width(confidence, 10)?, 0.3234033, epsilon = 1e-6);
+        let confidence = Confidence::new_upper(0.95);
+        assert_abs_diff_eq!(dkw_half_width(confidence, 100)?, 0.1223873, epsilon = 1e-6);
+
+        // ties are merged
+        let data = [3, 1, 2, 2, 4, 2, 1, 5];
+        let confidence = Confidence::new_two_sided(0.9);
+        let half_width = dkw_half_width(confidence, data.len())?;
+        let band = ecdf_band(confidence, &data)?;
+        let values = band.iter().map(|(x, _)| *x).collect::<Vec<_>>();
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+        let (_, interval) = band[1];
+        assert_abs_diff_eq!(interval.low_f(), 5. / 8. - half_width, epsilon = 1e-10);
+        assert_abs_diff_eq!(interval.high_f(), 1., epsilon = 1e-10);
+
+        // one-sided
+        let band = ecdf_band(Confidence::new_upper(0.9), &data)?;
+        assert!(band.iter().all(|(_, interval)| interval.high_f() == 1.));
+        let band = ecdf_band_ks(Confidence::new_lower(0.9), &data)?;
+        assert!(band.iter().all(|(_, interval)| interval.low_f() == 0.));
+
+        let empty: [f64; 0] = [];
+        assert!(matches!(
+            ecdf_band(confidence, &empty),
+            Err(error::CIError::TooFewSamples(0))
+        ));
+        let nan = [1., f64::NAN, 3.];
+        assert!(matches!(
+            ecdf_band(confidence, &nan),
+            Err(error::CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ecdf_band_ks(confidence, &nan),
+            Err(error::CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nan_policy() -> CIResult<()> {
+        let data = [5., f64::NAN, 1., 4., 2., 3., f64::NAN, 6., 9., 7., 8., 10.];
+        let clean = [5., 1., 4., 2., 3., 6., 9., 7., 8., 10.];
+        let confidence = Confidence::new_two_sided(0.9);
+        assert!(matches!(
+            ci_with_nan_policy(confidence, &data, 0.5, NanPolicy::Error),
+            Err(error::CIError::InvalidInputData)
+        ));
+        assert_eq!(
+            ci_with_nan_policy(confidence, &data, 0.5, NanPolicy::Ignore)?,
+            ci(confidence, &clean, 0.5)?
+        );
+        let nan = ci_with_nan_policy(confidence, &data, 0.5, NanPolicy::Propagate)?;
+        assert!(nan.low_f().is_nan() && nan.high_f().is_nan());
+        assert_eq!(
+            ci_with_nan_policy(confidence, &clean, 0.5, NanPolicy::Propagate)?,
+            ci(confidence, &clean, 0.5)?
+        );
+        Ok(())
+    }
 }
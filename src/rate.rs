@@ -0,0 +1,997 @@
+//!
+//! Confidence intervals for rates of events
+//!
+//! A rate is a number of events observed over some exposure (e.g., failures per hour of
+//! operation, incidents per day, or cases per person-year).
+//! The number of events is assumed to follow a Poisson distribution whose mean is
+//! proportional to the exposure.
+//!
+//! Real-life counts are often _overdispersed_, i.e., their variance is larger than their mean,
+//! in which case pure Poisson intervals are too narrow. The function [`ci_overdispersed`]
+//! estimates a dispersion parameter from repeated observations (quasi-Poisson model) and
+//! widens the interval accordingly.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let events = 10;
+//! let exposure = 2.; // e.g., 2 years of operation
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let interval = rate::ci(confidence, events, exposure)?;
+//! use approx::*;
+//! assert_abs_diff_eq!(interval, Interval::new(2.3977, 9.1952)?, epsilon = 1e-4);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! The confidence interval can also be computed incrementally, e.g., by merging the observations of several sources:
+//! ```
+//! # use stats_ci::*;
+//! let mut stats = rate::Exposure::default();
+//! stats.add_period(6, 1.5)?;
+//! stats += rate::Exposure::new(4, 0.5);
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let interval = stats.ci(confidence)?;
+//! # use approx::*;
+//! assert_abs_diff_eq!(interval, Interval::new(2.3977, 9.1952)?, epsilon = 1e-4);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! Taking overdispersion into account, with counts observed over several periods:
+//! ```
+//! # use stats_ci::*;
+//! // number of incidents per day (exposure of 1 day each)
+//! let data = [
+//!     (2, 1.), (8, 1.), (3, 1.), (12, 1.), (0, 1.),
+//!     (7, 1.), (5, 1.), (15, 1.), (1, 1.), (9, 1.),
+//! ];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let interval = rate::ci_overdispersed(confidence, &data)?;
+//! # use approx::*;
+//! assert_abs_diff_eq!(interval, Interval::new(3.5156, 10.9341)?, epsilon = 1e-4);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! The mean lifetime of units with a constant failure rate (MTTF or MTBF), i.e., the inverse of the rate of failures,
+//! has an interval computed by [`ci_mean_lifetime`] for tests stopped at a fixed time or after a fixed number of failures:
+//! ```
+//! # use stats_ci::*;
+//! // 4 failures over 5000 hours of cumulated operation, with a test stopped at the 4th failure
+//! let confidence = Confidence::new_two_sided(0.9);
+//! let interval = rate::ci_mean_lifetime(confidence, 4, 5000., rate::TestPlan::FailureTruncated)?;
+//! # use approx::*;
+//! assert_abs_diff_eq!(interval, Interval::new(644.857, 3659.469)?, epsilon = 1e-3);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * [Wikipedia - Poisson distribution (confidence interval)](https://en.wikipedia.org/wiki/Poisson_distribution#Confidence_interval)
+//! * F. Garwood. "Fiducial limits for the Poisson distribution." Biometrika 28(3/4): 437-442 (1936).
+//! * P. McCullagh, J.A. Nelder. Generalized Linear Models (2nd edition). Chapman & Hall, 1989.
+//!
+use crate::*;
+use error::*;
+
+///
+/// Computes the confidence interval over the rate of events given a number of events observed over some exposure.
+///
+/// The interval is the exact (Garwood) interval of the Poisson distribution, computed from the
+/// quantiles of the chi-square distribution, and divided by the exposure:
+/// \\[
+/// \left[ \frac{\chi^2_{\alpha/2}(2k)}{2T}, \frac{\chi^2_{1-\alpha/2}(2k+2)}{2T} \right]
+/// \\]
+/// where \\( k \\) is the number of events and \\( T \\) is the exposure.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `events` - the number of events observed
+/// * `exposure` - the exposure over which the events were observed (e.g., time, person-time)
+///
+/// # Errors
+///
+/// * `InvalidExposure` - if the exposure is not strictly positive and finite
+///
+/// # Notes
+///
+/// The exact interval is conservative: its coverage is at least the nominal confidence level.
+/// It is well-defined even when no event has been observed, in which case the lower bound is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = rate::ci(confidence, 10, 1.)?;
+/// assert_abs_diff_eq!(interval, Interval::new(4.7954, 18.3904)?, epsilon = 1e-4);
+///
+/// let interval = rate::ci(confidence, 0, 1.)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0., 3.6889)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci(confidence: Confidence, events: u64, exposure: f64) -> CIResult<Interval<f64>> {
+    if !(exposure > 0. && exposure.is_finite()) {
+        return Err(CIError::InvalidExposure(exposure));
+    }
+    let (lo, hi) = poisson_bounds(confidence, events)?;
+    let (lo, hi) = (lo / exposure, hi / exposure);
+
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Exact Poisson test of the rate of events against a reference rate \\( \lambda_0 \\), dual to the exact
+/// interval of [`ci`] (see [`hypothesis`]).
+///
+/// Under the null hypothesis, the number of events \\( k \\) follows a Poisson distribution with mean
+/// \\( \mu_0 = \lambda_0 T \\) where \\( T \\) is the exposure.
+/// The one-sided p-values are \\( P(X \geq k) \\) (rate greater than the reference) and \\( P(X \leq k) \\)
+/// (rate smaller than the reference), and the two-sided p-value is twice the smaller of the two (at most 1).
+/// The statistic of the result is the number of events.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the direction and the significance level of the test
+/// * `events` - the number of events observed
+/// * `exposure` - the exposure over which the events were observed (e.g., time, person-time)
+/// * `rate` - the rate under the null hypothesis (number of events per unit of exposure)
+///
+/// # Errors
+///
+/// * `InvalidExposure` - if the exposure is not strictly positive and finite
+/// * `InvalidReference` - if the reference rate is not strictly positive and finite
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // 10 failures in 2 years, against an expected rate of 2 failures per year
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let test = rate::poisson_test(confidence, 10, 2., 2.)?;
+/// assert_abs_diff_eq!(test.p_value(), 0.0163, epsilon = 1e-4);
+/// assert!(test.is_significant());
+/// assert!(!rate::ci(confidence, 10, 2.)?.contains(&2.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn poisson_test(
+    confidence: Confidence,
+    events: u64,
+    exposure: f64,
+    rate: f64,
+) -> CIResult<hypothesis::TestResult> {
+    if !(exposure > 0. && exposure.is_finite()) {
+        return Err(CIError::InvalidExposure(exposure));
+    }
+    if !(rate > 0. && rate.is_finite()) {
+        return Err(CIError::InvalidReference(rate));
+    }
+    let mu = rate * exposure;
+    let k = events as f64;
+    // P(X >= k) = P(k, mu) and P(X <= k) = Q(k + 1, mu) with the regularized incomplete gamma functions
+    let upper_tail = if events == 0 {
+        1.
+    } else {
+        special::gamma_p(k, mu)
+    };
+    let lower_tail = special::gamma_q(k + 1., mu);
+    hypothesis::TestResult::from_tails(confidence, k, upper_tail, lower_tail, None)
+}
+
+///
+/// Computes the confidence interval over the ratio of observed to expected counts,
+/// such as a standardized mortality ratio (SMR) or a standardized incidence ratio (SIR).
+///
+/// The observed count is assumed to follow a Poisson distribution, while the expected count
+/// (e.g., derived from reference rates or from a forecast baseline) is treated as known without error.
+/// The interval is made of the exact Poisson limits of the observed count divided by the expected count:
+/// \\[
+/// \left[ \frac{\chi^2_{\alpha/2}(2O)}{2E}, \frac{\chi^2_{1-\alpha/2}(2O+2)}{2E} \right]
+/// \\]
+/// where \\( O \\) is the observed count and \\( E \\) is the expected count.
+/// A ratio larger than 1 indicates more events than expected.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `observed` - the number of events observed
+/// * `expected` - the number of events expected under the reference (must be strictly positive)
+///
+/// # Errors
+///
+/// * `InvalidReference` - if the expected count is not strictly positive and finite
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // 10 incidents observed when the baseline forecast 5
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = rate::ci_standardized_ratio(confidence, 10, 5.)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.9591, 3.6781)?, epsilon = 1e-4);
+/// // the interval includes 1: the excess is not significant at the 95% level
+/// assert!(interval.contains(&1.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * [Wikipedia - Standardized mortality ratio](https://en.wikipedia.org/wiki/Standardized_mortality_ratio)
+/// * N.E. Breslow, N.E. Day. Statistical Methods in Cancer Research, Volume II: The Design and Analysis of Cohort Studies. IARC, 1987.
+///
+pub fn ci_standardized_ratio(
+    confidence: Confidence,
+    observed: u64,
+    expected: f64,
+) -> CIResult<Interval<f64>> {
+    if !(expected > 0. && expected.is_finite()) {
+        return Err(CIError::InvalidReference(expected));
+    }
+    ci(confidence, observed, expected)
+}
+
+///
+/// Design of a life test, which determines the distribution of the total time on test (see [`ci_mean_lifetime`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TestPlan {
+    ///
+    /// The test stops at a fixed time (type I censoring), e.g., after a given number of hours of operation,
+    /// so the number of failures is random.
+    ///
+    #[default]
+    TimeTruncated,
+
+    ///
+    /// The test stops at a fixed number of failures (type II censoring), so the total time on test is random.
+    ///
+    FailureTruncated,
+}
+
+///
+/// Computes the confidence interval over the mean lifetime \\( \theta \\) of an exponential lifetime model
+/// (i.e., the mean time to failure, MTTF, or between failures, MTBF, of units with a constant failure rate),
+/// given the number of failures observed over a total time on test.
+///
+/// The total time on test \\( T \\) is the sum of the operating times of all the units until their failure or the end
+/// of the test (including units that were replaced or repaired). The point estimate is \\( \hat\theta = T / r \\)
+/// where \\( r \\) is the number of failures, and the interval is based on the chi-square distribution of
+/// \\( 2 T / \theta \\):
+/// \\[
+/// \left[ \frac{2T}{\chi^2_{1-\alpha/2}(2r + 2)}, \frac{2T}{\chi^2_{\alpha/2}(2r)} \right]
+/// \quad \text{or} \quad
+/// \left[ \frac{2T}{\chi^2_{1-\alpha/2}(2r)}, \frac{2T}{\chi^2_{\alpha/2}(2r)} \right]
+/// \\]
+/// for a time-truncated and a failure-truncated test respectively (see [`TestPlan`]).
+/// The interval of a time-truncated test is the inverse of the exact interval on the failure rate (see [`ci`]).
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `failures` - the number of failures observed
+/// * `total_time` - the total time on test
+/// * `plan` - the design of the test
+///
+/// # Errors
+///
+/// * `InvalidExposure` - if the total time on test is not strictly positive and finite
+/// * `TooFewEvents` - if no failure has been observed in a failure-truncated test
+///
+/// # Notes
+///
+/// When no failure has been observed in a time-truncated test, a two-sided interval only has a lower bound (i.e., it is
+/// returned as an upper one-sided interval), which is the usual demonstration of a minimum MTBF,
+/// and a lower one-sided interval has an infinite upper bound.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // 4 failures over 5000 hours of cumulated operation, with a test stopped after a fixed duration
+/// let confidence = Confidence::new_two_sided(0.9);
+/// let interval = rate::ci_mean_lifetime(confidence, 4, 5000., rate::TestPlan::TimeTruncated)?;
+/// assert_abs_diff_eq!(interval, Interval::new(546.238, 3659.469)?, epsilon = 1e-3);
+///
+/// // no failure: lower bound on the MTBF
+/// let confidence = Confidence::new_upper(0.9);
+/// let interval = rate::ci_mean_lifetime(confidence, 0, 5000., rate::TestPlan::TimeTruncated)?;
+/// assert_abs_diff_eq!(interval, Interval::new_upper(2171.472), epsilon = 1e-3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.Q. Meeker, L.A. Escobar. Statistical Methods for Reliability Data. Wiley, 1998. Chapter 7.
+///
+pub fn ci_mean_lifetime(
+    confidence: Confidence,
+    failures: u64,
+    total_time: f64,
+    plan: TestPlan,
+) -> CIResult<Interval<f64>> {
+    if !(total_time > 0. && total_time.is_finite()) {
+        return Err(CIError::InvalidExposure(total_time));
+    }
+    if failures == 0 && plan == TestPlan::FailureTruncated {
+        return Err(CIError::TooFewEvents(failures));
+    }
+    let quantile = distributions::checked_quantile(confidence)?;
+    let dof = 2. * failures as f64;
+    let lower_dof = match plan {
+        TestPlan::TimeTruncated => dof + 2.,
+        TestPlan::FailureTruncated => dof,
+    };
+    let lo = 2. * total_time / distributions::chi2_inverse_cdf(quantile, lower_dof)?;
+    let hi = if failures == 0 {
+        f64::INFINITY
+    } else {
+        2. * total_time / distributions::chi2_inverse_cdf(1. - quantile, dof)?
+    };
+
+    match confidence {
+        Confidence::TwoSided(_) if failures == 0 => Ok(Interval::new_upper(lo)),
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Estimates the dispersion parameter of counts observed over several periods.
+///
+/// The dispersion is estimated as Pearson's statistic divided by its degrees of freedom:
+/// \\[
+/// \hat\phi = \frac{1}{m-1} \sum_{i=1}^m \frac{(k_i - \hat\lambda t_i)^2}{\hat\lambda t_i}
+/// \\]
+/// where \\( k_i \\) and \\( t_i \\) are the number of events and the exposure of period \\( i \\),
+/// \\( m \\) is the number of periods, and \\( \hat\lambda = \sum k_i / \sum t_i \\) is the estimated rate.
+/// A value close to 1 is consistent with a Poisson process, while a value larger than 1 indicates overdispersion.
+///
+/// Complexity: \\( O(m) \\) where \\( m \\) is the number of periods in `data`.
+///
+/// # Arguments
+///
+/// * `data` - the observations given as pairs of (events, exposure)
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are less than two periods
+/// * `TooFewEvents` - if no event has been observed at all
+/// * `InvalidExposure` - if some exposure is not strictly positive and finite
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [(4, 1.), (5, 1.), (6, 1.), (5, 1.)];
+/// let dispersion = rate::dispersion(&data)?;
+/// assert_abs_diff_eq!(dispersion, 0.1333, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn dispersion<I>(data: &I) -> CIResult<f64>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (u64, f64)>,
+{
+    let mut periods = 0_usize;
+    let mut events = 0_u64;
+    let mut exposure = utils::KahanSum::default();
+    for &(k, t) in data {
+        if !(t > 0. && t.is_finite()) {
+            return Err(CIError::InvalidExposure(t));
+        }
+        periods += 1;
+        events += k;
+        exposure += t;
+    }
+    if periods < 2 {
+        return Err(CIError::TooFewSamples(periods));
+    }
+    if events == 0 {
+        return Err(CIError::TooFewEvents(events));
+    }
+
+    let rate = events as f64 / exposure.value();
+    let mut pearson = utils::KahanSum::default();
+    for &(k, t) in data {
+        let expected = rate * t;
+        let diff = k as f64 - expected;
+        pearson += diff * diff / expected;
+    }
+    Ok(pearson.value() / (periods - 1) as f64)
+}
+
+///
+/// Computes the confidence interval over the rate of events, taking overdispersion into account (quasi-Poisson model).
+///
+/// The dispersion \\( \hat\phi \\) is estimated with [`dispersion`] and is floored at 1, so that the interval is never
+/// narrower than what the Poisson model alone would justify. The interval is computed on the logarithm of the rate:
+/// \\[
+/// \hat\lambda \exp\left(\pm t_{m-1} \sqrt{\frac{\hat\phi}{K}}\right)
+/// \\]
+/// where \\( K \\) is the total number of events, \\( m \\) is the number of periods,
+/// and \\( t_{m-1} \\) is the critical value of Student's t-distribution with \\( m-1 \\) degrees of freedom.
+///
+/// Complexity: \\( O(m) \\) where \\( m \\) is the number of periods in `data`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the observations given as pairs of (events, exposure), one pair per period
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if there are less than two periods
+/// * `TooFewEvents` - if no event has been observed at all
+/// * `InvalidExposure` - if some exposure is not strictly positive and finite
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [
+///     (2, 1.), (8, 1.), (3, 1.), (12, 1.), (0, 1.),
+///     (7, 1.), (5, 1.), (15, 1.), (1, 1.), (9, 1.),
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = rate::ci_overdispersed(confidence, &data)?;
+/// assert_abs_diff_eq!(interval, Interval::new(3.5156, 10.9341)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * P. McCullagh, J.A. Nelder. Generalized Linear Models (2nd edition). Chapman & Hall, 1989.
+/// * [Wikipedia - Quasi-likelihood](https://en.wikipedia.org/wiki/Quasi-likelihood)
+///
+pub fn ci_overdispersed<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (u64, f64)>,
+{
+    let phi = dispersion(data)?.max(1.);
+
+    let mut periods = 0_usize;
+    let mut events = 0_u64;
+    let mut exposure = utils::KahanSum::default();
+    for &(k, t) in data {
+        periods += 1;
+        events += k;
+        exposure += t;
+    }
+    let rate = events as f64 / exposure.value();
+    let std_err_log = (phi / events as f64).sqrt();
+    let degrees_of_freedom = (periods - 1) as f64;
+    let (lo, hi) = stats::interval_bounds(confidence, rate.ln(), std_err_log, degrees_of_freedom)?;
+    let (lo, hi) = (lo.exp(), hi.exp());
+
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Represents the state of the computation of a confidence interval for a rate of events.
+///
+/// The state holds the number of events observed and the total exposure (e.g., person-time) over which they were observed.
+/// Two states can be combined with `+` or `+=`, so that the observations gathered separately (e.g., by several shards
+/// or over several periods) compose into a single rate interval.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // events and person-years collected by two different sites
+/// let mut site_a = rate::Exposure::default();
+/// site_a.add_period(3, 1.5)?;
+/// site_a.add_period(4, 2.)?;
+/// let mut site_b = rate::Exposure::default();
+/// site_b.extend(&[(2, 1.), (1, 0.5)])?;
+///
+/// let total = site_a + site_b;
+/// assert_eq!(total, rate::Exposure::new(10, 5.));
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = total.ci(confidence)?;
+/// # use approx::*;
+/// assert_abs_diff_eq!(interval, Interval::new(0.9591, 3.6781)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exposure {
+    events: u64,
+    person_time: utils::KahanSum<f64>,
+}
+
+impl Exposure {
+    ///
+    /// Creates a new state with initial values for the number of events and the exposure.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the exposure is negative or not finite
+    ///
+    pub fn new(events: u64, person_time: f64) -> Self {
+        if !(person_time >= 0. && person_time.is_finite()) {
+            panic!("Exposure must be non-negative and finite.")
+        }
+        Exposure {
+            events,
+            person_time: utils::KahanSum::new(person_time),
+        }
+    }
+
+    ///
+    /// Returns the number of events observed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    ///
+    /// Returns the total exposure over which the events were observed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn person_time(&self) -> f64 {
+        self.person_time.value()
+    }
+
+    ///
+    /// Returns the observed rate of events (number of events per unit of exposure).
+    /// The result is `NaN` if there is no exposure.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn rate(&self) -> f64 {
+        self.events as f64 / self.person_time()
+    }
+
+    ///
+    /// Adds a number of events observed over some exposure.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - the number of events observed during the period
+    /// * `person_time` - the exposure of the period
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidExposure` - if the exposure is negative or not finite; the state is left unchanged
+    ///
+    pub fn add_period(&mut self, events: u64, person_time: f64) -> CIResult<()> {
+        if !(person_time >= 0. && person_time.is_finite()) {
+            return Err(CIError::InvalidExposure(person_time));
+        }
+        self.events += events;
+        self.person_time += person_time;
+        Ok(())
+    }
+
+    ///
+    /// Extend the state with additional observations given as pairs of (events, exposure).
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of pairs in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the observations given as an iterator or slice of pairs (events, exposure)
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidExposure` - if some exposure is negative or not finite; the pairs before it have already been added
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = rate::Exposure::default();
+    /// stats.extend(&[(2, 1.), (3, 1.), (0, 0.5)])?;
+    /// assert_eq!(stats, rate::Exposure::new(5, 2.5));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (u64, f64)>,
+    {
+        for &(events, person_time) in data {
+            self.add_period(events, person_time)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Computes the confidence interval over the rate of events.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level (must be in (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidExposure` - if the total exposure is zero
+    ///
+    /// # Notes
+    ///
+    /// The confidence interval is computed using the function [`ci`] (exact Poisson interval).
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        ci(confidence, self.events, self.person_time())
+    }
+
+    ///
+    /// Exact Poisson test of the rate of events against a reference rate, dual to [`Exposure::ci`]
+    /// (see [`hypothesis`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the direction and the significance level of the test
+    /// * `rate` - the rate under the null hypothesis (number of events per unit of exposure)
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidExposure` - if the total exposure is zero
+    /// * `InvalidReference` - if the reference rate is not strictly positive and finite
+    ///
+    /// # Notes
+    ///
+    /// The test is computed using the function [`poisson_test`].
+    ///
+    pub fn poisson_test(
+        &self,
+        confidence: Confidence,
+        rate: f64,
+    ) -> CIResult<hypothesis::TestResult> {
+        poisson_test(confidence, self.events, self.person_time(), rate)
+    }
+}
+
+impl core::ops::Add for Exposure {
+    type Output = Self;
+
+    ///
+    /// Combines two states by adding the number of events and the exposures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats1 = rate::Exposure::new(10, 2.5);
+    /// let stats2 = rate::Exposure::new(5, 1.5);
+    /// assert_eq!(stats1 + stats2, rate::Exposure::new(15, 4.));
+    /// ```
+    fn add(self, rhs: Self) -> Self::Output {
+        Exposure {
+            events: self.events + rhs.events,
+            person_time: self.person_time + rhs.person_time,
+        }
+    }
+}
+
+impl core::ops::AddAssign for Exposure {
+    ///
+    /// Combines two states by adding the number of events and the exposures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats1 = rate::Exposure::new(10, 2.5);
+    /// let stats2 = rate::Exposure::new(5, 1.5);
+    /// stats1 += stats2;
+    /// assert_eq!(stats1, rate::Exposure::new(15, 4.));
+    /// ```
+    fn add_assign(&mut self, rhs: Self) {
+        self.events += rhs.events;
+        self.person_time += rhs.person_time;
+    }
+}
+
+///
+/// Exact bounds on the mean of a Poisson distribution given an observed count.
+///
+fn poisson_bounds(confidence: Confidence, events: u64) -> CIResult<(f64, f64)> {
+    let k = events as f64;
+    let quantile = distributions::checked_quantile(confidence)?;
+    let lo = if events == 0 {
+        0.
+    } else {
+        distributions::chi2_inverse_cdf(1. - quantile, 2. * k)? / 2.
+    };
+    let hi = distributions::chi2_inverse_cdf(quantile, 2. * k + 2.)? / 2.;
+    Ok((lo, hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_rate_ci() -> CIResult<()> {
+        // reference values from tables of exact Poisson confidence limits
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = rate::ci(confidence, 10, 1.)?;
+        assert_abs_diff_eq!(ci, Interval::new(4.7954, 18.3904)?, epsilon = 1e-4);
+
+        let ci = rate::ci(confidence, 1, 1.)?;
+        assert_abs_diff_eq!(ci, Interval::new(0.0253, 5.5716)?, epsilon = 1e-4);
+
+        let ci = rate::ci(confidence, 0, 1.)?;
+        assert_abs_diff_eq!(ci, Interval::new(0., 3.6889)?, epsilon = 1e-4);
+
+        let ci = rate::ci(confidence, 10, 10.)?;
+        assert_abs_diff_eq!(ci, Interval::new(0.47954, 1.83904)?, epsilon = 1e-5);
+
+        let upper = rate::ci(Confidence::new_upper(0.975), 10, 1.)?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), ci.low_f() * 10., epsilon = 1e-4);
+
+        let lower = rate::ci(Confidence::new_lower(0.975), 10, 1.)?;
+        assert_eq!(lower.low_f(), 0.);
+        assert_abs_diff_eq!(lower.high_f(), ci.high_f() * 10., epsilon = 1e-4);
+
+        assert!(rate::ci(confidence, 10, 0.).is_err());
+        assert!(rate::ci(confidence, 10, f64::NAN).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_standardized_ratio() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        // exact Poisson limits for 10 events (see above) divided by the expected count
+        let ci = rate::ci_standardized_ratio(confidence, 10, 5.)?;
+        assert_abs_diff_eq!(ci, Interval::new(0.95908, 3.67808)?, epsilon = 1e-5);
+        let ci = rate::ci_standardized_ratio(confidence, 30, 15.)?;
+        assert!(!ci.contains(&1.));
+
+        let ci = rate::ci_standardized_ratio(confidence, 0, 2.)?;
+        assert_eq!(ci.low_f(), 0.);
+        assert_abs_diff_eq!(ci.high_f(), 3.68888 / 2., epsilon = 1e-5);
+
+        assert!(matches!(
+            rate::ci_standardized_ratio(confidence, 3, 0.),
+            Err(CIError::InvalidReference(_))
+        ));
+        assert!(matches!(
+            rate::ci_standardized_ratio(confidence, 3, f64::INFINITY),
+            Err(CIError::InvalidReference(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mean_lifetime() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.9);
+        let ci = rate::ci_mean_lifetime(confidence, 4, 5000., TestPlan::FailureTruncated)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(644.8570402863971, 3659.4691339104365)?,
+            epsilon = 1e-8
+        );
+        let ci = rate::ci_mean_lifetime(confidence, 4, 5000., TestPlan::TimeTruncated)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(546.2380080764071, 3659.4691339104365)?,
+            epsilon = 1e-8
+        );
+        // consistent with the exact interval on the failure rate
+        let rate = rate::ci(confidence, 4, 5000.)?;
+        assert_abs_diff_eq!(ci.low_f(), 1. / rate.high_f(), epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 1. / rate.low_f(), epsilon = 1e-8);
+
+        let upper = Confidence::new_upper(0.9);
+        let ci = rate::ci_mean_lifetime(upper, 4, 5000., TestPlan::TimeTruncated)?;
+        assert_abs_diff_eq!(ci, Interval::new_upper(625.5012152142632), epsilon = 1e-8);
+        let ci = rate::ci_mean_lifetime(upper, 4, 5000., TestPlan::FailureTruncated)?;
+        assert_abs_diff_eq!(ci, Interval::new_upper(748.4152604442129), epsilon = 1e-8);
+        let ci = rate::ci_mean_lifetime(
+            Confidence::new_lower(0.95),
+            4,
+            5000.,
+            TestPlan::TimeTruncated,
+        )?;
+        assert_eq!(ci.low_f(), 0.);
+        assert_abs_diff_eq!(ci.high_f(), 3659.4691339104365, epsilon = 1e-8);
+
+        // no failure
+        let ci = rate::ci_mean_lifetime(upper, 0, 5000., TestPlan::TimeTruncated)?;
+        assert_abs_diff_eq!(ci, Interval::new_upper(2171.472409516259), epsilon = 1e-8);
+        let ci = rate::ci_mean_lifetime(confidence, 0, 5000., TestPlan::TimeTruncated)?;
+        assert_abs_diff_eq!(ci, Interval::new_upper(1669.0410034766703), epsilon = 1e-8);
+        let ci = rate::ci_mean_lifetime(
+            Confidence::new_lower(0.9),
+            0,
+            5000.,
+            TestPlan::TimeTruncated,
+        )?;
+        assert_eq!(ci.high_f(), f64::INFINITY);
+
+        assert!(rate::ci_mean_lifetime(confidence, 0, 5000., TestPlan::FailureTruncated).is_err());
+        assert!(rate::ci_mean_lifetime(confidence, 4, 0., TestPlan::TimeTruncated).is_err());
+        assert!(
+            rate::ci_mean_lifetime(confidence, 4, f64::INFINITY, TestPlan::TimeTruncated).is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overdispersed() -> CIResult<()> {
+        let data = [
+            (2, 1.),
+            (8, 1.),
+            (3, 1.),
+            (12, 1.),
+            (0, 1.),
+            (7, 1.),
+            (5, 1.),
+            (15, 1.),
+            (1, 1.),
+            (9, 1.),
+        ];
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            rate::dispersion(&data)?,
+            3.8996415770609314,
+            epsilon = 1e-10
+        );
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = rate::ci_overdispersed(confidence, &data)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(3.5156157433373827, 10.934073233927668)?,
+            epsilon = 1e-6
+        );
+
+        // overdispersed interval is wider than the Poisson interval
+        let poisson = rate::ci(confidence, 62, 10.)?;
+        assert!(ci.includes(&poisson));
+
+        // underdispersed data does not narrow the interval below the Poisson model
+        let data = [(50, 1.), (51, 1.), (49, 1.), (50, 1.)];
+        let ci = rate::ci_overdispersed(confidence, &data)?;
+        let phi_one = (1. / 200_f64).sqrt();
+        let t = distributions::t_value(confidence, 3.)?;
+        assert_abs_diff_eq!(ci.high_f(), 50. * (t * phi_one).exp(), epsilon = 1e-8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exposure() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut stats = rate::Exposure::default();
+        assert!(matches!(
+            stats.ci(confidence),
+            Err(CIError::InvalidExposure(_))
+        ));
+        assert!(stats.rate().is_nan());
+
+        stats.add_period(4, 0.5)?;
+        stats.extend(&[(3, 0.25), (3, 0.25)])?;
+        assert_eq!(stats.events(), 10);
+        assert_eq!(stats.person_time(), 1.);
+        assert_eq!(stats.rate(), 10.);
+        assert_eq!(stats.ci(confidence)?, rate::ci(confidence, 10, 1.)?);
+
+        // invalid exposure leaves the state unchanged
+        assert!(stats.add_period(1, -1.).is_err());
+        assert!(stats.add_period(1, f64::INFINITY).is_err());
+        assert_eq!(stats, rate::Exposure::new(10, 1.));
+
+        // merging shards gives the same result as a single stream
+        let data = (0..1000).map(|i| (i % 7, 0.1)).collect::<Vec<_>>();
+        let mut whole = rate::Exposure::default();
+        whole.extend(&data)?;
+        let merged = data
+            .chunks(37)
+            .map(|chunk| {
+                let mut shard = rate::Exposure::default();
+                shard.extend(&chunk.to_vec()).unwrap();
+                shard
+            })
+            .fold(rate::Exposure::default(), |acc, shard| acc + shard);
+        assert_eq!(merged.events(), whole.events());
+        assert_abs_diff_eq!(merged.person_time(), 100., epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            merged.ci(confidence)?,
+            whole.ci(confidence)?,
+            epsilon = 1e-10
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overdispersed_errors() {
+        let confidence = Confidence::new_two_sided(0.95);
+        assert!(matches!(
+            rate::ci_overdispersed(confidence, &[(3, 1.)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            rate::ci_overdispersed(confidence, &[(0, 1.), (0, 2.)]),
+            Err(CIError::TooFewEvents(0))
+        ));
+        assert!(matches!(
+            rate::ci_overdispersed(confidence, &[(1, 1.), (2, -2.)]),
+            Err(CIError::InvalidExposure(_))
+        ));
+    }
+
+    #[test]
+    fn test_poisson_test() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        let test = rate::poisson_test(confidence, 10, 2., 2.)?;
+        assert_eq!(test.statistic(), 10.);
+        assert_abs_diff_eq!(test.p_value(), 0.016264485593867726, epsilon = 1e-9);
+        let test = rate::poisson_test(Confidence::new_upper(0.95), 10, 2., 2.)?;
+        assert_abs_diff_eq!(test.p_value(), 0.008132242796933863, epsilon = 1e-9);
+        let test = rate::poisson_test(Confidence::new_lower(0.95), 0, 1., 1.)?;
+        assert_abs_diff_eq!(test.p_value(), (-1_f64).exp(), epsilon = 1e-9);
+
+        // duality with the exact interval
+        let stats = rate::Exposure::new(12, 3.);
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let interval = stats.ci(confidence)?;
+            for rate in [1., 1.5, 2., 2.5, 3., 4., 5., 6., 7., 8., 9.] {
+                let test = stats.poisson_test(confidence, rate)?;
+                assert_eq!(test.is_significant(), !interval.contains(&rate));
+            }
+        }
+
+        assert!(matches!(
+            rate::Exposure::default().poisson_test(confidence, 1.),
+            Err(CIError::InvalidExposure(_))
+        ));
+        assert!(matches!(
+            rate::poisson_test(confidence, 3, 1., 0.),
+            Err(CIError::InvalidReference(_))
+        ));
+        Ok(())
+    }
+}
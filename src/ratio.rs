@@ -0,0 +1,798 @@
+//!
+//! Confidence intervals for the ratio of two means, using Fieller's theorem.
+//!
+//! Many performance metrics are ratios of two uncertain means, such as the throughput per watt or the cost per request.
+//! Dividing the bounds of the intervals of the two means is wrong (the result has no stated coverage), and the
+//! delta method fails when the mean of the denominator is close to zero relative to its uncertainty.
+//! Fieller's theorem gives an exact interval (under normality) for the ratio \\( \rho = \mu_a / \mu_b \\) by inverting the t-test of
+//! \\( \mu_a - \rho \mu_b = 0 \\):
+//! \\[
+//! \left\\{ \rho : \frac{(\bar{a} - \rho \bar{b})^2}{v_a - 2 \rho c + \rho^2 v_b} \leq t^2 \right\\}
+//! \\]
+//! where \\( v_a \\) and \\( v_b \\) are the squared standard errors of the means and \\( c \\) their covariance.
+//!
+//! This set is not necessarily an interval: when the mean of the denominator is not significantly different from zero, it is either
+//! the union of two rays or the whole real line, which is represented by [`Fieller`].
+//!
+//! * [`Paired`] - ratio of the means of paired (hence possibly correlated) observations, e.g., throughput and power measured in the same run
+//! * [`ci_unpaired`] - ratio of the means of two independent samples
+//! * [`ci_fieller`] - ratio of two means given their standard errors and covariance
+//!
+//! # Speedup across a benchmark suite
+//!
+//! Summarizing the performance of a system across a suite of benchmarks (e.g., SPEC-style) is a different question:
+//! the ratios of the benchmarks are not estimates of a common ratio, and the usual summary is their geometric mean.
+//! [`ci_geometric_speedup`] and [`ci_geometric_speedup_paired`] give an interval on the geometric mean speedup,
+//! treating the benchmarks as a sample of the workloads of interest.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // throughput (requests/s) and power (W) measured in the same runs
+//! let throughput = [1210., 1185., 1302., 1254., 1198., 1276., 1231., 1289.];
+//! let power = [251., 247., 268., 259., 250., 262., 255., 266.];
+//! let stats = ratio::Paired::from_iter(&throughput, &power)?;
+//! let ci = stats.ci_ratio(Confidence::new_two_sided(0.95))?;
+//! assert_abs_diff_eq!(stats.sample_ratio(), 4.832362, epsilon = 1e-6);
+//! let interval = ci.interval().unwrap();
+//! assert_abs_diff_eq!(interval.low_f(), 4.808825, epsilon = 1e-6);
+//! assert_abs_diff_eq!(interval.high_f(), 4.854872, epsilon = 1e-6);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * E.C. Fieller. "Some problems in interval estimation." Journal of the Royal Statistical Society, Series B 16(2): 175-185 (1954).
+//! * [Wikipedia - Fieller's theorem](https://en.wikipedia.org/wiki/Fieller%27s_theorem)
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Confidence set of a ratio given by Fieller's theorem.
+///
+/// When the mean of the denominator is significantly different from zero at the chosen confidence level, the set is a
+/// (finite) interval. Otherwise, the data is compatible with a denominator arbitrarily close to zero and the set is unbounded.
+///
+/// # Notes
+///
+/// With a one-sided confidence, the bound is that of the two-sided set for which each tail has the probability
+/// \\( 1 - \\) `confidence`. When that set is not an interval, no one-sided bound is available and the result is [`Fieller::Unbounded`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fieller {
+    ///
+    /// The confidence set is an interval (one-sided for a one-sided confidence).
+    ///
+    Interval(Interval<f64>),
+
+    ///
+    /// The confidence set is the union of two rays \\( (-\infty, l] \cup [h, +\infty) \\), i.e., it excludes only the values between
+    /// `l` and `h` (the first and second fields).
+    ///
+    Exclusive(f64, f64),
+
+    ///
+    /// The confidence set is the whole real line: the data gives no information on the ratio.
+    ///
+    Unbounded,
+}
+
+impl Fieller {
+    ///
+    /// Return the interval if the confidence set is an interval, or `None` if it is unbounded.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn interval(&self) -> Option<&Interval<f64>> {
+        match self {
+            Fieller::Interval(interval) => Some(interval),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Return `true` if the confidence set is an interval.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn is_interval(&self) -> bool {
+        matches!(self, Fieller::Interval(_))
+    }
+
+    ///
+    /// Test whether the confidence set contains a value of the ratio.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn contains(&self, ratio: &f64) -> bool {
+        match self {
+            Fieller::Interval(interval) => interval.contains(ratio),
+            Fieller::Exclusive(low, high) => ratio <= low || ratio >= high,
+            Fieller::Unbounded => true,
+        }
+    }
+}
+
+///
+/// Compute the confidence set of the ratio \\( \mu_a / \mu_b \\) of two means with Fieller's theorem, given the estimates
+/// of the means, their squared standard errors, and their covariance.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `mean_a` - the estimate of the mean of the numerator
+/// * `mean_b` - the estimate of the mean of the denominator
+/// * `variance_a` - the squared standard error of `mean_a`
+/// * `variance_b` - the squared standard error of `mean_b`
+/// * `covariance` - the covariance of the two estimates (zero for independent samples)
+/// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidInputData`] - if a value is not finite or a variance is negative
+/// * [`CIError::InvalidDegreesOfFreedom`] - if the degrees of freedom are not strictly positive
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = ratio::ci_fieller(confidence, 10., 2., 1., 0.04, 0., 20.)?;
+/// assert!(ci.is_interval());
+/// // neither mean is significantly different from zero
+/// let ci = ratio::ci_fieller(confidence, 10., 2., 100., 4., 0., 20.)?;
+/// assert_eq!(ci, ratio::Fieller::Unbounded);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_fieller(
+    confidence: Confidence,
+    mean_a: f64,
+    mean_b: f64,
+    variance_a: f64,
+    variance_b: f64,
+    covariance: f64,
+    degrees_of_freedom: f64,
+) -> CIResult<Fieller> {
+    if [mean_a, mean_b, variance_a, variance_b, covariance]
+        .iter()
+        .any(|x| !x.is_finite())
+        || variance_a < 0.
+        || variance_b < 0.
+    {
+        return Err(CIError::InvalidInputData);
+    }
+    let (_, t) = stats::interval_bounds(confidence, 0., 1., degrees_of_freedom)?;
+    let t2 = t * t;
+    // the set is { rho : a rho^2 - 2 b rho + c <= 0 }
+    let a = mean_b * mean_b - t2 * variance_b;
+    let b = mean_a * mean_b - t2 * covariance;
+    let c = mean_a * mean_a - t2 * variance_a;
+    let discriminant = b * b - a * c;
+    let fieller = if a > 0. {
+        let root = discriminant.max(0.).sqrt();
+        let (lo, hi) = ((b - root) / a, (b + root) / a);
+        match confidence {
+            Confidence::TwoSided(_) => Fieller::Interval(Interval::new(lo, hi)?),
+            Confidence::UpperOneSided(_) => Fieller::Interval(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Fieller::Interval(Interval::new_lower(hi)),
+        }
+    } else if a < 0. && discriminant > 0. && confidence.is_two_sided() {
+        let root = discriminant.sqrt();
+        // a < 0, so that the roots are in reverse order
+        Fieller::Exclusive((b + root) / a, (b - root) / a)
+    } else {
+        Fieller::Unbounded
+    };
+    Ok(fieller)
+}
+
+///
+/// Compute the confidence set of the ratio \\( \mu_a / \mu_b \\) of the means of two independent samples with Fieller's theorem.
+///
+/// The degrees of freedom are given by the Welch–Satterthwaite approximation for the variance of \\( \bar{a} - \hat{\rho} \bar{b} \\)
+/// at the estimate \\( \hat{\rho} = \bar{a} / \bar{b} \\).
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `stats` - the statistics of the two samples, the first sample being the numerator
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the samples has less than 2 observations
+/// * [`CIError::InvalidInputData`] - if both samples have zero variance
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // cost of a request on two independent sets of runs, before and after a change
+/// let before = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+/// let after = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1];
+/// let stats = comparison::Unpaired::from_iter(&before, &after)?;
+/// let ci = ratio::ci_unpaired(Confidence::new_two_sided(0.95), &stats)?;
+/// // the cost before the change is between 10% and 40% higher
+/// let interval = ci.interval().unwrap();
+/// assert!(interval.low_f() > 1.1 && interval.high_f() < 1.4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_unpaired<F: Float>(
+    confidence: Confidence,
+    stats: &comparison::Unpaired<F>,
+) -> CIResult<Fieller> {
+    let (stats_a, stats_b) = (stats.stats_a(), stats.stats_b());
+    let (count_a, count_b) = (stats_a.sample_count(), stats_b.sample_count());
+    if count_a < 2 || count_b < 2 {
+        return Err(CIError::TooFewSamples(count_a.min(count_b)));
+    }
+    let mean_a = stats_a.sample_mean().try_f64("mean_a")?;
+    let mean_b = stats_b.sample_mean().try_f64("mean_b")?;
+    // squared standard errors of the means, s^2 / n
+    let variance_a = stats_a.sample_std_dev().try_f64("std_dev_a")?.powi(2) / count_a as f64;
+    let variance_b = stats_b.sample_std_dev().try_f64("std_dev_b")?.powi(2) / count_b as f64;
+    let (dof_a, dof_b) = ((count_a - 1) as f64, (count_b - 1) as f64);
+    let ratio = mean_a / mean_b;
+    let degrees_of_freedom = if ratio.is_finite() {
+        let scaled_b = ratio * ratio * variance_b;
+        (variance_a + scaled_b).powi(2)
+            / (variance_a * variance_a / dof_a + scaled_b * scaled_b / dof_b)
+    } else {
+        // limit when the ratio tends to infinity
+        dof_b
+    };
+    if degrees_of_freedom.is_nan() {
+        return Err(CIError::InvalidInputData);
+    }
+    ci_fieller(
+        confidence,
+        mean_a,
+        mean_b,
+        variance_a,
+        variance_b,
+        0.,
+        degrees_of_freedom,
+    )
+}
+
+///
+/// Compute the geometric mean of per-benchmark ratios (e.g., the speedup \\( \text{new} / \text{old} \\) of each benchmark of a suite)
+/// with its confidence interval.
+///
+/// The ratios are log-transformed, and the interval is the t-interval on the mean of the log ratios,
+/// with \\( n - 1 \\) degrees of freedom for \\( n \\) benchmarks, transformed back by exponentiation.
+/// The resulting interval is not symmetric around the estimate, and a ratio and its inverse give reciprocal intervals,
+/// so that the conclusion does not depend on the direction in which the ratios are taken.
+///
+/// The interval accounts for the variation of the speedup between benchmarks, which usually dominates the measurement noise
+/// within each benchmark. When each benchmark is run several times, the ratio of the mean results should be given for each benchmark.
+///
+/// Complexity: \\( O(n) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `ratios` - the ratio obtained on each benchmark
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than two ratios
+/// * [`CIError::NonPositiveValue`] - if a ratio is not strictly positive
+/// * [`CIError::InvalidInputData`] - if a ratio is not finite
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // throughput of the new version relative to the old one on each benchmark of the suite
+/// let speedups = [1.12, 0.97, 1.31, 1.08, 1.22, 1.02, 1.15, 0.99, 1.27, 1.10];
+/// let speedup = ratio::ci_geometric_speedup(Confidence::new_two_sided(0.95), &speedups)?;
+/// assert_abs_diff_eq!(speedup.estimate(), 1.117680, epsilon = 1e-6);
+/// assert_abs_diff_eq!(speedup.interval().low_f(), 1.038636, epsilon = 1e-6);
+/// assert_abs_diff_eq!(speedup.interval().high_f(), 1.202739, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * P.J. Fleming, J.J. Wallace. "How not to lie with statistics: the correct way to summarize benchmark results." Communications of the ACM 29(3): 218-221 (1986).
+/// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
+///
+pub fn ci_geometric_speedup<F: Float, I>(
+    confidence: Confidence,
+    ratios: &I,
+) -> CIResult<CiEstimate<F>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    let stats = mean::Geometric::from_iter(ratios)?;
+    if stats.sample_count() < 2 {
+        return Err(CIError::TooFewSamples(stats.sample_count()));
+    }
+    stats.estimate(confidence)
+}
+
+///
+/// Compute the geometric mean of the speedups \\( \text{new}_i / \text{old}_i \\) of paired results on a suite of benchmarks
+/// (see [`ci_geometric_speedup`]).
+///
+/// The ratios are taken benchmark by benchmark before aggregating. The estimate is the same as the ratio of the geometric means
+/// of the two sets of results, but computing the interval of each geometric mean separately would ignore the pairing and include
+/// the variation of the results between benchmarks, which is unrelated to the speedup.
+///
+/// Complexity: \\( O(n) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `new` - the result of the new system on each benchmark (e.g., throughput)
+/// * `old` - the result of the old system on the same benchmarks, in the same order
+///
+/// # Errors
+///
+/// * [`CIError::DifferentSampleSizes`] - if the two samples do not have the same length
+/// * [`CIError::TooFewSamples`] - if there are less than two benchmarks
+/// * [`CIError::NonPositiveValue`] - if a result or a ratio is not strictly positive
+/// * [`CIError::InvalidInputData`] - if a ratio is not finite
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // execution times (in s) of the benchmarks of a suite before and after an optimization
+/// let old_times = [12.4, 3.1, 45.0, 7.7, 0.9, 21.3];
+/// let new_times = [11.0, 2.9, 38.2, 7.5, 0.8, 18.9];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // for execution times, the speedup is old / new
+/// let speedup = ratio::ci_geometric_speedup_paired(confidence, &old_times, &new_times)?;
+/// assert!(speedup.interval().low_f() > 1.);
+/// // which is significant only thanks to the pairing
+/// let old_mean = mean::Geometric::ci(confidence, &old_times)?;
+/// let new_mean = mean::Geometric::ci(confidence, &new_times)?;
+/// assert!(old_mean.intersects(&new_mean));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn ci_geometric_speedup_paired<F: Float, Ia, Ib>(
+    confidence: Confidence,
+    new: &Ia,
+    old: &Ib,
+) -> CIResult<CiEstimate<F>>
+where
+    for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+    for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+{
+    let new = new.into_iter().copied().collect::<Vec<_>>();
+    let old = old.into_iter().copied().collect::<Vec<_>>();
+    if new.len() != old.len() {
+        return Err(CIError::DifferentSampleSizes(new.len(), old.len()));
+    }
+    let ratios = new
+        .iter()
+        .zip(&old)
+        .map(|(&x_new, &x_old)| {
+            if x_old.is_nan() {
+                Err(CIError::InvalidInputData)
+            } else if x_old > F::zero() {
+                Ok(x_new / x_old)
+            } else {
+                Err(CIError::NonPositiveValue(x_old.try_f64("old")?))
+            }
+        })
+        .collect::<CIResult<Vec<_>>>()?;
+    ci_geometric_speedup(confidence, &ratios)
+}
+
+///
+/// Represents the state of the computation of the ratio of the means of paired observations \\( (a_i, b_i) \\),
+/// such as the throughput and the power consumption measured during the same run.
+///
+/// The pairing makes the two means correlated, which is accounted for by the covariance term of Fieller's theorem,
+/// with \\( n - 1 \\) degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // cost (in cents) and number of requests served by a batch job
+/// let cost = [41.2, 38.9, 45.1, 40.3, 43.8];
+/// let requests = [1020., 980., 1110., 1005., 1090.];
+/// let mut stats = ratio::Paired::new();
+/// stats.extend(&cost, &requests)?;
+/// let ci = stats.ci_ratio(Confidence::new_two_sided(0.95))?;
+/// assert!(ci.contains(&stats.sample_ratio()));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paired<F: Float> {
+    count: usize,
+    mean_a: F,
+    mean_b: F,
+    sum_sq_dev_a: F,
+    sum_sq_dev_b: F,
+    sum_co_dev: F,
+}
+
+impl<F: Float> Default for Paired<F> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean_a: F::zero(),
+            mean_b: F::zero(),
+            sum_sq_dev_a: F::zero(),
+            sum_sq_dev_b: F::zero(),
+            sum_co_dev: F::zero(),
+        }
+    }
+}
+
+impl<F: Float> Paired<F> {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state from the observations of the numerator and of the denominator
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two samples do not have the same length
+    /// * [`CIError::InvalidInputData`] - if some value is not finite
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<Ia, Ib>(data_a: &Ia, data_b: &Ib) -> CIResult<Self>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+    {
+        let mut state = Self::new();
+        state.extend(data_a, data_b)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append a pair of observations of the numerator and of the denominator
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if some value is not finite; the state is then left unchanged
+    ///
+    pub fn append_pair(&mut self, data_a: F, data_b: F) -> CIResult<()> {
+        if !data_a.is_finite() || !data_b.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.count += 1;
+        let n = F::from(self.count).convert("count")?;
+        let delta_a = data_a - self.mean_a;
+        let delta_b = data_b - self.mean_b;
+        self.mean_a = self.mean_a + delta_a / n;
+        self.mean_b = self.mean_b + delta_b / n;
+        self.sum_sq_dev_a = self.sum_sq_dev_a + delta_a * (data_a - self.mean_a);
+        self.sum_sq_dev_b = self.sum_sq_dev_b + delta_b * (data_b - self.mean_b);
+        self.sum_co_dev = self.sum_co_dev + delta_b * (data_a - self.mean_a);
+        Ok(())
+    }
+
+    ///
+    /// Append paired observations of the numerator and of the denominator
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two samples do not have the same length; the pairs before have already been appended
+    /// * [`CIError::InvalidInputData`] - if some value is not finite; the pairs before it have already been appended
+    ///
+    pub fn extend<Ia, Ib>(&mut self, data_a: &Ia, data_b: &Ib) -> CIResult<()>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+    {
+        let mut iter_a = data_a.into_iter();
+        let mut iter_b = data_b.into_iter();
+        let mut count = 0;
+        loop {
+            match (iter_a.next(), iter_b.next()) {
+                (Some(&a), Some(&b)) => self.append_pair(a, b)?,
+                (None, None) => return Ok(()),
+                (Some(_), None) => {
+                    return Err(CIError::DifferentSampleSizes(
+                        count + 1 + iter_a.count(),
+                        count,
+                    ))
+                }
+                (None, Some(_)) => {
+                    return Err(CIError::DifferentSampleSizes(
+                        count,
+                        count + 1 + iter_b.count(),
+                    ))
+                }
+            }
+            count += 1;
+        }
+    }
+
+    ///
+    /// Number of pairs of observations
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Estimate of the ratio \\( \bar{a} / \bar{b} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_ratio(&self) -> F {
+        self.mean_a / self.mean_b
+    }
+
+    ///
+    /// Confidence set of the ratio of the means with Fieller's theorem
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are less than two pairs of observations
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to `f64`
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_ratio(&self, confidence: Confidence) -> CIResult<Fieller> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let n = self.count as f64;
+        // squared standard errors and covariance of the means
+        let scale = n * (n - 1.);
+        ci_fieller(
+            confidence,
+            self.mean_a.try_f64("mean_a")?,
+            self.mean_b.try_f64("mean_b")?,
+            self.sum_sq_dev_a.try_f64("sum_sq_dev_a")? / scale,
+            self.sum_sq_dev_b.try_f64("sum_sq_dev_b")? / scale,
+            self.sum_co_dev.try_f64("sum_co_dev")? / scale,
+            n - 1.,
+        )
+    }
+
+    ///
+    /// Confidence set of the ratio of the means of paired observations with Fieller's theorem, with a single call
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two samples do not have the same length
+    /// * [`CIError::InvalidInputData`] - if some value is not finite
+    /// * [`CIError::TooFewSamples`] - if there are less than two pairs of observations
+    ///
+    pub fn ci<Ia, Ib>(confidence: Confidence, data_a: &Ia, data_b: &Ib) -> CIResult<Fieller>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a F>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b F>,
+    {
+        Self::from_iter(data_a, data_b)?.ci_ratio(confidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_ci_fieller() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let ci = ci_fieller(confidence, 10., 2., 1., 0.04, 0.1, 20.)?;
+        let interval = ci.interval().unwrap();
+        assert_abs_diff_eq!(interval.low_f(), 4.041241091906261, epsilon = 1e-9);
+        assert_abs_diff_eq!(interval.high_f(), 6.186218399607398, epsilon = 1e-9);
+
+        // without uncertainty on the denominator, this is the interval on the mean of the numerator divided by the denominator
+        let ci = ci_fieller(confidence, 10., 2., 1., 0., 0., 20.)?;
+        let t = distributions::t_value(confidence, 20.)?;
+        assert_abs_diff_eq!(
+            *ci.interval().unwrap(),
+            Interval::new((10. - t) / 2., (10. + t) / 2.)?,
+            epsilon = 1e-12
+        );
+
+        // denominator not significantly different from zero
+        let ci = ci_fieller(confidence, 10., 1., 1., 0.5, 0., 20.)?;
+        match ci {
+            Fieller::Exclusive(low, high) => {
+                assert_abs_diff_eq!(low, -20.90430312719372, epsilon = 1e-9);
+                assert_abs_diff_eq!(high, 3.892028582527791, epsilon = 1e-9);
+            }
+            _ => panic!("expected an exclusive set: {:?}", ci),
+        }
+        assert!(ci.contains(&10.) && ci.contains(&-1000.) && !ci.contains(&0.));
+        assert!(!ci.is_interval());
+        // numerator and denominator both compatible with zero
+        let ci = ci_fieller(confidence, 1., 1., 1., 1., 0., 20.)?;
+        assert_eq!(ci, Fieller::Unbounded);
+        assert!(ci.contains(&f64::MAX));
+
+        // one-sided bounds are those of the two-sided set at twice the error rate
+        let two_sided = ci_fieller(Confidence::new_two_sided(0.9), 10., 2., 1., 0.04, 0.1, 20.)?;
+        let upper = ci_fieller(Confidence::new_upper(0.95), 10., 2., 1., 0.04, 0.1, 20.)?;
+        assert_abs_diff_eq!(
+            upper.interval().unwrap().low_f(),
+            two_sided.interval().unwrap().low_f(),
+            epsilon = 1e-12
+        );
+        assert!(upper.interval().unwrap().is_upper());
+        let lower = ci_fieller(Confidence::new_lower(0.95), 10., 1., 1., 0.5, 0., 20.)?;
+        assert_eq!(lower, Fieller::Unbounded);
+
+        assert!(matches!(
+            ci_fieller(confidence, 10., 2., -1., 0.04, 0., 20.),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            ci_fieller(confidence, 10., f64::NAN, 1., 0.04, 0., 20.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_paired() -> CIResult<()> {
+        let throughput = [1210., 1185., 1302., 1254., 1198., 1276., 1231., 1289.];
+        let power = [251., 247., 268., 259., 250., 262., 255., 266.];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let stats = Paired::from_iter(&throughput, &power)?;
+        assert_eq!(stats.sample_count(), 8);
+        let ci = stats.ci_ratio(confidence)?;
+        let interval = ci.interval().unwrap();
+        assert_abs_diff_eq!(interval.low_f(), 4.808825275170365, epsilon = 1e-9);
+        assert_abs_diff_eq!(interval.high_f(), 4.854872422997909, epsilon = 1e-9);
+        assert_eq!(Paired::ci(confidence, &throughput, &power)?, ci);
+
+        // ignoring the correlation gives a much wider interval
+        let unpaired = comparison::Unpaired::from_iter(&throughput, &power)?;
+        let wide = ci_unpaired(confidence, &unpaired)?;
+        assert!(wide.interval().unwrap().includes(interval));
+
+        let stats32 = Paired::from_iter(&throughput.map(|x| x as f32), &power.map(|x| x as f32))?;
+        let ci32 = stats32.ci_ratio(confidence)?;
+        assert_abs_diff_eq!(*ci32.interval().unwrap(), *interval, epsilon = 1e-4);
+
+        assert!(matches!(
+            Paired::ci(confidence, &throughput, &power[..7].to_vec()),
+            Err(CIError::DifferentSampleSizes(8, 7))
+        ));
+        assert!(matches!(
+            Paired::ci(confidence, &[1.], &[2.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            Paired::new().append_pair(1., f64::INFINITY),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpaired() -> CIResult<()> {
+        let before = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+        let after = [10.2, 11.9, 9.8, 12.4, 10.7, 11.1];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let stats = comparison::Unpaired::from_iter(&before, &after)?;
+        let ci = ci_unpaired(confidence, &stats)?;
+        let interval = ci.interval().unwrap();
+        assert_abs_diff_eq!(interval.low_f(), 1.103789123232149, epsilon = 1e-9);
+        assert_abs_diff_eq!(interval.high_f(), 1.394984497217011, epsilon = 1e-9);
+
+        let stats = comparison::Unpaired::from_iter(&before, &[1.])?;
+        assert!(matches!(
+            ci_unpaired(confidence, &stats),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_geometric_speedup() -> CIResult<()> {
+        let speedups = [1.12, 0.97, 1.31, 1.08, 1.22, 1.02, 1.15, 0.99, 1.27, 1.10];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values computed in python
+        let speedup = ci_geometric_speedup(confidence, &speedups)?;
+        assert_abs_diff_eq!(speedup.estimate(), 1.117679859776811, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            speedup.interval().low_f(),
+            1.038636437215609,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            speedup.interval().high_f(),
+            1.202738729540056,
+            epsilon = 1e-9
+        );
+        assert_eq!(speedup.sample_count(), 10);
+
+        // the inverse ratios give the reciprocal interval
+        let slowdowns = speedups.map(|x| 1. / x);
+        let slowdown = ci_geometric_speedup(confidence, &slowdowns)?;
+        assert_abs_diff_eq!(
+            slowdown.estimate(),
+            1. / speedup.estimate(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            slowdown.interval().low_f(),
+            1. / speedup.interval().high_f(),
+            epsilon = 1e-12
+        );
+
+        let old_times = [12.4, 3.1, 45.0, 7.7, 0.9, 21.3];
+        let new_times = [11.0, 2.9, 38.2, 7.5, 0.8, 18.9];
+        let speedup = ci_geometric_speedup_paired(confidence, &old_times, &new_times)?;
+        assert_abs_diff_eq!(speedup.estimate(), 1.107746207164255, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            speedup.interval().low_f(),
+            1.052982922391208,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            speedup.interval().high_f(),
+            1.165357607794987,
+            epsilon = 1e-9
+        );
+        // same estimate as the ratio of the geometric means
+        let old_mean = mean::Geometric::from_iter(&old_times)?.sample_mean();
+        let new_mean = mean::Geometric::from_iter(&new_times)?.sample_mean();
+        assert_abs_diff_eq!(speedup.estimate(), old_mean / new_mean, epsilon = 1e-12);
+
+        assert!(matches!(
+            ci_geometric_speedup(confidence, &[1.1]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            ci_geometric_speedup(confidence, &[1.1, 0.]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            ci_geometric_speedup_paired(confidence, &old_times, &new_times[..5].to_vec()),
+            Err(CIError::DifferentSampleSizes(6, 5))
+        ));
+        assert!(matches!(
+            ci_geometric_speedup_paired(confidence, &[1., 2.], &[1., 0.]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        Ok(())
+    }
+}
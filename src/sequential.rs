@@ -0,0 +1,1318 @@
+//!
+//! Sequential estimation: collecting samples until the confidence interval is precise enough, and
+//! confidence sequences that remain valid when the interval is monitored continuously
+//!
+//! A common practice (e.g., when benchmarking) is to keep collecting samples until the confidence interval
+//! on the mean is narrower than some target, such as ±2% of the mean. Written by hand, such a loop usually
+//! stops too early: with few samples, the standard deviation is often underestimated by chance, the interval
+//! then looks narrow, and the loop stops precisely when the estimate is at its worst.
+//! The actual coverage of the resulting interval is then lower than its nominal confidence level.
+//!
+//! The structure [`SequentialMean`] implements the stopping rule of Chow and Robbins (1965), with the
+//! corrections recommended by Law and Kelton, to limit this bias:
+//!
+//! * a minimum number of samples (10 by default) before the criterion is evaluated, so that the first
+//!   estimates of the standard deviation do not trigger an early stop;
+//! * for a relative precision \\( \gamma \\), the half-width is compared to \\( \frac{\gamma}{1+\gamma} |\bar{x}| \\)
+//!   rather than \\( \gamma |\bar{x}| \\), which guarantees that the relative error with respect to the
+//!   _true_ mean (rather than the estimated one) is at most \\( \gamma \\) with the requested confidence.
+//!
+//! With these corrections, the coverage of the interval at the stopping time converges to the nominal confidence
+//! level as the target precision gets smaller (Chow and Robbins, 1965).
+//!
+//! When the interval must be valid at _any_ stopping time, including stopping times decided by peeking at the
+//! interval itself (e.g., continuously monitored A/B tests), the structures [`ConfidenceSequenceMean`] and
+//! [`ConfidenceSequenceProportion`] provide anytime-valid confidence sequences instead, and the structure
+//! [`ProportionComparison`] compares two proportions with the mixture sequential probability ratio test (mSPRT).
+//!
+//! When the data is only analyzed at a few interim looks, the group-sequential design [`GroupSequential`] adjusts
+//! the critical values of each look with an alpha-spending function (O'Brien–Fleming or Pocock).
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! use sequential::*;
+//! # let mut samples = [100., 104., 98., 101., 97., 103., 99., 102., 100., 96., 104., 101.].into_iter().cycle();
+//! # let mut run_benchmark = || samples.next().unwrap();
+//! let confidence = Confidence::new_two_sided(0.95);
+//! // keep benchmarking until the interval is within ±2% of the mean
+//! let mut seq = SequentialMean::new(confidence, Precision::Relative(0.02));
+//! while !seq.push(run_benchmark())? {}
+//! println!("mean: {} after {} runs", seq.ci()?, seq.n());
+//! assert!(seq.n() >= 10);
+//! assert!(seq.half_width()? <= 0.02 / 1.02 * seq.mean());
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * Y.S. Chow and H. Robbins. "On the asymptotic theory of fixed-width sequential confidence intervals for the mean." The Annals of Mathematical Statistics 36(2): 457-462 (1965).
+//! * Averill M. Law. Simulation Modeling and Analysis (5th ed.), McGraw-Hill, 2015. Section 9.4.1.
+//!
+use crate::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Target precision of a confidence interval, expressed through its half-width
+/// (for one-sided intervals, the distance between the mean and the bound).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Precision<F> {
+    /// Half-width at most the given value (in the unit of the samples)
+    Absolute(F),
+    /// Half-width at most the given fraction of the (absolute value of the) mean, e.g., `0.02` for ±2%
+    Relative(F),
+}
+
+///
+/// Sequential estimation of the arithmetic mean of a stream of samples, until the confidence interval reaches
+/// a target precision (see the [module documentation](self) for the stopping rule and its corrections).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use sequential::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut seq = SequentialMean::new(confidence, Precision::Absolute(0.5)).with_min_samples(5);
+/// let mut samples = [10., 11., 9., 10., 10., 11., 9., 10., 10., 10.].into_iter();
+/// let met = seq.run(100, || samples.next().unwrap())?;
+/// assert!(met);
+/// assert_eq!(seq.n(), 10);
+/// assert!(seq.half_width()? <= 0.5);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequentialMean<F: Float> {
+    confidence: Confidence,
+    precision: Precision<F>,
+    min_samples: usize,
+    acc: incremental::MeanAccumulator<F>,
+}
+
+impl<F: Float> SequentialMean<F> {
+    ///
+    /// Create a new sequential estimation with the given confidence level and target precision,
+    /// and a minimum of 10 samples
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the interval
+    /// * `precision` - the target precision of the interval
+    ///
+    /// # Panics
+    ///
+    /// * if the target precision is not strictly positive and finite
+    ///
+    pub fn new(confidence: Confidence, precision: Precision<F>) -> Self {
+        let (Precision::Absolute(target) | Precision::Relative(target)) = precision;
+        if !(target.is_finite() && target > F::zero()) {
+            panic!("Target precision must be strictly positive and finite.")
+        }
+        Self {
+            confidence,
+            precision,
+            min_samples: 10,
+            acc: incremental::MeanAccumulator::new(),
+        }
+    }
+
+    ///
+    /// Set the minimum number of samples before the stopping criterion is evaluated (at least 2)
+    ///
+    pub fn with_min_samples(self, min_samples: usize) -> Self {
+        Self {
+            min_samples: min_samples.max(2),
+            ..self
+        }
+    }
+
+    ///
+    /// Append a new sample and return whether the target precision is met
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite (NaN or infinite); the state is left unchanged
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<bool> {
+        self.acc.push(x)?;
+        self.is_done()
+    }
+
+    ///
+    /// Draw samples from `sample` until the target precision is met, or until the total number of samples
+    /// reaches `max_samples`, and return whether the target precision is met
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn run<S>(&mut self, max_samples: usize, mut sample: S) -> CIResult<bool>
+    where
+        S: FnMut() -> F,
+    {
+        let mut done = self.is_done()?;
+        while !done && self.n() < max_samples {
+            done = self.push(sample())?;
+        }
+        Ok(done)
+    }
+
+    ///
+    /// Whether the target precision is met with the samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn is_done(&self) -> CIResult<bool> {
+        if self.acc.n() < self.min_samples {
+            return Ok(false);
+        }
+        let target = match self.precision {
+            Precision::Absolute(target) => target,
+            Precision::Relative(gamma) => gamma / (F::one() + gamma) * self.acc.mean().abs(),
+        };
+        Ok(self.half_width()? <= target)
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.acc.n()
+    }
+
+    ///
+    /// Mean of the samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn mean(&self) -> F {
+        self.acc.mean()
+    }
+
+    ///
+    /// Half-width of the current confidence interval
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn half_width(&self) -> CIResult<F> {
+        if self.acc.n() < 2 {
+            return Err(CIError::TooFewSamples(self.acc.n()));
+        }
+        let degrees_of_freedom = F::from(self.acc.n() - 1).convert("count")?;
+        let (_, hi) = stats::float_interval_bounds(
+            self.confidence,
+            self.acc.mean(),
+            self.acc.sem(),
+            degrees_of_freedom,
+        )?;
+        Ok(hi - self.acc.mean())
+    }
+
+    ///
+    /// Current confidence interval of the mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    ///
+    pub fn ci(&self) -> CIResult<Interval<F>> {
+        self.acc.ci(self.confidence)
+    }
+
+    ///
+    /// Underlying accumulator
+    ///
+    pub fn accumulator(&self) -> &incremental::MeanAccumulator<F> {
+        &self.acc
+    }
+}
+
+///
+/// Anytime-valid confidence sequence over the arithmetic mean of a stream of samples.
+///
+/// Unlike the confidence interval computed for a fixed number of samples, which is only valid if the number of samples
+/// is decided in advance, the intervals of a confidence sequence hold _simultaneously_ for all numbers of samples:
+/// \\( P(\forall n: \mu \in C_n) \geq 1 - \alpha \\).
+/// The interval can therefore be monitored continuously (e.g., at every step of an A/B test), and the data collection
+/// stopped at any time, for any reason, without invalidating the coverage.
+///
+/// The sequence is the (asymptotic) normal-mixture confidence sequence of Waudby-Smith et al., with
+/// the half-width
+/// \\[
+/// \hat{\sigma}_n \sqrt{\frac{2(n\rho^2+1)}{n^2\rho^2} \log\left(\frac{\sqrt{n\rho^2+1}}{\alpha}\right)}
+/// \\]
+/// where \\( \hat{\sigma}_n \\) is the sample standard deviation and \\( \rho \\) is a tuning parameter chosen
+/// so that the sequence is tightest around a given number of samples.
+/// For one-sided confidence levels, the corresponding one-sided boundary of Howard et al. is used.
+///
+/// # Notes
+///
+/// The price of anytime validity is that the intervals are wider than fixed-sample intervals
+/// (about 1.5 to 2 times near the target number of samples).
+/// The coverage is asymptotic, as the standard deviation is estimated from the data; for bounded data such as proportions,
+/// [`ConfidenceSequenceProportion`] provides an exact (non-asymptotic) guarantee.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use sequential::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut cs = ConfidenceSequenceMean::new(confidence, 1000);
+/// for x in [12., 10., 14., 11., 9., 13., 12., 10., 11., 12.] {
+///     cs.push(x)?;
+///     if cs.n() >= 2 {
+///         // peeking at every step is allowed
+///         println!("{}", cs.ci()?);
+///     }
+/// }
+/// let ci = cs.ci()?;
+/// assert!(ci.contains(&11.4));
+/// // wider than the fixed-sample interval
+/// assert!(ci.includes(&cs.accumulator().ci(confidence)?));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * Ian Waudby-Smith, David Arbour, Ritwik Sinha, Edward H. Kennedy, Aaditya Ramdas. "Time-uniform central limit theory and asymptotic confidence sequences." arXiv:2103.06476 (2021).
+/// * Steven R. Howard, Aaditya Ramdas, Jon McAuliffe, Jasjeet Sekhon. "Time-uniform, nonparametric, nonasymptotic confidence sequences." The Annals of Statistics 49(2): 1055-1080 (2021).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfidenceSequenceMean<F: Float> {
+    confidence: Confidence,
+    rho_sq: f64,
+    acc: incremental::MeanAccumulator<F>,
+}
+
+impl<F: Float> ConfidenceSequenceMean<F> {
+    ///
+    /// Create a new confidence sequence, tightest around the given number of samples
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the sequence (holding simultaneously for all numbers of samples)
+    /// * `target_n` - the number of samples around which the sequence is tightest (e.g., the planned sample size)
+    ///
+    /// # Panics
+    ///
+    /// * if `target_n` is zero
+    ///
+    pub fn new(confidence: Confidence, target_n: usize) -> Self {
+        Self {
+            confidence,
+            rho_sq: mixture_rho_sq(confidence, target_n),
+            acc: incremental::MeanAccumulator::new(),
+        }
+    }
+
+    ///
+    /// Append a new sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the sample is not finite (NaN or infinite); the state is left unchanged
+    ///
+    pub fn push(&mut self, x: F) -> CIResult<()> {
+        self.acc.push(x)
+    }
+
+    ///
+    /// Append several samples
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some sample is not finite; the samples before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        self.acc.extend(data)
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.acc.n()
+    }
+
+    ///
+    /// Interval of the confidence sequence for the samples appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If less than two samples have been appended
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self) -> CIResult<Interval<F>> {
+        if self.acc.n() < 2 {
+            return Err(CIError::TooFewSamples(self.acc.n()));
+        }
+        let boundary = mixture_boundary(self.confidence, self.rho_sq, self.acc.n())?;
+        let span = self.acc.std_dev() * F::from(boundary).convert("boundary")?;
+        let (lo, hi) = (self.acc.mean() - span, self.acc.mean() + span);
+        match self.confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Underlying accumulator
+    ///
+    pub fn accumulator(&self) -> &incremental::MeanAccumulator<F> {
+        &self.acc
+    }
+}
+
+///
+/// Anytime-valid confidence sequence over a proportion (see [`ConfidenceSequenceMean`] for the properties of confidence sequences).
+///
+/// Bernoulli observations are bounded, hence sub-Gaussian with a variance factor \\( \frac{1}{4} \\), so that the
+/// normal-mixture boundary of Howard et al. holds exactly (not only asymptotically) with \\( \sigma = \frac{1}{2} \\).
+/// The intervals are conservative when the proportion is close to 0 or 1.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use sequential::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut cs = ConfidenceSequenceProportion::new(confidence, 10_000);
+/// for i in 0..5000 {
+///     cs.push(i % 10 < 3);
+/// }
+/// let ci = cs.ci()?;
+/// assert!(ci.contains(&0.3));
+/// assert!(ci.includes(&proportion::ci(confidence, 5000, 1500)?));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfidenceSequenceProportion {
+    confidence: Confidence,
+    rho_sq: f64,
+    stats: proportion::Stats,
+}
+
+impl ConfidenceSequenceProportion {
+    ///
+    /// Create a new confidence sequence, tightest around the given number of samples
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level of the sequence (holding simultaneously for all numbers of samples)
+    /// * `target_n` - the number of samples around which the sequence is tightest (e.g., the planned sample size)
+    ///
+    /// # Panics
+    ///
+    /// * if `target_n` is zero
+    ///
+    pub fn new(confidence: Confidence, target_n: usize) -> Self {
+        Self {
+            confidence,
+            rho_sq: mixture_rho_sq(confidence, target_n),
+            stats: proportion::Stats::default(),
+        }
+    }
+
+    ///
+    /// Append a new observation (success or failure)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn push(&mut self, success: bool) {
+        push_bool(&mut self.stats, success);
+    }
+
+    ///
+    /// Append several observations
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    pub fn extend<I>(&mut self, data: &I)
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a bool>,
+    {
+        self.stats.extend(data);
+    }
+
+    ///
+    /// Number of observations
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn n(&self) -> usize {
+        self.stats.population()
+    }
+
+    ///
+    /// Interval of the confidence sequence for the observations appended so far
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If no observation has been appended
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    ///
+    pub fn ci(&self) -> CIResult<Interval<f64>> {
+        let n = self.stats.population();
+        if n == 0 {
+            return Err(CIError::TooFewSamples(n));
+        }
+        let p = self.stats.successes() as f64 / n as f64;
+        let span = 0.5 * mixture_boundary(self.confidence, self.rho_sq, n)?;
+        let (lo, hi) = ((p - span).max(0.), (p + span).min(1.));
+        match self.confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+        }
+    }
+
+    ///
+    /// Underlying counts of successes and observations
+    ///
+    pub fn stats(&self) -> &proportion::Stats {
+        &self.stats
+    }
+}
+
+///
+/// Mixture parameter \\( \rho^2 \\) minimizing the width of the normal-mixture boundary at `target_n` samples
+/// (Waudby-Smith et al., 2021).
+///
+fn mixture_rho_sq(confidence: Confidence, target_n: usize) -> f64 {
+    if target_n == 0 {
+        panic!("Target number of samples must be strictly positive.")
+    }
+    let alpha = (1. - confidence.level()).clamp(f64::MIN_POSITIVE, 1.);
+    let log_alpha = -2. * alpha.ln();
+    (log_alpha + (log_alpha + 1.).ln()) / target_n as f64
+}
+
+///
+/// Half-width of the normal-mixture confidence sequence after `n` samples, for a unit standard deviation
+///
+fn mixture_boundary(confidence: Confidence, rho_sq: f64, n: usize) -> CIResult<f64> {
+    let level = confidence.level();
+    if !(level > 0. && level < 1.) {
+        return Err(CIError::InvalidConfidenceLevel(level));
+    }
+    let alpha = 1. - level;
+    let n = n as f64;
+    let v = n * rho_sq + 1.;
+    let log_term = match confidence {
+        Confidence::TwoSided(_) => (v.sqrt() / alpha).ln(),
+        Confidence::UpperOneSided(_) | Confidence::LowerOneSided(_) => {
+            (v.sqrt() / (2. * alpha) + 1.).ln()
+        }
+    };
+    Ok((2. * v / (n * n * rho_sq) * log_term).sqrt())
+}
+
+///
+/// Sequential comparison of two proportions (e.g., conversion rates of the control `a` and the treatment `b`
+/// of an A/B test) with the mixture sequential probability ratio test (mSPRT) of Johari et al.
+///
+/// The test monitors the difference \\( \theta = p_b - p_a \\) (the absolute lift) and provides, after every
+/// observation, an always-valid p-value for the null hypothesis \\( \theta = 0 \\) and an always-valid confidence
+/// interval on \\( \theta \\). The test can be stopped as soon as the result is significant, or at any other time,
+/// without inflating the false positive rate.
+///
+/// With the estimate \\( \hat{\theta}_n = \hat{p}_b - \hat{p}_a \\) of variance
+/// \\( V_n = \frac{\hat{p}_a (1-\hat{p}_a)}{n_a} + \frac{\hat{p}_b (1-\hat{p}_b)}{n_b} \\) and a normal mixture
+/// of scale \\( \tau \\) over the alternatives, the mixture likelihood ratio for a value \\( \theta_0 \\) is
+/// \\[
+/// \Lambda_n(\theta_0) = \sqrt{\frac{V_n}{V_n+\tau^2}} \exp\left(\frac{\tau^2 (\hat{\theta}_n - \theta_0)^2}{2 V_n (V_n+\tau^2)}\right)
+/// \\]
+/// The p-value is the running minimum of \\( 1 / \Lambda_n(0) \\), and the interval is the running intersection
+/// of the sets \\( \\{ \theta_0 : \Lambda_n(\theta_0) < 1/\alpha \\} \\).
+///
+/// # Notes
+///
+/// The mixture scale \\( \tau \\) should be of the order of the lifts expected (or worth detecting); the test is
+/// valid for any value, but most powerful for effects around \\( \tau \\).
+/// The variance is estimated from the data (normal approximation), so the guarantees are asymptotic.
+/// The mixture is symmetric: one-sided confidence levels yield one-sided intervals from the same boundary.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use sequential::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut test = ProportionComparison::new(confidence, 0.02);
+/// for i in 0..20_000 {
+///     test.push_a(i % 100 < 10); // 10% conversion rate
+///     test.push_b(i % 100 < 13); // 13% conversion rate
+///     if test.is_significant() {
+///         break;
+///     }
+/// }
+/// assert!(test.is_significant());
+/// let ci = test.ci()?;
+/// assert!(ci.low_f() > 0.);
+/// println!("lift: {} after {} observations per group", ci, test.stats_a().population());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * Ramesh Johari, Pete Koomen, Leonid Pekelis, David Walsh. "Peeking at A/B tests: why it matters, and what to do about it." KDD 2017.
+/// * Ramesh Johari, Leonid Pekelis, David Walsh. "Always valid inference: continuous monitoring of A/B tests." Operations Research 70(3): 1806-1821 (2022).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProportionComparison {
+    confidence: Confidence,
+    tau_sq: f64,
+    stats_a: proportion::Stats,
+    stats_b: proportion::Stats,
+    p_value: f64,
+    low: f64,
+    high: f64,
+}
+
+impl ProportionComparison {
+    ///
+    /// Create a new sequential comparison
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level (the test rejects the null hypothesis at level \\( \alpha = 1 - \\) `confidence`)
+    /// * `tau` - the scale of the normal mixture over the lifts, e.g., `0.02` for lifts of about 2 percentage points
+    ///
+    /// # Panics
+    ///
+    /// * if the confidence level is not in (0, 1)
+    /// * if the mixture scale is not strictly positive and finite
+    ///
+    pub fn new(confidence: Confidence, tau: f64) -> Self {
+        let level = confidence.level();
+        if !(level > 0. && level < 1.) {
+            panic!("Confidence level must be in (0, 1).")
+        }
+        if !(tau.is_finite() && tau > 0.) {
+            panic!("Mixture scale must be strictly positive and finite.")
+        }
+        Self {
+            confidence,
+            tau_sq: tau * tau,
+            stats_a: proportion::Stats::default(),
+            stats_b: proportion::Stats::default(),
+            p_value: 1.,
+            low: -1.,
+            high: 1.,
+        }
+    }
+
+    ///
+    /// Append a new observation to the control group `a`
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn push_a(&mut self, success: bool) {
+        push_bool(&mut self.stats_a, success);
+        self.update();
+    }
+
+    ///
+    /// Append a new observation to the treatment group `b`
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn push_b(&mut self, success: bool) {
+        push_bool(&mut self.stats_b, success);
+        self.update();
+    }
+
+    ///
+    /// Counts of the control group `a`
+    ///
+    pub fn stats_a(&self) -> &proportion::Stats {
+        &self.stats_a
+    }
+
+    ///
+    /// Counts of the treatment group `b`
+    ///
+    pub fn stats_b(&self) -> &proportion::Stats {
+        &self.stats_b
+    }
+
+    ///
+    /// Current estimate of the lift \\( \hat{p}_b - \hat{p}_a \\) (zero until both groups have observations)
+    ///
+    pub fn lift(&self) -> f64 {
+        self.estimate().map_or(0., |(theta, _)| theta)
+    }
+
+    ///
+    /// Always-valid p-value for the null hypothesis of equal proportions
+    ///
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    ///
+    /// Whether the null hypothesis of equal proportions is rejected, i.e., the p-value is at most \\( \alpha \\)
+    ///
+    pub fn is_significant(&self) -> bool {
+        self.p_value <= 1. - self.confidence.level()
+    }
+
+    ///
+    /// Always-valid confidence interval on the lift \\( p_b - p_a \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If one of the groups has no observation
+    ///
+    pub fn ci(&self) -> CIResult<Interval<f64>> {
+        let n = self.stats_a.population().min(self.stats_b.population());
+        if n == 0 {
+            return Err(CIError::TooFewSamples(n));
+        }
+        match self.confidence {
+            Confidence::TwoSided(_) => Interval::new(self.low, self.high).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(self.low, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(-1., self.high).map_err(|e| e.into()),
+        }
+    }
+
+    fn estimate(&self) -> Option<(f64, f64)> {
+        let (n_a, n_b) = (self.stats_a.population(), self.stats_b.population());
+        if n_a == 0 || n_b == 0 {
+            return None;
+        }
+        let p_a = self.stats_a.successes() as f64 / n_a as f64;
+        let p_b = self.stats_b.successes() as f64 / n_b as f64;
+        let variance = p_a * (1. - p_a) / n_a as f64 + p_b * (1. - p_b) / n_b as f64;
+        Some((p_b - p_a, variance))
+    }
+
+    fn update(&mut self) {
+        // the variance of a group cannot be estimated until both outcomes have been observed
+        let observed_both = |stats: &proportion::Stats| {
+            stats.successes() > 0 && stats.successes() < stats.population()
+        };
+        if !(observed_both(&self.stats_a) && observed_both(&self.stats_b)) {
+            return;
+        }
+        let Some((theta, variance)) = self.estimate() else {
+            return;
+        };
+        let alpha = 1. - self.confidence.level();
+        let v_tau = variance + self.tau_sq;
+        let log_lambda_0 =
+            0.5 * (variance / v_tau).ln() + self.tau_sq * theta * theta / (2. * variance * v_tau);
+        self.p_value = self.p_value.min((-log_lambda_0).exp());
+        let span = (2. * variance * v_tau / self.tau_sq
+            * ((1. / alpha).ln() + 0.5 * (v_tau / variance).ln()))
+        .sqrt();
+        let (low, high) = (self.low.max(theta - span), self.high.min(theta + span));
+        // the intersection can only be empty if the assumptions are violated (e.g., normal approximation
+        // with very few observations); the previous interval is then kept
+        if low <= high {
+            self.low = low;
+            self.high = high;
+        }
+    }
+}
+
+fn push_bool(stats: &mut proportion::Stats, success: bool) {
+    if success {
+        stats.add_success();
+    } else {
+        stats.add_failure();
+    }
+}
+
+///
+/// Alpha-spending function of a group-sequential design, i.e., the cumulative type I error \\( \alpha(t) \\)
+/// allowed at the information fraction \\( t \in (0, 1] \\) (e.g., the fraction of the planned samples collected so far),
+/// with \\( \alpha(1) = \alpha \\).
+///
+/// Both functions are the continuous approximations of Lan and DeMets, which do not require the number
+/// and timing of the interim analyses to be fixed in advance.
+/// For two-sided designs, the error \\( \\alpha/2 \\) is spent on each side.
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpendingFunction {
+    /// O'Brien–Fleming-type spending \\( \alpha(t) = 2 - 2 \Phi\left(\frac{z_{1-\alpha/2}}{\sqrt{t}}\right) \\):
+    /// very conservative at early looks, keeping nearly the whole error for the final analysis
+    OBrienFleming,
+    /// Pocock-type spending \\( \alpha(t) = \alpha \ln(1 + (e - 1) t) \\): nearly constant boundaries,
+    /// giving better chances to stop early at the cost of a stricter final analysis
+    Pocock,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl SpendingFunction {
+    ///
+    /// Cumulative type I error spent at the information fraction `t`, for a total error `alpha`
+    ///
+    pub fn spent(&self, alpha: f64, t: f64) -> f64 {
+        let t = t.clamp(0., 1.);
+        if t <= 0. {
+            return 0.;
+        }
+        match self {
+            SpendingFunction::OBrienFleming => {
+                let z = special::normal_inverse_cdf(1. - alpha / 2.);
+                2. * special::normal_cdf(-z / t.sqrt())
+            }
+            SpendingFunction::Pocock => alpha * (1. + (core::f64::consts::E - 1.) * t).ln(),
+        }
+    }
+}
+
+///
+/// Group-sequential design with alpha spending: critical values and repeated confidence intervals
+/// for a sequence of interim analyses (looks) of the same experiment.
+///
+/// Looking at a fixed-sample confidence interval several times during an experiment, and stopping as soon as it
+/// excludes zero, inflates the error rate. In a group-sequential design, each look \\( k \\) uses a larger critical value
+/// \\( c_k \\) (instead of e.g. 1.96), chosen so that the probability of crossing the boundaries at any of the looks is
+/// the spent error \\( \alpha(t_k) \\) (see [`SpendingFunction`]).
+/// The interval \\( \hat{\theta}_k \pm c_k ~ \mathrm{SE}_k \\) at each look is then a repeated confidence interval:
+/// all the intervals cover the true value simultaneously with the requested confidence.
+///
+/// The critical values are computed by recursive numerical integration over the (asymptotically normal)
+/// joint distribution of the test statistics (Armitage, McPherson and Rowe).
+/// Looks can be added as they happen, and their number need not be known in advance.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// use sequential::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut design = GroupSequential::new(confidence, SpendingFunction::OBrienFleming);
+/// // first interim analysis after 20% of the planned samples
+/// let c_1 = design.next_look(0.2)?;
+/// assert_abs_diff_eq!(c_1, 4.877, epsilon = 1e-3);
+/// // repeated confidence interval on the difference of means (estimate and standard error from the data)
+/// let ci = design.ci(1.2, 0.5)?;
+/// assert!(ci.contains(&0.));
+/// // second look after 40% of the samples
+/// let c_2 = design.next_look(0.4)?;
+/// assert_abs_diff_eq!(c_2, 3.357, epsilon = 1e-3);
+/// let ci = design.ci(1.1, 0.3)?;
+/// assert!(ci.low_f() > 0.); // the difference is significant: stop the experiment
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * K.K. Gordon Lan and David L. DeMets. "Discrete sequential boundaries for clinical trials." Biometrika 70(3): 659-663 (1983).
+/// * Christopher Jennison and Bruce W. Turnbull. Group Sequential Methods with Applications to Clinical Trials, Chapman & Hall/CRC, 2000.
+/// * P. Armitage, C.K. McPherson, B.C. Rowe. "Repeated significance tests on accumulating data." Journal of the Royal Statistical Society A 132(2): 235-244 (1969).
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupSequential {
+    confidence: Confidence,
+    spending: SpendingFunction,
+    looks: Vec<(f64, f64)>,
+    grid: Vec<f64>,
+    density: Vec<f64>,
+}
+
+/// Number of intervals of the integration grid (even, for Simpson's rule)
+#[cfg(any(test, feature = "std"))]
+const GRID_INTERVALS: usize = 400;
+
+/// Lower limit of the continuation region for one-sided designs
+#[cfg(any(test, feature = "std"))]
+const ONE_SIDED_LOWER_LIMIT: f64 = -10.;
+
+#[cfg(any(test, feature = "std"))]
+impl GroupSequential {
+    ///
+    /// Create a new design with no look yet
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the overall confidence level (the total error \\( \alpha \\) is spent over the looks)
+    /// * `spending` - the alpha-spending function
+    ///
+    pub fn new(confidence: Confidence, spending: SpendingFunction) -> Self {
+        Self {
+            confidence,
+            spending,
+            looks: Vec::new(),
+            grid: Vec::new(),
+            density: Vec::new(),
+        }
+    }
+
+    ///
+    /// Register a new look at the given information fraction and return its critical value
+    ///
+    /// # Arguments
+    ///
+    /// * `information_fraction` - the fraction of the planned information (e.g., of the planned samples) available at this look
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidInformationFraction`] - If the information fraction is not in (0, 1], or not larger than at the previous look
+    ///
+    pub fn next_look(&mut self, information_fraction: f64) -> CIResult<f64> {
+        let level = self.confidence.level();
+        if !(level > 0. && level < 1.) {
+            return Err(CIError::InvalidConfidenceLevel(level));
+        }
+        let t = information_fraction;
+        let t_prev = self.looks.last().map_or(0., |&(t, _)| t);
+        if !(t > t_prev && t <= 1.) {
+            return Err(CIError::InvalidInformationFraction(t));
+        }
+        let two_sided = self.confidence.is_two_sided();
+        // symmetric boundaries: the error is spent evenly over both sides of two-sided designs
+        let alpha = if two_sided {
+            (1. - level) / 2.
+        } else {
+            1. - level
+        };
+        let increment = self.spending.spent(alpha, t) - self.spending.spent(alpha, t_prev);
+
+        // probability of crossing the upper boundary `c` at this look (and not before)
+        let crossing = |c: f64| -> f64 {
+            if self.looks.is_empty() {
+                return special::normal_cdf(-c);
+            }
+            let delta = t - t_prev;
+            simpson(&self.grid, |i| {
+                let z = self.grid[i];
+                self.density[i]
+                    * special::normal_cdf(-(c * t.sqrt() - z * t_prev.sqrt()) / delta.sqrt())
+            })
+        };
+
+        // the crossing probability decreases with the critical value
+        let (mut lo, mut hi) = (0., 40.);
+        if increment <= crossing(hi) {
+            lo = hi;
+        }
+        while hi - lo > 1e-10 {
+            let mid = (lo + hi) / 2.;
+            if crossing(mid) > increment {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let c = (lo + hi) / 2.;
+
+        // density of the statistic on the continuation region at this look
+        let lower = if two_sided { -c } else { ONE_SIDED_LOWER_LIMIT };
+        let grid = (0..=GRID_INTERVALS)
+            .map(|i| lower + (c - lower) * i as f64 / GRID_INTERVALS as f64)
+            .collect::<Vec<_>>();
+        let density = if self.looks.is_empty() {
+            grid.iter().map(|&y| normal_pdf(y)).collect()
+        } else {
+            let delta = t - t_prev;
+            let scale = (t / delta).sqrt();
+            grid.iter()
+                .map(|&y| {
+                    simpson(&self.grid, |i| {
+                        let z = self.grid[i];
+                        self.density[i]
+                            * scale
+                            * normal_pdf((y * t.sqrt() - z * t_prev.sqrt()) / delta.sqrt())
+                    })
+                })
+                .collect()
+        };
+        self.grid = grid;
+        self.density = density;
+        self.looks.push((t, c));
+        Ok(c)
+    }
+
+    ///
+    /// Information fractions and critical values of the looks registered so far
+    ///
+    pub fn looks(&self) -> &[(f64, f64)] {
+        &self.looks
+    }
+
+    ///
+    /// Repeated confidence interval at the last look, for an estimate with the given standard error
+    /// (e.g., the difference of two means and its standard error)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If no look has been registered
+    /// * [`CIError::InvalidInputData`] - If the standard error is negative or not finite
+    ///
+    pub fn ci(&self, estimate: f64, std_err: f64) -> CIResult<Interval<f64>> {
+        let Some(&(_, c)) = self.looks.last() else {
+            return Err(CIError::TooFewSamples(0));
+        };
+        if !(std_err.is_finite() && std_err >= 0.) {
+            return Err(CIError::InvalidInputData);
+        }
+        let (lo, hi) = (estimate - c * std_err, estimate + c * std_err);
+        match self.confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    ///
+    /// Critical values of a design with looks at the given information fractions
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - If the confidence level is not in (0, 1)
+    /// * [`CIError::InvalidInformationFraction`] - If the information fractions are not increasing in (0, 1]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// use sequential::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let c = GroupSequential::critical_values(confidence, SpendingFunction::Pocock, &[0.5, 1.])?;
+    /// assert_abs_diff_eq!(c[0], 2.157, epsilon = 1e-3);
+    /// assert_abs_diff_eq!(c[1], 2.201, epsilon = 1e-3);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn critical_values(
+        confidence: Confidence,
+        spending: SpendingFunction,
+        information_fractions: &[f64],
+    ) -> CIResult<Vec<f64>> {
+        let mut design = Self::new(confidence, spending);
+        information_fractions
+            .iter()
+            .map(|&t| design.next_look(t))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2. * core::f64::consts::PI).sqrt()
+}
+
+///
+/// Simpson's rule over an evenly spaced grid with an even number of intervals
+///
+#[cfg(any(test, feature = "std"))]
+fn simpson<G: Fn(usize) -> f64>(grid: &[f64], f: G) -> f64 {
+    let m = grid.len() - 1;
+    let h = (grid[m] - grid[0]) / m as f64;
+    let sum = (0..=m)
+        .map(|i| {
+            let weight = if i == 0 || i == m {
+                1.
+            } else if i % 2 == 1 {
+                4.
+            } else {
+                2.
+            };
+            weight * f(i)
+        })
+        .sum::<f64>();
+    sum * h / 3.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_sequential_mean() -> CIResult<()> {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // relative precision
+        let mut seq = SequentialMean::new(confidence, Precision::Relative(0.02));
+        let met = seq.run(100_000, || 100. + rng.gen_range(-20. ..20.))?;
+        assert!(met);
+        let mean = seq.mean();
+        assert!(seq.half_width()? <= 0.02 / 1.02 * mean);
+        let ci = seq.ci()?;
+        assert!(ci.high_f() - ci.low_f() <= 2. * 0.02 * mean);
+        assert!(seq.n() > 10);
+
+        // absolute precision
+        let mut seq =
+            SequentialMean::new(confidence, Precision::Absolute(0.1)).with_min_samples(30);
+        assert!(!seq.is_done()?);
+        assert!(matches!(seq.half_width(), Err(CIError::TooFewSamples(0))));
+        let met = seq.run(100_000, || rng.gen::<f64>())?;
+        assert!(met);
+        assert!(seq.half_width()? <= 0.1);
+        assert!(seq.n() >= 30);
+        // one-sided intervals: distance between the mean and the bound
+        let upper = Confidence::new_upper(0.95);
+        let mut seq_upper = SequentialMean::new(upper, Precision::Absolute(0.1));
+        seq_upper.run(100_000, || rng.gen::<f64>())?;
+        assert!(seq_upper.half_width()? <= 0.1);
+        assert!(seq_upper.mean() - seq_upper.ci()?.low_f() <= 0.1);
+
+        // too many samples needed
+        let mut seq = SequentialMean::new(confidence, Precision::Relative(1e-6));
+        assert!(!seq.run(1000, || rng.gen::<f64>())?);
+        assert_eq!(seq.n(), 1000);
+
+        // samples are validated
+        let mut seq = SequentialMean::new(confidence, Precision::Absolute(1.));
+        assert!(matches!(seq.push(f64::NAN), Err(CIError::InvalidInputData)));
+        assert_eq!(seq.n(), 0);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sequential_mean_invalid_precision() {
+        let _ = SequentialMean::new(Confidence::new_two_sided(0.95), Precision::Relative(0.));
+    }
+
+    #[test]
+    fn test_confidence_sequence_mean() -> CIResult<()> {
+        // reference values computed in python
+        let data = [12., 10., 14., 11., 9., 13., 12., 10., 11., 12.];
+        let mut cs = ConfidenceSequenceMean::new(Confidence::new_two_sided(0.95), 1000);
+        assert!(matches!(cs.ci(), Err(CIError::TooFewSamples(0))));
+        cs.extend(&data)?;
+        assert_eq!(cs.n(), 10);
+        assert_abs_diff_eq!(
+            cs.ci()?,
+            Interval::new(7.074970295381342, 15.72502970461866)?,
+            epsilon = 1e-10
+        );
+        let mut cs = ConfidenceSequenceMean::new(Confidence::new_upper(0.9), 50);
+        cs.extend(&data)?;
+        assert_abs_diff_eq!(
+            cs.ci()?,
+            Interval::new_upper(10.081241324077112),
+            epsilon = 1e-10
+        );
+
+        // wider than the fixed-sample interval, but shrinks as samples accumulate
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut cs = ConfidenceSequenceMean::new(confidence, 1000);
+        cs.extend(&(0..100).map(|_| rng.gen::<f64>()).collect::<Vec<_>>())?;
+        let ci_100 = cs.ci()?;
+        assert!(ci_100.includes(&cs.accumulator().ci(confidence)?));
+        cs.extend(&(0..900).map(|_| rng.gen::<f64>()).collect::<Vec<_>>())?;
+        let ci_1000 = cs.ci()?;
+        assert!(ci_1000.width().unwrap() < ci_100.width().unwrap());
+        assert!(ci_1000.contains(&0.5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_confidence_sequence_proportion() -> CIResult<()> {
+        // reference values computed in python
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut cs = ConfidenceSequenceProportion::new(confidence, 10_000);
+        assert!(matches!(cs.ci(), Err(CIError::TooFewSamples(0))));
+        for i in 0..5000 {
+            cs.push(i % 10 < 3);
+        }
+        assert_abs_diff_eq!(
+            cs.ci()?,
+            Interval::new(0.27819587651815453, 0.32180412348184545)?,
+            epsilon = 1e-10
+        );
+        let mut cs = ConfidenceSequenceProportion::new(Confidence::new_lower(0.99), 100);
+        cs.extend(&(0..200).map(|i| i % 10 == 0).collect::<Vec<_>>());
+        assert_abs_diff_eq!(
+            cs.ci()?,
+            Interval::new(0., 0.2198461088793338)?,
+            epsilon = 1e-10
+        );
+        assert_eq!(cs.stats().successes(), 20);
+
+        // the sequence covers the true proportion at all times simultaneously,
+        // in (at least) 90% of the streams
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let confidence = Confidence::new_two_sided(0.9);
+        let p = 0.3;
+        let streams = 200;
+        let mut failures = 0;
+        for _ in 0..streams {
+            let mut cs = ConfidenceSequenceProportion::new(confidence, 500);
+            for _ in 0..2000 {
+                cs.push(rng.gen::<f64>() < p);
+                if !cs.ci()?.contains(&p) {
+                    failures += 1;
+                    break;
+                }
+            }
+        }
+        assert!(failures <= streams / 10, "failures: {}", failures);
+        Ok(())
+    }
+
+    #[test]
+    fn test_proportion_comparison() -> CIResult<()> {
+        // reference values computed in python
+        let mut test = ProportionComparison::new(Confidence::new_two_sided(0.95), 0.02);
+        assert!(matches!(test.ci(), Err(CIError::TooFewSamples(0))));
+        assert_eq!(test.p_value(), 1.);
+        for i in 0..300 {
+            test.push_a(i % 100 < 10);
+            test.push_b(i % 100 < 13);
+        }
+        assert!(!test.is_significant());
+        assert_abs_diff_eq!(test.p_value(), 0.9632827372688387, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            test.ci()?,
+            Interval::new(-0.07833804442096871, 0.1384787820894183)?,
+            epsilon = 1e-10
+        );
+        for i in 300..5000 {
+            test.push_a(i % 100 < 10);
+            test.push_b(i % 100 < 13);
+        }
+        assert!(test.is_significant());
+        assert_abs_diff_eq!(test.lift(), 0.03, epsilon = 1e-10);
+        assert_abs_diff_eq!(test.p_value(), 0.00013801998280416948, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            test.ci()?,
+            Interval::new(0.010880077760913546, 0.049358624860353625)?,
+            epsilon = 1e-10
+        );
+
+        let mut test = ProportionComparison::new(Confidence::new_upper(0.9), 0.05);
+        for i in 0..1000 {
+            test.push_a(i % 7 < 2);
+            test.push_b(i % 5 < 2);
+        }
+        assert_abs_diff_eq!(test.p_value(), 8.93284544527611e-06, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            test.ci()?,
+            Interval::new(0.05680599381510724, 1.)?,
+            epsilon = 1e-10
+        );
+
+        // A/A tests monitored after every observation: the false positive rate stays below alpha
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let runs = 200;
+        let mut false_positives = 0;
+        for _ in 0..runs {
+            let mut test = ProportionComparison::new(Confidence::new_two_sided(0.9), 0.05);
+            for _ in 0..2000 {
+                test.push_a(rng.gen::<f64>() < 0.2);
+                test.push_b(rng.gen::<f64>() < 0.2);
+                if test.is_significant() {
+                    false_positives += 1;
+                    break;
+                }
+            }
+        }
+        assert!(
+            false_positives <= runs / 10,
+            "false positives: {}",
+            false_positives
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_sequential() -> CIResult<()> {
+        // reference values from Lan and DeMets (e.g., R package ldbounds)
+        let confidence = Confidence::new_two_sided(0.95);
+        let fractions = [0.2, 0.4, 0.6, 0.8, 1.];
+        let c = GroupSequential::critical_values(
+            confidence,
+            SpendingFunction::OBrienFleming,
+            &fractions,
+        )?;
+        for (c, expected) in c.iter().zip([4.8769, 3.3569, 2.6803, 2.2898, 2.0310]) {
+            assert_abs_diff_eq!(*c, expected, epsilon = 1e-3);
+        }
+        let c = GroupSequential::critical_values(confidence, SpendingFunction::Pocock, &fractions)?;
+        for (c, expected) in c.iter().zip([2.4380, 2.4268, 2.4101, 2.3966, 2.3859]) {
+            assert_abs_diff_eq!(*c, expected, epsilon = 1e-3);
+        }
+        // a single look at the end is a fixed-sample analysis
+        let c = GroupSequential::critical_values(confidence, SpendingFunction::Pocock, &[1.])?;
+        assert_abs_diff_eq!(c[0], 1.959964, epsilon = 1e-5);
+        let c = GroupSequential::critical_values(
+            Confidence::new_upper(0.975),
+            SpendingFunction::OBrienFleming,
+            &fractions,
+        )?;
+        for (c, expected) in c.iter().zip([4.8769, 3.3569, 2.6803, 2.2898, 2.0310]) {
+            assert_abs_diff_eq!(*c, expected, epsilon = 1e-3);
+        }
+
+        let mut design = GroupSequential::new(confidence, SpendingFunction::OBrienFleming);
+        assert!(matches!(design.ci(0., 1.), Err(CIError::TooFewSamples(0))));
+        design.next_look(0.5)?;
+        assert!(matches!(
+            design.next_look(0.5),
+            Err(CIError::InvalidInformationFraction(_))
+        ));
+        assert!(matches!(
+            design.next_look(1.5),
+            Err(CIError::InvalidInformationFraction(_))
+        ));
+        assert_eq!(design.looks().len(), 1);
+        let c = design.looks()[0].1;
+        assert_abs_diff_eq!(
+            design.ci(1., 0.1)?,
+            Interval::new(1. - c * 0.1, 1. + c * 0.1)?
+        );
+        Ok(())
+    }
+}
@@ -1,38 +1,50 @@
 use crate::*;
+use error::*;
 
-use lazy_static::lazy_static;
-use statrs::distribution::ContinuousCDF;
-use statrs::distribution::{Normal, StudentsT};
+use distributions::{check_degrees_of_freedom, t_value, z_value};
+#[cfg(feature = "native-float")]
+use distributions::{t_value_as, z_value_as};
+use num_traits::Float;
 
 ///
-/// return the z-value of the normal distribution for a given confidence level.
+/// return the cumulative distribution function of the binomial distribution, i.e., the probability
+/// of observing at most `k` successes out of `n` trials with a probability of success `p`.
 ///
 /// # Arguments
 ///
-/// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+/// * `k` - the number of successes
+/// * `n` - the number of trials
+/// * `p` - the probability of success of each trial (must be in [0, 1])
 ///
-pub fn z_value(confidence: Confidence) -> f64 {
-    lazy_static! {
-        static ref NORMAL: Normal = Normal::new(0., 1.).unwrap();
-    }
-    NORMAL.inverse_cdf(confidence.quantile())
+/// # Panics
+///
+/// * if `p` is not in [0, 1]
+///
+#[cfg(feature = "statrs")]
+pub fn binomial_cdf(k: u64, n: u64, p: f64) -> f64 {
+    use statrs::distribution::{Binomial, DiscreteCDF};
+    let binomial = Binomial::new(p, n).unwrap();
+    binomial.cdf(k)
 }
 
 ///
-/// return the t-value of the t-distribution for a given confidence level and degree of freedom.
+/// return the cumulative distribution function of the binomial distribution, i.e., the probability
+/// of observing at most `k` successes out of `n` trials with a probability of success `p`.
 ///
 /// # Arguments
 ///
-/// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
-/// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
+/// * `k` - the number of successes
+/// * `n` - the number of trials
+/// * `p` - the probability of success of each trial (must be in [0, 1])
 ///
 /// # Panics
 ///
-/// * if `degrees_of_freedom` is negative or zero
+/// * if `p` is not in [0, 1]
 ///
-pub fn t_value(confidence: Confidence, degrees_of_freedom: f64) -> f64 {
-    let student_t = StudentsT::new(0., 1., degrees_of_freedom).unwrap();
-    student_t.inverse_cdf(confidence.quantile())
+#[cfg(not(feature = "statrs"))]
+pub fn binomial_cdf(k: u64, n: u64, p: f64) -> f64 {
+    assert!((0. ..=1.).contains(&p), "probability must be in [0, 1]");
+    special::binomial_cdf(k, n, p)
 }
 
 const POPULATION_LIMIT: f64 = 100_000.;
@@ -42,15 +54,81 @@ pub(crate) fn interval_bounds(
     mean: f64,
     std_err_mean: f64,
     degrees_of_freedom: f64,
-) -> (f64, f64) {
+) -> CIResult<(f64, f64)> {
+    check_degrees_of_freedom(degrees_of_freedom)?;
     let span = if degrees_of_freedom < POPULATION_LIMIT {
-        let t = t_value(confidence, degrees_of_freedom);
+        let t = t_value(confidence, degrees_of_freedom)?;
         t * std_err_mean
     } else {
-        let z = z_value(confidence);
+        let z = z_value(confidence)?;
         z * std_err_mean
     };
-    (mean - span, mean + span)
+    Ok((mean - span, mean + span))
+}
+
+///
+/// Return the bounds of the confidence interval on a mean with the given standard error, in the float type `F`
+/// of the samples.
+///
+/// With the feature `native-float`, the computation (including the critical value) is carried out in `F`;
+/// otherwise, the values are converted to `f64` and back (see [`interval_bounds`]).
+///
+#[cfg(feature = "native-float")]
+pub(crate) fn float_interval_bounds<F: Float>(
+    confidence: Confidence,
+    mean: F,
+    std_err_mean: F,
+    degrees_of_freedom: F,
+) -> CIResult<(F, F)> {
+    let span = if degrees_of_freedom < F::from(POPULATION_LIMIT).convert("limit")? {
+        t_value_as(confidence, degrees_of_freedom)? * std_err_mean
+    } else {
+        check_degrees_of_freedom(degrees_of_freedom)?;
+        z_value_as::<F>(confidence)? * std_err_mean
+    };
+    Ok((mean - span, mean + span))
+}
+
+///
+/// Return the bounds of the confidence interval on a mean with the given standard error, in the float type `F`
+/// of the samples.
+///
+/// With the feature `native-float`, the computation (including the critical value) is carried out in `F`;
+/// otherwise, the values are converted to `f64` and back (see [`interval_bounds`]).
+///
+#[cfg(not(feature = "native-float"))]
+pub(crate) fn float_interval_bounds<F: Float>(
+    confidence: Confidence,
+    mean: F,
+    std_err_mean: F,
+    degrees_of_freedom: F,
+) -> CIResult<(F, F)> {
+    let (lo, hi) = interval_bounds(
+        confidence,
+        mean.try_f64("mean")?,
+        std_err_mean.try_f64("std_err_mean")?,
+        degrees_of_freedom.try_f64("degrees_of_freedom")?,
+    )?;
+    Ok((F::from(lo).convert("lo")?, F::from(hi).convert("hi")?))
+}
+
+///
+/// Return the probability \\( P(T \geq t) \\) of the upper tail of the distribution of the t statistic, consistently with
+/// [`interval_bounds`], i.e., using the Student t-distribution up to `POPULATION_LIMIT` degrees of freedom, and the
+/// normal distribution above.
+///
+pub(crate) fn t_upper_tail(statistic: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    if degrees_of_freedom < POPULATION_LIMIT {
+        let half_tails = special::t_tails(statistic, degrees_of_freedom) / 2.;
+        Ok(if statistic >= 0. {
+            half_tails
+        } else {
+            1. - half_tails
+        })
+    } else {
+        Ok(special::normal_cdf(-statistic))
+    }
 }
 
 #[cfg(test)]
@@ -59,18 +137,12 @@ mod tests {
     use approx::*;
 
     #[test]
-    fn test_t_and_z_value() {
-        for confidence_level in [0.5, 0.8, 0.9, 0.95, 0.99, 0.999] {
-            for new_confidence in [
-                Confidence::new_two_sided,
-                Confidence::new_upper,
-                Confidence::new_lower,
-            ] {
-                let confidence = new_confidence(confidence_level);
-                let t_value = t_value(confidence, 1000.);
-                let z_value = z_value(confidence);
-                assert_abs_diff_eq!(t_value, z_value, epsilon = 1e-2);
-            }
+    fn test_invalid_arguments() {
+        for degrees_of_freedom in [0., -1., f64::NAN] {
+            assert!(matches!(
+                interval_bounds(Confidence::new(0.95), 0., 1., degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
         }
     }
 
@@ -87,7 +159,8 @@ mod tests {
             POPULATION_LIMIT + 2.,
         ] {
             let degrees_of_freedom = n - 1.;
-            let actual = interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom);
+            let actual =
+                interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom).unwrap();
             let bounds_t = only_t(confidence, mean, std_err_mean, degrees_of_freedom);
             let bounds_z = only_z(confidence, mean, std_err_mean);
 
@@ -104,18 +177,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_float_interval_bounds() -> CIResult<()> {
+        for confidence in [Confidence::new_two_sided(0.95), Confidence::new_upper(0.99)] {
+            for degrees_of_freedom in [1., 9., 99., POPULATION_LIMIT + 1.] {
+                let (lo, hi) = interval_bounds(confidence, 10., 0.5, degrees_of_freedom)?;
+                let (lo32, hi32) =
+                    float_interval_bounds(confidence, 10_f32, 0.5, degrees_of_freedom as f32)?;
+                assert_relative_eq!(lo32 as f64, lo, max_relative = 1e-6);
+                assert_relative_eq!(hi32 as f64, hi, max_relative = 1e-6);
+                let (lo64, hi64) = float_interval_bounds(confidence, 10., 0.5, degrees_of_freedom)?;
+                assert_relative_eq!(lo64, lo, max_relative = 1e-12);
+                assert_relative_eq!(hi64, hi, max_relative = 1e-12);
+            }
+        }
+        assert!(matches!(
+            float_interval_bounds(Confidence::new(0.95), 0_f32, 1., f32::NAN),
+            Err(CIError::InvalidDegreesOfFreedom(_))
+        ));
+        Ok(())
+    }
+
     fn only_t(
         confidence: Confidence,
         mean: f64,
         std_err_mean: f64,
         degrees_of_freedom: f64,
     ) -> (f64, f64) {
-        let t = t_value(confidence, degrees_of_freedom);
+        let t = t_value(confidence, degrees_of_freedom).unwrap();
         let span = t * std_err_mean;
         (mean - span, mean + span)
     }
     fn only_z(confidence: Confidence, mean: f64, std_err_mean: f64) -> (f64, f64) {
-        let z = z_value(confidence);
+        let z = z_value(confidence).unwrap();
         let span = z * std_err_mean;
         (mean - span, mean + span)
     }
@@ -0,0 +1,1320 @@
+//!
+//! Confidence intervals for survey sampling designs
+//!
+//! The other modules of this crate assume that the samples are drawn independently from a single population
+//! (simple random sampling). This module provides estimators for more complex sampling designs,
+//! where ignoring the design leads to biased estimates or to intervals that are too narrow.
+//!
+//! * [`Stratified`] - stratified sampling, where the population is partitioned into strata (e.g., machine classes)
+//!   of known relative sizes, and each stratum is sampled independently.
+//! * [`Clustered`] and [`ci_proportion_clustered`] - one-stage cluster sampling, where whole groups of units (e.g., all the requests of a user)
+//!   are sampled together, so that units of the same cluster are correlated.
+//! * [`Ratio`] - ratio estimator, using an auxiliary variable with a known population total (e.g., the number of requests)
+//!   to estimate the total of a correlated variable (e.g., CPU time).
+//! * [`HorvitzThompson`] - unequal-probability sampling, where each unit is sampled with its own known probability
+//!   (e.g., trace events sampled at different rates).
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // latencies measured on three machine classes, representing 50%, 30%, and 20% of the fleet
+//! let small = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+//! let medium = [20.2, 21.9, 19.8, 22.4, 20.7, 21.1];
+//! let large = [35.0, 41.2, 38.7, 36.9, 44.1, 39.5, 37.2, 40.8, 42.6, 36.1];
+//!
+//! let mut stats = survey::Stratified::new();
+//! stats.add_stratum(0.5, &small)?;
+//! stats.add_stratum(0.3, &medium)?;
+//! stats.add_stratum(0.2, &large)?;
+//! let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+//! assert_abs_diff_eq!(stats.sample_mean(), 20.9845, epsilon = 1e-10);
+//! assert_abs_diff_eq!(ci, Interval::new(20.250643, 21.718357)?, epsilon = 1e-6);
+//! // the pooled mean over-represents the large machines, which were over-sampled
+//! let pooled = mean::Arithmetic::from_iter(&[&small[..], &medium[..], &large[..]].concat())?;
+//! assert!(!ci.contains(&pooled.sample_mean()));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Represents the state of the computation of the mean of a population under stratified sampling.
+///
+/// The population is partitioned into strata of known relative sizes (weights) \\( W_h \\), normalized
+/// so that they sum to one, and each stratum is sampled independently. The stratified estimator of the mean is
+/// \\[
+/// \bar{y}_{st} = \sum_h W_h \bar{y}_h
+/// \\]
+/// with variance \\( \sum_h W_h^2 s_h^2 / n_h \\), where \\( \bar{y}_h \\), \\( s_h^2 \\), and \\( n_h \\) are
+/// the mean, variance, and number of samples of stratum \\( h \\).
+/// The interval uses the Student t distribution with the Satterthwaite approximation of the degrees of freedom:
+/// \\[
+/// \nu = \frac{\left(\sum_h a_h s_h^2\right)^2}{\sum_h (a_h s_h^2)^2 / (n_h - 1)}
+/// \qquad \text{where } a_h = W_h^2 / n_h
+/// \\]
+///
+/// # Notes
+///
+/// The strata are assumed to be large compared to the samples (i.e., no finite population correction).
+/// Each stratum must have at least two samples to estimate its variance.
+///
+/// # References
+///
+/// * W.G. Cochran. Sampling Techniques (3rd edition), Chapter 5. Wiley, 1977.
+/// * [Wikipedia - Stratified sampling](https://en.wikipedia.org/wiki/Stratified_sampling)
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stratified<F: Float> {
+    strata: Vec<(F, mean::Arithmetic<F>)>,
+}
+
+impl<F: Float> Stratified<F> {
+    ///
+    /// Create a new state without any stratum
+    ///
+    pub fn new() -> Self {
+        Self { strata: Vec::new() }
+    }
+
+    ///
+    /// Add a stratum from its weight and its samples
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - the relative size of the stratum in the population (e.g., its share or its number of units)
+    /// * `data` - the samples of the stratum
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the weight is not strictly positive and finite
+    /// * [`CIError::TooFewSamples`] - If the stratum has less than two samples
+    ///
+    pub fn add_stratum<I>(&mut self, weight: F, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        self.add_stratum_stats(weight, mean::Arithmetic::from_iter(data)?)
+    }
+
+    ///
+    /// Add a stratum from its weight and the statistics of its samples
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - the relative size of the stratum in the population (e.g., its share or its number of units)
+    /// * `stats` - the statistics of the samples of the stratum
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the weight is not strictly positive and finite
+    /// * [`CIError::TooFewSamples`] - If the stratum has less than two samples
+    ///
+    pub fn add_stratum_stats(&mut self, weight: F, stats: mean::Arithmetic<F>) -> CIResult<()> {
+        if !weight.is_finite() || weight <= F::zero() {
+            return Err(CIError::InvalidInputData);
+        }
+        if stats.sample_count() < 2 {
+            return Err(CIError::TooFewSamples(stats.sample_count()));
+        }
+        self.strata.push((weight, stats));
+        Ok(())
+    }
+
+    ///
+    /// Number of strata
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn strata_count(&self) -> usize {
+        self.strata.len()
+    }
+
+    ///
+    /// Total number of samples over all strata
+    ///
+    /// Complexity: \\( O(h) \\) where \\( h \\) is the number of strata
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.strata
+            .iter()
+            .map(|(_, stats)| stats.sample_count())
+            .sum()
+    }
+
+    ///
+    /// Stratified estimate of the mean of the population
+    /// (NaN if there is no stratum)
+    ///
+    /// Complexity: \\( O(h) \\) where \\( h \\) is the number of strata
+    ///
+    pub fn sample_mean(&self) -> F {
+        let total = self.total_weight();
+        self.strata.iter().fold(F::zero(), |acc, (weight, stats)| {
+            acc + *weight / total * stats.sample_mean()
+        })
+    }
+
+    ///
+    /// Standard error of the stratified estimate of the mean
+    /// (NaN if there is no stratum)
+    ///
+    /// Complexity: \\( O(h) \\) where \\( h \\) is the number of strata
+    ///
+    pub fn sample_sem(&self) -> F {
+        self.variance_terms()
+            .fold(F::zero(), |acc, (term, _)| acc + term)
+            .sqrt()
+    }
+
+    ///
+    /// Satterthwaite approximation of the degrees of freedom of the stratified estimate of the mean
+    /// (NaN if there is no stratum)
+    ///
+    /// Complexity: \\( O(h) \\) where \\( h \\) is the number of strata
+    ///
+    pub fn degrees_of_freedom(&self) -> F {
+        let (sum, sum_sq) = self
+            .variance_terms()
+            .fold((F::zero(), F::zero()), |(sum, sum_sq), (term, dof)| {
+                (sum + term, sum_sq + term * term / dof)
+            });
+        sum * sum / sum_sq
+    }
+
+    ///
+    /// Confidence interval of the stratified estimate of the mean
+    ///
+    /// Complexity: \\( O(h) \\) where \\( h \\) is the number of strata
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there is no stratum
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        if self.strata.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let mean = self.sample_mean();
+        let std_err_mean = self.sample_sem();
+        let (lo, hi) = if std_err_mean > F::zero() {
+            stats::float_interval_bounds(confidence, mean, std_err_mean, self.degrees_of_freedom())?
+        } else {
+            // all strata are constant
+            (mean, mean)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
+    fn total_weight(&self) -> F {
+        self.strata
+            .iter()
+            .fold(F::zero(), |acc, (weight, _)| acc + *weight)
+    }
+
+    ///
+    /// Iterate over the contributions \\( W_h^2 s_h^2 / n_h \\) of the strata to the variance of the estimate,
+    /// with their degrees of freedom \\( n_h - 1 \\).
+    ///
+    fn variance_terms(&self) -> impl Iterator<Item = (F, F)> + '_ {
+        let total = self.total_weight();
+        self.strata.iter().map(move |(weight, stats)| {
+            let share = *weight / total;
+            let n = F::from(stats.sample_count()).unwrap();
+            (share * share * stats.sample_variance() / n, n - F::one())
+        })
+    }
+}
+
+///
+/// Represents the state of the computation of the mean of a population under one-stage cluster sampling.
+///
+/// Clusters (e.g., users) are sampled at random and all the units of each sampled cluster (e.g., the requests of the user) are observed.
+/// Units of the same cluster tend to be correlated, so that treating them as independent samples leads to intervals that are too narrow.
+///
+/// With \\( k \\) clusters, where cluster \\( i \\) has \\( m_i \\) units with total \\( y_i \\), the mean is estimated by
+/// the ratio \\( \bar{y} = \sum_i y_i / \sum_i m_i \\) with the linearized variance
+/// \\[
+/// v(\bar{y}) = \frac{k}{k-1} \frac{\sum_i (y_i - \bar{y} m_i)^2}{\left(\sum_i m_i\right)^2}
+/// \\]
+/// and the interval uses the Student t distribution with \\( k - 1 \\) degrees of freedom.
+/// The design effect is the ratio of this variance to the variance \\( s^2 / n \\) that simple random sampling of the \\( n \\) units would have.
+///
+/// # Notes
+///
+/// The clusters are assumed to be drawn from a large population of clusters (i.e., no finite population correction).
+/// At least two clusters are needed to estimate the variance.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // latencies of the requests of six users
+/// let users = [
+///     vec![120., 135., 128., 140.],
+///     vec![95., 102., 99.],
+///     vec![210., 198., 225., 205., 215.],
+///     vec![150., 145.],
+///     vec![88., 92., 85., 90.],
+///     vec![175., 180., 170.],
+/// ];
+/// let mut stats = survey::Clustered::new();
+/// for requests in &users {
+///     stats.add_cluster(requests)?;
+/// }
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = stats.ci_mean(confidence)?;
+/// assert_abs_diff_eq!(ci, Interval::new(86.825638, 203.364839)?, epsilon = 1e-6);
+/// // the requests of a user are far from independent
+/// assert_abs_diff_eq!(stats.design_effect(), 4.806896, epsilon = 1e-6);
+/// let naive = mean::Arithmetic::ci(confidence, &users.concat())?;
+/// assert!(naive.width() < ci.width());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.G. Cochran. Sampling Techniques (3rd edition), Chapter 9. Wiley, 1977.
+/// * [Wikipedia - Design effect](https://en.wikipedia.org/wiki/Design_effect)
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clustered<F: Float> {
+    clusters: Vec<(F, F)>,
+    units: mean::Arithmetic<F>,
+}
+
+impl<F: Float> Clustered<F> {
+    ///
+    /// Create a new state without any cluster
+    ///
+    pub fn new() -> Self {
+        Self {
+            clusters: Vec::new(),
+            units: mean::Arithmetic::new(),
+        }
+    }
+
+    ///
+    /// Add a cluster from the values of all its units
+    ///
+    /// Complexity: \\( O(m) \\) where \\( m \\) is the number of units in the cluster
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the cluster is empty
+    /// * [`CIError::InvalidInputData`] - If some value is not finite
+    ///
+    pub fn add_cluster<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let units = mean::Arithmetic::from_iter(data)?;
+        let size = units.sample_count();
+        if size == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let size_f = F::from(size).convert("size")?;
+        let total = units.sample_mean() * size_f;
+        if !total.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.clusters.push((size_f, total));
+        self.units += units;
+        Ok(())
+    }
+
+    ///
+    /// Number of clusters
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn cluster_count(&self) -> usize {
+        self.clusters.len()
+    }
+
+    ///
+    /// Total number of units over all clusters
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.units.sample_count()
+    }
+
+    ///
+    /// Estimate of the mean of the population
+    /// (NaN if there is no cluster)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.units.sample_mean()
+    }
+
+    ///
+    /// Standard error of the mean, accounting for the clustering of the units
+    /// (NaN if there are less than two clusters)
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of clusters
+    ///
+    pub fn sample_sem(&self) -> F {
+        let mean = self.sample_mean();
+        let (k, size, residuals) = self.clusters.iter().fold(
+            (F::zero(), F::zero(), F::zero()),
+            |(k, size, residuals), &(m, y)| {
+                let residual = y - mean * m;
+                (k + F::one(), size + m, residuals + residual * residual)
+            },
+        );
+        (k / (k - F::one()) * residuals).sqrt() / size
+    }
+
+    ///
+    /// Design effect: ratio of the variance of the mean to the variance under simple random sampling of the same number of units
+    /// (NaN if there are less than two clusters)
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of clusters
+    ///
+    pub fn design_effect(&self) -> F {
+        let sem = self.sample_sem();
+        sem * sem / (self.units.sample_variance() / F::from(self.sample_count()).unwrap())
+    }
+
+    ///
+    /// Effective sample size: number of independent samples that would give the same precision
+    /// (NaN if there are less than two clusters)
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of clusters
+    ///
+    pub fn effective_sample_size(&self) -> F {
+        F::from(self.sample_count()).unwrap() / self.design_effect()
+    }
+
+    ///
+    /// Confidence interval of the mean, accounting for the clustering of the units
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of clusters
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two clusters
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let clusters = self.cluster_count();
+        if clusters < 2 {
+            return Err(CIError::TooFewSamples(clusters));
+        }
+        let mean = self.sample_mean();
+        let std_err_mean = self.sample_sem();
+        let degrees_of_freedom = F::from(clusters - 1).convert("clusters")?;
+        let (lo, hi) = if std_err_mean > F::zero() {
+            stats::float_interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom)?
+        } else {
+            (mean, mean)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+///
+/// Computes the confidence interval of a proportion under one-stage cluster sampling.
+///
+/// The design effect \\( D \\) is estimated from the variation of the proportion between clusters (see [`Clustered`]),
+/// and the Wilson score interval (see [`proportion::ci_wilson`]) is computed with the effective sample size \\( n / D \\)
+/// instead of the number of units \\( n \\).
+///
+/// Complexity: \\( O(k) \\) where \\( k \\) is the number of clusters
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `clusters` - the clusters given as pairs `(successes, units)`
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - If there are less than two clusters
+/// * [`CIError::InvalidSuccesses`] - If a cluster has more successes than units
+/// * [`CIError::TooFewSuccesses`] - If there are less than two successes overall
+/// * [`CIError::TooFewFailures`] - If there are less than two failures overall
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // number of failed requests, and total number of requests, for eight users
+/// let users = [(3, 10), (7, 12), (1, 8), (9, 11), (4, 9), (6, 10), (2, 7), (8, 10)];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = survey::ci_proportion_clustered(confidence, &users)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.359794, 0.675284)?, epsilon = 1e-6);
+/// // ignoring the clustering gives a much narrower interval
+/// let naive = proportion::ci(confidence, 77, 40)?;
+/// assert!(naive.width() < ci.width());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * L. Kish. Survey Sampling. Wiley, 1965.
+/// * [Wikipedia - Design effect](https://en.wikipedia.org/wiki/Design_effect)
+///
+pub fn ci_proportion_clustered<I>(confidence: Confidence, clusters: &I) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a (usize, usize)>,
+{
+    let mut count = 0;
+    let mut successes = 0;
+    let mut population = 0;
+    for &(s, m) in clusters {
+        if s > m {
+            return Err(CIError::InvalidSuccesses(s, m));
+        }
+        count += 1;
+        successes += s;
+        population += m;
+    }
+    if count < 2 {
+        return Err(CIError::TooFewSamples(count));
+    }
+    let n = population as f64;
+    let n_s = successes as f64;
+    if successes < 2 {
+        return Err(CIError::TooFewSuccesses(successes, population, n_s));
+    }
+    if population - successes < 2 {
+        return Err(CIError::TooFewFailures(
+            population - successes,
+            population,
+            n - n_s,
+        ));
+    }
+
+    let p = n_s / n;
+    let k = count as f64;
+    let residuals = clusters
+        .into_iter()
+        .map(|&(s, m)| s as f64 - p * m as f64)
+        .fold(0., |acc, r| acc + r * r);
+    let variance = k / (k - 1.) * residuals / (n * n);
+    let srs_variance = p * (1. - p) / (n - 1.);
+    let n_eff = n / (variance / srs_variance);
+
+    let z = distributions::z_value(confidence)?;
+    let z_sq = z * z;
+    let mean = (p + z_sq / (2. * n_eff)) / (1. + z_sq / n_eff);
+    let span =
+        z / (1. + z_sq / n_eff) * (p * (1. - p) / n_eff + z_sq / (4. * n_eff * n_eff)).sqrt();
+
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(mean - span, mean + span).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Interval::new(mean - span, 1.).map_err(|e| e.into()),
+        Confidence::LowerOneSided(_) => Interval::new(0., mean + span).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Represents the state of the computation of the ratio estimator.
+///
+/// Each sampled unit has a value \\( y_i \\) of the variable of interest and a value \\( x_i \\) of an auxiliary variable
+/// whose population total \\( X \\) (or mean \\( \bar{X} \\)) is known. The ratio \\( R = \bar{y} / \bar{x} \\) is
+/// estimated from the sample, and the total of \\( y \\) is estimated by \\( \hat{Y} = R X \\).
+/// When \\( y \\) is roughly proportional to \\( x \\), this is much more precise than estimating the total of \\( y \\) directly.
+///
+/// The variance of the ratio is estimated by linearization:
+/// \\[
+/// v(R) = \frac{1}{n \bar{x}^2} \frac{\sum_i (y_i - R x_i)^2}{n - 1}
+/// \\]
+/// and the interval uses the Student t distribution with \\( n - 1 \\) degrees of freedom.
+///
+/// # Notes
+///
+/// The population is assumed to be large compared to the sample (i.e., no finite population correction).
+/// The ratio estimator is biased for small samples; the bias is negligible when the coefficient of variation of \\( \bar{x} \\) is small.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // (CPU seconds, requests) for a sample of servers
+/// let data = [
+///     (52.1, 1020.), (38.4, 760.), (61.0, 1190.), (45.3, 905.),
+///     (70.2, 1400.), (33.9, 650.), (58.7, 1130.), (49.5, 980.),
+/// ];
+/// let stats = survey::Ratio::from_iter(&data)?;
+/// // the whole fleet served 250,000 requests
+/// let total = stats.ci_total(Confidence::new_two_sided(0.95), 250_000.)?;
+/// assert_abs_diff_eq!(total, Interval::new(12567.049783, 12890.324205)?, epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.G. Cochran. Sampling Techniques (3rd edition), Chapter 6. Wiley, 1977.
+/// * [Wikipedia - Ratio estimator](https://en.wikipedia.org/wiki/Ratio_estimator)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ratio<F: Float> {
+    count: usize,
+    mean_y: F,
+    mean_x: F,
+    sum_sq_dev_y: F,
+    sum_sq_dev_x: F,
+    sum_co_dev: F,
+}
+
+impl<F: Float> Default for Ratio<F> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean_y: F::zero(),
+            mean_x: F::zero(),
+            sum_sq_dev_y: F::zero(),
+            sum_sq_dev_x: F::zero(),
+            sum_co_dev: F::zero(),
+        }
+    }
+}
+
+impl<F: Float> Ratio<F> {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Create a new state from `(y, x)` pairs, where `y` is the variable of interest and `x` the auxiliary variable
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append a new unit with its value `y` of the variable of interest and its value `x` of the auxiliary variable
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite; the state is then left unchanged
+    ///
+    pub fn append(&mut self, y: F, x: F) -> CIResult<()> {
+        if !y.is_finite() || !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        self.count += 1;
+        let n = F::from(self.count).convert("count")?;
+        let delta_y = y - self.mean_y;
+        let delta_x = x - self.mean_x;
+        self.mean_y = self.mean_y + delta_y / n;
+        self.mean_x = self.mean_x + delta_x / n;
+        self.sum_sq_dev_y = self.sum_sq_dev_y + delta_y * (y - self.mean_y);
+        self.sum_sq_dev_x = self.sum_sq_dev_x + delta_x * (x - self.mean_x);
+        self.sum_co_dev = self.sum_co_dev + delta_x * (y - self.mean_y);
+        Ok(())
+    }
+
+    ///
+    /// Append `(y, x)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite; the pairs before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        for &(y, x) in data {
+            self.append(y, x)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of units
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Estimate of the ratio \\( R = \bar{y} / \bar{x} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_ratio(&self) -> F {
+        self.mean_y / self.mean_x
+    }
+
+    ///
+    /// Standard error of the ratio (linearized)
+    /// (NaN if there are less than two units)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let ratio = self.sample_ratio();
+        let n = F::from(self.count).unwrap();
+        // sum of squares of the residuals y - R x, which have mean zero
+        let residuals = self.sum_sq_dev_y - (ratio + ratio) * self.sum_co_dev
+            + ratio * ratio * self.sum_sq_dev_x;
+        (residuals.max(F::zero()) / (n - F::one()) / n).sqrt() / self.mean_x.abs()
+    }
+
+    ///
+    /// Confidence interval of the ratio \\( R = \bar{y} / \bar{x} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two units
+    /// * [`CIError::InvalidInputData`] - If the mean of the auxiliary variable is zero
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_ratio(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_scaled(confidence, F::one())
+    }
+
+    ///
+    /// Confidence interval of the population total of the variable of interest \\( \hat{Y} = R X \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `x_total` - the known population total of the auxiliary variable (must be positive)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two units
+    /// * [`CIError::InvalidInputData`] - If the mean of the auxiliary variable is zero, or `x_total` is not positive and finite
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_total(&self, confidence: Confidence, x_total: F) -> CIResult<Interval<F>> {
+        self.ci_scaled(confidence, x_total)
+    }
+
+    ///
+    /// Confidence interval of the population mean of the variable of interest \\( \hat{\bar{Y}} = R \bar{X} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `x_mean` - the known population mean of the auxiliary variable (must be positive)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two units
+    /// * [`CIError::InvalidInputData`] - If the mean of the auxiliary variable is zero, or `x_mean` is not positive and finite
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence, x_mean: F) -> CIResult<Interval<F>> {
+        self.ci_scaled(confidence, x_mean)
+    }
+
+    fn ci_scaled(&self, confidence: Confidence, scale: F) -> CIResult<Interval<F>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        if self.mean_x == F::zero() || !scale.is_finite() || scale <= F::zero() {
+            return Err(CIError::InvalidInputData);
+        }
+        let ratio = self.sample_ratio();
+        let std_err = self.sample_sem();
+        let degrees_of_freedom = F::from(self.count - 1).convert("count")?;
+        let (lo, hi) = if std_err > F::zero() {
+            stats::float_interval_bounds(confidence, ratio, std_err, degrees_of_freedom)?
+        } else {
+            (ratio, ratio)
+        };
+        let (lo, hi) = (lo * scale, hi * scale);
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+///
+/// Represents the state of the computation of the Horvitz–Thompson estimator, for samples drawn with unequal probabilities.
+///
+/// Each sampled unit has a value \\( y_i \\) and a known inclusion probability \\( \pi_i \\) (e.g., the sampling rate of a trace event),
+/// and thus represents \\( 1 / \pi_i \\) units of the population. The total of the population is estimated by
+/// \\[
+/// \hat{Y} = \sum_i \frac{y_i}{\pi_i}
+/// \\]
+/// and its mean by the Hájek estimator \\( \hat{\mu} = \hat{Y} / \hat{N} \\), where \\( \hat{N} = \sum_i 1 / \pi_i \\) is
+/// the estimated size of the population.
+///
+/// The variances are approximated as if the units were drawn with replacement:
+/// \\[
+/// v(\hat{Y}) = \frac{n}{n-1} \sum_i \left( \frac{y_i}{\pi_i} - \frac{\hat{Y}}{n} \right)^2
+/// \qquad
+/// v(\hat{\mu}) = \frac{n}{n-1} \frac{1}{\hat{N}^2} \sum_i \left( \frac{y_i - \hat{\mu}}{\pi_i} \right)^2
+/// \\]
+/// and the intervals use the Student t distribution with \\( n - 1 \\) degrees of freedom.
+///
+/// # Notes
+///
+/// The with-replacement approximation of the variance is slightly conservative when the inclusion probabilities are small,
+/// which is typically the case of sampled telemetry.
+/// The Hájek estimator of the mean does not require the size of the population to be known; if it is, the interval on the mean
+/// can also be obtained by dividing the interval on the total by that size.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // (latency, sampling rate) of trace events: fast requests are sampled at 1%, slow ones at 10%, errors always
+/// let events = [
+///     (12., 0.01), (15., 0.01), (11., 0.01), (14., 0.01), (13., 0.01),
+///     (250., 1.), (310., 1.),
+///     (95., 0.1), (120., 0.1), (88., 0.1), (105., 0.1),
+/// ];
+/// let stats = survey::HorvitzThompson::from_iter(&events)?;
+/// assert_abs_diff_eq!(stats.population_size(), 542., epsilon = 1e-10);
+/// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+/// assert_abs_diff_eq!(ci, Interval::new(10.169411, 30.937600)?, epsilon = 1e-6);
+/// // ignoring the sampling rates grossly over-represents the slow requests
+/// let naive = mean::Arithmetic::from_iter(&events.map(|(y, _)| y))?;
+/// assert!(ci.high_f() < naive.sample_mean());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * D.G. Horvitz and D.J. Thompson. A Generalization of Sampling Without Replacement From a Finite Universe.
+///   Journal of the American Statistical Association, 47(260), 1952.
+/// * C.-E. Särndal, B. Swensson, and J. Wretman. Model Assisted Survey Sampling. Springer, 1992.
+/// * [Wikipedia - Horvitz–Thompson estimator](https://en.wikipedia.org/wiki/Horvitz%E2%80%93Thompson_estimator)
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HorvitzThompson<F: Float> {
+    units: Vec<(F, F)>,
+}
+
+impl<F: Float> HorvitzThompson<F> {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Self { units: Vec::new() }
+    }
+
+    ///
+    /// Create a new state from `(value, inclusion probability)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some probability is not in \\( (0, 1] \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        let mut state = Self::new();
+        state.extend(data)?;
+        Ok(state)
+    }
+
+    ///
+    /// Append a new unit with its value and its inclusion probability
+    ///
+    /// Complexity: \\( O(1) \\) amortized
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If the value is not finite, or the probability is not in \\( (0, 1] \\);
+    ///   the state is then left unchanged
+    ///
+    pub fn append(&mut self, y: F, probability: F) -> CIResult<()> {
+        if !y.is_finite()
+            || probability.is_nan()
+            || probability <= F::zero()
+            || probability > F::one()
+        {
+            return Err(CIError::InvalidInputData);
+        }
+        self.units.push((y, probability.recip()));
+        Ok(())
+    }
+
+    ///
+    /// Append `(value, inclusion probability)` pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - If some value is not finite, or some probability is not in \\( (0, 1] \\);
+    ///   the pairs before it have already been appended
+    ///
+    pub fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, F)>,
+    {
+        for &(y, probability) in data {
+            self.append(y, probability)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Number of sampled units
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.units.len()
+    }
+
+    ///
+    /// Estimated size of the population \\( \hat{N} = \sum_i 1 / \pi_i \\)
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    pub fn population_size(&self) -> F {
+        self.units
+            .iter()
+            .fold(F::zero(), |acc, &(_, weight)| acc + weight)
+    }
+
+    ///
+    /// Horvitz–Thompson estimate of the total of the population
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    pub fn sample_total(&self) -> F {
+        self.units
+            .iter()
+            .fold(F::zero(), |acc, &(y, weight)| acc + y * weight)
+    }
+
+    ///
+    /// Standard error of the estimate of the total
+    /// (NaN if there are less than two units)
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    pub fn sample_total_sem(&self) -> F {
+        let n = F::from(self.sample_count()).unwrap();
+        let mean = self.sample_total() / n;
+        let sum_sq = self.units.iter().fold(F::zero(), |acc, &(y, weight)| {
+            let deviation = y * weight - mean;
+            acc + deviation * deviation
+        });
+        (n / (n - F::one()) * sum_sq).sqrt()
+    }
+
+    ///
+    /// Hájek estimate of the mean of the population \\( \hat{Y} / \hat{N} \\)
+    /// (NaN if there is no unit)
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.sample_total() / self.population_size()
+    }
+
+    ///
+    /// Standard error of the estimate of the mean (linearized)
+    /// (NaN if there are less than two units)
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let n = F::from(self.sample_count()).unwrap();
+        let size = self.population_size();
+        let mean = self.sample_total() / size;
+        let sum_sq = self.units.iter().fold(F::zero(), |acc, &(y, weight)| {
+            let residual = (y - mean) * weight;
+            acc + residual * residual
+        });
+        (n / (n - F::one()) * sum_sq).sqrt() / size
+    }
+
+    ///
+    /// Confidence interval of the total of the population
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two units
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_total(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_estimate(confidence, self.sample_total(), self.sample_total_sem())
+    }
+
+    ///
+    /// Confidence interval of the mean of the population
+    ///
+    /// Complexity: \\( O(n) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If there are less than two units
+    /// * [`CIError::FloatConversionError`] - If some value cannot be converted from/to a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_estimate(confidence, self.sample_mean(), self.sample_sem())
+    }
+
+    fn ci_estimate(
+        &self,
+        confidence: Confidence,
+        estimate: F,
+        std_err: F,
+    ) -> CIResult<Interval<F>> {
+        let n = self.sample_count();
+        if n < 2 {
+            return Err(CIError::TooFewSamples(n));
+        }
+        let degrees_of_freedom = F::from(n - 1).convert("count")?;
+        let (lo, hi) = if std_err > F::zero() {
+            stats::float_interval_bounds(confidence, estimate, std_err, degrees_of_freedom)?
+        } else {
+            (estimate, estimate)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+}
+
+estimate::impl_statistic_for_mean!(Stratified<F>);
+estimate::impl_statistic_for_mean!(Clustered<F>);
+estimate::impl_statistic_for_mean!(HorvitzThompson<F>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const SMALL: [f64; 8] = [12.1, 14.3, 11.8, 15.2, 13.9, 12.7, 16.1, 13.3];
+    const MEDIUM: [f64; 6] = [20.2, 21.9, 19.8, 22.4, 20.7, 21.1];
+    const LARGE: [f64; 10] = [35.0, 41.2, 38.7, 36.9, 44.1, 39.5, 37.2, 40.8, 42.6, 36.1];
+
+    #[test]
+    fn test_stratified() -> CIResult<()> {
+        let mut stats = Stratified::new();
+        assert!(matches!(
+            stats.ci_mean(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(0))
+        ));
+        // weights as number of units: only their proportions matter
+        stats.add_stratum(50., &SMALL)?;
+        stats.add_stratum(30., &MEDIUM)?;
+        stats.add_stratum_stats(20., mean::Arithmetic::from_iter(&LARGE)?)?;
+        assert_eq!(stats.strata_count(), 3);
+        assert_eq!(stats.sample_count(), 24);
+
+        // reference values computed in python
+        assert_abs_diff_eq!(stats.sample_mean(), 20.9845, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_sem(), 0.34670686007836193, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.degrees_of_freedom(),
+            16.30853783570104,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            stats.ci_mean(Confidence::new_two_sided(0.95))?,
+            Interval::new(20.25064277251923, 21.718357227480777)?,
+            epsilon = 1e-6
+        );
+        let upper = stats.ci_mean(Confidence::new_upper(0.9))?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), 20.521414401341612, epsilon = 1e-6);
+
+        // a single stratum is a simple random sample
+        let mut single = Stratified::new();
+        single.add_stratum(1., &LARGE)?;
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            single.ci_mean(confidence)?,
+            mean::Arithmetic::ci(confidence, &LARGE)?,
+            epsilon = 1e-10
+        );
+
+        // errors
+        assert!(matches!(
+            stats.add_stratum(0., &LARGE),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            stats.add_stratum(1., &[1.]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert_eq!(stats.strata_count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clustered() -> CIResult<()> {
+        let users = [
+            vec![120., 135., 128., 140.],
+            vec![95., 102., 99.],
+            vec![210., 198., 225., 205., 215.],
+            vec![150., 145.],
+            vec![88., 92., 85., 90.],
+            vec![175., 180., 170.],
+        ];
+        let mut stats = Clustered::new();
+        stats.add_cluster(&users[0])?;
+        assert!(matches!(
+            stats.ci_mean(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(1))
+        ));
+        for requests in &users[1..] {
+            stats.add_cluster(requests)?;
+        }
+        assert_eq!(stats.cluster_count(), 6);
+        assert_eq!(stats.sample_count(), 21);
+
+        // reference values computed in python
+        assert_abs_diff_eq!(stats.sample_mean(), 145.0952380952381, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_sem(), 22.667864393036986, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.design_effect(), 4.806895660603202, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.effective_sample_size(),
+            4.368723908886505,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            stats.ci_mean(Confidence::new_two_sided(0.95))?,
+            Interval::new(86.82563763383007, 203.36483855664613)?,
+            epsilon = 1e-6
+        );
+        let lower = stats.ci_mean(Confidence::new_lower(0.9))?;
+        assert!(lower.is_lower());
+        assert_abs_diff_eq!(lower.high_f(), 178.55037757383775, epsilon = 1e-6);
+
+        // clusters of a single unit are a simple random sample
+        let data = [3., 5., 4., 8., 6., 7.];
+        let mut singletons = Clustered::new();
+        for x in data {
+            singletons.add_cluster(&[x])?;
+        }
+        assert_abs_diff_eq!(singletons.design_effect(), 1., epsilon = 1e-10);
+        let confidence = Confidence::new_two_sided(0.9);
+        assert_abs_diff_eq!(
+            singletons.ci_mean(confidence)?,
+            mean::Arithmetic::ci(confidence, &data)?,
+            epsilon = 1e-10
+        );
+
+        assert!(matches!(
+            stats.add_cluster(&Vec::<f64>::new()),
+            Err(CIError::TooFewSamples(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_proportion_clustered() -> CIResult<()> {
+        let users = [
+            (3, 10),
+            (7, 12),
+            (1, 8),
+            (9, 11),
+            (4, 9),
+            (6, 10),
+            (2, 7),
+            (8, 10),
+        ];
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            ci_proportion_clustered(Confidence::new_two_sided(0.95), &users)?,
+            Interval::new(0.3597936914127806, 0.6752840850283273)?,
+            epsilon = 1e-8
+        );
+        let upper = ci_proportion_clustered(Confidence::new_upper(0.975), &users)?;
+        assert_abs_diff_eq!(upper.low_f(), 0.3597936914127806, epsilon = 1e-8);
+        assert_eq!(upper.high_f(), 1.);
+
+        // clusters of a single unit: the design effect is one
+        let singletons: Vec<_> = (0..40).map(|i| (usize::from(i % 3 == 0), 1)).collect();
+        assert_abs_diff_eq!(
+            ci_proportion_clustered(Confidence::new_two_sided(0.95), &singletons)?,
+            proportion::ci(Confidence::new_two_sided(0.95), 40, 14)?,
+            epsilon = 1e-10
+        );
+
+        assert!(matches!(
+            ci_proportion_clustered(Confidence::new_two_sided(0.95), &[(3, 2), (1, 5)]),
+            Err(CIError::InvalidSuccesses(3, 2))
+        ));
+        assert!(matches!(
+            ci_proportion_clustered(Confidence::new_two_sided(0.95), &[(3, 10)]),
+            Err(CIError::TooFewSamples(1))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ratio() -> CIResult<()> {
+        let data = [
+            (52.1, 1020.),
+            (38.4, 760.),
+            (61.0, 1190.),
+            (45.3, 905.),
+            (70.2, 1400.),
+            (33.9, 650.),
+            (58.7, 1130.),
+            (49.5, 980.),
+        ];
+        let stats = Ratio::from_iter(&data)?;
+        assert_eq!(stats.sample_count(), 8);
+
+        // reference values computed in python
+        assert_abs_diff_eq!(stats.sample_ratio(), 0.050914747977598006, epsilon = 1e-12);
+        assert_abs_diff_eq!(stats.sample_sem(), 0.0002734256164565553, epsilon = 1e-12);
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            stats.ci_ratio(confidence)?,
+            Interval::new(0.050268199133918126, 0.051561296821277885)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            stats.ci_total(confidence, 250_000.)?,
+            Interval::new(12567.049783479531, 12890.324205319472)?,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence, 1000.)?,
+            Interval::new(50.268199133918124, 51.561296821277885)?,
+            epsilon = 1e-7
+        );
+        let upper = stats.ci_total(Confidence::new_upper(0.9), 250_000.)?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), 12631.967882610259, epsilon = 1e-6);
+
+        // exactly proportional data
+        let exact = Ratio::from_iter(&[(2., 1.), (6., 3.), (10., 5.)])?;
+        assert_eq!(exact.ci_ratio(confidence)?, Interval::new(2., 2.)?);
+
+        // errors
+        let mut single = Ratio::new();
+        single.append(1., 2.)?;
+        assert!(matches!(
+            single.ci_ratio(confidence),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            stats.ci_total(confidence, -1.),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            single.append(f64::NAN, 1.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_horvitz_thompson() -> CIResult<()> {
+        let events = [
+            (12., 0.01),
+            (15., 0.01),
+            (11., 0.01),
+            (14., 0.01),
+            (13., 0.01),
+            (250., 1.),
+            (310., 1.),
+            (95., 0.1),
+            (120., 0.1),
+            (88., 0.1),
+            (105., 0.1),
+        ];
+        let stats = HorvitzThompson::from_iter(&events)?;
+        assert_eq!(stats.sample_count(), 11);
+
+        // reference values computed in python
+        assert_abs_diff_eq!(stats.population_size(), 542., epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_total(), 11140., epsilon = 1e-9);
+        assert_abs_diff_eq!(stats.sample_total_sem(), 1345.6745520370073, epsilon = 1e-8);
+        assert_abs_diff_eq!(stats.sample_mean(), 20.55350553505535, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_sem(), 4.66043437077798, epsilon = 1e-10);
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            stats.ci_total(confidence)?,
+            Interval::new(8141.650248477119, 14138.349751522881)?,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            Interval::new(10.169410646392729, 30.937600423717974)?,
+            epsilon = 1e-8
+        );
+        let lower = stats.ci_mean(Confidence::new_lower(0.9))?;
+        assert!(lower.is_lower());
+        assert_abs_diff_eq!(lower.high_f(), 26.948477339105228, epsilon = 1e-8);
+
+        // with equal probabilities, the mean is the usual arithmetic mean
+        let data = [3., 5., 4., 8., 6., 7.];
+        let uniform = HorvitzThompson::from_iter(&data.map(|x| (x, 0.25)))?;
+        assert_abs_diff_eq!(
+            uniform.ci_mean(confidence)?,
+            mean::Arithmetic::ci(confidence, &data)?,
+            epsilon = 1e-10
+        );
+
+        // errors
+        let mut single = HorvitzThompson::new();
+        single.append(1., 0.5)?;
+        assert!(matches!(
+            single.ci_total(confidence),
+            Err(CIError::TooFewSamples(1))
+        ));
+        assert!(matches!(
+            single.append(1., 0.),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            single.append(1., 1.5),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+}
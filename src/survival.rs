@@ -0,0 +1,988 @@
+//!
+//! Confidence intervals for survival analysis, i.e., for the distribution of the time until an event (e.g., a failure,
+//! or the churn of a user) when some observations are censored (the event had not occurred yet when the observation ended).
+//!
+//! * [`KaplanMeier`] - the Kaplan–Meier estimator of the survival function, with pointwise intervals based on the
+//!   variance of Greenwood (on a log or log-log scale, see [`SurvivalTransform`]) and the simultaneous confidence bands of
+//!   Hall and Wellner.
+//! * [`Weibull`] - the maximum likelihood fit of the Weibull lifetime model, with intervals of its shape and scale and of
+//!   derived quantities such as the B10 life (the time by which 10% of the units have failed) or the reliability at a
+//!   given time.
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // remission times (in weeks) of leukemia patients treated with 6-MP, `true` if censored
+//! let data = [
+//!     (6., false), (6., false), (6., false), (6., true), (7., false), (9., true), (10., false),
+//!     (10., true), (11., true), (13., false), (16., false), (17., true), (19., true), (20., true),
+//!     (22., false), (23., false), (25., true), (32., true), (32., true), (34., true), (35., true),
+//! ];
+//! let km = survival::KaplanMeier::from_iter(&data)?;
+//! assert_abs_diff_eq!(km.survival(12.), 0.752941, epsilon = 1e-6);
+//! let ci = km.ci_survival(
+//!     Confidence::new_two_sided(0.95),
+//!     12.,
+//!     survival::SurvivalTransform::LogLog,
+//! )?;
+//! assert!(ci.contains(&km.survival(12.)));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * E.L. Kaplan, P. Meier. "Nonparametric estimation from incomplete observations."
+//!   Journal of the American Statistical Association 53(282): 457-481 (1958).
+//! * W.J. Hall, J.A. Wellner. "Confidence bands for a survival curve from censored data." Biometrika 67(1): 133-143 (1980).
+//! * J.P. Klein, M.L. Moeschberger. Survival Analysis: Techniques for Censored and Truncated Data, 2nd Edition.
+//!   Springer, 2003. Chapter 4.
+//! * W.Q. Meeker, L.A. Escobar. Statistical Methods for Reliability Data. Wiley, 1998.
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Scale on which the intervals of the survival function are symmetric (see [`KaplanMeier::ci_survival`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurvivalTransform {
+    ///
+    /// Log-log scale, i.e., \\( \ln(-\ln S(t)) \\), which keeps the interval within \\( [0, 1] \\) and performs well
+    /// with small samples.
+    ///
+    #[default]
+    LogLog,
+
+    ///
+    /// Log scale, i.e., \\( \ln S(t) \\), whose upper bound is clamped to 1.
+    ///
+    Log,
+}
+
+///
+/// Step of the Kaplan–Meier estimator at a time with some events.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Step {
+    time: f64,
+    survival: f64,
+    greenwood: f64,
+}
+
+///
+/// Kaplan–Meier estimator of the survival function \\( S(t) = P(T > t) \\) from observations \\( (t_i, c_i) \\), where
+/// \\( t_i \\) is the time of the event or of the censoring (if \\( c_i \\) is true) of the \\( i \\)-th individual:
+/// \\[
+/// \hat{S}(t) = \prod_{t_j \leq t} \left( 1 - \frac{d_j}{n_j} \right)
+/// \\]
+/// where \\( d_j \\) is the number of events at time \\( t_j \\) and \\( n_j \\) the number of individuals still at risk
+/// (individuals censored at time \\( t_j \\) are at risk at that time).
+///
+/// The variance of the estimator is estimated with Greenwood's formula
+/// \\[
+/// \hat{V}(\hat{S}(t)) = \hat{S}(t)^2 \sigma^2(t) \qquad \sigma^2(t) = \sum_{t_j \leq t} \frac{d_j}{n_j (n_j - d_j)}
+/// \\]
+/// and the pointwise intervals ([`KaplanMeier::ci_survival`]) are symmetric on a log or log-log scale
+/// (see [`SurvivalTransform`]), e.g., \\( \hat{S}(t) \exp(\pm z \sigma(t)) \\) on the log scale.
+///
+/// The simultaneous bands of Hall and Wellner ([`KaplanMeier::ci_band`]) contain the whole survival function over an
+/// interval of time \\( [t_L, t_U] \\) with the given confidence. They replace \\( z \sigma(t) \\) with
+/// \\( k (1 + n \sigma^2(t)) / \sqrt{n} \\), where \\( k \\) is the quantile of the supremum of the absolute value of a
+/// Brownian bridge over \\( [x(t_L), x(t_U)] \\), with \\( x(t) = n \sigma^2(t) / (1 + n \sigma^2(t)) \\).
+///
+/// # Notes
+///
+/// The censoring is assumed to be independent of the time of the event.
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [
+///     (6., false), (6., false), (6., false), (6., true), (7., false), (9., true), (10., false),
+///     (10., true), (11., true), (13., false), (16., false), (17., true), (19., true), (20., true),
+///     (22., false), (23., false), (25., true), (32., true), (32., true), (34., true), (35., true),
+/// ];
+/// let km = survival::KaplanMeier::from_iter(&data)?;
+/// assert_eq!(km.event_times(), vec![6., 7., 10., 13., 16., 22., 23.]);
+/// assert_abs_diff_eq!(km.survival(13.), 0.690196, epsilon = 1e-6);
+/// assert_abs_diff_eq!(km.std_err(13.), 0.106815, epsilon = 1e-6);
+///
+/// // band over the range of the event times
+/// let band = km.ci_band(
+///     Confidence::new_two_sided(0.95),
+///     survival::SurvivalTransform::LogLog,
+///     6.,
+///     23.,
+/// )?;
+/// assert_eq!(band.len(), 7);
+/// for (time, interval) in band {
+///     let pointwise = km.ci_survival(
+///         Confidence::new_two_sided(0.95),
+///         time,
+///         survival::SurvivalTransform::LogLog,
+///     )?;
+///     assert!(interval.includes(&pointwise));
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KaplanMeier {
+    sample_count: usize,
+    steps: Vec<Step>,
+}
+
+impl KaplanMeier {
+    ///
+    /// Compute the Kaplan–Meier estimator from the observations.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the observations as pairs `(time, censored)`, where `censored` is true if the observation ended before
+    ///   the event
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no observations
+    /// * [`CIError::InvalidInputData`] - if some time is not finite
+    /// * [`CIError::FloatConversionError`] - if some time cannot be converted to `f64`
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<F: Float, I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+    {
+        let mut observations = data
+            .into_iter()
+            .map(|&(time, censored)| {
+                let time = time.try_f64("time")?;
+                if time.is_finite() {
+                    Ok((time, censored))
+                } else {
+                    Err(CIError::InvalidInputData)
+                }
+            })
+            .collect::<CIResult<Vec<_>>>()?;
+        let sample_count = observations.len();
+        if sample_count == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        observations.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut steps = Vec::new();
+        let (mut survival, mut greenwood) = (1., 0.);
+        let mut at_risk = sample_count;
+        let mut start = 0;
+        while start < sample_count {
+            let time = observations[start].0;
+            let end = start
+                + observations[start..]
+                    .iter()
+                    .take_while(|(t, _)| *t == time)
+                    .count();
+            let events = observations[start..end]
+                .iter()
+                .filter(|(_, censored)| !censored)
+                .count();
+            if events > 0 {
+                let (n, d) = (at_risk as f64, events as f64);
+                survival *= 1. - d / n;
+                greenwood += if events < at_risk {
+                    d / (n * (n - d))
+                } else {
+                    f64::INFINITY
+                };
+                steps.push(Step {
+                    time,
+                    survival,
+                    greenwood,
+                });
+            }
+            at_risk -= end - start;
+            start = end;
+        }
+        Ok(Self {
+            sample_count,
+            steps,
+        })
+    }
+
+    ///
+    /// Number of observations \\( n \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    ///
+    /// Distinct times of the events (i.e., of the steps of the survival function), in increasing order
+    ///
+    pub fn event_times(&self) -> Vec<f64> {
+        self.steps.iter().map(|step| step.time).collect()
+    }
+
+    ///
+    /// Estimate \\( \hat{S}(t) \\) of the probability that the event occurs after the given time
+    ///
+    pub fn survival(&self, time: f64) -> f64 {
+        self.step(time).map_or(1., |step| step.survival)
+    }
+
+    ///
+    /// Standard error of the estimate of the survival function at the given time (Greenwood's formula)
+    ///
+    /// It is zero before the first event and after the survival function drops to zero.
+    ///
+    pub fn std_err(&self, time: f64) -> f64 {
+        let (survival, greenwood) = self.greenwood(time);
+        if survival > 0. {
+            survival * greenwood.sqrt()
+        } else {
+            0.
+        }
+    }
+
+    ///
+    /// Pointwise confidence interval of the survival function at the given time, symmetric on the scale of the transform.
+    ///
+    /// The interval is reduced to the estimate before the first event (where it is 1) and after the survival function
+    /// drops to zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `time` - the time at which the survival function is estimated
+    /// * `transform` - the scale on which the interval is symmetric
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_survival(
+        &self,
+        confidence: Confidence,
+        time: f64,
+        transform: SurvivalTransform,
+    ) -> CIResult<Interval<f64>> {
+        let z = distributions::z_value(confidence)?;
+        let (survival, greenwood) = self.greenwood(time);
+        interval(confidence, survival, z * greenwood.sqrt(), transform)
+    }
+
+    ///
+    /// Simultaneous confidence band of the survival function over the interval of time `[lower, upper]` (Hall and Wellner),
+    /// i.e., the intervals contain the whole survival function over `[lower, upper]` with the given confidence.
+    ///
+    /// The band is a step function, returned as the pairs `(time, interval)` at `lower` and at each event time in
+    /// `(lower, upper]`, where each interval holds until the next time (or `upper`).
+    /// As for the pointwise intervals, the band is reduced to the estimate before the first event.
+    /// One-sided intervals are the corresponding bounds of the two-sided band with the same confidence level,
+    /// which are conservative.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `transform` - the scale on which the intervals are symmetric
+    /// * `lower` - the start of the interval of time
+    /// * `upper` - the end of the interval of time, before the survival function drops to zero
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if there are no events in `(lower, upper]`, or the survival function is zero at `upper`
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_band(
+        &self,
+        confidence: Confidence,
+        transform: SurvivalTransform,
+        lower: f64,
+        upper: f64,
+    ) -> CIResult<Vec<(f64, Interval<f64>)>> {
+        distributions::checked_quantile(confidence)?;
+        let n = self.sample_count as f64;
+        let scale = |greenwood: f64| n * greenwood / (1. + n * greenwood);
+        let (a, b) = (self.greenwood(lower).1, self.greenwood(upper).1);
+        if !b.is_finite() || scale(b) <= scale(a) {
+            return Err(CIError::InvalidInputData);
+        }
+        let k = special::brownian_bridge_sup_inverse_cdf(confidence.level(), scale(a), scale(b));
+        let times = core::iter::once(lower).chain(
+            self.steps
+                .iter()
+                .map(|step| step.time)
+                .filter(|&time| lower < time && time <= upper),
+        );
+        times
+            .map(|time| {
+                let (survival, greenwood) = self.greenwood(time);
+                let span = k * (1. + n * greenwood) / n.sqrt();
+                Ok((time, interval(confidence, survival, span, transform)?))
+            })
+            .collect()
+    }
+
+    ///
+    /// Last step at or before the given time, if any.
+    ///
+    fn step(&self, time: f64) -> Option<&Step> {
+        let index = self.steps.partition_point(|step| step.time <= time);
+        index.checked_sub(1).map(|index| &self.steps[index])
+    }
+
+    ///
+    /// Survival function and Greenwood's sum \\( \sigma^2(t) \\) at the given time.
+    ///
+    fn greenwood(&self, time: f64) -> (f64, f64) {
+        self.step(time)
+            .map_or((1., 0.), |step| (step.survival, step.greenwood))
+    }
+}
+
+///
+/// Interval of the survival function whose bounds are at the given span from the estimate on the scale of the transform
+/// (relatively to the standard error of the estimate on the log scale).
+///
+fn interval(
+    confidence: Confidence,
+    survival: f64,
+    span: f64,
+    transform: SurvivalTransform,
+) -> CIResult<Interval<f64>> {
+    let (lo, hi) = if survival <= 0. || survival >= 1. || span == 0. {
+        (survival, survival)
+    } else {
+        match transform {
+            SurvivalTransform::Log => (survival * (-span).exp(), (survival * span.exp()).min(1.)),
+            SurvivalTransform::LogLog => {
+                let theta = (span / survival.ln()).exp();
+                (survival.powf(1. / theta), survival.powf(theta))
+            }
+        }
+    };
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+        Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+    }
+}
+
+///
+/// Weibull lifetime model \\( S(t) = \exp(-(t / \eta)^\beta) \\), with shape \\( \beta \\) and scale \\( \eta \\),
+/// fitted by maximum likelihood to observations \\( (t_i, c_i) \\), where \\( t_i \\) is the time of the failure or of
+/// the censoring (if \\( c_i \\) is true) of the \\( i \\)-th unit.
+///
+/// The shape is the root of the profile likelihood equation
+/// \\[
+/// \frac{\sum_i t_i^\beta \ln t_i}{\sum_i t_i^\beta} - \frac{1}{\beta} - \frac{1}{r} \sum_{i: \neg c_i} \ln t_i = 0
+/// \\]
+/// over all the observations, where \\( r \\) is the number of failures, and the scale follows as
+/// \\( \hat\eta = (\sum_i t_i^{\hat\beta} / r)^{1 / \hat\beta} \\).
+/// A shape below 1 indicates a decreasing failure rate (e.g., infant mortality), and above 1 an increasing one
+/// (e.g., wear-out), while a shape of 1 is the exponential model (see [`rate::ci_mean_lifetime`]).
+///
+/// The intervals are Wald intervals on the log scale (or the log-log scale of the reliability), whose standard errors
+/// are derived with the delta method from the inverse of the observed Fisher information of
+/// \\( (\ln \hat\eta, \ln \hat\beta) \\). Working on the log scale keeps the bounds positive and improves the coverage
+/// with small samples. The derived quantities are:
+/// * the quantiles \\( t_p = \eta (-\ln(1 - p))^{1 / \beta} \\), i.e., the time by which a fraction \\( p \\) of the
+///   units has failed (e.g., the B10 life for \\( p = 0.1 \\)), see [`Weibull::ci_quantile`];
+/// * the reliability \\( S(t) \\) at a given time, see [`Weibull::ci_reliability`].
+///
+/// # Notes
+///
+/// The censoring is assumed to be independent of the time of the failure.
+/// The maximum likelihood estimate is preferred to median-rank regression since its variance is estimated consistently,
+/// including with censored observations.
+///
+/// This type is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // lifetimes of ball bearings (in millions of revolutions), without censoring
+/// let data = [
+///     17.88, 28.92, 33.00, 41.52, 42.12, 45.60, 48.48, 51.84, 51.96, 54.12, 55.56, 67.80,
+///     68.64, 68.64, 68.88, 84.12, 93.12, 98.64, 105.12, 105.84, 127.92, 128.04, 173.40,
+/// ];
+/// let data = data.map(|time| (time, false));
+/// let weibull = survival::Weibull::from_iter(&data)?;
+/// assert_abs_diff_eq!(weibull.shape(), 2.1021, epsilon = 1e-4);
+/// assert_abs_diff_eq!(weibull.scale(), 81.8783, epsilon = 1e-4);
+///
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let shape = weibull.ci_shape(confidence)?;
+/// assert!(shape.low_f() > 1.); // increasing failure rate
+///
+/// // B10 life
+/// assert_abs_diff_eq!(weibull.quantile(0.1)?, 28.0694, epsilon = 1e-4);
+/// let b10 = weibull.ci_quantile(confidence, 0.1)?;
+/// assert_abs_diff_eq!(b10, Interval::new(18.0601, 43.6261)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.Q. Meeker, L.A. Escobar. Statistical Methods for Reliability Data. Wiley, 1998. Chapter 8.
+/// * J. Lieblein, M. Zelen. "Statistical investigation of the fatigue life of deep-groove ball bearings."
+///   Journal of Research of the National Bureau of Standards 57(5): 273-316 (1956).
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weibull {
+    sample_count: usize,
+    failure_count: usize,
+    shape: f64,
+    scale: f64,
+    var_log_scale: f64,
+    var_log_shape: f64,
+    covariance: f64,
+}
+
+impl Weibull {
+    ///
+    /// Fit the Weibull model to the observations by maximum likelihood.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the observations as pairs `(time, censored)`, where `censored` is true if the observation ended before
+    ///   the failure
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no observations
+    /// * [`CIError::TooFewEvents`] - if there are no failures
+    /// * [`CIError::InvalidInputData`] - if some time is not strictly positive and finite, or if all the failures occur at
+    ///   the largest time (the estimate of the shape is then infinite)
+    /// * [`CIError::FloatConversionError`] - if some time cannot be converted to `f64`
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<F: Float, I>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (F, bool)>,
+    {
+        let observations = data
+            .into_iter()
+            .map(|&(time, censored)| {
+                let time = time.try_f64("time")?;
+                if time > 0. && time.is_finite() {
+                    Ok((time.ln(), censored))
+                } else {
+                    Err(CIError::InvalidInputData)
+                }
+            })
+            .collect::<CIResult<Vec<_>>>()?;
+        let sample_count = observations.len();
+        if sample_count == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let failure_count = observations
+            .iter()
+            .filter(|(_, censored)| !censored)
+            .count();
+        if failure_count == 0 {
+            return Err(CIError::TooFewEvents(0));
+        }
+        let r = failure_count as f64;
+
+        // log times relative to the largest one, so that the powers do not overflow
+        let max = observations
+            .iter()
+            .map(|&(x, _)| x)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let failure_mean = observations
+            .iter()
+            .filter(|(_, censored)| !censored)
+            .map(|&(x, _)| x - max)
+            .sum::<f64>()
+            / r;
+        if failure_mean >= 0. {
+            return Err(CIError::InvalidInputData);
+        }
+        // the profile likelihood equation, increasing with the shape
+        let equation = |shape: f64| {
+            let (sum, weighted) = observations
+                .iter()
+                .fold((0., 0.), |(sum, weighted), &(x, _)| {
+                    let power = (shape * (x - max)).exp();
+                    (sum + power, weighted + power * (x - max))
+                });
+            weighted / sum - 1. / shape - failure_mean
+        };
+        let (mut lo, mut hi) = (1., 1.);
+        while equation(lo) > 0. {
+            lo /= 2.;
+        }
+        while equation(hi) < 0. {
+            hi *= 2.;
+        }
+        while hi - lo > 1e-12 * hi {
+            let mid = (lo + hi) / 2.;
+            if equation(mid) < 0. {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let shape = (lo + hi) / 2.;
+        let sum = observations
+            .iter()
+            .map(|&(x, _)| (shape * (x - max)).exp())
+            .sum::<f64>();
+        let log_scale = max + (sum / r).ln() / shape;
+
+        // observed information of (ln scale, ln shape), with u = shape * (ln t - ln scale)
+        let (first, second) = observations
+            .iter()
+            .fold((0., 0.), |(first, second), &(x, _)| {
+                let u = shape * (x - log_scale);
+                let power = u.exp();
+                (first + power * u, second + power * u * u)
+            });
+        let (i_scale, i_shape, i_cross) = (shape * shape * r, r + second, -shape * first);
+        let determinant = i_scale * i_shape - i_cross * i_cross;
+        Ok(Self {
+            sample_count,
+            failure_count,
+            shape,
+            scale: log_scale.exp(),
+            var_log_scale: i_shape / determinant,
+            var_log_shape: i_scale / determinant,
+            covariance: -i_cross / determinant,
+        })
+    }
+
+    ///
+    /// Number of observations \\( n \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    ///
+    /// Number of failures \\( r \\), i.e., of uncensored observations
+    ///
+    pub fn failure_count(&self) -> usize {
+        self.failure_count
+    }
+
+    ///
+    /// Estimate \\( \hat\beta \\) of the shape
+    ///
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    ///
+    /// Estimate \\( \hat\eta \\) of the scale, i.e., the time by which a fraction \\( 1 - e^{-1} \approx 63.2\\% \\) of the
+    /// units has failed
+    ///
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    ///
+    /// Estimate of the quantile \\( t_p \\), i.e., of the time by which a fraction `p` of the units has failed
+    /// (e.g., the B10 life for `p = 0.1`)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidQuantile`] - if `p` is not in (0, 1)
+    ///
+    pub fn quantile(&self, p: f64) -> CIResult<f64> {
+        Ok(self.log_quantile(p)?.0.exp())
+    }
+
+    ///
+    /// Estimate of the reliability \\( S(t) \\), i.e., of the probability that a unit survives beyond the given time
+    ///
+    pub fn reliability(&self, time: f64) -> f64 {
+        if time > 0. {
+            (-(time / self.scale).powf(self.shape)).exp()
+        } else {
+            1.
+        }
+    }
+
+    ///
+    /// Confidence interval of the shape \\( \beta \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_shape(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        log_interval(confidence, self.shape.ln(), self.var_log_shape.sqrt())
+    }
+
+    ///
+    /// Confidence interval of the scale \\( \eta \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_scale(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        log_interval(confidence, self.scale.ln(), self.var_log_scale.sqrt())
+    }
+
+    ///
+    /// Confidence interval of the quantile \\( t_p \\), i.e., of the time by which a fraction `p` of the units has
+    /// failed (e.g., the B10 life for `p = 0.1`)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `p` - the fraction of failed units
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidQuantile`] - if `p` is not in (0, 1)
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_quantile(&self, confidence: Confidence, p: f64) -> CIResult<Interval<f64>> {
+        let (log_quantile, std_err) = self.log_quantile(p)?;
+        log_interval(confidence, log_quantile, std_err)
+    }
+
+    ///
+    /// Confidence interval of the reliability \\( S(t) \\) at the given time, symmetric on the log-log scale
+    /// (i.e., on the scale of \\( \ln(-\ln S(t)) = \beta (\ln t - \ln \eta) \\)).
+    ///
+    /// The interval is reduced to 1 at non-positive times.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `time` - the time at which the reliability is estimated
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    ///
+    pub fn ci_reliability(&self, confidence: Confidence, time: f64) -> CIResult<Interval<f64>> {
+        let z = distributions::z_value(confidence)?;
+        let (lo, hi) = if time > 0. {
+            let log_cumulative = self.shape * (time.ln() - self.scale.ln());
+            let variance = self.shape * self.shape * self.var_log_scale
+                + log_cumulative * log_cumulative * self.var_log_shape
+                - 2. * self.shape * log_cumulative * self.covariance;
+            let span = z * variance.sqrt();
+            (
+                (-(log_cumulative + span).exp()).exp(),
+                (-(log_cumulative - span).exp()).exp(),
+            )
+        } else {
+            (1., 1.)
+        };
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Interval::new(lo, 1.).map_err(|e| e.into()),
+            Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+        }
+    }
+
+    ///
+    /// Logarithm of the quantile \\( \ln t_p = \ln \eta + w_p / \beta \\), with \\( w_p = \ln(-\ln(1 - p)) \\),
+    /// and its standard error.
+    ///
+    fn log_quantile(&self, p: f64) -> CIResult<(f64, f64)> {
+        if !(p > 0. && p < 1.) {
+            return Err(CIError::InvalidQuantile(p));
+        }
+        let w = (-(-p).ln_1p()).ln() / self.shape;
+        let variance = self.var_log_scale + w * w * self.var_log_shape - 2. * w * self.covariance;
+        Ok((self.scale.ln() + w, variance.sqrt()))
+    }
+}
+
+///
+/// Interval of a positive quantity that is symmetric on the log scale, from the logarithm of its estimate and the
+/// standard error of that logarithm.
+///
+fn log_interval(
+    confidence: Confidence,
+    log_estimate: f64,
+    std_err: f64,
+) -> CIResult<Interval<f64>> {
+    let span = distributions::z_value(confidence)? * std_err;
+    let (lo, hi) = ((log_estimate - span).exp(), (log_estimate + span).exp());
+    match confidence {
+        Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+        Confidence::LowerOneSided(_) => Interval::new(0., hi).map_err(|e| e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_kaplan_meier() -> CIResult<()> {
+        // reference values computed in python
+        let data = [
+            (6., false),
+            (6., false),
+            (6., false),
+            (6., true),
+            (7., false),
+            (9., true),
+            (10., false),
+            (10., true),
+            (11., true),
+            (13., false),
+            (16., false),
+            (17., true),
+            (19., true),
+            (20., true),
+            (22., false),
+            (23., false),
+            (25., true),
+            (32., true),
+            (32., true),
+            (34., true),
+            (35., true),
+        ];
+        let km = KaplanMeier::from_iter(&data)?;
+        assert_eq!(km.sample_count(), 21);
+        let expected = [
+            (6., 0.8571428571428572, 0.07636035483212125),
+            (7., 0.8067226890756303, 0.0869352851800572),
+            (10., 0.7529411764705882, 0.09634965299432051),
+            (13., 0.6901960784313725, 0.10681470777500983),
+            (16., 0.6274509803921569, 0.11405386525675254),
+            (22., 0.5378151260504203, 0.12823375169303403),
+            (23., 0.44817927170868355, 0.13459145675576048),
+        ];
+        assert_eq!(
+            km.event_times(),
+            expected
+                .iter()
+                .map(|(time, _, _)| *time)
+                .collect::<Vec<_>>()
+        );
+        for (time, survival, std_err) in expected {
+            assert_abs_diff_eq!(km.survival(time), survival, epsilon = 1e-12);
+            assert_abs_diff_eq!(km.survival(time + 0.5), survival, epsilon = 1e-12);
+            assert_abs_diff_eq!(km.std_err(time), std_err, epsilon = 1e-12);
+        }
+        assert_eq!(km.survival(5.), 1.);
+        assert_eq!(km.std_err(5.), 0.);
+        assert_abs_diff_eq!(km.survival(40.), 0.44817927170868355, epsilon = 1e-12);
+
+        let two_sided = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            km.ci_survival(two_sided, 13., SurvivalTransform::Log)?,
+            Interval::new(0.5096130991017803, 0.9347691955361301)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            km.ci_survival(two_sided, 13., SurvivalTransform::LogLog)?,
+            Interval::new(0.43161022248618414, 0.8490659633494507)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            km.ci_survival(Confidence::new_upper(0.95), 13., SurvivalTransform::LogLog)?,
+            Interval::new(0.47870030967424404, 1.)?,
+            epsilon = 1e-10
+        );
+        assert_eq!(
+            km.ci_survival(two_sided, 5., SurvivalTransform::LogLog)?,
+            Interval::new(1., 1.)?
+        );
+
+        // Hall-Wellner band over the range of the event times
+        let band = km.ci_band(two_sided, SurvivalTransform::LogLog, 6., 23.)?;
+        let expected = [
+            (6., 0.24570744536855746, 0.983213030927644),
+            (7., 0.31213840137498344, 0.9611558272696874),
+            (10., 0.3229006484025878, 0.9312433723785049),
+            (13., 0.298096192971416, 0.8926274983004212),
+            (16., 0.26022419061398033, 0.8509762368825391),
+            (22., 0.17527528443415033, 0.8017860538267026),
+            (23., 0.10036960411412087, 0.7556473620434755),
+        ];
+        assert_eq!(band.len(), expected.len());
+        for ((time, interval), (expected_time, lo, hi)) in band.into_iter().zip(expected) {
+            assert_eq!(time, expected_time);
+            assert_abs_diff_eq!(interval, Interval::new(lo, hi)?, epsilon = 1e-8);
+        }
+        let band = km.ci_band(two_sided, SurvivalTransform::Log, 6., 23.)?;
+        assert_abs_diff_eq!(
+            band[3].1,
+            Interval::new(0.4451118850724069, 1.)?,
+            epsilon = 1e-8
+        );
+
+        assert!(matches!(
+            km.ci_band(two_sided, SurvivalTransform::LogLog, 6., 6.5),
+            Err(CIError::InvalidInputData)
+        ));
+        assert!(matches!(
+            KaplanMeier::from_iter(&Vec::<(f64, bool)>::new()),
+            Err(CIError::TooFewSamples(0))
+        ));
+        assert!(matches!(
+            KaplanMeier::from_iter(&[(f64::NAN, false)]),
+            Err(CIError::InvalidInputData)
+        ));
+
+        // all the individuals at risk fail: the survival drops to zero
+        let km = KaplanMeier::from_iter(&[(1., false), (2., true), (3., false)])?;
+        assert_abs_diff_eq!(km.survival(2.), 2. / 3., epsilon = 1e-12);
+        assert_eq!(km.survival(3.), 0.);
+        assert_eq!(km.std_err(3.), 0.);
+        assert!(matches!(
+            km.ci_band(two_sided, SurvivalTransform::LogLog, 0., 3.),
+            Err(CIError::InvalidInputData)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_weibull() -> CIResult<()> {
+        // reference values computed in python
+        let bearings = [
+            17.88, 28.92, 33.00, 41.52, 42.12, 45.60, 48.48, 51.84, 51.96, 54.12, 55.56, 67.80,
+            68.64, 68.64, 68.88, 84.12, 93.12, 98.64, 105.12, 105.84, 127.92, 128.04, 173.40,
+        ];
+        let weibull = Weibull::from_iter(&bearings.map(|time| (time, false)))?;
+        assert_eq!(weibull.sample_count(), 23);
+        assert_eq!(weibull.failure_count(), 23);
+        assert_abs_diff_eq!(weibull.shape(), 2.102058875194621, epsilon = 1e-9);
+        assert_abs_diff_eq!(weibull.scale(), 81.87833405163154, epsilon = 1e-8);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = weibull.ci_shape(confidence)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(1.5472030849531198, 2.8558962671137396)?,
+            epsilon = 1e-8
+        );
+        let ci = weibull.ci_scale(confidence)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(66.64373885197348, 100.59552033779754)?,
+            epsilon = 1e-7
+        );
+        assert_abs_diff_eq!(weibull.quantile(0.1)?, 28.069414010704993, epsilon = 1e-8);
+        let ci = weibull.ci_quantile(confidence, 0.1)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(18.06010310462248, 43.62610768831662)?,
+            epsilon = 1e-7
+        );
+        assert_abs_diff_eq!(weibull.quantile(0.5)?, 68.77741334872339, epsilon = 1e-8);
+        let ci = weibull.ci_quantile(confidence, 0.5)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(54.696498742859944, 86.48327947241137)?,
+            epsilon = 1e-7
+        );
+        assert_abs_diff_eq!(weibull.reliability(50.), 0.7014526091375514, epsilon = 1e-9);
+        let ci = weibull.ci_reliability(confidence, 50.)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(0.5188520541036886, 0.8256033574113187)?,
+            epsilon = 1e-9
+        );
+        assert_eq!(
+            weibull.ci_reliability(confidence, 0.)?,
+            Interval::new(1., 1.)?
+        );
+
+        // one-sided intervals share the bound of the two-sided interval at twice the risk
+        let ci = weibull.ci_shape(Confidence::new_two_sided(0.9))?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(1.6253462294275645, 2.7185909283714245)?,
+            epsilon = 1e-8
+        );
+        let upper = weibull.ci_shape(Confidence::new_upper(0.95))?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), ci.low_f(), epsilon = 1e-9);
+        let b10 = weibull.ci_quantile(Confidence::new_two_sided(0.9), 0.1)?;
+        assert_abs_diff_eq!(
+            b10,
+            Interval::new(19.386993678123233, 40.640236231852626)?,
+            epsilon = 1e-7
+        );
+        let lower = weibull.ci_quantile(Confidence::new_lower(0.95), 0.1)?;
+        assert_eq!(lower.low_f(), 0.);
+        assert_abs_diff_eq!(lower.high_f(), b10.high_f(), epsilon = 1e-7);
+
+        // with censoring (remission times of the 6-MP group, see above)
+        let data = [
+            (6., false),
+            (6., false),
+            (6., false),
+            (6., true),
+            (7., false),
+            (9., true),
+            (10., false),
+            (10., true),
+            (11., true),
+            (13., false),
+            (16., false),
+            (17., true),
+            (19., true),
+            (20., true),
+            (22., false),
+            (23., false),
+            (25., true),
+            (32., true),
+            (32., true),
+            (34., true),
+            (35., true),
+        ];
+        let weibull = Weibull::from_iter(&data)?;
+        assert_eq!(weibull.failure_count(), 9);
+        assert_abs_diff_eq!(weibull.shape(), 1.3537345238262208, epsilon = 1e-9);
+        assert_abs_diff_eq!(weibull.scale(), 33.765150967634966, epsilon = 1e-8);
+        let ci = weibull.ci_shape(confidence)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(0.7844419240671932, 2.3361795242881866)?,
+            epsilon = 1e-8
+        );
+        let ci = weibull.ci_scale(confidence)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(19.759531692063636, 57.69799799076681)?,
+            epsilon = 1e-7
+        );
+        let ci = weibull.ci_quantile(confidence, 0.1)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(2.795081505452829, 14.677515849231373)?,
+            epsilon = 1e-8
+        );
+        let ci = weibull.ci_reliability(confidence, 12.)?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(0.58006426931779, 0.8944448602175301)?,
+            epsilon = 1e-9
+        );
+
+        assert!(Weibull::from_iter::<f64, [(f64, bool); 0]>(&[]).is_err());
+        assert!(Weibull::from_iter(&[(1., true), (2., true)]).is_err());
+        assert!(Weibull::from_iter(&[(0., false), (2., false)]).is_err());
+        assert!(Weibull::from_iter(&[(3., false), (3., false), (2., true)]).is_err());
+        assert!(weibull.quantile(1.).is_err());
+        assert!(weibull.ci_quantile(confidence, 0.).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,581 @@
+//!
+//! Tolerance intervals, i.e., intervals that contain a given proportion of the population with a given confidence
+//! (e.g., "99% of the requests complete within the interval, with 95% confidence").
+//!
+//! A tolerance interval is not a confidence interval on a parameter, nor a prediction interval for the next observation:
+//! with a coverage \\( p \\) and a confidence level \\( 1 - \alpha \\), the interval contains at least a proportion \\( p \\)
+//! of the population in a fraction \\( 1 - \alpha \\) of the experiments. Unlike a confidence interval on the mean,
+//! its width does not shrink to zero as the sample grows, but to the width of the central \\( p \\) of the population.
+//!
+//! * [`normal`] - tolerance interval for a normal population, with the k-factor given by [`normal_k_factor`]
+//! * [`nonparametric`] - distribution-free tolerance interval delimited by order statistics, for any continuous population,
+//!   with the minimal number of samples given by [`nonparametric_sample_size`]
+//!
+//! # Examples
+//!
+//! ```
+//! # use stats_ci::*;
+//! # use approx::*;
+//! // diameter (in mm) of machined parts
+//! let data = [9.8, 10.2, 10.1, 9.9, 10.4, 9.7, 10.0, 10.3, 9.6, 10.1, 10.2, 9.9];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! // 99% of the parts are within the interval, with 95% confidence
+//! let interval = tolerance::normal(confidence, 0.99, &data)?;
+//! assert_abs_diff_eq!(interval, Interval::new(8.994296, 11.039037)?, epsilon = 1e-6);
+//!
+//! // the confidence interval on the mean is much narrower, and says nothing about individual parts
+//! let ci = mean::Arithmetic::ci(confidence, &data)?;
+//! assert!(ci.is_included_in(&interval));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * NIST/SEMATECH e-Handbook of Statistical Methods, Section 7.2.6.3 "Tolerance intervals for a normal distribution". [Online](https://www.itl.nist.gov/div898/handbook/prc/section2/prc263.htm)
+//! * K. Krishnamoorthy, T. Mathew. Statistical Tolerance Regions: Theory, Applications, and Computation. Wiley, 2009.
+//! * [Wikipedia - Tolerance interval](https://en.wikipedia.org/wiki/Tolerance_interval)
+//!
+use super::*;
+use error::*;
+use mean::StatisticsOps;
+use num_traits::Float;
+
+///
+/// Compute the tolerance interval for a normal population, from the sample mean \\( \bar{x} \\) and standard deviation \\( s \\)
+/// of the data: \\( \bar{x} \pm k s \\) for a two-sided confidence, and \\( [\bar{x} - k s, \infty) \\) or \\( (-\infty, \bar{x} + k s] \\)
+/// for an upper or lower one-sided confidence, where \\( k \\) is given by [`normal_k_factor`].
+///
+/// Complexity: \\( O(n) \\), where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion of the population to be contained in the interval (must be in (0, 1))
+/// * `data` - the samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to or from `f64`
+///
+/// # Notes
+///
+/// The interval relies heavily on the normality of the population, more so than a confidence interval on the mean, since it
+/// is about the tails of the distribution. The distribution-free interval of [`nonparametric`] should be preferred for
+/// skewed data such as latencies.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [9.8, 10.2, 10.1, 9.9, 10.4, 9.7, 10.0, 10.3, 9.6, 10.1, 10.2, 9.9];
+/// // at least 99% of the parts are above the lower bound, with 95% confidence
+/// let interval = tolerance::normal(Confidence::new_upper(0.95), 0.99, &data)?;
+/// assert_abs_diff_eq!(interval.low_f(), 9.101143, epsilon = 1e-6);
+/// assert!(interval.is_upper());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn normal<F, I>(confidence: Confidence, coverage: f64, data: &I) -> CIResult<Interval<F>>
+where
+    F: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a F>,
+{
+    normal_from_stats(confidence, coverage, &mean::Arithmetic::from_iter(data)?)
+}
+
+///
+/// Compute the tolerance interval for a normal population from the statistics of the samples (see [`normal`]).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion of the population to be contained in the interval (must be in (0, 1))
+/// * `stats` - the statistics of the samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::FloatConversionError`] - if some value cannot be converted to or from `f64`
+///
+pub fn normal_from_stats<F: Float>(
+    confidence: Confidence,
+    coverage: f64,
+    stats: &mean::Arithmetic<F>,
+) -> CIResult<Interval<F>> {
+    let k = normal_k_factor(confidence, coverage, stats.sample_count())?;
+    let mean = stats.sample_mean();
+    let span = F::from(k).convert("k")? * stats.sample_std_dev();
+    match confidence {
+        Confidence::TwoSided(_) => Ok(Interval::new(mean - span, mean + span)?),
+        Confidence::UpperOneSided(_) => Ok(Interval::new_upper(mean - span)),
+        Confidence::LowerOneSided(_) => Ok(Interval::new_lower(mean + span)),
+    }
+}
+
+///
+/// Compute the k-factor of the tolerance interval for a normal population, i.e., the number of sample standard deviations
+/// on each side of the sample mean (see [`normal`]).
+///
+/// * For a one-sided interval, the factor is exact: \\( k_1 = t'_{1-\alpha}(n - 1, z_p \sqrt{n}) / \sqrt{n} \\), where
+///   \\( t'_{1-\alpha}(\nu, \delta) \\) is the quantile of the noncentral t-distribution with \\( \nu \\) degrees of freedom
+///   and noncentrality \\( \delta \\), and \\( z_p \\) is the \\( p \\)-quantile of the standard normal distribution.
+/// * For a two-sided interval, the factor uses Howe's approximation with Guenther's correction:
+///   \\[
+///   k_2 = z_{(1+p)/2} \sqrt{\frac{(n - 1)(1 + 1/n)}{\chi^2_{\alpha, n-1}}} \sqrt{1 + \frac{n - 3 - \chi^2_{\alpha, n-1}}{2 (n + 1)^2}}
+///   \\]
+///   where \\( \chi^2_{\alpha, n-1} \\) is the \\( \alpha \\)-quantile of the chi-square distribution with \\( n - 1 \\) degrees of freedom,
+///   which is within a fraction of a percent of the exact factor (and slightly conservative).
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion \\( p \\) of the population to be contained in the interval (must be in (0, 1))
+/// * `population` - the number of samples \\( n \\)
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are less than 2 samples
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // tabulated factors for 10 samples, 90% coverage, and 95% confidence
+/// let k2 = tolerance::normal_k_factor(Confidence::new_two_sided(0.95), 0.9, 10)?;
+/// assert_abs_diff_eq!(k2, 2.856, epsilon = 5e-3);
+/// let k1 = tolerance::normal_k_factor(Confidence::new_upper(0.95), 0.9, 10)?;
+/// assert_abs_diff_eq!(k1, 2.355, epsilon = 1e-3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * W.G. Howe. "Two-sided tolerance limits for normal populations—some improvements." Journal of the American Statistical Association 64(326): 610-620 (1969).
+/// * W.C. Guenther. "Sampling inspection in statistical quality control." Griffin's Statistical Monographs 37 (1977).
+///
+pub fn normal_k_factor(confidence: Confidence, coverage: f64, population: usize) -> CIResult<f64> {
+    if population < 2 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    check_coverage(coverage)?;
+    distributions::checked_quantile(confidence)?;
+    let level = confidence.level();
+    let n = population as f64;
+    let dof = n - 1.;
+    if confidence.is_two_sided() {
+        let z = distributions::normal_inverse_cdf((1. + coverage) / 2.)?;
+        let chi2 = distributions::chi2_inverse_cdf(1. - level, dof)?;
+        let howe = z * (dof * (1. + 1. / n) / chi2).sqrt();
+        Ok(howe * (1. + (n - 3. - chi2) / (2. * (n + 1.) * (n + 1.))).sqrt())
+    } else {
+        let delta = distributions::normal_inverse_cdf(coverage)? * n.sqrt();
+        Ok(noncentral_t_quantile(level, dof, delta) / n.sqrt())
+    }
+}
+
+///
+/// Compute the distribution-free tolerance interval of a sample, delimited by order statistics (see [`nonparametric_ranks`]).
+///
+/// The samples are copied and sorted (see [`nonparametric_sorted_unchecked`]).
+///
+/// Complexity: \\( O(n \log n) \\), where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion of the population to be contained in the interval (must be in (0, 1))
+/// * `data` - the samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are too few samples to form the interval (see [`nonparametric_sample_size`])
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidInputData`] - if the data contains elements that are not comparable (e.g., NaN)
+///
+/// # Notes
+///
+/// This function is only available with the `std` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // response times (in ms): skewed, far from normal
+/// let data = [
+///     12, 15, 11, 48, 13, 19, 14, 95, 12, 16, 13, 22, 17, 11, 14, 31, 12, 18, 15, 13,
+///     26, 14, 12, 61, 16, 13, 15, 12, 20, 14, 11, 38, 13, 17, 12, 14, 24, 15, 13, 16,
+/// ];
+/// // 75% of the requests are within the interval, with 90% confidence
+/// let interval = tolerance::nonparametric(Confidence::new_two_sided(0.9), 0.75, &data)?;
+/// assert_eq!(interval, Interval::new(11, 48)?);
+/// // 75% of the requests complete within 24 ms, with 90% confidence
+/// let interval = tolerance::nonparametric(Confidence::new_lower(0.9), 0.75, &data)?;
+/// assert_eq!(interval, Interval::new_lower(24));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "std"))]
+pub fn nonparametric<T, I>(confidence: Confidence, coverage: f64, data: &I) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let sorted = quantile::sorted_copy(data)?;
+    nonparametric_sorted_unchecked(confidence, coverage, &sorted)
+}
+
+///
+/// Compute the distribution-free tolerance interval of a sample, assuming that the data is __already sorted__
+/// (see [`nonparametric`] and [`nonparametric_ranks`]).
+///
+/// Complexity: \\( O(\log n) \\), where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion of the population to be contained in the interval (must be in (0, 1))
+/// * `sorted` - the samples, sorted in increasing order
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are too few samples to form the interval (see [`nonparametric_sample_size`])
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+pub fn nonparametric_sorted_unchecked<T>(
+    confidence: Confidence,
+    coverage: f64,
+    sorted: &[T],
+) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Clone,
+{
+    match nonparametric_ranks(confidence, coverage, sorted.len())?.into() {
+        (Some(lo), Some(hi)) => Ok(Interval::new(sorted[lo].clone(), sorted[hi].clone())?),
+        (Some(lo), None) => Ok(Interval::new_upper(sorted[lo].clone())),
+        (None, Some(hi)) => Ok(Interval::new_lower(sorted[hi].clone())),
+        (None, None) => unreachable!("tolerance intervals are bounded on at least one side"),
+    }
+}
+
+///
+/// Compute the (zero-based) ranks of the order statistics delimiting the distribution-free tolerance interval of a sample
+/// of the given size.
+///
+/// The coverage \\( C = F(x_{(s)}) - F(x_{(r)}) \\) of the interval between the order statistics \\( x_{(r)} < x_{(s)} \\) of a
+/// continuous distribution does not depend on the distribution: it follows the beta distribution \\( \operatorname{Beta}(s - r, n - s + r + 1) \\),
+/// so that the confidence that the interval covers at least a proportion \\( p \\) of the population is
+/// \\[
+/// P(C \geq p) = P(B \leq s - r - 1) \qquad \text{where } B \sim \operatorname{Binomial}(n, p)
+/// \\]
+/// (Wilks, 1941). The two-sided interval discards the same number of samples on each side, and the one-sided intervals discard
+/// samples on one side only, as many as possible while keeping the confidence above the requested level.
+///
+/// Complexity: \\( O(\log n) \\), where \\( n \\) is the number of samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion \\( p \\) of the population to be contained in the interval (must be in (0, 1))
+/// * `population` - the number of samples \\( n \\)
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are too few samples to form the interval, even with the extreme samples
+///   (see [`nonparametric_sample_size`])
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // with 100 samples, 90% of the population lies between the second smallest and the second largest samples
+/// let ranks = tolerance::nonparametric_ranks(confidence, 0.9, 100)?;
+/// assert_eq!(ranks, Interval::new(1, 98)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * S.S. Wilks. "Determination of sample sizes for setting tolerance limits." The Annals of Mathematical Statistics 12(1): 91-96 (1941).
+///
+pub fn nonparametric_ranks(
+    confidence: Confidence,
+    coverage: f64,
+    population: usize,
+) -> CIResult<Interval<usize>> {
+    check_coverage(coverage)?;
+    distributions::checked_quantile(confidence)?;
+    let level = confidence.level();
+    let n = population as u64;
+    // confidence reached when at most k samples lie strictly between the bounds
+    let is_confident =
+        |k: Option<u64>| k.is_some_and(|k| stats::binomial_cdf(k, n, coverage) >= level);
+    if confidence.is_two_sided() {
+        // number of values m such that the confidence is reached when discarding m samples on each side
+        let count = quantile::partition_point(n / 2, |m| is_confident(n.checked_sub(2 * m + 2)));
+        if count == 0 {
+            return Err(CIError::TooFewSamples(population));
+        }
+        let m = (count - 1) as usize;
+        Ok(Interval::new(m, population - 1 - m)?)
+    } else {
+        // the missing bound is beyond the largest (or smallest) sample
+        let count = quantile::partition_point(n, |m| is_confident(n.checked_sub(m + 1)));
+        if count == 0 {
+            return Err(CIError::TooFewSamples(population));
+        }
+        let m = (count - 1) as usize;
+        match confidence {
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(m)),
+            _ => Ok(Interval::new_lower(population - 1 - m)),
+        }
+    }
+}
+
+///
+/// Compute the smallest number of samples for which a distribution-free tolerance interval can be formed (see [`nonparametric_ranks`]),
+/// i.e., for which the interval between the smallest and the largest samples (two-sided), or beyond the smallest or the largest
+/// sample (one-sided), covers at least a proportion \\( p \\) of the population with the requested confidence.
+///
+/// For one-sided intervals, this is the smallest \\( n \\) such that \\( 1 - p^n \geq 1 - \alpha \\),
+/// and for two-sided intervals, the smallest \\( n \\) such that \\( 1 - p^n - n (1 - p) p^{n-1} \geq 1 - \alpha \\).
+///
+/// Complexity: \\( O(\log n) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level and the kind of interval
+/// * `coverage` - the proportion \\( p \\) of the population to be contained in the interval (must be in (0, 1))
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if the coverage is not in (0, 1)
+/// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+/// * [`CIError::Overflow`] - if the required sample size cannot be represented
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // classic values: 59 samples for a one-sided 95%/95% interval, 93 for a two-sided one
+/// assert_eq!(tolerance::nonparametric_sample_size(Confidence::new_upper(0.95), 0.95)?, 59);
+/// assert_eq!(tolerance::nonparametric_sample_size(Confidence::new_two_sided(0.95), 0.95)?, 93);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn nonparametric_sample_size(confidence: Confidence, coverage: f64) -> CIResult<usize> {
+    check_coverage(coverage)?;
+    distributions::checked_quantile(confidence)?;
+    let level = confidence.level();
+    let is_enough = |n: u64| {
+        if confidence.is_two_sided() {
+            n >= 2 && stats::binomial_cdf(n - 2, n, coverage) >= level
+        } else {
+            n >= 1 && stats::binomial_cdf(n - 1, n, coverage) >= level
+        }
+    };
+    let mut limit = 2_u64;
+    while !is_enough(limit) {
+        limit = limit
+            .checked_mul(2)
+            .ok_or_else(|| CIError::Overflow("sample size".to_string()))?;
+    }
+    let n = quantile::partition_point(limit, |n| !is_enough(n));
+    usize::try_from(n).map_err(|_| CIError::Overflow("sample size".to_string()))
+}
+
+///
+/// Check that the coverage of a tolerance interval is in (0, 1).
+///
+fn check_coverage(coverage: f64) -> CIResult<()> {
+    if coverage > 0. && coverage < 1. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidProbability(coverage))
+    }
+}
+
+///
+/// Find \\( t \\) such that \\( P(T \leq t \mid \delta) = p \\) for the noncentral t-distribution, by bisection.
+///
+fn noncentral_t_quantile(p: f64, degrees_of_freedom: f64, delta: f64) -> f64 {
+    let cdf = |t: f64| special::noncentral_t_cdf(t, degrees_of_freedom, delta) - p;
+    let mut step = 1.;
+    let (mut lo, mut hi) = (delta - step, delta + step);
+    while cdf(lo) > 0. {
+        step *= 2.;
+        lo = delta - step;
+    }
+    while cdf(hi) < 0. {
+        step *= 2.;
+        hi = delta + step;
+    }
+    let tolerance = 1e-12 * delta.abs().max(1.);
+    while hi - lo > tolerance {
+        let mid = (lo + hi) / 2.;
+        if cdf(mid) < 0. {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo + hi) / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_normal_k_factor() -> CIResult<()> {
+        // reference values computed in python
+        let two_sided = Confidence::new_two_sided(0.95);
+        let upper = Confidence::new_upper(0.95);
+        assert_abs_diff_eq!(
+            normal_k_factor(two_sided, 0.9, 10)?,
+            2.859659729317293,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            normal_k_factor(two_sided, 0.99, 30)?,
+            3.35887845776463,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            normal_k_factor(upper, 0.9, 10)?,
+            2.35464013182906,
+            epsilon = 1e-8
+        );
+        assert_abs_diff_eq!(
+            normal_k_factor(upper, 0.99, 30)?,
+            3.063901126238169,
+            epsilon = 1e-8
+        );
+        assert_eq!(
+            normal_k_factor(Confidence::new_lower(0.95), 0.99, 30)?,
+            normal_k_factor(upper, 0.99, 30)?
+        );
+        // the factor tends to the normal quantile of the coverage
+        assert_abs_diff_eq!(
+            normal_k_factor(upper, 0.99, 100_000)?,
+            distributions::normal_inverse_cdf(0.99)?,
+            epsilon = 2e-2
+        );
+
+        assert!(matches!(
+            normal_k_factor(two_sided, 0.9, 1),
+            Err(CIError::TooFewSamples(1))
+        ));
+        for coverage in [0., 1., f64::NAN] {
+            assert!(matches!(
+                normal_k_factor(two_sided, coverage, 10),
+                Err(CIError::InvalidProbability(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_normal() -> CIResult<()> {
+        let data = [
+            9.8, 10.2, 10.1, 9.9, 10.4, 9.7, 10.0, 10.3, 9.6, 10.1, 10.2, 9.9,
+        ];
+        // reference values computed in python
+        assert_abs_diff_eq!(
+            normal(Confidence::new_two_sided(0.95), 0.99, &data)?,
+            Interval::new(8.994295855073456, 11.03903747825988)?,
+            epsilon = 1e-9
+        );
+        let interval = normal(Confidence::new_upper(0.95), 0.99, &data)?;
+        assert!(interval.is_upper());
+        assert_abs_diff_eq!(interval.low_f(), 9.101142788675646, epsilon = 1e-8);
+        let interval = normal(Confidence::new_lower(0.95), 0.99, &data)?;
+        assert!(interval.is_lower());
+        assert_abs_diff_eq!(interval.high_f(), 10.93219054465769, epsilon = 1e-8);
+
+        let interval = normal(
+            Confidence::new_two_sided(0.95),
+            0.99,
+            &data.map(|x| x as f32),
+        )?;
+        assert_abs_diff_eq!(interval.low_f(), 8.994296, epsilon = 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nonparametric() -> CIResult<()> {
+        // reference values computed in python
+        let two_sided = Confidence::new_two_sided(0.95);
+        let upper = Confidence::new_upper(0.95);
+        let lower = Confidence::new_lower(0.95);
+        assert_eq!(
+            nonparametric_ranks(two_sided, 0.9, 100)?,
+            Interval::new(1, 98)?
+        );
+        assert_eq!(
+            nonparametric_ranks(upper, 0.9, 100)?,
+            Interval::new_upper(4)
+        );
+        assert_eq!(
+            nonparametric_ranks(lower, 0.9, 100)?,
+            Interval::new_lower(95)
+        );
+        let confidence = Confidence::new_two_sided(0.9);
+        assert_eq!(
+            nonparametric_ranks(confidence, 0.75, 40)?,
+            Interval::new(2, 37)?
+        );
+        assert_eq!(
+            nonparametric_ranks(Confidence::new_upper(0.9), 0.75, 40)?,
+            Interval::new_upper(6)
+        );
+
+        // minimal sample sizes: the extreme samples are just enough
+        assert_eq!(nonparametric_sample_size(two_sided, 0.9)?, 46);
+        assert_eq!(nonparametric_sample_size(two_sided, 0.99)?, 473);
+        assert_eq!(nonparametric_sample_size(upper, 0.9)?, 29);
+        assert_eq!(nonparametric_sample_size(lower, 0.99)?, 299);
+        assert_eq!(
+            nonparametric_ranks(two_sided, 0.9, 46)?,
+            Interval::new(0, 45)?
+        );
+        assert!(matches!(
+            nonparametric_ranks(two_sided, 0.9, 45),
+            Err(CIError::TooFewSamples(45))
+        ));
+        assert_eq!(nonparametric_ranks(upper, 0.9, 29)?, Interval::new_upper(0));
+        assert!(matches!(
+            nonparametric_ranks(upper, 0.9, 28),
+            Err(CIError::TooFewSamples(28))
+        ));
+        assert!(matches!(
+            nonparametric_ranks(two_sided, 0.9, 0),
+            Err(CIError::TooFewSamples(0))
+        ));
+
+        let data = (1..=100).rev().collect::<Vec<_>>();
+        assert_eq!(nonparametric(two_sided, 0.9, &data)?, Interval::new(2, 99)?);
+        assert_eq!(nonparametric(upper, 0.9, &data)?, Interval::new_upper(5));
+        assert!(matches!(
+            nonparametric(two_sided, 1., &data),
+            Err(CIError::InvalidProbability(_))
+        ));
+        Ok(())
+    }
+}
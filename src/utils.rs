@@ -4,7 +4,12 @@
 //! Offers support for the computation of compensated floating point sums (aka. Kahan summation).
 //!
 //! The main type is [`KahanSum`] where you can find further explanations and an example.
+//!
+//! Also defines the policy [`NanPolicy`] for handling NaN values in the sample data, which is re-exported at the root of the crate,
+//! and the settings [`Bootstrap`] of the bootstrap intervals.
 
+use crate::error::*;
+use crate::{Confidence, Interval};
 use num_traits::Float;
 
 ///
@@ -130,6 +135,273 @@ fn kahan_add<T: Float>(current_sum: &mut T, x: T, compensation: &mut T) {
     *current_sum = t;
 }
 
+///
+/// Policy for handling NaN values in the sample data.
+///
+/// It is accepted by the functions `ci_with_nan_policy` of the modules [`mean`](crate::mean) (through [`StatisticsOps`](crate::StatisticsOps))
+/// and [`quantile`](crate::quantile), and by [`proportion::ci_if_with_nan_policy`](crate::proportion::ci_if_with_nan_policy).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let data = [1., 2., f64::NAN, 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // by default, NaN values are rejected
+/// assert!(mean::Arithmetic::ci_with_nan_policy(confidence, &data, NanPolicy::Error).is_err());
+/// // ignore NaN values
+/// let ci = mean::Arithmetic::ci_with_nan_policy(confidence, &data, NanPolicy::Ignore)?;
+/// assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+/// // propagate NaN values to the result
+/// let ci = mean::Arithmetic::ci_with_nan_policy(confidence, &data, NanPolicy::Propagate)?;
+/// assert!(ci.low_f().is_nan() && ci.high_f().is_nan());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NanPolicy {
+    ///
+    /// Fail with [`CIError::InvalidInputData`] (default).
+    ///
+    #[default]
+    Error,
+
+    ///
+    /// Ignore NaN values, as if they were not part of the sample.
+    ///
+    Ignore,
+
+    ///
+    /// Return an interval whose bounds are NaN.
+    ///
+    Propagate,
+}
+
+///
+/// Action resulting from applying a [`NanPolicy`] to a sample value.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NanAction {
+    /// The value is kept in the sample.
+    Keep,
+    /// The value is ignored.
+    Ignore,
+    /// The result of the computation must be NaN.
+    Propagate,
+}
+
+impl NanPolicy {
+    ///
+    /// Applies the policy to a sample value.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the value is NaN and the policy is [`NanPolicy::Error`]
+    ///
+    pub(crate) fn apply<F: Float>(&self, x: F) -> CIResult<NanAction> {
+        if !x.is_nan() {
+            return Ok(NanAction::Keep);
+        }
+        match self {
+            NanPolicy::Error => Err(CIError::InvalidInputData),
+            NanPolicy::Ignore => Ok(NanAction::Ignore),
+            NanPolicy::Propagate => Ok(NanAction::Propagate),
+        }
+    }
+
+    ///
+    /// Interval with NaN bounds, of the same kind as the given confidence.
+    ///
+    pub(crate) fn nan_interval<F: Float>(confidence: Confidence) -> Interval<F> {
+        match confidence {
+            Confidence::TwoSided(_) => Interval::TwoSided(F::nan(), F::nan()),
+            Confidence::UpperOneSided(_) => Interval::UpperOneSided(F::nan()),
+            Confidence::LowerOneSided(_) => Interval::LowerOneSided(F::nan()),
+        }
+    }
+}
+
+///
+/// Settings of a nonparametric bootstrap: the number of resamples and the seed of the pseudo-random generator.
+///
+/// Each resample draws as many units (e.g., items, subjects) as the sample, with replacement, and the interval is delimited
+/// by the percentiles of the statistic over the resamples.
+/// The resamples are drawn with a small deterministic generator (SplitMix64), so that an interval is reproducible for a given seed.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let bootstrap = utils::Bootstrap::new(5_000, 42);
+/// assert_eq!(bootstrap.resamples(), 5_000);
+/// assert_eq!(utils::Bootstrap::default().resamples(), 2_000);
+/// ```
+///
+/// # References
+///
+/// * B. Efron, R.J. Tibshirani. An Introduction to the Bootstrap. Chapman & Hall, 1993.
+/// * [Wikipedia - Bootstrapping (statistics)](https://en.wikipedia.org/wiki/Bootstrapping_(statistics))
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bootstrap {
+    resamples: usize,
+    seed: u64,
+}
+
+impl Default for Bootstrap {
+    fn default() -> Self {
+        Self::new(2_000, 0)
+    }
+}
+
+impl Bootstrap {
+    ///
+    /// Create the settings of a bootstrap.
+    ///
+    /// # Arguments
+    ///
+    /// * `resamples` - the number of resamples (a few thousands are usually enough for a 95% interval)
+    /// * `seed` - the seed of the pseudo-random generator
+    ///
+    pub fn new(resamples: usize, seed: u64) -> Self {
+        Self { resamples, seed }
+    }
+
+    ///
+    /// Number of resamples
+    ///
+    pub fn resamples(&self) -> usize {
+        self.resamples
+    }
+
+    ///
+    /// Seed of the pseudo-random generator
+    ///
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    ///
+    /// Bounds of the percentile interval of a statistic computed on resamples of `count` units, each given as the indices of
+    /// the drawn units. Both bounds are returned regardless of the kind of confidence, with the tail of the one-sided level.
+    /// Resamples where the statistic is not finite (e.g., undefined) are discarded.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::TooFewSamples`] - if there are no units, or the statistic is not finite on any resample
+    ///
+    #[cfg(any(test, feature = "std"))]
+    pub(crate) fn percentile_bounds<S>(
+        &self,
+        confidence: Confidence,
+        count: usize,
+        statistic: S,
+    ) -> CIResult<(f64, f64)>
+    where
+        S: FnMut(&[usize]) -> f64,
+    {
+        self.stratified_percentile_bounds(confidence, &[count], statistic)
+    }
+
+    ///
+    /// Bounds of the percentile interval of a statistic computed on stratified resamples, where each stratum
+    /// (e.g., independent samples) is resampled separately with its own size.
+    /// The units are numbered stratum after stratum, so that the units of the stratum `s` are numbered from
+    /// `counts[..s].iter().sum()` and the indices of the drawn units follow the same order.
+    /// See [`Self::percentile_bounds`] for the bounds.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the confidence level is not in (0, 1)
+    /// * [`CIError::TooFewSamples`] - if there are no strata or some stratum has no units, or the statistic is not finite on any resample
+    ///
+    #[cfg(any(test, feature = "std"))]
+    pub(crate) fn stratified_percentile_bounds<S>(
+        &self,
+        confidence: Confidence,
+        counts: &[usize],
+        mut statistic: S,
+    ) -> CIResult<(f64, f64)>
+    where
+        S: FnMut(&[usize]) -> f64,
+    {
+        crate::distributions::checked_quantile(confidence)?;
+        if counts.is_empty() || counts.contains(&0) {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let mut rng = SplitMix64(self.seed);
+        let mut indices = vec![0; counts.iter().sum()];
+        let mut replicates = Vec::with_capacity(self.resamples);
+        for _ in 0..self.resamples {
+            let mut start = 0;
+            for &count in counts {
+                indices[start..start + count]
+                    .iter_mut()
+                    .for_each(|i| *i = start + rng.below(count));
+                start += count;
+            }
+            let replicate = statistic(&indices);
+            if replicate.is_finite() {
+                replicates.push(replicate);
+            }
+        }
+        if replicates.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        replicates.sort_by(|a, b| a.total_cmp(b));
+        let m = replicates.len();
+        let tail = match confidence {
+            Confidence::TwoSided(level) => (1. - level) / 2.,
+            Confidence::UpperOneSided(level) | Confidence::LowerOneSided(level) => 1. - level,
+        };
+        let lo = replicates[((tail * m as f64).floor() as usize).min(m - 1)];
+        let hi = replicates[((1. - tail) * m as f64).ceil().max(1.) as usize - 1];
+        Ok((lo, hi))
+    }
+}
+
+///
+/// Converts a required sample size computed in floating point (already rounded up) to an integer of at least 1.
+///
+/// # Errors
+///
+/// * [`CIError::Overflow`] - if the sample size does not fit in `usize`
+///
+pub(crate) fn sample_size_from_f64(n: f64) -> CIResult<usize> {
+    if n >= usize::MAX as f64 {
+        return Err(CIError::Overflow("sample size".to_string()));
+    }
+    Ok((n as usize).max(1))
+}
+
+///
+/// SplitMix64 pseudo-random generator, used to draw the bootstrap resamples.
+///
+#[cfg(any(test, feature = "std"))]
+struct SplitMix64(u64);
+
+#[cfg(any(test, feature = "std"))]
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    ///
+    /// Uniform integer in `[0, n)` (by multiplication, with a negligible bias for small `n`).
+    ///
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
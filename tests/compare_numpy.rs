@@ -5,7 +5,6 @@ use approx::*;
 use serde::Deserialize;
 use stats_ci::*;
 use std::path::Path;
-use toml;
 
 mod common;
 